@@ -0,0 +1,16 @@
+#![no_main]
+
+use ethabi::ethereum_types::U256;
+use libfuzzer_sys::fuzz_target;
+use optimex_sol_smartcontract::bytes_to_u64_number;
+
+fuzz_target!(|bytes: Vec<u8>| {
+    let amount = bytes_to_u64_number(&bytes);
+    if bytes.len() <= 32 {
+        let value = U256::from_big_endian(&bytes);
+        match amount {
+            Ok(amount) => assert_eq!(U256::from(amount), value),
+            Err(_) => assert!(value > U256::from(u64::MAX)),
+        }
+    }
+});
@@ -0,0 +1,43 @@
+#![no_main]
+
+use ethabi::{decode, ParamType};
+use libfuzzer_sys::fuzz_target;
+use optimex_sol_smartcontract::{TradeInfo, TradeInput};
+
+type Chain = [Vec<u8>; 3];
+
+fuzz_target!(|data: ([u8; 32], [u8; 20], [u8; 32], Chain, Chain)| {
+    let (session_id, solver, amount_in, from_chain, to_chain) = data;
+    let input = TradeInput {
+        session_id,
+        solver,
+        trade_info: TradeInfo {
+            amount_in,
+            from_chain,
+            to_chain,
+        },
+    };
+    let encoded = input.encode_trade_input();
+    assert_eq!(encoded.len() % 32, 0);
+
+    // The encoding is decoded back to the same chains
+    let chain = ParamType::FixedArray(Box::new(ParamType::Bytes), 3);
+    let types = [
+        ParamType::Uint(256),
+        ParamType::Address,
+        ParamType::Tuple(vec![ParamType::Uint(256), chain.clone(), chain]),
+    ];
+    let tokens = decode(&types, &encoded).unwrap();
+    let trade_info = tokens[2].clone().into_tuple().unwrap();
+    for (token, chain) in trade_info[1..].iter().zip([&input.trade_info.from_chain, &input.trade_info.to_chain]) {
+        let decoded: Vec<Vec<u8>> = token
+            .clone()
+            .into_fixed_array()
+            .unwrap()
+            .into_iter()
+            .map(|bytes| bytes.into_bytes().unwrap())
+            .collect();
+        assert_eq!(&decoded, chain);
+    }
+    input.calculate_trade_id();
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use optimex_sol_smartcontract::vec_u8_to_address;
+
+fuzz_target!(|bytes: Vec<u8>| {
+    match vec_u8_to_address(&bytes) {
+        Ok(Some(pubkey)) => assert_eq!(pubkey.to_string().into_bytes(), bytes),
+        Ok(None) => assert_eq!(bytes, b"native"),
+        Err(_) => {}
+    }
+});
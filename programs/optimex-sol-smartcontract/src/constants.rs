@@ -1,3 +1,5 @@
 //! This module contains the constants for the protocol.
 use anchor_lang::prelude::*;
-pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
\ No newline at end of file
+pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+/// The denominator of the basis points, 100% in basis points.
+pub const BPS_DENOMINATOR: u16 = 10_000;
\ No newline at end of file
@@ -29,4 +29,28 @@ pub enum CustomError {
     InvalidTotalFee,
     InvalidFeeReceiver,
     AmountExceeds,
+    InstructionPaused,
+    InvalidTokenProgram,
+    UnsupportedMintExtension,
+    MaxAmountExceeded,
+    OutstandingLimitReached,
+    MaxFeeExceeded,
+    InvalidMaxFeeBps,
+    InvalidSettlementLegs,
+    InvalidPmmAccount,
+    PaymentAlreadyFulfilled,
+    InvalidMpcGroup,
+    MpcNotRegistered,
+    InvalidValidityWindow,
+    InsufficientAvailableFee,
+    LegacyFeeAlreadyAccrued,
+    InvalidFeeShare,
+    InvalidBatchAccounts,
+    TimelockRequired,
+    TimelockNotReady,
+    InvalidPendingAction,
+    TimeoutExtensionExceeded,
+    InvalidSignature,
+    InvalidDepositIntent,
+    InvalidSolverSignature,
 }
@@ -1,6 +1,8 @@
 //! This module contains the events for the protocol.
 use anchor_lang::prelude::*;
 
+use crate::{Role, SettlementLeg, TimelockAction};
+
 #[event]
 /**
     - @dev Event emitted when a user successfully deposits tokens or SOL
@@ -57,3 +59,367 @@ pub struct PaymentTransferred {
     pub total_fee: u64,
     pub protocol: Pubkey,
 }
+
+/**
+    - @dev Event emitted when the protocol is initialized
+    - Related function: init()
+*/
+#[event]
+pub struct Initialized {
+    pub admin: Pubkey,
+    pub operator: Pubkey,
+}
+
+/**
+    - @dev Event emitted when MPC sets the total fee of a trade
+    - Related function: set_total_fee()
+*/
+#[event]
+pub struct TotalFeeSet {
+    pub trade_id: [u8; 32],
+    pub operator: Pubkey,
+    pub total_fee: u64,
+}
+
+/**
+    - @dev Event emitted when a finished trade is closed and its rent is reclaimed
+    - Related function: close_finished_trade()
+*/
+#[event]
+pub struct TradeClosed {
+    pub trade_id: [u8; 32],
+    pub operator: Pubkey,
+    pub to_pubkey: Pubkey, // receiver of the reclaimed rent
+    pub token: Option<Pubkey>,
+}
+
+/**
+    - @dev Event emitted when a payment receipt is closed and its rent is reclaimed
+    - Related function: close_payment_receipt()
+*/
+#[event]
+pub struct PaymentReceiptClosed {
+    pub trade_id: [u8; 32],
+    pub payment_receipt: Pubkey,
+    pub from_pubkey: Pubkey,
+    pub to_pubkey: Pubkey,
+    pub token: Option<Pubkey>,
+}
+
+/**
+    - @dev Event emitted when the protocol fee is withdrawn to a fee receiver
+    - Related function: distribute_fees()
+*/
+#[event]
+pub struct FeeWithdrawn {
+    pub operator: Pubkey,
+    pub to_pubkey: Pubkey,
+    pub token: Option<Pubkey>,
+    pub amount: u64,
+    pub protocol: Pubkey,
+}
+
+/**
+    - @dev Event emitted when a token is added to, updated in or removed from the whitelist
+    - Related function: add_or_update_whitelist(), remove_whitelist()
+*/
+#[event]
+pub struct WhitelistUpdated {
+    pub token: Pubkey,
+    pub operator: Pubkey,
+    pub amount: u64,
+    pub max_amount: u64,
+    pub max_outstanding: u64,
+    pub is_add: bool, // false when the token is removed from the whitelist
+}
+
+/**
+    - @dev Event emitted when admin adds or removes a fee receiver
+    - Related function: add_fee_receiver(), remove_fee_receiver()
+*/
+#[event]
+pub struct FeeReceiverChanged {
+    pub receiver: Pubkey,
+    pub admin: Pubkey,
+    pub is_add: bool,
+}
+
+/**
+    - @dev Event emitted when operator changes the waiting durations for closing trades and payment receipts
+    - Related function: set_close_wait_duration()
+*/
+#[event]
+pub struct CloseDurationChanged {
+    pub operator: Pubkey,
+    pub close_trade_duration: u64,
+    pub close_payment_duration: u64,
+}
+
+/**
+    - @dev Event emitted when admin or guardian pauses or unpauses the protocol instructions
+    - Related function: set_pause()
+*/
+#[event]
+pub struct PauseChanged {
+    pub operator: Pubkey,
+    pub deposit: bool,
+    pub payment: bool,
+    pub settlement: bool,
+    pub distribute_fees: bool,
+}
+
+/**
+    - @dev Event emitted when admin proposes a new admin
+    - Related function: propose_admin()
+*/
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+/**
+    - @dev Event emitted when the admin of the protocol is changed
+    - Related function: init(), accept_admin()
+*/
+#[event]
+pub struct AdminChanged {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+/**
+    - @dev Event emitted when a fee manager sets the maximum total fee, globally or for a token
+    - Related function: set_max_fee()
+*/
+#[event]
+pub struct MaxFeeChanged {
+    pub operator: Pubkey,
+    pub token: Option<Pubkey>, // None when the global maximum fee is changed
+    pub max_fee_bps: Option<u16>,
+}
+
+/**
+    - @dev Event emitted when MPC successfully settles the trade to multiple pmms
+    - Related function: split_settlement()
+*/
+#[event]
+pub struct SplitSettled {
+    pub trade_id: [u8; 32],
+    pub operator: Pubkey,
+    pub token: Option<Pubkey>,
+    pub legs: Vec<SettlementLeg>, // amount after fee paid to each pmm
+    pub total_fee: u64,
+    pub vault: Pubkey,
+    pub protocol: Pubkey,
+}
+
+/**
+    - @dev Event emitted when MPC and user agree to refund the deposit before the timeout
+    - Related function: cooperative_refund()
+*/
+#[event]
+pub struct Refunded {
+    pub trade_id: [u8; 32],
+    pub token: Option<Pubkey>,
+    pub to_pubkey: Pubkey,
+    pub operator: Pubkey,
+    pub amount: u64,
+}
+
+/**
+    - @dev Event emitted when MPC publishes the expected payment of a trade
+    - Related function: create_payment_expectation()
+*/
+#[event]
+pub struct PaymentExpectationCreated {
+    pub trade_id: [u8; 32],
+    pub mpc_pubkey: Pubkey,
+    pub to_pubkey: Pubkey,
+    pub token: Option<Pubkey>,
+    pub min_amount: u64,
+    pub max_fee: u64,
+    pub deadline: i64,
+}
+
+/**
+    - @dev Event emitted when a payment fulfills the expected payment of a trade
+    - Related function: strict_payment()
+*/
+#[event]
+pub struct PaymentExpectationFulfilled {
+    pub trade_id: [u8; 32],
+    pub mpc_pubkey: Pubkey,
+    pub to_pubkey: Pubkey,
+    pub token: Option<Pubkey>,
+    pub received_amount: u64,
+}
+
+/**
+    - @dev Event emitted when MPC closes the expected payment of a trade
+    - Related function: close_payment_expectation()
+*/
+#[event]
+pub struct PaymentExpectationClosed {
+    pub trade_id: [u8; 32],
+    pub mpc_pubkey: Pubkey,
+    pub fulfilled: bool,
+}
+
+/**
+    - @dev Event emitted when Admin adds or removes an MPC group
+    - Related function: add_mpc_group(), remove_mpc_group()
+*/
+#[event]
+pub struct MpcGroupUpdated {
+    pub mpc_group: Pubkey,
+    pub group_id: u64,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub admin: Pubkey,
+    pub is_add: bool,
+}
+
+/**
+    - @dev Event emitted when Admin registers, updates or retires an MPC key
+    - Related function: add_or_update_mpc_registry(), retire_mpc_registry()
+*/
+#[event]
+pub struct MpcRegistryUpdated {
+    pub mpc_pubkey: Pubkey,
+    pub admin: Pubkey,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub is_active: bool,
+}
+
+/**
+    - @dev Event emitted when admin accrues the fee held by the protocol before the fee ledger
+    - Related function: accrue_legacy_fee()
+*/
+#[event]
+pub struct LegacyFeeAccrued {
+    pub admin: Pubkey,
+    pub token: Option<Pubkey>,
+    pub amount: u64,
+}
+
+/**
+    - @dev Event emitted when admin sets the share of a fee receiver
+    - Related function: set_fee_receiver_share()
+*/
+#[event]
+pub struct FeeShareChanged {
+    pub receiver: Pubkey,
+    pub admin: Pubkey,
+    pub share_bps: u16,
+    pub total_fee_share_bps: u16,
+}
+
+/**
+    - @dev Event emitted when a batch close skips the accounts that cannot be closed yet, or are already closed
+    - Related function: batch_close_finished_trades(), batch_close_payment_receipts()
+*/
+#[event]
+pub struct BatchCloseSkipped {
+    pub operator: Pubkey,
+    pub accounts: Vec<Pubkey>, // the trade detail or payment receipt PDAs
+}
+
+/**
+    - @dev Event emitted when admin queues a sensitive operation behind the timelock
+    - Related function: queue_pending_action()
+*/
+#[event]
+pub struct PendingActionQueued {
+    pub action_id: [u8; 32],
+    pub action: TimelockAction,
+    pub admin: Pubkey,
+    pub eta: i64, // the time from which the action can be executed
+}
+
+/**
+    - @dev Event emitted when a queued sensitive operation is executed
+    - Related function: grant_role(), revoke_role(), add_fee_receiver(), remove_fee_receiver(), set_timelock_delay()
+*/
+#[event]
+pub struct PendingActionExecuted {
+    pub action_id: [u8; 32],
+    pub action: TimelockAction,
+    pub admin: Pubkey,
+}
+
+/**
+    - @dev Event emitted when admin or guardian cancels a queued sensitive operation
+    - Related function: cancel_pending_action()
+*/
+#[event]
+pub struct PendingActionCancelled {
+    pub action_id: [u8; 32],
+    pub action: TimelockAction,
+    pub operator: Pubkey,
+}
+
+/**
+    - @dev Event emitted when admin changes the timelock delay of the sensitive operations
+    - Related function: set_timelock_delay()
+*/
+#[event]
+pub struct TimelockDelayChanged {
+    pub admin: Pubkey,
+    pub timelock_delay: u64,
+}
+
+/**
+    - @dev Event emitted when admin grants or revokes a role, or migrates a legacy operator or guardian
+    - Related function: grant_role(), revoke_role(), migrate_operator(), migrate_guardian()
+*/
+#[event]
+pub struct RoleChanged {
+    pub role: Role,
+    pub member: Pubkey,
+    pub admin: Pubkey,
+    pub is_grant: bool,
+}
+
+/**
+    - @dev Event emitted when the MPC and the user extend the timeout of a trade
+    - Related function: extend_timeout()
+*/
+#[event]
+pub struct TimeoutExtended {
+    pub trade_id: [u8; 32],
+    pub operator: Pubkey,
+    pub previous_timeout: i64,
+    pub new_timeout: i64,
+}
+
+/**
+    - @dev Event emitted when operator changes the maximum timeout extension of the trades
+    - Related function: set_max_timeout_extension()
+*/
+#[event]
+pub struct MaxTimeoutExtensionChanged {
+    pub operator: Pubkey,
+    pub max_timeout_extension: u64,
+}
+
+/**
+    - @dev Event emitted when a relayer pays the rent of a deposit, emitted with the `Deposited` event
+    - Related function: sponsored_deposit()
+*/
+#[event]
+pub struct DepositSponsored {
+    pub trade_id: [u8; 32],
+    pub payer: Pubkey,
+}
+
+/**
+    - @dev Event emitted when admin sets whether the deposits require the solver signature
+    - Related function: set_solver_signature_required()
+*/
+#[event]
+pub struct SolverSignatureRequiredChanged {
+    pub admin: Pubkey,
+    pub required: bool,
+}
@@ -0,0 +1,44 @@
+//! This module contains the logic for accepting the admin role of the protocol.
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::CustomError;
+use crate::event::AdminChanged;
+
+/// Handles the accepting of the admin role, completes the admin transfer.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [AcceptAdmin] required for accepting the admin role.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::pending_admin].
+pub fn handler_accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_admin = config.admin;
+    config.admin = ctx.accounts.signer.key();
+    config.pending_admin = Pubkey::default();
+
+    emit!(AdminChanged {
+        previous_admin,
+        admin: config.admin,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the accept admin instruction.
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// The signer account that is authorized to perform the accept admin instruction.
+    /// Must be the [Config::pending_admin]
+    #[account(
+        mut,
+        address = config.pending_admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,96 @@
+//! This module contains the logic for accruing the fee held by the protocol before the fee ledger was introduced.
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use crate::constants::WSOL_MINT;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::LegacyFeeAccrued;
+use crate::utils::{assert_keys_equal, assert_token_program, get_token_account_amount};
+use crate::ID;
+
+/// Handles the one-off accrual of the fee held by the protocol before the fee ledger was introduced.
+///
+/// The fee held is the balance of the protocol for the token: the SOL above the rent exemption of the protocol PDA,
+/// or the amount of its associated token account. The part of it that is not already available in the [FeeLedger] is accrued.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [AccrueLegacyFee] required for accruing the legacy fee.
+/// * `token` - The token of the fee, none for SOL.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+/// * [CustomError::LegacyFeeAlreadyAccrued] when the legacy fee of the token was already accrued.
+/// * [CustomError::InvalidTokenAccount] when the protocol token account is missing for a token, or given for SOL.
+/// * [CustomError::InvalidTokenProgram] when the protocol token account is not owned by a token program.
+/// * [CustomError::InvalidDestinationAta] when the protocol token account is not the associated token account of the protocol PDA and the token.
+pub fn handler_accrue_legacy_fee(ctx: Context<AccrueLegacyFee>, token: Option<Pubkey>) -> Result<()> {
+    let protocol = &ctx.accounts.protocol;
+    let held = match (token, ctx.accounts.protocol_token_account.as_ref()) {
+        (Some(mint), Some(protocol_token_account)) => {
+            assert_token_program(protocol_token_account.owner)?;
+            let protocol_ata =
+                get_associated_token_address_with_program_id(protocol.key, &mint, protocol_token_account.owner);
+            assert_keys_equal(protocol_token_account.key, &protocol_ata, CustomError::InvalidDestinationAta)?;
+            get_token_account_amount(protocol_token_account)?
+        }
+        (None, None) => protocol
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(protocol.data_len())),
+        _ => return Err(CustomError::InvalidTokenAccount.into()),
+    };
+
+    let amount = ctx.accounts.fee_ledger.accrue_legacy(token.unwrap_or(WSOL_MINT), held)?;
+
+    emit!(LegacyFeeAccrued {
+        admin: ctx.accounts.signer.key(),
+        token,
+        amount,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the accrue legacy fee instruction.
+#[derive(Accounts)]
+#[instruction(token: Option<Pubkey>)]
+pub struct AccrueLegacyFee<'info> {
+    /// The signer account that is authorized to perform the accrue legacy fee instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK:
+    /// The protocol PDA account which own the protocol fee.
+    #[account(
+        seeds = [b"protocol"],
+        bump,
+        owner = ID, // This PDA must come from our smart-contract
+    )]
+    pub protocol: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The associated token account of the protocol PDA for the token, none for SOL. Checked by the instruction.
+    pub protocol_token_account: Option<UncheckedAccount<'info>>,
+
+    /// The fee ledger PDA of the token, WSOL for SOL.
+    /// The legacy fee is added to its accrued fee. Will be initialized by the signer if needed.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [FeeLedger::SEED, token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
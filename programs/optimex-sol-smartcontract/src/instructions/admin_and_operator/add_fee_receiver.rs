@@ -2,6 +2,9 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::CustomError;
+use crate::FeeReceiverChanged;
+
+use super::execute_pending_action;
 
 /// The context accounts required for the add fee receiver instruction.
 #[derive(Accounts)]
@@ -35,18 +38,43 @@ pub struct AddFeeReceiverAccounts<'info> {
 
     /// System program.
     pub system_program: Program<'info, System>,
+
+    /// The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.
+    /// Will be closed and transferred rent fee to the signer.
+    #[account(
+        mut,
+        close = signer,
+    )]
+    pub pending_action: Option<Account<'info, PendingAction>>,
 }
 
 
 /// Handles the adding of protocol fee receiver.
 /// 
+/// When the [Config::timelock_delay] is set, the operation must be queued as a [TimelockAction::AddFeeReceiver] first.
 /// # Arguments
 /// * `ctx` - A [Context] of [AddFeeReceiver] required for adding the fee receiver.
 /// * `receiver_pubkey` - The pubkey of the fee receiver.
 /// # Errors
 /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+/// * [CustomError::TimelockRequired] - The operation must be queued first.
+/// * [CustomError::InvalidPendingAction] - The pending action is not the operation.
+/// * [CustomError::TimelockNotReady] - The pending action cannot be executed yet.
 pub fn handler_add_fee_receiver(ctx: Context<AddFeeReceiverAccounts>, receiver_pubkey: Pubkey) -> Result<()> {
+    execute_pending_action(
+        &ctx.accounts.config,
+        ctx.accounts.pending_action.as_deref(),
+        TimelockAction::AddFeeReceiver(receiver_pubkey),
+        ctx.accounts.signer.key(),
+    )?;
+
     let fee_receiver = &mut ctx.accounts.fee_receiver;
     fee_receiver.receiver = receiver_pubkey;
+
+    emit!(FeeReceiverChanged {
+        receiver: receiver_pubkey,
+        admin: ctx.accounts.signer.key(),
+        is_add: true,
+    });
     Ok(())
 }
\ No newline at end of file
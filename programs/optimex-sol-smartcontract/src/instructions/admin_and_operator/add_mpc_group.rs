@@ -0,0 +1,79 @@
+//! This module contains the logic for adding the MPC group for the protocol.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::CustomError;
+use crate::MpcGroupUpdated;
+
+/// The context accounts required for the add MPC group instruction.
+#[derive(Accounts)]
+#[instruction(group_id: u64)]
+pub struct AddMpcGroup<'info> {
+    /// The admin that is authorized to perform the add MPC group instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    /// The [Config::next_mpc_group_id] is moved past the `group_id`.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The MPC group PDA account that contains the members and the threshold.
+    /// Will be initialized by the signer. A group is immutable once created,
+    /// its members are rotated by adding a new group with a new `group_id`.
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + MpcGroup::INIT_SPACE,
+        seeds = [MpcGroup::SEED, &group_id.to_le_bytes()],
+        bump,
+    )]
+    pub mpc_group: Account<'info, MpcGroup>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Handles the adding of the MPC group for the protocol.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [AddMpcGroup] required for adding the MPC group.
+/// * `group_id` - The identifier of the group, at least the [Config::next_mpc_group_id].
+/// * `members` - The member keys of the group.
+/// * `threshold` - The number of member signatures required to authorize an instruction.
+/// # Errors
+/// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+/// * [CustomError::InvalidMpcGroup] - The `group_id` was already used, or the members or the threshold are invalid, see [MpcGroup::initialize].
+pub fn handler_add_mpc_group(
+    ctx: Context<AddMpcGroup>,
+    group_id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    // The identifier of a removed group is never reused, so the trades referencing it cannot be taken over
+    let config = &mut ctx.accounts.config;
+    if group_id < config.next_mpc_group_id {
+        return Err(CustomError::InvalidMpcGroup.into());
+    }
+    config.next_mpc_group_id = group_id.checked_add(1).ok_or(CustomError::InvalidMpcGroup)?;
+
+    let mpc_group = &mut ctx.accounts.mpc_group;
+    mpc_group.initialize(group_id, members, threshold)?;
+
+    emit!(MpcGroupUpdated {
+        mpc_group: mpc_group.key(),
+        group_id,
+        members: mpc_group.members.clone(),
+        threshold,
+        admin: ctx.accounts.signer.key(),
+        is_add: true,
+    });
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! This module contains the logic for registering or updating an MPC key in the MPC registry.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::CustomError;
+use crate::MpcRegistryUpdated;
+
+/// The context accounts required for the add or update MPC registry instruction.
+#[derive(Accounts)]
+#[instruction(mpc_pubkey: Pubkey)]
+pub struct AddOrUpdateMpcRegistry<'info> {
+    /// The admin that is authorized to perform the add or update MPC registry instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The MPC registry PDA account of the MPC key.
+    /// Will be initialized by the signer if needed.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + MpcRegistry::INIT_SPACE,
+        seeds = [MpcRegistry::SEED, mpc_pubkey.as_ref()],
+        bump,
+    )]
+    pub mpc_registry: Account<'info, MpcRegistry>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Handles the registering or updating of an MPC key, the key is active after this instruction.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [AddOrUpdateMpcRegistry] required for registering the MPC key.
+/// * `mpc_pubkey` - The MPC key, or the address of an [MpcGroup].
+/// * `valid_from` - The time from which the deposits can reference the MPC key.
+/// * `valid_until` - The time until which the deposits can reference the MPC key. Zero means no limit.
+/// # Errors
+/// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+/// * [CustomError::InvalidPublicKey] - The MPC key is the default pubkey.
+/// * [CustomError::InvalidValidityWindow] - The `valid_until` is before the `valid_from`.
+pub fn handler_add_or_update_mpc_registry(
+    ctx: Context<AddOrUpdateMpcRegistry>,
+    mpc_pubkey: Pubkey,
+    valid_from: i64,
+    valid_until: i64,
+) -> Result<()> {
+    if mpc_pubkey == Pubkey::default() {
+        return Err(CustomError::InvalidPublicKey.into());
+    }
+    if valid_until != 0 && valid_until < valid_from {
+        return Err(CustomError::InvalidValidityWindow.into());
+    }
+
+    let mpc_registry = &mut ctx.accounts.mpc_registry;
+    mpc_registry.mpc_pubkey = mpc_pubkey;
+    mpc_registry.is_active = true;
+    mpc_registry.valid_from = valid_from;
+    mpc_registry.valid_until = valid_until;
+
+    emit!(MpcRegistryUpdated {
+        mpc_pubkey,
+        admin: ctx.accounts.signer.key(),
+        valid_from,
+        valid_until,
+        is_active: true,
+    });
+    Ok(())
+}
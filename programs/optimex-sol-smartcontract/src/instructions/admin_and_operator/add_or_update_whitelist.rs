@@ -1,18 +1,17 @@
 //! This module contains the logic for adding or updating the whitelist token for the protocol.
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 use crate::state::*;
 use crate::CustomError;
+use crate::WhitelistUpdated;
+use crate::utils::assert_supported_mint;
 
 /// The context accounts required for the add or update whitelist instruction.
 #[derive(Accounts)]
 pub struct AddOrUpdateWhitelist<'info> {
     /// The operator that is authorized to perform the add or update whitelist instruction.
-    /// Must be the [Config::operators]
-    #[account(
-        mut,
-        constraint = config.operators.contains(operator.key) @ CustomError::Unauthorized,
-    )]
+    /// Must have the [Role::WhitelistManager] role.
+    #[account(mut)]
     pub operator: Signer<'info>,
 
     /// The config PDA account that contains the protocol configuration.
@@ -34,11 +33,19 @@ pub struct AddOrUpdateWhitelist<'info> {
     pub whitelist_token: Account<'info, WhitelistToken>,
 
     /// The mint token account that we want to set whitelist.
+    /// Can be a SPL Token or a Token-2022 mint, see [assert_supported_mint] for the supported extensions.
     #[account()]
-    pub token: Box<Account<'info, Mint>>,
+    pub token: Box<InterfaceAccount<'info, Mint>>,
     
     /// System program.
     pub system_program: Program<'info, System>,
+
+    /// CHECK:
+    /// The role member PDA account of the operator, which must grant the [Role::WhitelistManager] role.
+    #[account(
+        constraint = RoleMember::has_role(role_member.as_ref(), Role::WhitelistManager, operator.key) @ CustomError::Unauthorized,
+    )]
+    pub role_member: UncheckedAccount<'info>,
 }
 
 
@@ -47,10 +54,29 @@ pub struct AddOrUpdateWhitelist<'info> {
 /// # Arguments
 /// * `ctx` - A [Context] of [AddOrUpdateWhitelist] required for adding or updating the whitelist.
 /// * `amount` - The minimum amount to set for the whitelisted token.
+/// * `max_amount` - The maximum amount of a single deposit. Zero means no limit.
+/// * `max_outstanding` - The maximum amount held by the trade vaults. Zero means no limit.
 /// # Errors
-/// * [CustomError::Unauthorized] - The caller is not authorized, or not the operator.
-pub fn handler_add_or_update_whitelist(ctx: Context<AddOrUpdateWhitelist>, amount: u64) -> Result<()> {
+/// * [CustomError::Unauthorized] - The caller is not authorized, or not a [Role::WhitelistManager].
+/// * [CustomError::UnsupportedMintExtension] - The Token-2022 mint has an extension that can endanger the vault funds.
+pub fn handler_add_or_update_whitelist(
+    ctx: Context<AddOrUpdateWhitelist>,
+    amount: u64,
+    max_amount: u64,
+    max_outstanding: u64,
+) -> Result<()> {
     let token = &ctx.accounts.token;
+    assert_supported_mint(&token.to_account_info())?;
     let whitelist_token = &mut ctx.accounts.whitelist_token;
-    whitelist_token.initialize(token.key(), amount)
+    whitelist_token.initialize(token.key(), amount, max_amount, max_outstanding)?;
+
+    emit!(WhitelistUpdated {
+        token: token.key(),
+        operator: ctx.accounts.operator.key(),
+        amount,
+        max_amount,
+        max_outstanding,
+        is_add: true,
+    });
+    Ok(())
 }
\ No newline at end of file
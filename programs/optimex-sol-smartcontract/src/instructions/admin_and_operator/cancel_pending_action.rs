@@ -0,0 +1,66 @@
+//! This module contains the logic for cancelling a queued sensitive admin operation.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::PendingActionCancelled;
+
+/// Handles the cancellation of a queued sensitive admin operation, before or after its [PendingAction::eta].
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [CancelPendingAction] required for cancelling the action.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not the [Config::admin] or a [Role::Guardian].
+/// * [CustomError::InvalidUserAccount] when the admin account is not the [PendingAction::admin], who receives the rent fee.
+pub fn handler_cancel_pending_action(ctx: Context<CancelPendingAction>) -> Result<()> {
+    let action = ctx.accounts.pending_action.action.clone();
+
+    emit!(PendingActionCancelled {
+        action_id: action.id(),
+        action,
+        operator: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+/// The context accounts required for the cancel pending action instruction.
+#[derive(Accounts)]
+pub struct CancelPendingAction<'info> {
+    /// The signer account that is authorized to perform the cancel pending action instruction.
+    /// Must be the [Config::admin] or have the [Role::Guardian] role.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The pending action PDA account to cancel.
+    /// Will be closed and transferred rent fee to the admin who queued it.
+    #[account(
+        mut,
+        seeds = [PendingAction::SEED, pending_action.action.id().as_ref()],
+        bump,
+        close = admin,
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    /// CHECK:
+    /// The admin who queued the action and paid its rent fee, refunded on cancellation.
+    /// Must be the [PendingAction::admin].
+    #[account(
+        mut,
+        address = pending_action.admin @ CustomError::InvalidUserAccount,
+    )]
+    pub admin: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The role member PDA account of the signer as [Role::Guardian], unless the signer is the [Config::admin].
+    #[account(
+        constraint = signer.key() == config.admin
+            || RoleMember::has_role(guardian.as_ref(), Role::Guardian, signer.key) @ CustomError::Unauthorized,
+    )]
+    pub guardian: UncheckedAccount<'info>,
+}
@@ -0,0 +1,86 @@
+//! This module contains the logic for granting a role of the protocol.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::RoleChanged;
+
+use super::execute_pending_action;
+
+/// Handles the granting of a role to a member.
+///
+/// When the [Config::timelock_delay] is set, the operation must be queued as a [TimelockAction::GrantRole] first.
+/// # Arguments
+/// * `ctx` - A [Context] of [GrantRole] required for granting the role.
+/// * `role` - The [Role] to grant.
+/// * `member` - The member of the role.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+/// * [CustomError::InvalidPublicKey] when the member is the default pubkey.
+/// * [CustomError::TimelockRequired] when the operation must be queued first.
+/// * [CustomError::InvalidPendingAction] when the pending action is not the operation.
+/// * [CustomError::TimelockNotReady] when the pending action cannot be executed yet.
+pub fn handler_grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+    if member == Pubkey::default() {
+        return Err(CustomError::InvalidPublicKey.into());
+    }
+    execute_pending_action(
+        &ctx.accounts.config,
+        ctx.accounts.pending_action.as_deref(),
+        TimelockAction::GrantRole(role, member),
+        ctx.accounts.signer.key(),
+    )?;
+
+    let role_member = &mut ctx.accounts.role_member;
+    role_member.role = role;
+    role_member.member = member;
+
+    emit!(RoleChanged {
+        role,
+        member,
+        admin: ctx.accounts.signer.key(),
+        is_grant: true,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the grant role instruction.
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct GrantRole<'info> {
+    /// The signer account that is authorized to perform the grant role instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The role member PDA account of the role and member.
+    /// Will be initialized by the signer.
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + RoleMember::INIT_SPACE,
+        seeds = [RoleMember::SEED, &[role as u8], member.as_ref()],
+        bump,
+    )]
+    pub role_member: Account<'info, RoleMember>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.
+    /// Will be closed and transferred rent fee to the signer.
+    #[account(
+        mut,
+        close = signer,
+    )]
+    pub pending_action: Option<Account<'info, PendingAction>>,
+}
@@ -1,6 +1,7 @@
 //! This module contains the logic for initializing the protocol.
 use anchor_lang::prelude::*;
 use crate::state::Config;
+use crate::event::{AdminChanged, Initialized};
 
 use crate::program::OptimexSolSmartcontract;
 
@@ -19,8 +20,21 @@ pub struct InitArgs {
 pub fn handler_init(ctx: Context<Init>, init_args: InitArgs) -> Result<()> {
     let config = &mut ctx.accounts.config;
     if let Some(admin) = init_args.admin {
+        let previous_admin = config.admin;
         config.admin = admin;
+        // Discard any admin transfer that was in progress
+        config.pending_admin = Pubkey::default();
+
+        emit!(AdminChanged {
+            previous_admin,
+            admin,
+        });
     }
+
+    emit!(Initialized {
+        admin: config.admin,
+        operator: ctx.accounts.signer.key(),
+    });
     Ok(())
 }
 
@@ -0,0 +1,69 @@
+//! This module contains the logic for migrating the legacy guardian to the roles of the protocol.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::RoleChanged;
+
+/// Handles the migration of the legacy [Config::guardian].
+///
+/// The guardian is granted the [Role::Guardian] role, which replaces its permissions, and the legacy guardian is cleared.
+/// As it carries over existing permissions, the operation is not timelocked.
+/// # Arguments
+/// * `ctx` - A [Context] of [MigrateGuardian] required for migrating the guardian.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+/// * [CustomError::InvalidPublicKey] when there is no legacy guardian.
+pub fn handler_migrate_guardian(ctx: Context<MigrateGuardian>) -> Result<()> {
+    let guardian = ctx.accounts.config.guardian;
+    if guardian == Pubkey::default() {
+        return Err(CustomError::InvalidPublicKey.into());
+    }
+    ctx.accounts.config.guardian = Pubkey::default();
+
+    let role_member = &mut ctx.accounts.role_member;
+    role_member.role = Role::Guardian;
+    role_member.member = guardian;
+
+    emit!(RoleChanged {
+        role: Role::Guardian,
+        member: guardian,
+        admin: ctx.accounts.signer.key(),
+        is_grant: true,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the migrate guardian instruction.
+#[derive(Accounts)]
+pub struct MigrateGuardian<'info> {
+    /// The signer account that is authorized to perform the migrate guardian instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    /// Its legacy guardian is cleared.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The role member PDA account of the legacy guardian as [Role::Guardian].
+    /// Will be initialized by the signer.
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + RoleMember::INIT_SPACE,
+        seeds = [RoleMember::SEED, &[Role::Guardian as u8], config.guardian.as_ref()],
+        bump,
+    )]
+    pub role_member: Account<'info, RoleMember>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
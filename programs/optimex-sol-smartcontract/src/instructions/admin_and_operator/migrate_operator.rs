@@ -0,0 +1,81 @@
+//! This module contains the logic for migrating a legacy operator to the roles of the protocol.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::RoleChanged;
+
+/// Handles the migration of a legacy operator of [Config::operators].
+///
+/// The operator is granted the [Role::WhitelistManager] and [Role::DurationManager] roles, which replace its permissions,
+/// and removed from the legacy operators. As it carries over existing permissions, the operation is not timelocked.
+/// # Arguments
+/// * `ctx` - A [Context] of [MigrateOperator] required for migrating the operator.
+/// * `operator` - The legacy operator to migrate.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+/// * [CustomError::OperatorNotFound] when the operator is not in the legacy operators.
+pub fn handler_migrate_operator(ctx: Context<MigrateOperator>, operator: Pubkey) -> Result<()> {
+    ctx.accounts.config.remove_operator(operator)?;
+
+    for (role_member, role) in [
+        (&mut ctx.accounts.whitelist_manager, Role::WhitelistManager),
+        (&mut ctx.accounts.duration_manager, Role::DurationManager),
+    ] {
+        role_member.role = role;
+        role_member.member = operator;
+        emit!(RoleChanged {
+            role,
+            member: operator,
+            admin: ctx.accounts.signer.key(),
+            is_grant: true,
+        });
+    }
+    Ok(())
+}
+
+/// The context accounts required for the migrate operator instruction.
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct MigrateOperator<'info> {
+    /// The signer account that is authorized to perform the migrate operator instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    /// The operator is removed from its legacy operators.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The role member PDA account of the operator as [Role::WhitelistManager].
+    /// Will be initialized by the signer.
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + RoleMember::INIT_SPACE,
+        seeds = [RoleMember::SEED, &[Role::WhitelistManager as u8], operator.as_ref()],
+        bump,
+    )]
+    pub whitelist_manager: Account<'info, RoleMember>,
+
+    /// The role member PDA account of the operator as [Role::DurationManager].
+    /// Will be initialized by the signer.
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + RoleMember::INIT_SPACE,
+        seeds = [RoleMember::SEED, &[Role::DurationManager as u8], operator.as_ref()],
+        bump,
+    )]
+    pub duration_manager: Account<'info, RoleMember>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
@@ -1,16 +1,52 @@
 //! This module contains the instructions that only performed by the admin or operator.
 pub mod init;
-pub mod add_or_remove_operator;
 pub mod add_or_update_whitelist;
 pub mod remove_whitelist;
 pub mod set_close_wait_duration;
 pub mod add_fee_receiver;
 pub mod remove_fee_receiver;
+pub mod set_pause;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod set_max_fee;
+pub mod add_mpc_group;
+pub mod remove_mpc_group;
+pub mod add_or_update_mpc_registry;
+pub mod retire_mpc_registry;
+pub mod accrue_legacy_fee;
+pub mod set_fee_receiver_share;
+pub mod queue_pending_action;
+pub mod cancel_pending_action;
+pub mod set_timelock_delay;
+pub mod grant_role;
+pub mod revoke_role;
+pub mod migrate_operator;
+pub mod migrate_guardian;
+pub mod set_max_timeout_extension;
+pub mod set_solver_signature_required;
 
 pub use init::*;
-pub use add_or_remove_operator::*;
 pub use add_or_update_whitelist::*;
 pub use remove_whitelist::*;
 pub use set_close_wait_duration::*;
 pub use add_fee_receiver::*;
-pub use remove_fee_receiver::*;
\ No newline at end of file
+pub use remove_fee_receiver::*;
+pub use set_pause::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use set_max_fee::*;
+pub use add_mpc_group::*;
+pub use remove_mpc_group::*;
+pub use add_or_update_mpc_registry::*;
+pub use retire_mpc_registry::*;
+pub use accrue_legacy_fee::*;
+pub use set_fee_receiver_share::*;
+pub use queue_pending_action::*;
+pub use cancel_pending_action::*;
+pub use set_timelock_delay::*;
+pub use grant_role::*;
+pub use revoke_role::*;
+pub use migrate_operator::*;
+pub use migrate_guardian::*;
+pub use set_max_timeout_extension::*;
+pub use set_solver_signature_required::*;
@@ -0,0 +1,44 @@
+//! This module contains the logic for proposing a new admin for the protocol.
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::CustomError;
+use crate::event::AdminProposed;
+
+/// Handles the proposing of a new admin for the protocol.
+///
+/// The proposed admin only takes the role after performing the accept admin instruction.
+/// # Arguments
+/// * `ctx` - A [Context] of [ProposeAdmin] required for proposing the new admin.
+/// * `new_admin` - The proposed admin. [Pubkey::default] to cancel the pending proposal.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+pub fn handler_propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = new_admin;
+
+    emit!(AdminProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the propose admin instruction.
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    /// The signer account that is authorized to perform the propose admin instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,87 @@
+//! This module contains the logic for queuing a sensitive admin operation behind the timelock.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::{PendingActionExecuted, PendingActionQueued};
+
+/// Handles the queuing of a sensitive admin operation.
+///
+/// The action can be executed by its instruction once the [Config::timelock_delay] has passed, see [execute_pending_action].
+/// # Arguments
+/// * `ctx` - A [Context] of [QueuePendingAction] required for queuing the action.
+/// * `action` - The [TimelockAction] to queue.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+pub fn handler_queue_pending_action(ctx: Context<QueuePendingAction>, action: TimelockAction) -> Result<()> {
+    let eta = Clock::get()?.unix_timestamp + ctx.accounts.config.timelock_delay as i64;
+    let pending_action = &mut ctx.accounts.pending_action;
+    pending_action.action = action.clone();
+    pending_action.admin = ctx.accounts.signer.key();
+    pending_action.eta = eta;
+
+    emit!(PendingActionQueued {
+        action_id: action.id(),
+        action,
+        admin: ctx.accounts.signer.key(),
+        eta,
+    });
+    Ok(())
+}
+
+/// Asserts that the `action` can be performed, and emits the [PendingActionExecuted] event when it was queued.
+///
+/// Shared by the instructions of the [TimelockAction], which close the `pending_action` once executed.
+/// # Errors
+/// * [CustomError::TimelockRequired] when the action must be queued first.
+/// * [CustomError::InvalidPendingAction] when the pending action is not the `action`.
+/// * [CustomError::TimelockNotReady] when the pending action cannot be executed yet.
+pub(crate) fn execute_pending_action(
+    config: &Config,
+    pending_action: Option<&PendingAction>,
+    action: TimelockAction,
+    admin: Pubkey,
+) -> Result<()> {
+    config.assert_timelock(pending_action, &action, Clock::get()?.unix_timestamp)?;
+    if pending_action.is_some() {
+        emit!(PendingActionExecuted {
+            action_id: action.id(),
+            action,
+            admin,
+        });
+    }
+    Ok(())
+}
+
+/// The context accounts required for the queue pending action instruction.
+#[derive(Accounts)]
+#[instruction(action: TimelockAction)]
+pub struct QueuePendingAction<'info> {
+    /// The signer account that is authorized to perform the queue pending action instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The pending action PDA account of the action.
+    /// Will be initialized by the signer, the same action cannot be queued twice.
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PendingAction::INIT_SPACE,
+        seeds = [PendingAction::SEED, action.id().as_ref()],
+        bump,
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
@@ -2,6 +2,9 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::CustomError;
+use crate::FeeReceiverChanged;
+
+use super::execute_pending_action;
 
 /// The context accounts required for the add fee receiver instruction.
 #[derive(Accounts)]
@@ -16,7 +19,9 @@ pub struct RemoveFeeReceiverAccounts<'info> {
     pub signer: Signer<'info>,
 
     /// The config PDA account that contains the protocol configuration.
+    /// The share of the fee receiver is removed from its [Config::total_fee_share_bps].
     #[account(
+        mut,
         seeds = [Config::SEED],
         bump,
     )]
@@ -34,15 +39,42 @@ pub struct RemoveFeeReceiverAccounts<'info> {
 
     /// System program.
     pub system_program: Program<'info, System>,
+
+    /// The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.
+    /// Will be closed and transferred rent fee to the signer.
+    #[account(
+        mut,
+        close = signer,
+    )]
+    pub pending_action: Option<Account<'info, PendingAction>>,
 }
 
 
 /// Handles the removing of protocol fee receiver.
 /// 
+/// When the [Config::timelock_delay] is set, the operation must be queued as a [TimelockAction::RemoveFeeReceiver] first.
 /// # Arguments
 /// * `ctx` - A [Context] of [RemoveFeeReceiver] required for removing the fee receiver.
 /// # Errors
 /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
-pub fn handler_remove_fee_receiver(_ctx: Context<RemoveFeeReceiverAccounts>, _receiver_pubkey: Pubkey) -> Result<()> {
+/// * [CustomError::TimelockRequired] - The operation must be queued first.
+/// * [CustomError::InvalidPendingAction] - The pending action is not the operation.
+/// * [CustomError::TimelockNotReady] - The pending action cannot be executed yet.
+pub fn handler_remove_fee_receiver(ctx: Context<RemoveFeeReceiverAccounts>, receiver_pubkey: Pubkey) -> Result<()> {
+    execute_pending_action(
+        &ctx.accounts.config,
+        ctx.accounts.pending_action.as_deref(),
+        TimelockAction::RemoveFeeReceiver(receiver_pubkey),
+        ctx.accounts.signer.key(),
+    )?;
+
+    let share_bps = ctx.accounts.fee_receiver_account.share_bps;
+    ctx.accounts.config.update_fee_share(share_bps, 0)?;
+
+    emit!(FeeReceiverChanged {
+        receiver: receiver_pubkey,
+        admin: ctx.accounts.signer.key(),
+        is_add: false,
+    });
     Ok(())
 }
@@ -0,0 +1,58 @@
+//! This module contains the logic for removing the MPC group for the protocol.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::CustomError;
+use crate::MpcGroupUpdated;
+
+/// The context accounts required for the remove MPC group instruction.
+#[derive(Accounts)]
+#[instruction(group_id: u64)]
+pub struct RemoveMpcGroup<'info> {
+    /// The admin that is authorized to perform the remove MPC group instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The MPC group PDA account that contains the members and the threshold.
+    /// Will be closed and transferred rent fee to the signer.
+    /// The trades referencing this group can no longer be authorized by the MPC, only claimed after the timeout.
+    #[account(
+        mut,
+        seeds = [MpcGroup::SEED, &group_id.to_le_bytes()],
+        bump,
+        close = signer,
+    )]
+    pub mpc_group: Account<'info, MpcGroup>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Handles the removing of the MPC group.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [RemoveMpcGroup] required for removing the MPC group.
+/// * `group_id` - The identifier of the group.
+/// # Errors
+/// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+pub fn handler_remove_mpc_group(ctx: Context<RemoveMpcGroup>, group_id: u64) -> Result<()> {
+    emit!(MpcGroupUpdated {
+        mpc_group: ctx.accounts.mpc_group.key(),
+        group_id,
+        members: vec![],
+        threshold: 0,
+        admin: ctx.accounts.signer.key(),
+        is_add: false,
+    });
+    Ok(())
+}
@@ -1,18 +1,16 @@
 //! This module contains the logic for removing the whitelist token for the protocol.
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 use crate::state::*;
 use crate::CustomError;
+use crate::WhitelistUpdated;
 
 /// The context accounts required for the remove whitelist instruction.
 #[derive(Accounts)]
 pub struct RemoveWhitelist<'info> {
     /// The operator that is authorized to perform the remove whitelist instruction.
-    /// Must be the [Config::operators]
-    #[account(
-        mut,
-        constraint = config.operators.contains(operator.key) @ CustomError::Unauthorized,
-    )]
+    /// Must have the [Role::WhitelistManager] role.
+    #[account(mut)]
     pub operator: Signer<'info>,
 
     /// The config PDA account that contains the protocol configuration.
@@ -32,10 +30,17 @@ pub struct RemoveWhitelist<'info> {
 
     /// The mint token account that we want to remove whitelist.
     #[account()]
-    pub token: Box<Account<'info, Mint>>,
+    pub token: Box<InterfaceAccount<'info, Mint>>,
     
     /// System program.
     pub system_program: Program<'info, System>,
+
+    /// CHECK:
+    /// The role member PDA account of the operator, which must grant the [Role::WhitelistManager] role.
+    #[account(
+        constraint = RoleMember::has_role(role_member.as_ref(), Role::WhitelistManager, operator.key) @ CustomError::Unauthorized,
+    )]
+    pub role_member: UncheckedAccount<'info>,
 }
 
 /// Handles the removing the whitelist token for the protocol.
@@ -43,7 +48,17 @@ pub struct RemoveWhitelist<'info> {
 /// # Arguments
 /// * `ctx` - A [Context] of [RemoveWhitelist] required for removing the whitelist.
 /// # Errors
-/// * [CustomError::Unauthorized] when the caller is not authorized, or not the operator.
-pub fn handler_remove_whitelist(_ctx: Context<RemoveWhitelist>) -> Result<()> {
+/// * [CustomError::Unauthorized] when the caller is not authorized, or not a [Role::WhitelistManager].
+pub fn handler_remove_whitelist(ctx: Context<RemoveWhitelist>) -> Result<()> {
+    let whitelist_token = &ctx.accounts.whitelist_token;
+
+    emit!(WhitelistUpdated {
+        token: whitelist_token.token,
+        operator: ctx.accounts.operator.key(),
+        amount: whitelist_token.amount,
+        max_amount: whitelist_token.max_amount,
+        max_outstanding: whitelist_token.max_outstanding,
+        is_add: false,
+    });
     Ok(())
 }
\ No newline at end of file
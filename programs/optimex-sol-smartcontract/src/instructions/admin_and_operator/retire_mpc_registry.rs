@@ -0,0 +1,56 @@
+//! This module contains the logic for retiring an MPC key of the MPC registry.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::CustomError;
+use crate::MpcRegistryUpdated;
+
+/// The context accounts required for the retire MPC registry instruction.
+#[derive(Accounts)]
+#[instruction(mpc_pubkey: Pubkey)]
+pub struct RetireMpcRegistry<'info> {
+    /// The admin that is authorized to perform the retire MPC registry instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The MPC registry PDA account of the MPC key.
+    /// Is kept after the retirement, so the key can be registered again with [AddOrUpdateMpcRegistry].
+    #[account(
+        mut,
+        seeds = [MpcRegistry::SEED, mpc_pubkey.as_ref()],
+        bump,
+    )]
+    pub mpc_registry: Account<'info, MpcRegistry>,
+}
+
+/// Handles the retiring of an MPC key.
+///
+/// The new deposits can no longer reference the MPC key.
+/// The trades deposited before are not affected, the MPC key can still settle or refund them.
+/// # Arguments
+/// * `ctx` - A [Context] of [RetireMpcRegistry] required for retiring the MPC key.
+/// * `mpc_pubkey` - The MPC key to retire.
+/// # Errors
+/// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+pub fn handler_retire_mpc_registry(ctx: Context<RetireMpcRegistry>, mpc_pubkey: Pubkey) -> Result<()> {
+    let mpc_registry = &mut ctx.accounts.mpc_registry;
+    mpc_registry.is_active = false;
+
+    emit!(MpcRegistryUpdated {
+        mpc_pubkey,
+        admin: ctx.accounts.signer.key(),
+        valid_from: mpc_registry.valid_from,
+        valid_until: mpc_registry.valid_until,
+        is_active: false,
+    });
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! This module contains the logic for revoking a role of the protocol.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::RoleChanged;
+
+use super::execute_pending_action;
+
+/// Handles the revoking of the role of a member.
+///
+/// When the [Config::timelock_delay] is set, the operation must be queued as a [TimelockAction::RevokeRole] first.
+/// # Arguments
+/// * `ctx` - A [Context] of [RevokeRole] required for revoking the role.
+/// * `role` - The [Role] to revoke.
+/// * `member` - The member of the role.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+/// * [CustomError::TimelockRequired] when the operation must be queued first.
+/// * [CustomError::InvalidPendingAction] when the pending action is not the operation.
+/// * [CustomError::TimelockNotReady] when the pending action cannot be executed yet.
+pub fn handler_revoke_role(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+    execute_pending_action(
+        &ctx.accounts.config,
+        ctx.accounts.pending_action.as_deref(),
+        TimelockAction::RevokeRole(role, member),
+        ctx.accounts.signer.key(),
+    )?;
+
+    emit!(RoleChanged {
+        role,
+        member,
+        admin: ctx.accounts.signer.key(),
+        is_grant: false,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the revoke role instruction.
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct RevokeRole<'info> {
+    /// The signer account that is authorized to perform the revoke role instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The role member PDA account of the role and member.
+    /// Will be closed and transferred rent fee to the signer.
+    #[account(
+        mut,
+        seeds = [RoleMember::SEED, &[role as u8], member.as_ref()],
+        bump,
+        close = signer,
+    )]
+    pub role_member: Account<'info, RoleMember>,
+
+    /// The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.
+    /// Will be closed and transferred rent fee to the signer.
+    #[account(
+        mut,
+        close = signer,
+    )]
+    pub pending_action: Option<Account<'info, PendingAction>>,
+}
@@ -2,6 +2,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::CustomError;
+use crate::CloseDurationChanged;
 
 
 /// Parameters required for setting the close wait duration.
@@ -19,11 +20,8 @@ pub struct SetCloseWaitDurationArgs {
 #[derive(Accounts)]
 pub struct SetCloseWaitDuration<'info> {
     /// The operator that is authorized to perform the set close wait duration instruction.
-    /// Must be [Config::operators]
-    #[account(
-        mut,
-        constraint = config.operators.contains(operator.key) @ CustomError::Unauthorized,
-    )]
+    /// Must have the [Role::DurationManager] role.
+    #[account(mut)]
     pub operator: Signer<'info>,
 
     /// The config PDA account that contains the protocol configuration.
@@ -33,6 +31,13 @@ pub struct SetCloseWaitDuration<'info> {
         bump,
     )]
     pub config: Account<'info, Config>,
+
+    /// CHECK:
+    /// The role member PDA account of the operator, which must grant the [Role::DurationManager] role.
+    #[account(
+        constraint = RoleMember::has_role(role_member.as_ref(), Role::DurationManager, operator.key) @ CustomError::Unauthorized,
+    )]
+    pub role_member: UncheckedAccount<'info>,
 }
 
 /// Handles the setting the close wait duration for the protocol.
@@ -40,10 +45,16 @@ pub struct SetCloseWaitDuration<'info> {
 /// * `ctx` - A [Context] of [SetCloseWaitDuration] required for setting the waiting duration.
 /// * `set_close_wait_duration_args` - An argument [SetCloseWaitDurationArgs] required for setting the waiting duration.
 /// # Errors
-/// * [CustomError::Unauthorized] when the caller is not authorized.
+/// * [CustomError::Unauthorized] when the caller is not authorized, not a [Role::DurationManager].
 pub fn handler_set_close_wait_duration(ctx: Context<SetCloseWaitDuration>, set_close_wait_duration_args: SetCloseWaitDurationArgs) -> Result<()> {
     let config = &mut ctx.accounts.config;
     config.set_close_trade_duration(set_close_wait_duration_args.close_trade_duration)?;
     config.set_close_payment_duration(set_close_wait_duration_args.close_payment_duration)?;
+
+    emit!(CloseDurationChanged {
+        operator: ctx.accounts.operator.key(),
+        close_trade_duration: config.close_trade_duration,
+        close_payment_duration: config.close_payment_duration,
+    });
     Ok(())
 }
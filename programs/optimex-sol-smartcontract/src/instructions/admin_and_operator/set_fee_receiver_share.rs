@@ -0,0 +1,62 @@
+//! This module contains the logic for setting the share of a fee receiver.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::CustomError;
+use crate::FeeShareChanged;
+
+/// The context accounts required for the set fee receiver share instruction.
+#[derive(Accounts)]
+#[instruction(receiver_pubkey: Pubkey)]
+pub struct SetFeeReceiverShareAccounts<'info> {
+    /// The admin that is authorized to perform the set fee receiver share instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    /// The [Config::total_fee_share_bps] is updated with the new share.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The fee receiver PDA account that contains the fee receiver information.
+    #[account(
+        mut,
+        seeds = [FeeReceiver::SEED, receiver_pubkey.as_ref()],
+        bump,
+    )]
+    pub fee_receiver: Account<'info, FeeReceiver>,
+}
+
+/// Handles the setting of the share of a fee receiver.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [SetFeeReceiverShareAccounts] required for setting the share.
+/// * `receiver_pubkey` - The pubkey of the fee receiver.
+/// * `share_bps` - The share of the fee receiver in the distributed fee, in basis points.
+/// # Errors
+/// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+/// * [CustomError::InvalidFeeShare] - The shares of the fee receivers would sum to more than 100%.
+pub fn handler_set_fee_receiver_share(
+    ctx: Context<SetFeeReceiverShareAccounts>,
+    receiver_pubkey: Pubkey,
+    share_bps: u16,
+) -> Result<()> {
+    let fee_receiver = &mut ctx.accounts.fee_receiver;
+    let config = &mut ctx.accounts.config;
+    config.update_fee_share(fee_receiver.share_bps, share_bps)?;
+    fee_receiver.share_bps = share_bps;
+
+    emit!(FeeShareChanged {
+        receiver: receiver_pubkey,
+        admin: ctx.accounts.signer.key(),
+        share_bps,
+        total_fee_share_bps: config.total_fee_share_bps,
+    });
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! This module contains the logic for setting the maximum total fee of the protocol.
+use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+use crate::state::*;
+use crate::CustomError;
+use crate::MaxFeeChanged;
+
+/// Handles the setting of the maximum total fee, globally or for a whitelisted token.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [SetMaxFee] required for setting the maximum fee.
+/// * `max_fee_bps` - The maximum total fee in basis points of the amount. None to remove the limit, or the token override.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not a [Role::FeeManager].
+/// * [CustomError::InvalidMaxFeeBps] when the maximum fee is greater than 100%.
+pub fn handler_set_max_fee(ctx: Context<SetMaxFee>, max_fee_bps: Option<u16>) -> Result<()> {
+    if max_fee_bps.unwrap_or_default() > BPS_DENOMINATOR {
+        return err!(CustomError::InvalidMaxFeeBps);
+    }
+
+    // Set the token override when the whitelist token is provided, otherwise the global maximum fee
+    let token = match ctx.accounts.whitelist_token.as_mut() {
+        Some(whitelist_token) => {
+            whitelist_token.max_fee_bps = max_fee_bps;
+            Some(whitelist_token.token)
+        }
+        None => {
+            ctx.accounts.config.max_fee_bps = max_fee_bps;
+            None
+        }
+    };
+
+    emit!(MaxFeeChanged {
+        operator: ctx.accounts.signer.key(),
+        token,
+        max_fee_bps,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the set max fee instruction.
+#[derive(Accounts)]
+pub struct SetMaxFee<'info> {
+    /// The signer account that is authorized to perform the set max fee instruction.
+    /// Must have the [Role::FeeManager] role.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The whitelist token PDA account to set the maximum fee override for.
+    /// If it is none, the global maximum fee [Config::max_fee_bps] is set.
+    #[account(
+        mut,
+        seeds = [WhitelistToken::SEED, whitelist_token.token.as_ref()],
+        bump,
+    )]
+    pub whitelist_token: Option<Account<'info, WhitelistToken>>,
+
+    /// CHECK:
+    /// The role member PDA account of the signer, which must grant the [Role::FeeManager] role.
+    #[account(
+        constraint = RoleMember::has_role(role_member.as_ref(), Role::FeeManager, signer.key) @ CustomError::Unauthorized,
+    )]
+    pub role_member: UncheckedAccount<'info>,
+}
@@ -0,0 +1,45 @@
+//! This module contains the logic for setting the maximum timeout extension of the trades.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::CustomError;
+use crate::MaxTimeoutExtensionChanged;
+
+/// The context accounts required for the set max timeout extension instruction.
+#[derive(Accounts)]
+pub struct SetMaxTimeoutExtension<'info> {
+    /// The operator that is authorized to perform the set max timeout extension instruction.
+    /// Must have the [Role::DurationManager] role.
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK:
+    /// The role member PDA account of the operator, which must grant the [Role::DurationManager] role.
+    #[account(
+        constraint = RoleMember::has_role(role_member.as_ref(), Role::DurationManager, operator.key) @ CustomError::Unauthorized,
+    )]
+    pub role_member: UncheckedAccount<'info>,
+}
+
+/// Handles the setting of the maximum total extension of a trade timeout.
+/// # Arguments
+/// * `ctx` - A [Context] of [SetMaxTimeoutExtension] required for setting the maximum timeout extension.
+/// * `max_timeout_extension` - The new [Config::max_timeout_extension], in seconds. Zero disables the extensions.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not a [Role::DurationManager].
+pub fn handler_set_max_timeout_extension(ctx: Context<SetMaxTimeoutExtension>, max_timeout_extension: u64) -> Result<()> {
+    ctx.accounts.config.max_timeout_extension = max_timeout_extension;
+
+    emit!(MaxTimeoutExtensionChanged {
+        operator: ctx.accounts.operator.key(),
+        max_timeout_extension,
+    });
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! This module contains the logic for pausing or unpausing the protocol instructions.
+use anchor_lang::prelude::*;
+use crate::state::{Config, Role, RoleMember};
+use crate::error::CustomError;
+use crate::event::PauseChanged;
+
+/// Parameters required for pausing or unpausing the protocol instructions.
+#[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetPauseArgs {
+    /// Whether to pause the `deposit` instruction. If it is none, the flag will not changed.
+    pub deposit: Option<bool>,
+    /// Whether to pause the `payment` instruction. If it is none, the flag will not changed.
+    pub payment: Option<bool>,
+    /// Whether to pause the `settlement` instruction. If it is none, the flag will not changed.
+    pub settlement: Option<bool>,
+    /// Whether to pause the `distribute_fees` instruction. If it is none, the flag will not changed.
+    pub distribute_fees: Option<bool>,
+}
+
+impl SetPauseArgs {
+    /// Whether the arguments only pause instructions, never unpause.
+    pub fn is_pause_only(&self) -> bool {
+        [self.deposit, self.payment, self.settlement, self.distribute_fees]
+            .iter()
+            .all(|flag| flag.unwrap_or(true))
+    }
+}
+
+/// Handles the pausing or unpausing the protocol instructions.
+///
+/// The [Config::admin] can pause and unpause, a [Role::Guardian] can only pause.
+/// # Arguments
+/// * `ctx` - A [Context] of [SetPause] required for setting the pause flags.
+/// * `set_pause_args` - An argument [SetPauseArgs] required for setting the pause flags.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not the [Config::admin] or a [Role::Guardian].
+/// * [CustomError::Unauthorized] when a [Role::Guardian] tries to unpause an instruction.
+pub fn handler_set_pause(ctx: Context<SetPause>, set_pause_args: SetPauseArgs) -> Result<()> {
+    let signer = &ctx.accounts.signer;
+    let config = &mut ctx.accounts.config;
+
+    // The guardian is only allowed to pause, unpausing is reserved for the admin
+    if signer.key() != config.admin && !set_pause_args.is_pause_only() {
+        return err!(CustomError::Unauthorized);
+    }
+    config.set_pause(&set_pause_args)?;
+
+    emit!(PauseChanged {
+        operator: signer.key(),
+        deposit: config.pause.deposit,
+        payment: config.pause.payment,
+        settlement: config.pause.settlement,
+        distribute_fees: config.pause.distribute_fees,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the set pause instruction.
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    /// The signer account that is authorized to perform the set pause instruction.
+    /// Must be the [Config::admin] or have the [Role::Guardian] role.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK:
+    /// The role member PDA account of the signer as [Role::Guardian], unless the signer is the [Config::admin].
+    #[account(
+        constraint = signer.key() == config.admin
+            || RoleMember::has_role(guardian.as_ref(), Role::Guardian, signer.key) @ CustomError::Unauthorized,
+    )]
+    pub guardian: UncheckedAccount<'info>,
+}
@@ -0,0 +1,42 @@
+//! This module contains the logic for requiring the solver signature of the deposits.
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::CustomError;
+use crate::event::SolverSignatureRequiredChanged;
+
+/// Handles the setting of whether the deposits must be authorized by the solver of the trade.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [SetSolverSignatureRequired] required for setting the solver signature requirement.
+/// * `required` - The new [Config::solver_signature_required].
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+pub fn handler_set_solver_signature_required(ctx: Context<SetSolverSignatureRequired>, required: bool) -> Result<()> {
+    ctx.accounts.config.solver_signature_required = required;
+
+    emit!(SolverSignatureRequiredChanged {
+        admin: ctx.accounts.signer.key(),
+        required,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the set solver signature required instruction.
+#[derive(Accounts)]
+pub struct SetSolverSignatureRequired<'info> {
+    /// The signer account that is authorized to perform the set solver signature required instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+}
@@ -0,0 +1,62 @@
+//! This module contains the logic for setting the timelock delay of the sensitive admin operations.
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::CustomError;
+use crate::event::TimelockDelayChanged;
+
+use super::execute_pending_action;
+
+/// Handles the setting of the timelock delay.
+///
+/// When the [Config::timelock_delay] is set, the new delay must be queued as a [TimelockAction::SetTimelockDelay] first.
+/// # Arguments
+/// * `ctx` - A [Context] of [SetTimelockDelay] required for setting the delay.
+/// * `timelock_delay` - The new delay in seconds, zero to disable the timelock.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
+/// * [CustomError::TimelockRequired] when the new delay must be queued first.
+/// * [CustomError::InvalidPendingAction] when the pending action is not the new delay.
+/// * [CustomError::TimelockNotReady] when the pending action cannot be executed yet.
+pub fn handler_set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: u64) -> Result<()> {
+    execute_pending_action(
+        &ctx.accounts.config,
+        ctx.accounts.pending_action.as_deref(),
+        TimelockAction::SetTimelockDelay(timelock_delay),
+        ctx.accounts.signer.key(),
+    )?;
+    ctx.accounts.config.timelock_delay = timelock_delay;
+
+    emit!(TimelockDelayChanged {
+        admin: ctx.accounts.signer.key(),
+        timelock_delay,
+    });
+    Ok(())
+}
+
+/// The context accounts required for the set timelock delay instruction.
+#[derive(Accounts)]
+pub struct SetTimelockDelay<'info> {
+    /// The signer account that is authorized to perform the set timelock delay instruction.
+    /// Must be the [Config::admin]
+    #[account(
+        mut,
+        address = config.admin @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// The config PDA account that contains the protocol configuration.
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The pending action PDA account of the new delay, required when the [Config::timelock_delay] is set.
+    /// Will be closed and transferred rent fee to the signer.
+    #[account(
+        mut,
+        close = signer,
+    )]
+    pub pending_action: Option<Account<'info, PendingAction>>,
+}
@@ -0,0 +1,134 @@
+//! This module contains the logic for closing the finished trades in batch.
+use anchor_lang::prelude::*;
+
+use crate::{assert_keys_equal, assert_token_program, error::CustomError, event::*, state::*, ID};
+
+use super::close_vault_token_account;
+
+/// The number of remaining accounts of each trade of the batch close finished trades instruction.
+pub const CLOSE_FINISHED_TRADE_GROUP_SIZE: usize = 7;
+
+/// Handles the close of the finished trades in batch.
+///
+/// Each trade is checked with the same rules as the close finished trade instruction, see [TradeDetail::assert_close_finished_trade].
+/// The signer is the MPC of the trade when it is the [TradeDetail::mpc_pubkey]. For the trades of the given [MpcGroup],
+/// the signer and the co-signing members must reach the [MpcGroup::threshold]. The trades of another group are closed like by anyone.
+///
+/// When the [MpcGroup] is given, the remaining accounts start with the co-signing members, the only signers of the remaining accounts.
+/// Then the remaining accounts are groups of [CLOSE_FINISHED_TRADE_GROUP_SIZE] accounts, one for each trade:
+/// the [TradeDetail] PDA, the [TradeVault] PDA, the user account, the token program, the mint, the vault and the user token accounts.
+/// The user account is the [TradeDetail::rent_payer], and the user token account the associated token account of the [TradeDetail::user_pubkey].
+/// For a trade with SOL, the last four accounts are not used, the program ID can be given instead.
+///
+/// The trades that cannot be closed yet, or are already closed, are skipped and reported by the [BatchCloseSkipped] event.
+/// # Arguments
+/// * `ctx` - A [Context] of [BatchCloseFinishedTradesAccounts] required for closing the trades.
+/// # Errors
+/// * [CustomError::InvalidBatchAccounts] when the remaining accounts are not groups of [CLOSE_FINISHED_TRADE_GROUP_SIZE] accounts, or a vault is not the [TradeVault] PDA of its trade.
+/// * [CustomError::InvalidUserAccount] when a user account is not match to [TradeDetail::rent_payer].
+/// * [CustomError::InvalidTokenProgram] when a token program is not the SPL Token or the Token-2022 program.
+/// * [CustomError::InvalidTokenAccount] when the token accounts do not match the trade.
+/// * [CustomError::InvalidMintKey] when the mint is not match with the [TradeDetail::token].
+pub fn handler_batch_close_finished_trades<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, BatchCloseFinishedTradesAccounts<'info>>,
+) -> Result<()> {
+    let mpc_group = ctx.accounts.mpc_group.as_ref();
+    // The co-signers come first, the trade PDAs of the groups cannot sign
+    let co_signers_count = match mpc_group {
+        Some(_) => ctx.remaining_accounts.iter().take_while(|account| account.is_signer).count(),
+        None => 0,
+    };
+    let (co_signers, remaining_accounts) = ctx.remaining_accounts.split_at(co_signers_count);
+    let groups = remaining_accounts.chunks_exact(CLOSE_FINISHED_TRADE_GROUP_SIZE);
+    if remaining_accounts.is_empty() || !groups.remainder().is_empty() {
+        return Err(CustomError::InvalidBatchAccounts.into());
+    }
+    let signer = ctx.accounts.signer.as_ref();
+    let close_trade_duration = ctx.accounts.config.close_trade_duration;
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+
+    let mut skipped: Vec<Pubkey> = vec![];
+    for group in groups {
+        let [trade_detail_info, vault_info, user_account, token_program, mint, vault_token_account, user_token_account] = group else {
+            return Err(CustomError::InvalidBatchAccounts.into());
+        };
+
+        // Already closed, by another batch or the close finished trade instruction
+        if trade_detail_info.owner != &ID || trade_detail_info.data_is_empty() {
+            skipped.push(trade_detail_info.key());
+            continue;
+        }
+        let user_trade_detail: Account<TradeDetail> = Account::try_from(trade_detail_info)?;
+        let trade_mpc_group = mpc_group
+            .filter(|mpc_group| mpc_group.key() == user_trade_detail.mpc_pubkey)
+            .map(|mpc_group| &**mpc_group);
+        let is_mpc = user_trade_detail.is_mpc_authorized(trade_mpc_group, signer, co_signers);
+        if user_trade_detail
+            .assert_close_finished_trade(current_timestamp, close_trade_duration, is_mpc)
+            .is_err()
+        {
+            skipped.push(trade_detail_info.key());
+            continue;
+        }
+
+        let trade_id = user_trade_detail.trade_id;
+        assert_keys_equal(user_account.key, &user_trade_detail.rent_payer(), CustomError::InvalidUserAccount)?;
+        let (vault_key, bump) = Pubkey::find_program_address(&[TradeVault::SEED, &trade_id], &ID);
+        assert_keys_equal(vault_info.key, &vault_key, CustomError::InvalidBatchAccounts)?;
+        let vault: Account<TradeVault> = Account::try_from(vault_info)?;
+
+        if let Some(token) = user_trade_detail.token {
+            assert_keys_equal(mint.key, &token, CustomError::InvalidMintKey)?;
+            // The token program gets the signature of the vault
+            assert_token_program(token_program.key)?;
+            close_vault_token_account(
+                token_program,
+                mint,
+                vault_token_account,
+                user_token_account,
+                vault_info,
+                &user_trade_detail.user_pubkey,
+                user_account,
+                &[TradeVault::SEED, &trade_id, &[bump]],
+            )?;
+        }
+
+        emit!(TradeClosed {
+            trade_id,
+            operator: signer.key(),
+            to_pubkey: user_account.key(),
+            token: user_trade_detail.token,
+        });
+
+        // Send the rent fee of the trade detail and the vault to the user
+        user_trade_detail.close(user_account.clone())?;
+        vault.close(user_account.clone())?;
+    }
+
+    if !skipped.is_empty() {
+        emit!(BatchCloseSkipped {
+            operator: signer.key(),
+            accounts: skipped,
+        });
+    }
+
+    Ok(())
+}
+
+/// The context accounts required for the batch close finished trades instruction.
+#[derive(Accounts)]
+pub struct BatchCloseFinishedTradesAccounts<'info> {
+    /// The signer account that performs the batch close finished trades instruction.
+    /// Depends on the trade status and timeout, the signer can be the MPC or anyone.
+    pub signer: Signer<'info>,
+
+    /// The config PDA that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The MPC group of the trades closed by their MPC group, if any.
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
+}
@@ -0,0 +1,76 @@
+//! This module contains the logic for closing the payment receipts in batch.
+use anchor_lang::prelude::*;
+
+use crate::{assert_keys_equal, error::CustomError, event::*, state::*, ID};
+
+/// Handles the close of the payment receipts in batch.
+///
+/// Each payment receipt is checked with the same rules as the close payment receipt instruction, see [PaymentReceipt::assert_close].
+/// The remaining accounts are the [PaymentReceipt] PDAs, all paid by the signer.
+///
+/// The payment receipts that cannot be closed yet, or are already closed, are skipped and reported by the [BatchCloseSkipped] event.
+/// # Arguments
+/// * `ctx` - A [Context] of [BatchClosePaymentReceiptsAccounts] required for closing the payment receipts.
+/// # Errors
+/// * [CustomError::InvalidBatchAccounts] when there is no payment receipt.
+/// * [CustomError::InvalidUserAccount] when the signer is not match to a [PaymentReceipt::from_pubkey].
+pub fn handler_batch_close_payment_receipts<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, BatchClosePaymentReceiptsAccounts<'info>>,
+) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() {
+        return Err(CustomError::InvalidBatchAccounts.into());
+    }
+    let signer = &ctx.accounts.signer;
+    let close_payment_duration = ctx.accounts.config.close_payment_duration;
+
+    let mut skipped: Vec<Pubkey> = vec![];
+    for payment_receipt_info in ctx.remaining_accounts {
+        // Already closed, by another batch or the close payment receipt instruction
+        if payment_receipt_info.owner != &ID || payment_receipt_info.data_is_empty() {
+            skipped.push(payment_receipt_info.key());
+            continue;
+        }
+        let payment_receipt: Account<PaymentReceipt> = Account::try_from(payment_receipt_info)?;
+        assert_keys_equal(signer.key, &payment_receipt.from_pubkey, CustomError::InvalidUserAccount)?;
+        if payment_receipt.assert_close(close_payment_duration).is_err() {
+            skipped.push(payment_receipt_info.key());
+            continue;
+        }
+
+        emit!(PaymentReceiptClosed {
+            trade_id: payment_receipt.trade_id,
+            payment_receipt: payment_receipt.key(),
+            from_pubkey: payment_receipt.from_pubkey,
+            to_pubkey: payment_receipt.to_pubkey,
+            token: payment_receipt.token,
+        });
+
+        // Send the rent fee of the payment receipt to the signer
+        payment_receipt.close(signer.to_account_info())?;
+    }
+
+    if !skipped.is_empty() {
+        emit!(BatchCloseSkipped {
+            operator: signer.key(),
+            accounts: skipped,
+        });
+    }
+
+    Ok(())
+}
+
+/// Accounts required for the batch close payment receipts instruction.
+#[derive(Accounts)]
+pub struct BatchClosePaymentReceiptsAccounts<'info> {
+    /// The signer account, which is authorized to perform the batch close payment receipts instruction.
+    /// Must be the [PaymentReceipt::from_pubkey] of all the payment receipts.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The config PDA that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+}
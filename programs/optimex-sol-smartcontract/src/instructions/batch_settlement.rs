@@ -0,0 +1,197 @@
+//! This module contains the logic for the batch settlement instruction.
+use anchor_lang::prelude::*;
+
+use crate::{constants::WSOL_MINT, error::CustomError, event::*, state::*, utils::*, ID};
+
+/// Parameters required for the batch settlement instruction.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct BatchSettlementArgs {
+    /// Token public key of all the trades, none if the trades are with SOL.
+    pub token: Option<Pubkey>,
+    /// The tradeIds of the trades to settle, in the order of the remaining accounts.
+    pub trade_ids: Vec<[u8; 32]>,
+}
+
+/// Handles the settlement of several trades sharing the same MPC, PMM and token.
+///
+/// Each trade is checked with the same rules as the settlement instruction, and emits its own [Settled] event.
+/// The remaining accounts are, for each trade in the order of [BatchSettlementArgs::trade_ids]:
+/// the [TradeDetail] PDA, the [TradeVault] PDA, the user account, the user ephemeral account which must sign,
+/// and the [NonceCheckAccount] PDA of the ephemeral account.
+/// For a token trade, they are followed by the token program, the mint, the vault, the PMM associated token accounts,
+/// and the protocol associated token account when the total fee of the trade is set.
+/// When the MPC is an [MpcGroup], the other signing members are appended at the end.
+/// # Arguments
+/// * `ctx` - A [Context] of [BatchSettlementAccounts] required for settling the trades.
+/// * `batch_settlement_args` - An argument [BatchSettlementArgs] required for settling the trades.
+/// # Errors
+/// * [CustomError::InstructionPaused] when the settlement instruction is paused.
+/// * [CustomError::InvalidBatchAccounts] when there is no trade, or an account is not the PDA of its trade.
+/// * [CustomError::Unauthorized] when a trade is not authorized by both its MPC, see [TradeDetail::is_mpc_authorized], and its [TradeDetail::user_ephemeral_pubkey].
+/// * [CustomError::InvalidUserAccount] when a user account is not match with [TradeDetail::rent_payer].
+/// * [CustomError::InvalidMintKey] when a trade is not with the [BatchSettlementArgs::token].
+/// * [CustomError::TimeOut] when a trade timeout is expired.
+/// * [CustomError::InvalidTradeStatus] when a trade status is not [TradeStatus::Deposited].
+/// * [CustomError::InvalidSourceAta] when a source to transfer from is not the associated token account of the vault and mint.
+/// * [CustomError::InvalidDestinationAta] when a destination to transfer to is not the associated token account of the pmm, or the protocol, and mint.
+pub fn handler_batch_settlement<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, BatchSettlementAccounts<'info>>,
+    batch_settlement_args: BatchSettlementArgs,
+) -> Result<()> {
+    if batch_settlement_args.trade_ids.is_empty() {
+        return Err(CustomError::InvalidBatchAccounts.into());
+    }
+    let signer = ctx.accounts.signer.as_ref();
+    let pmm = &ctx.accounts.pmm.to_account_info();
+    let protocol = &ctx.accounts.protocol.to_account_info();
+    let mpc_group = &ctx.accounts.mpc_group;
+    let token = batch_settlement_args.token;
+
+    let accounts = &mut ctx.remaining_accounts.iter();
+    for trade_id in batch_settlement_args.trade_ids.iter() {
+        let trade_detail_info = next_account_info(accounts)?;
+        let vault_info = next_account_info(accounts)?;
+        let user_account = next_account_info(accounts)?;
+        let user_ephemeral_account = next_account_info(accounts)?;
+        let nonce_check_info = next_account_info(accounts)?;
+
+        let (trade_detail_key, _) = Pubkey::find_program_address(&[trade_id], &ID);
+        assert_keys_equal(trade_detail_info.key, &trade_detail_key, CustomError::InvalidBatchAccounts)?;
+        let mut user_trade_detail: Account<TradeDetail> = Account::try_from(trade_detail_info)?;
+
+        // Check the MPC of the trade, or the threshold of its MPC group, authorized the instruction
+        if let Some(mpc_group) = mpc_group {
+            assert_keys_equal(&mpc_group.key(), &user_trade_detail.mpc_pubkey, CustomError::Unauthorized)?;
+        }
+        user_trade_detail.assert_mpc_authorized(mpc_group.as_deref(), signer, ctx.remaining_accounts)?;
+        if !user_ephemeral_account.is_signer || *user_ephemeral_account.key != user_trade_detail.user_ephemeral_pubkey {
+            return Err(CustomError::Unauthorized.into());
+        }
+        assert_keys_equal(user_account.key, &user_trade_detail.rent_payer(), CustomError::InvalidUserAccount)?;
+        if user_trade_detail.token != token {
+            return Err(CustomError::InvalidMintKey.into());
+        }
+        // Check if the trade is able to settled
+        user_trade_detail.assert_settlement()?;
+
+        let (vault_key, bump) = Pubkey::find_program_address(&[TradeVault::SEED, trade_id], &ID);
+        assert_keys_equal(vault_info.key, &vault_key, CustomError::InvalidBatchAccounts)?;
+        let vault: Account<TradeVault> = Account::try_from(vault_info)?;
+        let (nonce_check_key, _) =
+            Pubkey::find_program_address(&[NonceCheckAccount::SEED, user_ephemeral_account.key.as_ref()], &ID);
+        assert_keys_equal(nonce_check_info.key, &nonce_check_key, CustomError::InvalidBatchAccounts)?;
+        let nonce_check_account: Account<NonceCheckAccount> = Account::try_from(nonce_check_info)?;
+
+        // Calculate the settlement amount after deducting the protocol fee
+        let total_fee = user_trade_detail.total_fee.unwrap_or(0);
+        let settlement_amount = user_trade_detail.amount - total_fee;
+
+        // Transfer asset from the vault to the pmm and protocol
+        let received_fee = match token {
+            Some(token_pubkey) => {
+                let seeds_signer: &[&[u8]] = &[TradeVault::SEED, trade_id, &[bump]];
+                let (_, received_fee) = transfer_spl_token(
+                    accounts,
+                    &token_pubkey,
+                    vault_info.key,
+                    pmm.key,
+                    vault_info,
+                    settlement_amount,
+                    &[seeds_signer],
+                    user_trade_detail.total_fee,
+                )?;
+                received_fee
+            }
+            None => {
+                **vault_info.try_borrow_mut_lamports()? -= user_trade_detail.amount;
+                **pmm.try_borrow_mut_lamports()? += settlement_amount;
+                **protocol.try_borrow_mut_lamports()? += total_fee;
+                total_fee
+            }
+        };
+        user_trade_detail.status = TradeStatus::Settled;
+        user_trade_detail.settled_pmm = pmm.key();
+        WhitelistToken::release_outstanding(&ctx.accounts.whitelist_token, user_trade_detail.amount)?;
+        // Only the fee actually received by the protocol, net of any Token-2022 transfer fee, can be withdrawn
+        ctx.accounts.fee_ledger.accrue(token.unwrap_or(WSOL_MINT), received_fee)?;
+
+        emit!(Settled {
+            trade_id: *trade_id,
+            token,
+            to_pubkey: pmm.key(),
+            operator: signer.key(),
+            settlement_amount,
+            total_fee,
+            vault: vault.key(),
+            protocol: protocol.key(),
+        });
+
+        // Persist the trade before the next one, so a duplicated trade is not settled twice
+        user_trade_detail.exit(&ID)?;
+        nonce_check_account.close(user_account.clone())?;
+    }
+
+    Ok(())
+}
+
+/// Context accounts for the batch settlement instruction.
+#[derive(Accounts)]
+#[instruction(batch_settlement_args: BatchSettlementArgs)]
+pub struct BatchSettlementAccounts<'info> {
+    /// The signer who is authorized to settle the trades.
+    /// Must be the [TradeDetail::mpc_pubkey] of all the trades, or a member of their [MpcGroup], see [TradeDetail::is_mpc_authorized].
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK:
+    /// The protocol PDA account.
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump,
+        owner = ID, // This PDA must come from our smart-contract
+    )]
+    pub protocol: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The pmm account, which receives the settlement amount of all the trades.
+    #[account(mut)]
+    pub pmm: UncheckedAccount<'info>,
+
+    /// The config PDA that contains the protocol configuration.
+    /// The settlement instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.settlement @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK:
+    /// The whitelist token PDA of the token, WSOL if the trades are with SOL.
+    /// The trade amounts are removed from its outstanding amount. It may have been removed since the deposits, then nothing is updated.
+    #[account(
+        mut,
+        seeds = [WhitelistToken::SEED, batch_settlement_args.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub whitelist_token: UncheckedAccount<'info>,
+
+    /// The fee ledger PDA of the token, WSOL if the trades are with SOL.
+    /// The total fees are added to its accrued fee. Will be initialized by the signer if needed.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [FeeLedger::SEED, batch_settlement_args.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// The MPC group of the trades, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].
+    /// The other signing members are appended to the remaining accounts.
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
+}
@@ -1,5 +1,5 @@
 //! This module contains the logic for claiming the deposited amount after the timeout.
-use crate::{error::*, event::*, state::*, utils::*, ID};
+use crate::{constants::WSOL_MINT, error::*, event::*, state::*, utils::*, ID};
 use anchor_lang::prelude::*;
 
 /// Parameters required for the claim function.
@@ -15,7 +15,7 @@ pub struct ClaimArgs {
 /// * `ctx` - A [Context] of [Claim] required for claiming the deposited amount.
 /// * `claim_args` - An argument [ClaimArgs] required for claiming the deposited amount.
 /// # Errors
-/// * [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::user_pubkey].
+/// * [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::rent_payer].
 /// * [CustomError::InvalidRefundPubkey] when the refund pubkey address is not match with the [TradeDetail::refund_pubkey].
 /// * [CustomError::ClaimNotAvailable] when the [TradeDetail::timeout] is not expired, so we cannot claim the deposited amount.
 /// * [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited], we only claim the Deposited trade and timed out.
@@ -61,6 +61,7 @@ pub fn handler_claim<'c: 'info, 'info>(
         }
     }
     user_trade_detail.status = TradeStatus::Claimed;
+    WhitelistToken::release_outstanding(&ctx.accounts.whitelist_token, user_trade_detail.amount)?;
 
     // Emit claim event
     emit!(Claimed {
@@ -85,11 +86,11 @@ pub struct Claim<'info> {
 
     /// CHECK:
     /// 
-    /// The user account that is the depositor of the trade.
-    /// Must be the [TradeDetail::user_pubkey]
+    /// The user account that paid the rent of the trade.
+    /// Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored.
     #[account(
         mut,
-        address = user_trade_detail.user_pubkey @ CustomError::InvalidUserAccount,
+        constraint = user_account.key() == user_trade_detail.rent_payer() @ CustomError::InvalidUserAccount,
     )]
     pub user_account: UncheckedAccount<'info>,
 
@@ -132,6 +133,16 @@ pub struct Claim<'info> {
     )]
     pub refund_account: UncheckedAccount<'info>,
 
+    /// CHECK:
+    /// The whitelist token PDA of the trade token, WSOL if the trade is with SOL.
+    /// The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated.
+    #[account(
+        mut,
+        seeds = [WhitelistToken::SEED, user_trade_detail.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub whitelist_token: UncheckedAccount<'info>,
+
     /// System program.
     pub system_program: Program<'info, System>,
 }
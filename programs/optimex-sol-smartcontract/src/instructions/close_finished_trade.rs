@@ -1,9 +1,9 @@
 //! This module contains the logic for closing the finished (settled or claimed) trade.
-use crate::{assert_keys_equal, error::CustomError, state::*};
+use crate::{assert_keys_equal, error::CustomError, event::*, get_token_account_amount, harvest_withheld_fees, state::*};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::get_associated_token_address_with_program_id,
-    token::{self, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 /// Parameters rquired for the deposit function.
@@ -19,9 +19,11 @@ pub struct CloseFinishedTradeArgs {
 /// * `ctx` - A [Context] of [CloseFinishedTradeAccounts] required for closing the trade.
 /// * `_close_finished_trade_args` - An argument [CloseFinishedTradeArgs] required for closing the trade.
 /// # Errors
-/// * [CustomError::InvalidUserAccount] when the user account is not match to [TradeDetail::user_pubkey]. This account will receive the claimed rent fee.
+/// * [CustomError::InvalidUserAccount] when the user account is not match to [TradeDetail::rent_payer]. This account will receive the claimed rent fee.
 /// * [CustomError::InvalidTradeStatus] when the trade status is [TradeStatus::Deposited].
 /// * [CustomError::CloseNotAvailable] when the trade is not the available time to close.
+/// * [CustomError::InvalidTokenAccount] when the token accounts or the mint are missing or do not match the trade.
+/// * [CustomError::InvalidMintKey] when the mint is not match with the [TradeDetail::token].
 pub fn handler_close_finished_trade<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, CloseFinishedTradeAccounts<'info>>,
     close_finished_trade_args: CloseFinishedTradeArgs,
@@ -33,7 +35,12 @@ pub fn handler_close_finished_trade<'c: 'info, 'info>(
     let user_trade_detail = &mut ctx.accounts.user_trade_detail;
     let vault_token_account = &ctx.accounts.vault_token_account;
     let user_token_account = &ctx.accounts.user_token_account;
-    let is_mpc = ctx.accounts.signer.key() == user_trade_detail.mpc_pubkey;
+    let mint = &ctx.accounts.mint;
+    let is_mpc = user_trade_detail.is_mpc_authorized(
+        ctx.accounts.mpc_group.as_deref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+    );
     let current_timestamp = Clock::get()?.unix_timestamp as u64;
     user_trade_detail.assert_close_finished_trade(
         current_timestamp,
@@ -44,70 +51,90 @@ pub fn handler_close_finished_trade<'c: 'info, 'info>(
     match user_trade_detail.token {
         // If trade is with token, close the token account
         Some(token) => {
-            if vault_token_account.is_none() || user_token_account.is_none() {
+            if vault_token_account.is_none() || user_token_account.is_none() || mint.is_none() {
                 return Err(CustomError::InvalidTokenAccount.into());
             }
-            let vault_token_account = vault_token_account.as_ref().unwrap();
-            let user_token_account = user_token_account.as_ref().unwrap();
-            let calculated_vault_ta = get_associated_token_address_with_program_id(
-                &vault.key(),
-                &token,
-                &ctx.accounts.token_program.key,
-            );
-            assert_keys_equal(
-                &vault_token_account.key(),
-                &calculated_vault_ta,
-                CustomError::InvalidTokenAccount,
+            assert_keys_equal(&mint.as_ref().unwrap().key(), &token, CustomError::InvalidMintKey)?;
+            close_vault_token_account(
+                &ctx.accounts.token_program.to_account_info(),
+                &mint.as_ref().unwrap().to_account_info(),
+                &vault_token_account.as_ref().unwrap().to_account_info(),
+                &user_token_account.as_ref().unwrap().to_account_info(),
+                &vault.to_account_info(),
+                &user_trade_detail.user_pubkey,
+                &user_account.to_account_info(),
+                &[b"vault", &close_finished_trade_args.trade_id, &[ctx.bumps.vault]],
             )?;
-            let calculated_user_ta = get_associated_token_address_with_program_id(
-                &user_account.key(),
-                &token,
-                &ctx.accounts.token_program.key,
-            );
-            assert_keys_equal(
-                &user_token_account.key(),
-                &calculated_user_ta,
-                CustomError::InvalidTokenAccount,
-            )?;
-            let seeds: &[&[u8]] = &[
-                b"vault",
-                &close_finished_trade_args.trade_id,
-                &[ctx.bumps.vault],
-            ];
-            let seeds_signer = &mut seeds.to_vec();
-
-            let remaining_amount = vault_token_account.amount;
-            if remaining_amount > 0 {
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        token::Transfer {
-                            from: vault_token_account.to_account_info(),
-                            to: user_token_account.to_account_info(),
-                            authority: vault.to_account_info(),
-                        },
-                        &[seeds_signer],
-                    ),
-                    remaining_amount,
-                )?;
-            }
-            token::close_account(CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::CloseAccount {
-                    account: vault_token_account.to_account_info(),
-                    destination: user_account.to_account_info(),
-                    authority: vault.to_account_info(),
-                },
-                &[seeds_signer],
-            ))?;
         }
         // If trade is with SOL, do nothing
         None => {}
     }
 
+    // Emit close finished trade event
+    emit!(TradeClosed {
+        trade_id: close_finished_trade_args.trade_id,
+        operator: ctx.accounts.signer.key(),
+        to_pubkey: user_account.key(),
+        token: user_trade_detail.token,
+    });
+
     Ok(())
 }
 
+/// Transfers the remaining tokens of the vault token account to the user token account, then closes the vault token account.
+///
+/// The tokens belong to the `user_pubkey` of the trade, while the rent fee of the vault token account goes to the `user_account`,
+/// the [TradeDetail::rent_payer].
+/// Shared by the close finished trade and the batch close finished trades instructions, the `mint` must be checked against the trade.
+/// # Errors
+/// * [CustomError::InvalidTokenAccount] when the token accounts are not the associated token accounts of the vault and the `user_pubkey`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn close_vault_token_account<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    vault_token_account: &AccountInfo<'info>,
+    user_token_account: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    user_pubkey: &Pubkey,
+    user_account: &AccountInfo<'info>,
+    vault_seeds: &[&[u8]],
+) -> Result<()> {
+    let calculated_vault_ta = get_associated_token_address_with_program_id(vault.key, mint.key, token_program.key);
+    assert_keys_equal(vault_token_account.key, &calculated_vault_ta, CustomError::InvalidTokenAccount)?;
+    let calculated_user_ta = get_associated_token_address_with_program_id(user_pubkey, mint.key, token_program.key);
+    assert_keys_equal(user_token_account.key, &calculated_user_ta, CustomError::InvalidTokenAccount)?;
+    let decimals = Mint::try_deserialize(&mut &mint.try_borrow_data()?[..])?.decimals;
+
+    let remaining_amount = get_token_account_amount(vault_token_account)?;
+    if remaining_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                token_interface::TransferChecked {
+                    from: vault_token_account.clone(),
+                    mint: mint.clone(),
+                    to: user_token_account.clone(),
+                    authority: vault.clone(),
+                },
+                &[vault_seeds],
+            ),
+            remaining_amount,
+            decimals,
+        )?;
+    }
+    // Token-2022 account with withheld transfer fees cannot be closed
+    harvest_withheld_fees(token_program, mint, vault_token_account)?;
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        token_interface::CloseAccount {
+            account: vault_token_account.clone(),
+            destination: user_account.clone(),
+            authority: vault.clone(),
+        },
+        &[vault_seeds],
+    ))
+}
+
 /// The context accounts required for the close finished trade instruction.
 #[derive(Accounts)]
 #[instruction(close_finished_trade: CloseFinishedTradeArgs)]
@@ -119,10 +146,10 @@ pub struct CloseFinishedTradeAccounts<'info> {
 
     /// CHECK:
     /// The user_account that receive the rent fee of closed account.
-    /// Must be the [TradeDetail::user_pubkey]
+    /// Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored.
     #[account(
         mut,
-        address = user_trade_detail.user_pubkey @ CustomError::InvalidUserAccount,
+        constraint = user_account.key() == user_trade_detail.rent_payer() @ CustomError::InvalidUserAccount,
     )]
     pub user_account: UncheckedAccount<'info>,
 
@@ -156,12 +183,25 @@ pub struct CloseFinishedTradeAccounts<'info> {
     /// The token account of the trade.
     /// This account will be closed by the instruction.
     #[account(mut)]
-    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// The user token account that is used to receive the amount if someone transfer the token after closed the trade.
+    /// Must be the associated token account of the [TradeDetail::user_pubkey], even when the deposit was sponsored.
+    #[account(mut)]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the trade token. Required when the trade is with token.
+    /// Must be the [TradeDetail::token].
     #[account(mut)]
-    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// The token program, either the SPL Token or the Token-2022 program.
+    pub token_program: Interface<'info, TokenInterface>,
 
-    /// The token program.
-    pub token_program: Program<'info, Token>,
+    /// The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup] that closes the trade.
+    /// The other signing members are appended to the remaining accounts.
+    #[account(
+        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized,
+    )]
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
 }
@@ -0,0 +1,67 @@
+//! This module contains the logic for closing the payment expectation.
+use anchor_lang::prelude::*;
+
+use crate::{error::CustomError, event::*, state::*};
+
+/// Handles the close of the payment expectation.
+/// # Arguments
+/// * `ctx` - A [Context] of [ClosePaymentExpectationAccounts] required for closing the payment expectation.
+/// # Errors
+/// * [CustomError::Unauthorized] - When the caller is not authorized by the MPC of the expectation, see [PaymentExpectation::is_mpc_authorized].
+/// * [CustomError::CloseNotAvailable] - When the payment expectation is not fulfilled and the deadline is not passed.
+pub fn handler_close_payment_expectation<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClosePaymentExpectationAccounts<'info>>,
+) -> Result<()> {
+    if !ctx.accounts.payment_expectation.is_mpc_authorized(
+        ctx.accounts.mpc_group.as_deref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+    ) {
+        return Err(CustomError::Unauthorized.into());
+    }
+
+    let payment_expectation = &ctx.accounts.payment_expectation;
+    if !payment_expectation.fulfilled
+        && Clock::get()?.unix_timestamp <= payment_expectation.deadline
+    {
+        return Err(CustomError::CloseNotAvailable.into());
+    }
+
+    emit!(PaymentExpectationClosed {
+        trade_id: payment_expectation.trade_id,
+        mpc_pubkey: payment_expectation.mpc_pubkey,
+        fulfilled: payment_expectation.fulfilled,
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the close payment expectation instruction.
+#[derive(Accounts)]
+pub struct ClosePaymentExpectationAccounts<'info> {
+    /// The MPC account who published the payment expectation, and receive the rent fee.
+    /// Must be the [PaymentExpectation::mpc_pubkey], or a member of its MPC group.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The payment expectation PDA.
+    /// This PDA will be closed by the instruction.
+    #[account(
+        mut,
+        seeds = [
+            PaymentExpectation::SEED,
+            &payment_expectation.trade_id,
+            payment_expectation.mpc_pubkey.as_ref(),
+        ],
+        bump,
+        close = signer,
+    )]
+    pub payment_expectation: Account<'info, PaymentExpectation>,
+
+    /// The MPC group of the expectation, only when the [PaymentExpectation::mpc_pubkey] is an [MpcGroup].
+    /// The other signing members are appended to the remaining accounts.
+    #[account(
+        address = payment_expectation.mpc_pubkey @ CustomError::Unauthorized,
+    )]
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
+}
@@ -1,7 +1,7 @@
 //! This module contains the logic for closing the payment receipt.
 use anchor_lang::prelude::*;
 
-use crate::{error::CustomError, state::*};
+use crate::{error::CustomError, event::*, state::*};
 
 /// Handles the close of the payment receipt.
 /// # Arguments
@@ -15,6 +15,16 @@ pub fn handler_close_payment_receipt<'c: 'info, 'info>(
     let payment_receipt = &ctx.accounts.payment_receipt;
     let config = &ctx.accounts.config;
     payment_receipt.assert_close(config.close_payment_duration)?;
+
+    // Emit close payment receipt event
+    emit!(PaymentReceiptClosed {
+        trade_id: payment_receipt.trade_id,
+        payment_receipt: payment_receipt.key(),
+        from_pubkey: payment_receipt.from_pubkey,
+        to_pubkey: payment_receipt.to_pubkey,
+        token: payment_receipt.token,
+    });
+
     Ok(())
 }
 
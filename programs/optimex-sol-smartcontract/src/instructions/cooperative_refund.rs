@@ -0,0 +1,168 @@
+//! This module contains the logic for refunding the deposited amount before the timeout, agreed by the MPC and the user.
+use crate::{constants::WSOL_MINT, error::*, event::*, state::*, utils::*, ID};
+use anchor_lang::prelude::*;
+
+/// Parameters required for the cooperative refund function.
+#[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CooperativeRefundArgs {
+    /// The tradeId, unique identifier for the trade.
+    pub trade_id: [u8; 32],
+}
+
+/// Handles the logic for refunding the deposited amount before the timeout.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [CooperativeRefundAccounts] required for refunding the deposited amount.
+/// * `cooperative_refund_args` - An argument [CooperativeRefundArgs] required for refunding the deposited amount.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+/// * [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::rent_payer].
+/// * [CustomError::InvalidRefundPubkey] when the refund pubkey address is not match with the [TradeDetail::refund_pubkey].
+/// * [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited].
+/// * [CustomError::InvalidMintKey] when the mint key is not match with the [TradeDetail::token]
+/// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the vault and mint.
+/// * [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the refund pubkey.
+pub fn handler_cooperative_refund<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CooperativeRefundAccounts<'info>>,
+    cooperative_refund_args: CooperativeRefundArgs,
+) -> Result<()> {
+    // Check the MPC of the trade, or the threshold of its MPC group, authorized the instruction
+    ctx.accounts.user_trade_detail.assert_mpc_authorized(
+        ctx.accounts.mpc_group.as_deref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let vault = &ctx.accounts.vault.to_account_info();
+    let refund_account = &ctx.accounts.refund_account.to_account_info();
+    let user_trade_detail = &mut ctx.accounts.user_trade_detail;
+
+    // Check if the trade is able to refunded
+    user_trade_detail.assert_refund()?;
+    // Handle token or SOL refund
+    // Transfer asset from the vault to the refund account
+    match user_trade_detail.token {
+        Some(token_pubkey) => {
+            let seeds: &[&[u8]] = &[TradeVault::SEED, &cooperative_refund_args.trade_id];
+            let (_, bump) = Pubkey::find_program_address(seeds, &ID);
+            let seeds_signer = &mut seeds.to_vec();
+            let binding = [bump];
+            seeds_signer.push(&binding);
+
+            // Transfer tokens from vault to user
+            transfer_spl_token(
+                &mut ctx.remaining_accounts.iter(),
+                &token_pubkey,
+                vault.key,
+                &user_trade_detail.refund_pubkey,
+                vault,
+                user_trade_detail.amount,
+                &[seeds_signer],
+                None,
+            )?;
+        }
+        None => {
+            // Transfer SOL from vault to user
+            **vault.try_borrow_mut_lamports()? -= user_trade_detail.amount;
+            **refund_account.try_borrow_mut_lamports()? += user_trade_detail.amount;
+        }
+    }
+    user_trade_detail.status = TradeStatus::Refunded;
+    WhitelistToken::release_outstanding(&ctx.accounts.whitelist_token, user_trade_detail.amount)?;
+
+    // Emit refund event
+    emit!(Refunded {
+        trade_id: cooperative_refund_args.trade_id,
+        token: user_trade_detail.token,
+        to_pubkey: user_trade_detail.refund_pubkey,
+        operator: ctx.accounts.signer.key(),
+        amount: user_trade_detail.amount,
+    });
+
+    Ok(())
+}
+
+/// The context accounts required for the cooperative refund instruction.
+#[derive(Accounts)]
+#[instruction(cooperative_refund_args: CooperativeRefundArgs)]
+pub struct CooperativeRefundAccounts<'info> {
+    /// The signer who is authorized to refund the trade.
+    /// Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized].
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK:
+    ///
+    /// The user account that paid the rent of the trade. This account will receive the rent fee of the nonce check account PDA.
+    /// Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored.
+    #[account(
+        mut,
+        constraint = user_account.key() == user_trade_detail.rent_payer() @ CustomError::InvalidUserAccount,
+    )]
+    pub user_account: UncheckedAccount<'info>,
+
+    /// The user ephemeral account of the trade, need to sign this transaction too.
+    /// Must be the [TradeDetail::user_ephemeral_pubkey].
+    #[account(
+        address = user_trade_detail.user_ephemeral_pubkey @ CustomError::Unauthorized, // Check user ephemeral pubkey
+    )]
+    pub user_ephemeral_account: Signer<'info>,
+
+    /// The nonce check account PDA that flag whether the nonce is currently active or not.
+    /// Will be closed and transferred rent to the user_account.
+    #[account(
+        mut,
+        seeds = [NonceCheckAccount::SEED, user_ephemeral_account.key().as_ref()],
+        bump,
+        close = user_account,
+    )]
+    pub nonce_check_account: Account<'info, NonceCheckAccount>,
+
+    /// The trade detail PDA that contains the trade information.
+    #[account(
+        mut,
+        seeds = [&cooperative_refund_args.trade_id],
+        bump,
+        owner = ID,
+    )]
+    pub user_trade_detail: Account<'info, TradeDetail>,
+
+    /// The trade vault PDA that corresponds to the trade.
+    #[account(
+        mut,
+        seeds = [TradeVault::SEED, &cooperative_refund_args.trade_id],
+        bump,
+        owner = ID, // This PDA must come from our smart-contract
+    )]
+    pub vault: Account<'info, TradeVault>,
+
+    /// CHECK:
+    ///
+    /// The refund account of the trade.
+    /// Must be the [TradeDetail::refund_pubkey]
+    #[account(
+        mut,
+        address = user_trade_detail.refund_pubkey @ CustomError::InvalidRefundPubkey, // Check refund_pubkey
+    )]
+    pub refund_account: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The whitelist token PDA of the trade token, WSOL if the trade is with SOL.
+    /// The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated.
+    #[account(
+        mut,
+        seeds = [WhitelistToken::SEED, user_trade_detail.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub whitelist_token: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].
+    /// The other signing members are appended to the remaining accounts.
+    #[account(
+        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized,
+    )]
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
+}
@@ -0,0 +1,120 @@
+//! This module contains the logic for publishing the payment expectation of a trade.
+use anchor_lang::prelude::*;
+
+use crate::{error::CustomError, event::*, state::*};
+
+/// Parameters required for the create payment expectation instruction.
+#[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CreatePaymentExpectationArgs {
+    /// Unique identifier for the trade.
+    pub trade_id: [u8; 32],
+    /// The pubkey that must receive the payment.
+    pub to_pubkey: Pubkey,
+    /// Token public key for SPL token payments, none if SOL payment.
+    pub token: Option<Pubkey>,
+    /// The minimum amount the receiver must get, excluded fee.
+    pub min_amount: u64,
+    /// The maximum total fee of the payment.
+    pub max_fee: u64,
+    /// The latest time that the payment can be made.
+    pub deadline: i64,
+}
+
+/// Handles the create payment expectation instruction.
+///
+/// The expectation is published by a registered MPC key, or by the threshold of an [MpcGroup] on behalf of the group.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [CreatePaymentExpectationAccounts] required for publishing the payment expectation.
+/// * `args` - An argument [CreatePaymentExpectationArgs] required for publishing the payment expectation.
+/// # Errors
+/// * [CustomError::Unauthorized] when the `mpc_group` is given, and less than [MpcGroup::threshold] members signed.
+/// * [CustomError::MpcNotRegistered] when the MPC key, or the address of the `mpc_group`, is not registered and active.
+/// * [CustomError::InvalidAmount] when the [CreatePaymentExpectationArgs::min_amount] is zero.
+/// * [CustomError::DeadlineExceeded] when the [CreatePaymentExpectationArgs::deadline] is already passed.
+pub fn handler_create_payment_expectation<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CreatePaymentExpectationAccounts<'info>>,
+    args: CreatePaymentExpectationArgs,
+) -> Result<()> {
+    let signer = ctx.accounts.signer.as_ref();
+    let mpc_group = ctx.accounts.mpc_group.as_ref();
+    if let Some(mpc_group) = mpc_group {
+        if !mpc_group.is_signed_by(std::iter::once(signer).chain(ctx.remaining_accounts)) {
+            return Err(CustomError::Unauthorized.into());
+        }
+    }
+    let mpc_pubkey = *PaymentExpectation::mpc_key(signer.key, mpc_group);
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    MpcRegistry::assert_active(&ctx.accounts.mpc_registry, &mpc_pubkey, current_timestamp)?;
+
+    if args.min_amount == 0 {
+        return Err(CustomError::InvalidAmount.into());
+    }
+    if current_timestamp > args.deadline {
+        return Err(CustomError::DeadlineExceeded.into());
+    }
+
+    let payment_expectation = &mut ctx.accounts.payment_expectation;
+    payment_expectation.trade_id = args.trade_id;
+    payment_expectation.mpc_pubkey = mpc_pubkey;
+    payment_expectation.to_pubkey = args.to_pubkey;
+    payment_expectation.token = args.token;
+    payment_expectation.min_amount = args.min_amount;
+    payment_expectation.max_fee = args.max_fee;
+    payment_expectation.deadline = args.deadline;
+    payment_expectation.fulfilled = false;
+
+    // Emit payment expectation event
+    emit!(PaymentExpectationCreated {
+        trade_id: args.trade_id,
+        mpc_pubkey,
+        to_pubkey: args.to_pubkey,
+        token: args.token,
+        min_amount: args.min_amount,
+        max_fee: args.max_fee,
+        deadline: args.deadline,
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the create payment expectation instruction.
+#[derive(Accounts)]
+#[instruction(args: CreatePaymentExpectationArgs)]
+pub struct CreatePaymentExpectationAccounts<'info> {
+    /// The MPC account who publishes the payment expectation, and pays the rent fee.
+    /// A member of the `mpc_group` when the expectation is published by a group.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The MPC group publishing the expectation, none if published by the signer's own MPC key.
+    /// The other signing members are appended to the remaining accounts.
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
+
+    /// CHECK:
+    /// The MPC registry PDA of the MPC key of the expectation, see [PaymentExpectation::mpc_key].
+    /// The MPC key must be registered and active, see [MpcRegistry::assert_active].
+    #[account(
+        seeds = [MpcRegistry::SEED, PaymentExpectation::mpc_seed(signer.key, mpc_group.as_ref())],
+        bump,
+    )]
+    pub mpc_registry: UncheckedAccount<'info>,
+
+    /// The payment expectation PDA that contains the expected payment of the trade.
+    /// This PDA will be initialized by the instruction.
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PaymentExpectation::INIT_SPACE,
+        seeds = [
+            PaymentExpectation::SEED,
+            &args.trade_id,
+            PaymentExpectation::mpc_seed(signer.key, mpc_group.as_ref()),
+        ],
+        bump,
+    )]
+    pub payment_expectation: Account<'info, PaymentExpectation>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
@@ -1,6 +1,6 @@
 //! This module contains the logic for depositing the trade.
-use crate::{constants::*, error::CustomError, state::*, utils::*, ID};
-use anchor_lang::prelude::*;
+use crate::{constants::*, error::CustomError, event::*, state::*, utils::*, ID};
+use anchor_lang::{prelude::*, solana_program::sysvar};
 
 /// Parameters rquired for the deposit function
 #[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -11,6 +11,8 @@ pub struct DepositArgs {
     pub data: TradeDetailInput,
     /// The tradeId, unique identifier for the trade.
     pub trade_id: [u8; 32],
+    /// The scheme the [DepositArgs::trade_id] is derived with from the [DepositArgs::input].
+    pub trade_id_scheme: TradeIdScheme,
 }
 
 #[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -25,16 +27,23 @@ pub struct TradeDetailInput {
 /// * `ctx` - A [Context] of [DepositAccounts] required for the deposit.
 /// * `deposit_args` - An argument [DepositArgs] required for the deposit.
 /// # Errors 
+/// * [CustomError::InstructionPaused] when the deposit instruction is paused.
 /// * [CustomError::NotWhitelistedToken] when the token is not whitelisted.
 /// * [CustomError::NonceAccountBeingUsed] when the nonce account is being used by another trade, or not yet closed.
 /// * [CustomError::Unauthorized] when the signer is not match with the pubkey in the [DepositArgs]
 /// * [CustomError::InvalidTimeout] when the current timestamp is greater than the deposit timeout.
+/// * [CustomError::MpcNotRegistered] when the [TradeDetailInput::mpc_pubkey] is not registered, or not active in the [MpcRegistry].
 /// * [CustomError::DepositZeroAmount] when the deposit amount is zero.
 /// * [CustomError::InvalidAmount] when the deposit amount is less than the whitelisted amount.
-/// * [CustomError::InvalidTradeId] when the calculated trade ID is not match with the trade ID in the [DepositArgs].
+/// * [CustomError::MaxAmountExceeded] when the deposit amount is greater than the [WhitelistToken::max_amount].
+/// * [CustomError::OutstandingLimitReached] when the deposit makes the [WhitelistToken::outstanding] greater than the [WhitelistToken::max_outstanding].
+/// * [CustomError::InvalidTradeId] when the trade ID calculated with the [DepositArgs::trade_id_scheme] is not match with the trade ID in the [DepositArgs].
+/// * [CustomError::InvalidTokenProgram] when the token program is not the SPL Token or the Token-2022 program.
 /// * [CustomError::InvalidMintKey] when the mint key is not match with the mint of the trade.
 /// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.
 /// * [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the vault and mint.
+/// * [CustomError::InvalidSolverSignature] when the [Config::solver_signature_required], and the solver signature of the trade id
+///   is not verified by a previous instruction.
 pub fn handler_deposit<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, DepositAccounts<'info>>,
     deposit_args: DepositArgs,
@@ -42,10 +51,118 @@ pub fn handler_deposit<'c: 'info, 'info>(
     // Extract account information
     let signer = &ctx.accounts.signer.to_account_info();
     let vault = &ctx.accounts.vault.to_account_info();
-    let user_trade_detail = &mut ctx.accounts.user_trade_detail;
     let ephemeral_account = &ctx.accounts.ephemeral_account.to_account_info();
-    let whitelist_token = &ctx.accounts.whitelist_token;
 
+    validate_solver_signature(
+        &ctx.accounts.config,
+        ctx.accounts.instructions_sysvar.as_ref(),
+        &deposit_args,
+    )?;
+
+    let (deposited_amount, from_token_pubkey) = deposit_to_vault(
+        signer,
+        vault,
+        ephemeral_account,
+        &mut ctx.accounts.whitelist_token,
+        &ctx.accounts.mpc_registry,
+        ctx.remaining_accounts,
+        &deposit_args,
+    )?;
+
+    // Assign value for user's trade detail, the user pays the rent
+    ctx.accounts.user_trade_detail.assign_value(
+        &deposit_args,
+        deposited_amount,
+        from_token_pubkey,
+        signer.key(),
+        ephemeral_account.key(),
+        signer.key(),
+    )?;
+
+    // Emit deposit event
+    emit!(Deposited {
+        trade_id: deposit_args.trade_id,
+        from_pubkey: signer.key(),
+        token: from_token_pubkey,
+        amount: deposited_amount,
+        vault: vault.key(),
+    });
+
+    Ok(())
+}
+
+/// Validates the deposit and transfers the deposited amount from the `signer` to the `vault`.
+///
+/// Shared by the deposit and the sponsored deposit instructions, see [handler_deposit] for the errors.
+/// Returns the amount the vault actually received, and the token of the trade, None if the trade is SOL.
+pub(crate) fn deposit_to_vault<'c: 'info, 'info>(
+    signer: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    ephemeral_account: &AccountInfo<'info>,
+    whitelist_token: &mut WhitelistToken,
+    mpc_registry: &AccountInfo<'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    deposit_args: &DepositArgs,
+) -> Result<(u64, Option<Pubkey>)> {
+    let (number_from_bytes, from_token_pubkey) =
+        validate_deposit(signer.key, ephemeral_account, whitelist_token, mpc_registry, deposit_args)?;
+
+    // Handle token or SOL deposit
+    // Transfer asset from the signer to the vault
+    // The deposited amount is what the vault actually received, after the Token-2022 transfer fee if any
+    let deposited_amount = match from_token_pubkey {
+        Some(token_pubkey) => {
+            let (received_amount, _) = transfer_spl_token(
+                &mut remaining_accounts.iter(),
+                &token_pubkey,
+                &signer.key,
+                &vault.key,
+                signer,
+                number_from_bytes,
+                &[],
+                None,
+            )?;
+            received_amount
+        }
+        None => {
+            // SOL deposit
+            transfer_sol(signer, vault, number_from_bytes)?;
+            number_from_bytes
+        }
+    };
+
+    // Check the deposit limits, and track the amount held by the vaults
+    whitelist_token.add_outstanding(number_from_bytes, deposited_amount)?;
+
+    Ok((deposited_amount, from_token_pubkey))
+}
+
+/// Validates the solver of the trade signed the trade id, when the [Config::solver_signature_required].
+///
+/// The secp256k1 program instruction that verifies the signature must precede the deposit instruction, see [assert_secp256k1_signature].
+pub(crate) fn validate_solver_signature(
+    config: &Config,
+    instructions_sysvar: Option<&UncheckedAccount>,
+    deposit_args: &DepositArgs,
+) -> Result<()> {
+    if !config.solver_signature_required {
+        return Ok(());
+    }
+    let instructions_sysvar = instructions_sysvar.ok_or(CustomError::InvalidSolverSignature)?;
+    assert_secp256k1_signature(instructions_sysvar, &deposit_args.input.solver, &deposit_args.trade_id)
+}
+
+/// Validates the deposit of the `user`, before the transfer to the vault.
+///
+/// Shared by all the deposit instructions, see [handler_deposit] for the errors.
+/// Returns the amount to deposit, and the token of the trade, None if the trade is SOL.
+pub(crate) fn validate_deposit(
+    user: &Pubkey,
+    ephemeral_account: &AccountInfo,
+    whitelist_token: &WhitelistToken,
+    mpc_registry: &AccountInfo,
+    deposit_args: &DepositArgs,
+) -> Result<(u64, Option<Pubkey>)> {
     // Check ephemeral account exists to prevent multiple trade uses the same nonce account
     // Deposit using existed and available nonce account will likely be rejected when settle
     if !ephemeral_account.data_is_empty() {
@@ -56,7 +173,7 @@ pub fn handler_deposit<'c: 'info, 'info>(
     let from_user_pubkey_bytes: &Vec<u8> = &deposit_args.input.trade_info.from_chain[0];
     let user_key_input = vec_u8_to_publickey(from_user_pubkey_bytes)?;
 
-    assert_keys_equal(&user_key_input, signer.key, CustomError::Unauthorized)?;
+    assert_keys_equal(&user_key_input, user, CustomError::Unauthorized)?;
 
     let from_token_pubkey_bytes: &Vec<u8> = &deposit_args.input.trade_info.from_chain[2];
     let from_token_pubkey = vec_u8_to_address(from_token_pubkey_bytes)?;
@@ -68,6 +185,13 @@ pub fn handler_deposit<'c: 'info, 'info>(
         return err!(CustomError::InvalidTimeout);
     }
 
+    // Check the MPC of the trade is approved by the admin
+    MpcRegistry::assert_active(
+        mpc_registry,
+        &deposit_args.data.mpc_pubkey,
+        current_timestamp,
+    )?;
+
     // Validate the deposit amount
     let number_from_bytes = bytes_to_u64_number(&deposit_args.input.trade_info.amount_in)?;
     if number_from_bytes <= 0 {
@@ -79,53 +203,21 @@ pub fn handler_deposit<'c: 'info, 'info>(
     }
 
     // Calculate and validate the trade ID
-    let trade_id = deposit_args.input.calculate_trade_id();
+    let trade_id = deposit_args
+        .input
+        .calculate_trade_id_with_scheme(deposit_args.trade_id_scheme)?;
     if trade_id != deposit_args.trade_id {
         return err!(CustomError::InvalidTradeId);
     }
 
-    // Handle token or SOL deposit
-    // Transfer asset from the signer to the vault
-    match from_token_pubkey {
-        Some(token_pubkey) => {
-            assert_keys_equal(
-                &token_pubkey,
-                &whitelist_token.token,
-                CustomError::NotWhitelistedToken,
-            )?;
-            transfer_spl_token(
-                &mut ctx.remaining_accounts.iter(),
-                &token_pubkey,
-                &signer.key,
-                &vault.key,
-                signer,
-                number_from_bytes,
-                &[],
-                None,
-            )?;
-        }
-        None => {
-            // In case of native SOL deposit, we use whitelist token WSOL
-            assert_keys_equal(
-                &WSOL_MINT,
-                &whitelist_token.token,
-                CustomError::NotWhitelistedToken,
-            )?;
-            // SOL deposit
-            transfer_sol(signer, vault, number_from_bytes)?;
-        }
-    }
-
-    // Assign value for user's trade detail
-    user_trade_detail.assign_value(
-        &deposit_args,
-        number_from_bytes,
-        from_token_pubkey,
-        signer.key(),
-        ephemeral_account.key(),
+    // Check the token of the deposit is whitelisted, in case of native SOL deposit, we use whitelist token WSOL
+    assert_keys_equal(
+        &from_token_pubkey.unwrap_or(WSOL_MINT),
+        &whitelist_token.token,
+        CustomError::NotWhitelistedToken,
     )?;
 
-    Ok(())
+    Ok((number_from_bytes, from_token_pubkey))
 }
 
 /// The context accounts required for the deposit instruction.
@@ -176,9 +268,36 @@ pub struct DepositAccounts<'info> {
 
     /// CHECK
     /// The whitelist token PDA, only token has been whitelisted can be deposited
+    /// The deposited amount is added to its outstanding amount.
     #[account(
+        mut,
         owner = ID @ CustomError::NotWhitelistedToken,  // This PDA must come from our smart-contract
     )]
     pub whitelist_token: Account<'info, WhitelistToken>,
+
+    /// The config PDA that contains the protocol configuration.
+    /// The deposit instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.deposit @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
     pub system_program: Program<'info, System>,
+
+    /// CHECK:
+    /// The MPC registry PDA of the [TradeDetailInput::mpc_pubkey].
+    /// The MPC key must be registered and active, see [MpcRegistry::assert_active].
+    #[account(
+        seeds = [MpcRegistry::SEED, deposit_args.data.mpc_pubkey.as_ref()],
+        bump,
+    )]
+    pub mpc_registry: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The instructions sysvar, to read the secp256k1 program instruction that verifies the solver signature.
+    /// Required when the [Config::solver_signature_required].
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
 }
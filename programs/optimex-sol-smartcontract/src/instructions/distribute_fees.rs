@@ -0,0 +1,161 @@
+//! This module contains the logic for the distribute fees instruction.
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+use crate::{
+    constants::{BPS_DENOMINATOR, WSOL_MINT},
+    error::CustomError,
+    event::*,
+    state::*,
+    utils::*,
+    ID,
+};
+
+/// Parameters required for the distribute fees instruction.
+#[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DistributeFeesArgs {
+    /// Token public key for SPL token fee, none if SOL fee.
+    pub token: Option<Pubkey>,
+}
+
+/// Handles the distribute fees instruction.
+///
+/// The fee to distribute is the [FeeLedger::available] fee of the token, bounded by the balance of the protocol PDA.
+/// Each fee receiver gets its [FeeReceiver::share_bps] of it, rounded down, the rest stays in the protocol PDA.
+///
+/// The remaining accounts are, for each fee receiver:
+/// * SOL: the [FeeReceiver] PDA, then the receiver account.
+/// * SPL token: the [FeeReceiver] PDA, then the token program, the mint, the protocol PDA and the receiver associated token accounts.
+/// # Arguments
+/// * `ctx` - A [Context] of [DistributeFeesAccounts] required for the distribution.
+/// * `distribute_fees_args` - An argument [DistributeFeesArgs] required for the distribution.
+/// # Errors
+/// * [CustomError::InstructionPaused] when the distribute fees instruction is paused.
+/// * [CustomError::InvalidFeeShare] when the shares of the fee receivers do not sum to 100%, or a fee receiver is missing or duplicated.
+/// * [CustomError::InvalidFeeReceiver] when a fee receiver account is not a [FeeReceiver] PDA.
+/// * [CustomError::InvalidUserAccount] when the receiver account is not the [FeeReceiver::receiver].
+/// * [CustomError::InvalidMintKey] when the mint key is not match with the [DistributeFeesArgs::token].
+/// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the protocol PDA and mint.
+/// * [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the receiver and mint.
+pub fn handler_distribute_fees<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, DistributeFeesAccounts<'info>>,
+    distribute_fees_args: DistributeFeesArgs,
+) -> Result<()> {
+    if ctx.accounts.config.total_fee_share_bps != BPS_DENOMINATOR {
+        return Err(CustomError::InvalidFeeShare.into());
+    }
+    let protocol = &ctx.accounts.protocol.to_account_info();
+    let remaining_accounts = ctx.remaining_accounts;
+
+    // The fee to distribute, bounded by what the protocol PDA actually holds
+    let balance = match distribute_fees_args.token {
+        Some(token_pubkey) => {
+            if remaining_accounts.len() < 4 {
+                return Err(CustomError::InvalidFeeShare.into());
+            }
+            let (token_program, mint, source) = (&remaining_accounts[1], &remaining_accounts[2], &remaining_accounts[3]);
+            assert_keys_equal(mint.key, &token_pubkey, CustomError::InvalidMintKey)?;
+            let source_key = get_associated_token_address_with_program_id(protocol.key, mint.key, token_program.key);
+            assert_keys_equal(source.key, &source_key, CustomError::InvalidSourceAta)?;
+            get_token_account_amount(source)?
+        }
+        None => protocol.lamports().saturating_sub(Rent::get()?.minimum_balance(0)),
+    };
+    let distributable = ctx.accounts.fee_ledger.available().min(balance);
+
+    let accounts = &mut remaining_accounts.iter();
+    let mut receivers: Vec<Pubkey> = vec![];
+    let mut total_share_bps: u32 = 0;
+    let mut distributed: u64 = 0;
+    while let Some(fee_receiver_info) = accounts.next() {
+        if fee_receiver_info.owner != &ID {
+            return Err(CustomError::InvalidFeeReceiver.into());
+        }
+        let fee_receiver = FeeReceiver::try_deserialize(&mut &fee_receiver_info.try_borrow_data()?[..])?;
+        let (fee_receiver_key, _) =
+            Pubkey::find_program_address(&[FeeReceiver::SEED, fee_receiver.receiver.as_ref()], &ID);
+        assert_keys_equal(fee_receiver_info.key, &fee_receiver_key, CustomError::InvalidFeeReceiver)?;
+        if receivers.contains(&fee_receiver.receiver) {
+            return Err(CustomError::InvalidFeeShare.into());
+        }
+        receivers.push(fee_receiver.receiver);
+        total_share_bps += fee_receiver.share_bps as u32;
+
+        let amount = fee_receiver.share_of(distributable);
+        match distribute_fees_args.token {
+            Some(token_pubkey) => {
+                let protocol_seeds: &[&[u8]] = &[b"protocol", &[ctx.bumps.protocol]];
+                transfer_spl_token(
+                    accounts,
+                    &token_pubkey,
+                    protocol.key,
+                    &fee_receiver.receiver,
+                    protocol,
+                    amount,
+                    &[protocol_seeds],
+                    None,
+                )?;
+            }
+            None => {
+                let to_user = next_account_info(accounts)?;
+                assert_keys_equal(to_user.key, &fee_receiver.receiver, CustomError::InvalidUserAccount)?;
+                **protocol.try_borrow_mut_lamports()? -= amount;
+                **to_user.try_borrow_mut_lamports()? += amount;
+            }
+        }
+        distributed += amount;
+
+        emit!(FeeWithdrawn {
+            operator: ctx.accounts.signer.key(),
+            to_pubkey: fee_receiver.receiver,
+            token: distribute_fees_args.token,
+            amount,
+            protocol: protocol.key(),
+        });
+    }
+
+    // All the fee receivers with a share must be part of the distribution
+    if total_share_bps != BPS_DENOMINATOR as u32 {
+        return Err(CustomError::InvalidFeeShare.into());
+    }
+    ctx.accounts.fee_ledger.withdraw(distributed)?;
+
+    Ok(())
+}
+
+/// Accounts required for the distribute fees instruction.
+#[derive(Accounts)]
+#[instruction(distribute_fees_args: DistributeFeesArgs)]
+pub struct DistributeFeesAccounts<'info> {
+    /// The signer account who perform the distribution.
+    /// Can be anyone
+    pub signer: Signer<'info>,
+
+    /// CHECK:
+    /// The protocol PDA account which own the protocol fee.
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump,
+        owner = ID, // This PDA must come from our smart-contract
+    )]
+    pub protocol: UncheckedAccount<'info>,
+
+    /// The config PDA that contains the protocol configuration.
+    /// The distribute fees instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.distribute_fees @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The fee ledger PDA of the token, WSOL if the fee is SOL.
+    /// The distributed fee is added to its withdrawn fee.
+    #[account(
+        mut,
+        seeds = [FeeLedger::SEED, distribute_fees_args.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+}
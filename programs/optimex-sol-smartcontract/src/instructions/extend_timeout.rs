@@ -0,0 +1,91 @@
+//! This module contains the logic for extending the timeout of a trade, agreed by the MPC and the user.
+use crate::{error::*, event::*, state::*, ID};
+use anchor_lang::prelude::*;
+
+/// Parameters required for the extend timeout function.
+#[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ExtendTimeoutArgs {
+    /// The tradeId, unique identifier for the trade.
+    pub trade_id: [u8; 32],
+    /// The new timeout of the trade, after the current [TradeDetail::timeout].
+    pub new_timeout: i64,
+}
+
+/// Handles the logic for extending the timeout of a trade.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [ExtendTimeoutAccounts] required for extending the timeout.
+/// * `extend_timeout_args` - An argument [ExtendTimeoutArgs] required for extending the timeout.
+/// # Errors
+/// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+/// * [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited].
+/// * [CustomError::TimeOut] when the trade is already expired.
+/// * [CustomError::InvalidTimeout] when the new timeout is not after the current [TradeDetail::timeout].
+/// * [CustomError::TimeoutExtensionExceeded] when the total extension of the trade exceeds the [Config::max_timeout_extension].
+pub fn handler_extend_timeout<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ExtendTimeoutAccounts<'info>>,
+    extend_timeout_args: ExtendTimeoutArgs,
+) -> Result<()> {
+    // Check the MPC of the trade, or the threshold of its MPC group, authorized the instruction
+    ctx.accounts.user_trade_detail.assert_mpc_authorized(
+        ctx.accounts.mpc_group.as_deref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let user_trade_detail = &mut ctx.accounts.user_trade_detail;
+    let previous_timeout = user_trade_detail.timeout;
+    user_trade_detail.extend_timeout(
+        extend_timeout_args.new_timeout,
+        ctx.accounts.config.max_timeout_extension,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    emit!(TimeoutExtended {
+        trade_id: extend_timeout_args.trade_id,
+        operator: ctx.accounts.signer.key(),
+        previous_timeout,
+        new_timeout: user_trade_detail.timeout,
+    });
+
+    Ok(())
+}
+
+/// The context accounts required for the extend timeout instruction.
+#[derive(Accounts)]
+#[instruction(extend_timeout_args: ExtendTimeoutArgs)]
+pub struct ExtendTimeoutAccounts<'info> {
+    /// The signer who is authorized to extend the timeout of the trade.
+    /// Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized].
+    pub signer: Signer<'info>,
+
+    /// The user ephemeral account of the trade, need to sign this transaction too.
+    /// Must be the [TradeDetail::user_ephemeral_pubkey].
+    #[account(
+        address = user_trade_detail.user_ephemeral_pubkey @ CustomError::Unauthorized, // Check user ephemeral pubkey
+    )]
+    pub user_ephemeral_account: Signer<'info>,
+
+    /// The trade detail PDA that contains the trade information.
+    #[account(
+        mut,
+        seeds = [&extend_timeout_args.trade_id],
+        bump,
+        owner = ID,
+    )]
+    pub user_trade_detail: Account<'info, TradeDetail>,
+
+    /// The config PDA that contains the protocol configuration, with the [Config::max_timeout_extension].
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].
+    /// The other signing members are appended to the remaining accounts.
+    #[account(
+        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized,
+    )]
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
+}
@@ -0,0 +1,20 @@
+//! This module contains the logic for the get fee ledger instruction.
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+/// Handles the get fee ledger instruction, a read only query of the fee of a token.
+///
+/// Intended to be simulated, the numbers are set as the return data of the transaction.
+/// # Arguments
+/// * `ctx` - A [Context] of [GetFeeLedgerAccounts] required for the query.
+pub fn handler_get_fee_ledger(ctx: Context<GetFeeLedgerAccounts>) -> Result<FeeLedgerBalance> {
+    Ok(ctx.accounts.fee_ledger.balance())
+}
+
+/// Accounts required for the get fee ledger instruction.
+#[derive(Accounts)]
+pub struct GetFeeLedgerAccounts<'info> {
+    /// The fee ledger PDA of the token, WSOL for SOL.
+    pub fee_ledger: Account<'info, FeeLedger>,
+}
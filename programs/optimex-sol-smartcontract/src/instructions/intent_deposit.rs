@@ -0,0 +1,224 @@
+//! This module contains the logic for depositing the trade from an intent signed off-chain by the user, and submitted by a relayer.
+use crate::{error::CustomError, event::*, instructions::deposit::*, state::*, utils::*, ID};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+/// The deposit intent, authorized off-chain by the user with its Ed25519 signature over the [DepositIntent::message].
+#[derive(Debug, InitSpace, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DepositIntent {
+    /// The tradeId, unique identifier for the trade.
+    pub trade_id: [u8; 32],
+    /// The amount to deposit, with decimals.
+    pub amount: u64,
+    /// The mint of the token to deposit.
+    pub mint: Pubkey,
+    /// The timeout of the trade.
+    pub timeout: i64,
+    /// The mpc of the trade.
+    pub mpc_pubkey: Pubkey,
+    /// The refund pubkey of the trade.
+    pub refund_pubkey: Pubkey,
+    /// The ephemeral pubkey of the trade, chosen by the user as it must sign the settlement.
+    pub user_ephemeral_pubkey: Pubkey,
+}
+
+impl DepositIntent {
+    /// The domain of the signed message, so that a signature of a deposit intent cannot be used for anything else.
+    pub const DOMAIN: &'static [u8] = b"optimex:deposit_intent";
+    /// The seed of the delegate PDA that the user approves on its token account, to pull the deposited amount.
+    pub const DELEGATE_SEED: &'static [u8] = b"intent_delegate";
+
+    /// Builds the deposit intent of the deposit arguments.
+    ///
+    /// # Errors
+    /// * [CustomError::InvalidDepositIntent] when the deposit is SOL, which cannot be pulled through a token delegate.
+    pub fn new(deposit_args: &DepositArgs, user_ephemeral_pubkey: Pubkey) -> Result<Self> {
+        let mint = vec_u8_to_address(&deposit_args.input.trade_info.from_chain[2])?
+            .ok_or(CustomError::InvalidDepositIntent)?;
+        Ok(Self {
+            trade_id: deposit_args.trade_id,
+            amount: bytes_to_u64_number(&deposit_args.input.trade_info.amount_in)?,
+            mint,
+            timeout: deposit_args.data.timeout,
+            mpc_pubkey: deposit_args.data.mpc_pubkey,
+            refund_pubkey: deposit_args.data.refund_pubkey,
+            user_ephemeral_pubkey,
+        })
+    }
+
+    /// The message signed by the user: the [DepositIntent::DOMAIN], the program ID, and the serialized intent.
+    pub fn message(&self) -> Result<Vec<u8>> {
+        Ok([Self::DOMAIN, ID.as_ref(), &self.try_to_vec()?].concat())
+    }
+}
+
+/// Handles the deposit of tokens into the vault from a deposit intent of the user.
+///
+/// The user does not sign the transaction. The instruction must be preceded by an Ed25519 program instruction that verifies
+/// the user signature over the [DepositIntent::message], and the amount is transferred by the delegate PDA that the user approved.
+/// The relayer pays the rent of the trade accounts, and is recorded in the [TradeDetail::payer_pubkey].
+/// # Arguments
+/// * `ctx` - A [Context] of [IntentDepositAccounts] required for the deposit.
+/// * `deposit_args` - An argument [DepositArgs] required for the deposit.
+/// # Errors
+/// * [CustomError::InvalidDepositIntent] when the deposit is SOL.
+/// * [CustomError::InvalidSignature] when the user signature of the deposit intent is not verified by the previous instruction.
+/// * The errors of the deposit instruction, see [handler_deposit].
+pub fn handler_intent_deposit<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, IntentDepositAccounts<'info>>,
+    deposit_args: DepositArgs,
+) -> Result<()> {
+    // Extract account information
+    let user = &ctx.accounts.user;
+    let payer = &ctx.accounts.payer;
+    let vault = &ctx.accounts.vault.to_account_info();
+    let ephemeral_account = &ctx.accounts.ephemeral_account.to_account_info();
+    let delegate = &ctx.accounts.delegate.to_account_info();
+
+    // Check the user signed the deposit intent
+    let intent = DepositIntent::new(&deposit_args, ephemeral_account.key())?;
+    assert_ed25519_signature(&ctx.accounts.instructions_sysvar, user.key, &intent.message()?)?;
+    validate_solver_signature(&ctx.accounts.config, Some(&ctx.accounts.instructions_sysvar), &deposit_args)?;
+
+    let (amount, _) = validate_deposit(
+        user.key,
+        ephemeral_account,
+        &ctx.accounts.whitelist_token,
+        &ctx.accounts.mpc_registry,
+        &deposit_args,
+    )?;
+
+    // Transfer the tokens from the user to the vault, by the delegate PDA
+    // The deposited amount is what the vault actually received, after the Token-2022 transfer fee if any
+    let delegate_seeds: &[&[u8]] = &[DepositIntent::DELEGATE_SEED, &[ctx.bumps.delegate]];
+    let (deposited_amount, _) = transfer_spl_token(
+        &mut ctx.remaining_accounts.iter(),
+        &intent.mint,
+        user.key,
+        vault.key,
+        delegate,
+        amount,
+        &[delegate_seeds],
+        None,
+    )?;
+
+    // Check the deposit limits, and track the amount held by the vaults
+    ctx.accounts.whitelist_token.add_outstanding(amount, deposited_amount)?;
+
+    // Assign value for user's trade detail, the relayer pays the rent
+    ctx.accounts.user_trade_detail.assign_value(
+        &deposit_args,
+        deposited_amount,
+        Some(intent.mint),
+        user.key(),
+        ephemeral_account.key(),
+        payer.key(),
+    )?;
+
+    // Emit deposit event
+    emit!(Deposited {
+        trade_id: deposit_args.trade_id,
+        from_pubkey: user.key(),
+        token: Some(intent.mint),
+        amount: deposited_amount,
+        vault: vault.key(),
+    });
+    emit!(DepositSponsored {
+        trade_id: deposit_args.trade_id,
+        payer: payer.key(),
+    });
+
+    Ok(())
+}
+
+/// The context accounts required for the intent deposit instruction.
+#[derive(Accounts)]
+#[instruction(deposit_args: DepositArgs)]
+pub struct IntentDepositAccounts<'info> {
+    /// The payer account that submits the deposit intent and pays the rent of the trade accounts, usually a relayer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK:
+    /// The user account that performs the deposit, authorized by its signature of the [DepositIntent].
+    pub user: UncheckedAccount<'info>,
+
+    /// The trade detail PDA that contains the trade information.
+    /// This PDA will be initialized by the instruction.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TradeDetail::INIT_SPACE,
+        seeds = [&deposit_args.trade_id,],
+        bump
+    )]
+    pub user_trade_detail: Account<'info, TradeDetail>,
+
+    /// CHECK: User ephemeral account, used as nonce account too.
+    /// Part of the [DepositIntent], so it does not need to sign.
+    pub ephemeral_account: UncheckedAccount<'info>,
+
+    /// CHECK: The nonce check account, used to check the nonce account is being used by another trade, or not yet closed.
+    /// This PDA will be initialized by the instruction.
+    #[account(
+        init,
+        payer = payer,
+        space = 8,
+        seeds = [NonceCheckAccount::SEED, ephemeral_account.key.as_ref()],
+        bump,
+    )]
+    pub nonce_check_account: Account<'info, NonceCheckAccount>,
+
+    /// The trade vault PDA that corresponds to the trade.
+    /// This PDA will be initialized by the instruction.
+    #[account(
+        init,
+        space = 8 + TradeVault::INIT_SPACE,
+        payer = payer,
+        seeds = [TradeVault::SEED, &deposit_args.trade_id],
+        bump,
+    )]
+    pub vault: Account<'info, TradeVault>,
+
+    /// CHECK
+    /// The whitelist token PDA, only token has been whitelisted can be deposited
+    /// The deposited amount is added to its outstanding amount.
+    #[account(
+        mut,
+        owner = ID @ CustomError::NotWhitelistedToken,  // This PDA must come from our smart-contract
+    )]
+    pub whitelist_token: Account<'info, WhitelistToken>,
+
+    /// The config PDA that contains the protocol configuration.
+    /// The deposit instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.deposit @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK:
+    /// The MPC registry PDA of the [TradeDetailInput::mpc_pubkey].
+    /// The MPC key must be registered and active, see [MpcRegistry::assert_active].
+    #[account(
+        seeds = [MpcRegistry::SEED, deposit_args.data.mpc_pubkey.as_ref()],
+        bump,
+    )]
+    pub mpc_registry: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The delegate PDA that the user approved on its token account, which transfers the deposited amount.
+    #[account(
+        seeds = [DepositIntent::DELEGATE_SEED],
+        bump,
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The instructions sysvar, to read the Ed25519 program instruction that verifies the user signature,
+    /// and the secp256k1 program instruction that verifies the solver signature when the [Config::solver_signature_required].
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
@@ -1,19 +1,43 @@
 pub mod admin_and_operator;
 pub mod claim;
+pub mod cooperative_refund;
 pub mod deposit;
 pub mod payment;
+pub mod create_payment_expectation;
+pub mod strict_payment;
+pub mod close_payment_expectation;
 pub mod set_total_fee;
 pub mod settlement;
+pub mod split_settlement;
 pub mod close_finished_trade;
 pub mod close_payment_receipt;
-pub mod withdraw_total_fee;
+pub mod get_fee_ledger;
+pub mod distribute_fees;
+pub mod batch_close_finished_trades;
+pub mod batch_close_payment_receipts;
+pub mod batch_settlement;
+pub mod extend_timeout;
+pub mod sponsored_deposit;
+pub mod intent_deposit;
 
 pub use claim::*;
+pub use cooperative_refund::*;
 pub use deposit::*;
 pub use payment::*;
+pub use create_payment_expectation::*;
+pub use strict_payment::*;
+pub use close_payment_expectation::*;
 pub use set_total_fee::*;
 pub use settlement::*;
+pub use split_settlement::*;
 pub use close_finished_trade::*;
 pub use close_payment_receipt::*;
 pub use admin_and_operator::*;
-pub use withdraw_total_fee::*;
\ No newline at end of file
+pub use get_fee_ledger::*;
+pub use distribute_fees::*;
+pub use batch_close_finished_trades::*;
+pub use batch_close_payment_receipts::*;
+pub use batch_settlement::*;
+pub use extend_timeout::*;
+pub use sponsored_deposit::*;
+pub use intent_deposit::*;
\ No newline at end of file
@@ -1,7 +1,7 @@
 //! This module contains the logic for the payment instruction.
 use anchor_lang::prelude::*;
 
-use crate::{constants::WSOL_MINT, error::CustomError, state::*, utils::*, ID};
+use crate::{constants::WSOL_MINT, error::CustomError, event::*, state::*, utils::*, ID};
 
 /// Parameters rquired for the payment instruction.
 #[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -24,9 +24,11 @@ pub struct PaymentArgs {
 /// * `ctx` - A [Context] of [PaymentAccounts] required for the payment.
 /// * `payment_args` - An argument [PaymentArgs] required for the payment.
 /// # Errors
+/// * [CustomError::InstructionPaused] when the payment instruction is paused.
 /// * [CustomError::NotWhitelistedToken] when the token is not whitelisted.
 /// * [CustomError::DeadlineExceeded] when the current timestamp is greater than the [PaymentArgs::deadline].
 /// * [CustomError::InvalidAmount] when the amount [PaymentArgs::amount] is less than the [PaymentArgs::total_fee].
+/// * [CustomError::MaxFeeExceeded] when the [PaymentArgs::total_fee] is greater than the maximum fee of the token, or the [Config::max_fee_bps].
 /// * [CustomError::InvalidMintKey] when the mint key is not match with the [PaymentReceipt::token].
 /// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.
 /// * [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the [PaymentReceipt::to_pubkey] and mint.
@@ -35,11 +37,24 @@ pub fn handler_payment<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, PaymentAccounts<'info>>,
     payment_args: PaymentArgs,
 ) -> Result<()> {
-    let to_user = &ctx.accounts.to_user.to_account_info();
-    let signer = &ctx.accounts.signer.to_account_info();
-    let protocol = &ctx.accounts.protocol.to_account_info();
-    let whitelist_token = &ctx.accounts.whitelist_token;
-    let payment_receipt = &mut ctx.accounts.payment_receipt;
+    process_payment(ctx.accounts, ctx.remaining_accounts, &payment_args)?;
+    Ok(())
+}
+
+/// Performs the payment transfer and records the [PaymentReceipt].
+///
+/// Shared by the payment and the strict payment instructions.
+/// Returns the amount received by the `to_user`, net of the transfer fee of a Token-2022 mint.
+pub(crate) fn process_payment<'c: 'info, 'info>(
+    accounts: &mut PaymentAccounts<'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    payment_args: &PaymentArgs,
+) -> Result<u64> {
+    let to_user = &accounts.to_user.to_account_info();
+    let signer = &accounts.signer.to_account_info();
+    let protocol = &accounts.protocol.to_account_info();
+    let whitelist_token = &accounts.whitelist_token;
+    let payment_receipt = &mut accounts.payment_receipt;
 
     // Validate the deadline
     let clock = Clock::get()?;
@@ -52,12 +67,18 @@ pub fn handler_payment<'c: 'info, 'info>(
     if payment_args.amount <= payment_args.total_fee {
         return Err(CustomError::InvalidAmount.into());
     }
+    accounts.config.assert_max_fee(
+        whitelist_token.max_fee_bps,
+        payment_args.amount,
+        payment_args.total_fee,
+    )?;
 
     let payment_amount = payment_args.amount - payment_args.total_fee;
 
     // Handle the SOL or SPL token payment
     // Transfer asset from the signer to the toUser, and transfer fee to the protocol.
-    match payment_args.token {
+    // The amounts received are net of the transfer fee of a Token-2022 mint
+    let (received_amount, received_fee) = match payment_args.token {
         Some(token_pubkey) => {
             // transfer SPL token from signer to toUser
             assert_keys_equal(
@@ -66,7 +87,7 @@ pub fn handler_payment<'c: 'info, 'info>(
                 CustomError::NotWhitelistedToken,
             )?;
             transfer_spl_token(
-                &mut ctx.remaining_accounts.iter(),
+                &mut remaining_accounts.iter(),
                 &token_pubkey,
                 &signer.key,
                 &to_user.key,
@@ -74,7 +95,7 @@ pub fn handler_payment<'c: 'info, 'info>(
                 payment_amount,
                 &[],
                 Some(payment_args.total_fee),
-            )?;
+            )?
         }
         None => {
             assert_keys_equal(
@@ -93,8 +114,14 @@ pub fn handler_payment<'c: 'info, 'info>(
                     payment_args.total_fee,
                 )?;
             }
+            (payment_amount, payment_args.total_fee)
         }
-    }
+    };
+
+    // Only the fee actually received by the protocol can be withdrawn
+    accounts
+        .fee_ledger
+        .accrue(payment_args.token.unwrap_or(WSOL_MINT), received_fee)?;
 
     payment_receipt.assign_value(
         payment_args.trade_id,
@@ -105,7 +132,18 @@ pub fn handler_payment<'c: 'info, 'info>(
         payment_args.total_fee,
     )?;
 
-    Ok(())
+    // Emit payment event
+    emit!(PaymentTransferred {
+        trade_id: payment_args.trade_id,
+        from_pubkey: signer.key(),
+        to_pubkey: to_user.key(),
+        token: payment_args.token,
+        payment_amount,
+        total_fee: payment_args.total_fee,
+        protocol: protocol.key(),
+    });
+
+    Ok(received_amount)
 }
 
 /// Accounts required for the payment instruction.
@@ -156,6 +194,26 @@ pub struct PaymentAccounts<'info> {
     )]
     pub payment_receipt: Account<'info, PaymentReceipt>,
 
+    /// The config PDA that contains the protocol configuration.
+    /// The payment instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.payment @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The fee ledger PDA of the payment token, WSOL if the payment is with SOL.
+    /// The total fee is added to its accrued fee. Will be initialized by the signer if needed.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [FeeLedger::SEED, payment_args.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
     /// System program.
     pub system_program: Program<'info, System>,
 }
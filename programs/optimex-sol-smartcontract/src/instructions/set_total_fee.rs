@@ -1,7 +1,7 @@
 //! This module contains the logic for setting the total fee for the trade.
 use anchor_lang::prelude::*;
 
-use crate::{CustomError, TradeDetail, ID};
+use crate::{constants::WSOL_MINT, Config, CustomError, MpcGroup, TotalFeeSet, TradeDetail, WhitelistToken, ID};
 
 /// Parameters rquired for setting the total fee
 #[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -18,12 +18,21 @@ pub struct SetTotalFeeArgs {
 /// * `ctx` - A [Context] of [SetTotalFee] required for setting the total fee.
 /// * `set_total_fee_args` - An argument [SetTotalFeeArgs] required for setting the total fee.
 /// # Errors
-/// * [CustomError::Unauthorized] when the caller is not authorized, or not the [TradeDetail::mpc_pubkey].
+/// * [CustomError::Unauthorized] when the caller is not authorized by the MPC of the trade, see [TradeDetail::is_mpc_authorized].
 /// * [CustomError::TimeOut] when the trade timeout is expired, so we cannot set the total fee anymore.
-pub fn handler_set_total_fee(
-    ctx: Context<SetTotalFee>,
+/// * [CustomError::InvalidTotalFee] when the total fee is greater than the [TradeDetail::amount].
+/// * [CustomError::MaxFeeExceeded] when the total fee is greater than the maximum fee of the token, or the [Config::max_fee_bps].
+pub fn handler_set_total_fee<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SetTotalFee<'info>>,
     set_total_fee_args: SetTotalFeeArgs,
 ) -> Result<()> {
+    // Check the MPC of the trade, or the threshold of its MPC group, authorized the instruction
+    ctx.accounts.user_trade_detail.assert_mpc_authorized(
+        ctx.accounts.mpc_group.as_deref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+
     let user_trade_detail = &mut ctx.accounts.user_trade_detail;
 
     // Check timeout
@@ -37,8 +46,22 @@ pub fn handler_set_total_fee(
     if user_trade_detail.amount < set_total_fee_args.amount {
         return err!(CustomError::InvalidTotalFee);
     }
+    let token_max_fee_bps = WhitelistToken::try_load(&ctx.accounts.whitelist_token)?
+        .and_then(|whitelist_token| whitelist_token.max_fee_bps);
+    ctx.accounts.config.assert_max_fee(
+        token_max_fee_bps,
+        user_trade_detail.amount,
+        set_total_fee_args.amount,
+    )?;
     user_trade_detail.total_fee = Some(set_total_fee_args.amount);
 
+    // Emit set total fee event
+    emit!(TotalFeeSet {
+        trade_id: set_total_fee_args.trade_id,
+        operator: ctx.accounts.signer.key(),
+        total_fee: set_total_fee_args.amount,
+    });
+
     Ok(())
 }
 
@@ -47,11 +70,8 @@ pub fn handler_set_total_fee(
 #[instruction(set_total_fee_args: SetTotalFeeArgs)]
 pub struct SetTotalFee<'info> {
     /// The signer account who is authorized to set the total fee.
-    /// Must be the [TradeDetail::mpc_pubkey].
-    #[account(
-        mut,
-        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized // Check authorization
-    )]
+    /// Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized].
+    #[account(mut)]
     pub signer: Signer<'info>,
 
     /// The user trade detail account that contains the trade information.
@@ -62,4 +82,27 @@ pub struct SetTotalFee<'info> {
         owner = ID
     )]
     pub user_trade_detail: Account<'info, TradeDetail>,
+
+    /// The config PDA that contains the protocol configuration.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK:
+    /// The whitelist token PDA of the trade token, WSOL if the trade is with SOL. Used for the maximum fee override.
+    /// It may have been removed since the deposit, then the [Config::max_fee_bps] is used.
+    #[account(
+        seeds = [WhitelistToken::SEED, user_trade_detail.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub whitelist_token: UncheckedAccount<'info>,
+
+    /// The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].
+    /// The other signing members are appended to the remaining accounts.
+    #[account(
+        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized,
+    )]
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
 }
@@ -1,7 +1,7 @@
 //! This module contains the logic for the settlement instruction.
 use anchor_lang::prelude::*;
 
-use crate::{error::CustomError, event::*, state::*, utils::*, ID};
+use crate::{constants::WSOL_MINT, error::CustomError, event::*, state::*, utils::*, ID};
 
 /// Parameters rquired for the settlement function
 #[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -15,8 +15,9 @@ pub struct SettlementArgs {
 /// * `ctx` - A [Context] of [SettlementAccounts] required for settling the trade.
 /// * `payment_args` - An argument [SettlementArgs] required for settling the trade.
 /// # Errors
-/// * [CustomError::Unauthorized] when the caller is not authorized by both [TradeDetail::mpc_pubkey] and [TradeDetail::user_ephemeral_pubkey].
-/// * [CustomError::InvalidUserAccount] when the user account is not match with [TradeDetail::user_pubkey].
+/// * [CustomError::InstructionPaused] when the settlement instruction is paused.
+/// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+/// * [CustomError::InvalidUserAccount] when the user account is not match with [TradeDetail::rent_payer].
 /// * [CustomError::InvalidRefundPubkey] when the refund pubkey is not match with [TradeDetail::refund_pubkey].
 /// * [CustomError::TimeOut] when the trade timeout is expired, so we cannot settle the trade anymore.
 /// * [CustomError::InvalidTradeStatus] when the trade status is not [TradeStatus::Deposited]. We only can settle the trade has deposited status.
@@ -28,6 +29,13 @@ pub fn handler_settlement<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, SettlementAccounts<'info>>,
     settlement_args: SettlementArgs,
 ) -> Result<()> {
+    // Check the MPC of the trade, or the threshold of its MPC group, authorized the instruction
+    ctx.accounts.user_trade_detail.assert_mpc_authorized(
+        ctx.accounts.mpc_group.as_deref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+
     let vault = &ctx.accounts.vault.to_account_info();
     let signer = &ctx.accounts.signer;
     let pmm = &ctx.accounts.pmm.to_account_info();
@@ -45,7 +53,7 @@ pub fn handler_settlement<'c: 'info, 'info>(
 
     // Handle token or SOL settlement
     // Transfer asset from the vault to the pmm and protocol
-    match user_trade_detail.token {
+    let received_fee = match user_trade_detail.token {
         Some(token_pubkey) => {
             let seeds: &[&[u8]] = &[b"vault", &settlement_args.trade_id];
             let (_, bump) = Pubkey::find_program_address(&seeds, &ID);
@@ -53,7 +61,7 @@ pub fn handler_settlement<'c: 'info, 'info>(
             let binding = [bump];
             seeds_signer.push(&binding);
 
-            transfer_spl_token(
+            let (_, received_fee) = transfer_spl_token(
                 &mut ctx.remaining_accounts.iter(),
                 &token_pubkey,
                 &vault.key,
@@ -63,6 +71,7 @@ pub fn handler_settlement<'c: 'info, 'info>(
                 &[seeds_signer],
                 user_trade_detail.total_fee,
             )?;
+            received_fee
         }
         None => {
             // transfer SOL from vault to pmm and protocol
@@ -72,10 +81,16 @@ pub fn handler_settlement<'c: 'info, 'info>(
             if total_fee != 0 {
                 **ctx.accounts.protocol.try_borrow_mut_lamports()? += total_fee;
             }
+            total_fee
         }
-    }
+    };
     user_trade_detail.status = TradeStatus::Settled;
     user_trade_detail.settled_pmm = pmm.key();
+    WhitelistToken::release_outstanding(&ctx.accounts.whitelist_token, user_trade_detail.amount)?;
+    // Only the fee actually received by the protocol, net of any Token-2022 transfer fee, can be withdrawn
+    ctx.accounts
+        .fee_ledger
+        .accrue(user_trade_detail.token.unwrap_or(WSOL_MINT), received_fee)?;
 
     // Emit settlement event
     emit!(Settled {
@@ -97,19 +112,16 @@ pub fn handler_settlement<'c: 'info, 'info>(
 #[instruction(settlement_args: SettlementArgs)]
 pub struct SettlementAccounts<'info> {
     /// The signer who is authorized to settle the trade.
-    /// Must be the [TradeDetail::mpc_pubkey]
-    #[account(
-        mut,
-        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized // Check authorization
-    )]
+    /// Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized].
+    #[account(mut)]
     pub signer: Signer<'info>,
 
     /// CHECK:
-    /// The user account that is the depositor of the trade. This account will receive the rent fee of the nonce check account PDA.
-    /// Must be the [TradeDetail::user_pubkey].
+    /// The user account that paid the rent of the trade. This account will receive the rent fee of the nonce check account PDA.
+    /// Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored.
     #[account(
         mut,
-        address =  user_trade_detail.user_pubkey @ CustomError::InvalidUserAccount, // check user account
+        constraint = user_account.key() == user_trade_detail.rent_payer() @ CustomError::InvalidUserAccount, // check user account
     )]
     pub user_account: UncheckedAccount<'info>,
 
@@ -173,6 +185,43 @@ pub struct SettlementAccounts<'info> {
     #[account(mut)]
     pub pmm: UncheckedAccount<'info>,
 
+    /// The config PDA that contains the protocol configuration.
+    /// The settlement instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.settlement @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK:
+    /// The whitelist token PDA of the trade token, WSOL if the trade is with SOL.
+    /// The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated.
+    #[account(
+        mut,
+        seeds = [WhitelistToken::SEED, user_trade_detail.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub whitelist_token: UncheckedAccount<'info>,
+
+    /// The fee ledger PDA of the trade token, WSOL if the trade is with SOL.
+    /// The total fee is added to its accrued fee. Will be initialized by the signer if needed.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [FeeLedger::SEED, user_trade_detail.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
     /// System program.
     pub system_program: Program<'info, System>,
+
+    /// The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].
+    /// The other signing members are appended to the remaining accounts.
+    #[account(
+        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized,
+    )]
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
 }
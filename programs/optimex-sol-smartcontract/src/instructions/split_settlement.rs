@@ -0,0 +1,258 @@
+//! This module contains the logic for the split settlement instruction, settling a trade to multiple pmms.
+use anchor_lang::prelude::*;
+
+use crate::{constants::WSOL_MINT, error::CustomError, event::*, state::*, utils::*, ID};
+
+/// A leg of the split settlement, the amount paid to a pmm.
+#[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SettlementLeg {
+    /// The pmm that receives the amount.
+    pub pmm: Pubkey,
+    /// The amount paid to the pmm, with decimals.
+    pub amount: u64,
+}
+
+/// Parameters required for the split settlement function
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SplitSettlementArgs {
+    /// The tradeId, unique identifier for the trade
+    pub trade_id: [u8; 32],
+    /// The legs of the settlement. The sum of the amounts must be the settlement amount, [TradeDetail::amount] minus [TradeDetail::total_fee].
+    pub legs: Vec<SettlementLeg>,
+}
+
+/// Handles the settlement of the trade to multiple pmms.
+///
+/// The remaining accounts are:
+/// * When the trade is with SOL: the pmm accounts, in the same order as the legs.
+/// * When the trade is with token: for each leg, the token program, the mint, the vault token account and the pmm token account.
+///   The protocol token account follows the accounts of the first leg.
+/// # Arguments
+/// * `ctx` - A [Context] of [SplitSettlementAccounts] required for settling the trade.
+/// * `split_settlement_args` - An argument [SplitSettlementArgs] required for settling the trade.
+/// # Errors
+/// * [CustomError::InstructionPaused] when the settlement instruction is paused.
+/// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+/// * [CustomError::InvalidUserAccount] when the user account is not match with [TradeDetail::rent_payer].
+/// * [CustomError::InvalidRefundPubkey] when the refund pubkey is not match with [TradeDetail::refund_pubkey].
+/// * [CustomError::TimeOut] when the trade timeout is expired, so we cannot settle the trade anymore.
+/// * [CustomError::InvalidTradeStatus] when the trade status is not [TradeStatus::Deposited]. We only can settle the trade has deposited status.
+/// * [CustomError::InvalidSettlementLegs] when there is no leg, a leg has zero amount, or the sum of the legs is not the settlement amount.
+/// * [CustomError::InvalidPmmAccount] when the pmm account is not match with the pmm of the leg.
+/// * [CustomError::InvalidMintKey] when the mint key is not match with the mint of the trade.
+/// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the vault and mint.
+/// * [CustomError::InvalidDestinationAta] when the destination to transfer a leg is not the associated token account of the pmm and mint.
+/// * [CustomError::InvalidDestinationAta] when the destination to transfer total fee is not the associated token account of the protocol and mint.
+pub fn handler_split_settlement<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SplitSettlementAccounts<'info>>,
+    split_settlement_args: SplitSettlementArgs,
+) -> Result<()> {
+    // Check the MPC of the trade, or the threshold of its MPC group, authorized the instruction
+    ctx.accounts.user_trade_detail.assert_mpc_authorized(
+        ctx.accounts.mpc_group.as_deref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let vault = &ctx.accounts.vault.to_account_info();
+    let signer = &ctx.accounts.signer;
+    let user_trade_detail = &mut ctx.accounts.user_trade_detail;
+    let legs = &split_settlement_args.legs;
+
+    // Check if the trade is able to settled
+    user_trade_detail.assert_settlement()?;
+
+    // Calculate the settlement amount after deducting the protocol fee, and check the legs cover it exactly
+    let total_fee = user_trade_detail.total_fee.unwrap_or_default();
+    let settlement_amount = user_trade_detail.amount - total_fee;
+    let mut legs_amount: u64 = 0;
+    for leg in legs {
+        if leg.amount == 0 {
+            return err!(CustomError::InvalidSettlementLegs);
+        }
+        legs_amount = legs_amount
+            .checked_add(leg.amount)
+            .ok_or(CustomError::InvalidSettlementLegs)?;
+    }
+    if legs.is_empty() || legs_amount != settlement_amount {
+        return err!(CustomError::InvalidSettlementLegs);
+    }
+
+    // Handle token or SOL settlement
+    // Transfer asset from the vault to the pmms and protocol
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+    let received_fee = match user_trade_detail.token {
+        Some(token_pubkey) => {
+            let seeds: &[&[u8]] = &[TradeVault::SEED, &split_settlement_args.trade_id];
+            let (_, bump) = Pubkey::find_program_address(seeds, &ID);
+            let seeds_signer = &mut seeds.to_vec();
+            let binding = [bump];
+            seeds_signer.push(&binding);
+
+            let mut received_fee = 0;
+            for (index, leg) in legs.iter().enumerate() {
+                // The total fee is transferred along with the first leg
+                let fee_amount = if index == 0 { user_trade_detail.total_fee } else { None };
+                let (_, leg_received_fee) = transfer_spl_token(
+                    remaining_accounts,
+                    &token_pubkey,
+                    vault.key,
+                    &leg.pmm,
+                    vault,
+                    leg.amount,
+                    &[seeds_signer],
+                    fee_amount,
+                )?;
+                received_fee += leg_received_fee;
+            }
+            received_fee
+        }
+        None => {
+            // transfer SOL from vault to pmms and protocol
+            **vault.try_borrow_mut_lamports()? -= user_trade_detail.amount;
+            for leg in legs {
+                let pmm = next_account_info(remaining_accounts)?;
+                assert_keys_equal(pmm.key, &leg.pmm, CustomError::InvalidPmmAccount)?;
+                **pmm.try_borrow_mut_lamports()? += leg.amount;
+            }
+
+            if total_fee != 0 {
+                **ctx.accounts.protocol.try_borrow_mut_lamports()? += total_fee;
+            }
+            total_fee
+        }
+    };
+    user_trade_detail.status = TradeStatus::Settled;
+    user_trade_detail.settled_pmm = legs[0].pmm;
+    WhitelistToken::release_outstanding(&ctx.accounts.whitelist_token, user_trade_detail.amount)?;
+    // Only the fee actually received by the protocol, net of any Token-2022 transfer fee, can be withdrawn
+    ctx.accounts
+        .fee_ledger
+        .accrue(user_trade_detail.token.unwrap_or(WSOL_MINT), received_fee)?;
+
+    // Emit split settlement event
+    emit!(SplitSettled {
+        trade_id: split_settlement_args.trade_id,
+        operator: signer.key(),
+        token: user_trade_detail.token,
+        legs: split_settlement_args.legs,
+        total_fee,
+        vault: vault.key(),
+        protocol: ctx.accounts.protocol.key(),
+    });
+
+    Ok(())
+}
+
+/// Context accounts for the split settlement instruction.
+#[derive(Accounts)]
+#[instruction(split_settlement_args: SplitSettlementArgs)]
+pub struct SplitSettlementAccounts<'info> {
+    /// The signer who is authorized to settle the trade.
+    /// Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized].
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK:
+    /// The user account that paid the rent of the trade. This account will receive the rent fee of the nonce check account PDA.
+    /// Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored.
+    #[account(
+        mut,
+        constraint = user_account.key() == user_trade_detail.rent_payer() @ CustomError::InvalidUserAccount, // check user account
+    )]
+    pub user_account: UncheckedAccount<'info>,
+
+    /// The user ephemeral account of the trade, need to sign this transaction too.
+    /// Must be the [TradeDetail::user_ephemeral_pubkey].
+    #[account(
+        address = user_trade_detail.user_ephemeral_pubkey @ CustomError::Unauthorized, // Check user ephemeral pubkey
+    )]
+    pub user_ephemeral_account: Signer<'info>,
+
+    /// The user trade detail PDA that contains the trade information.
+    #[account(
+        mut,
+        seeds = [&split_settlement_args.trade_id],
+        bump,
+        owner = ID,
+    )]
+    pub user_trade_detail: Account<'info, TradeDetail>,
+
+    /// The nonce check account PDA, used to check the nonce account is being used by another trade, or not yet closed.
+    /// This PDA will be closed by the instruction.
+    #[account(
+        mut,
+        seeds = [NonceCheckAccount::SEED, user_ephemeral_account.key().as_ref()],
+        bump,
+        close = user_account,
+    )]
+    pub nonce_check_account: Account<'info, NonceCheckAccount>,
+
+    /// The trade vault PDA that corresponds to the trade.
+    #[account(
+        mut,
+        seeds = [TradeVault::SEED, &split_settlement_args.trade_id],
+        bump,
+        owner = ID, // This PDA must come from our smart-contract
+    )]
+    pub vault: Account<'info, TradeVault>,
+
+    /// CHECK:
+    /// The refund account of the trade.
+    /// Must be the [TradeDetail::refund_pubkey].
+    #[account(
+        mut,
+        address = user_trade_detail.refund_pubkey @ CustomError::InvalidRefundPubkey // Check refund_pubkey
+    )]
+    pub refund_account: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The protocol PDA account.
+    #[account(
+        mut,
+        seeds = [b"protocol"],
+        bump,
+        owner = ID, // This PDA must come from our smart-contract
+    )]
+    pub protocol: UncheckedAccount<'info>,
+
+    /// The config PDA that contains the protocol configuration.
+    /// The settlement instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.settlement @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK:
+    /// The whitelist token PDA of the trade token, WSOL if the trade is with SOL.
+    /// The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated.
+    #[account(
+        mut,
+        seeds = [WhitelistToken::SEED, user_trade_detail.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub whitelist_token: UncheckedAccount<'info>,
+
+    /// The fee ledger PDA of the trade token, WSOL if the trade is with SOL.
+    /// The total fee is added to its accrued fee. Will be initialized by the signer if needed.
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [FeeLedger::SEED, user_trade_detail.token.unwrap_or(WSOL_MINT).as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].
+    /// The other signing members are appended to the remaining accounts.
+    #[account(
+        address = user_trade_detail.mpc_pubkey @ CustomError::Unauthorized,
+    )]
+    pub mpc_group: Option<Account<'info, MpcGroup>>,
+}
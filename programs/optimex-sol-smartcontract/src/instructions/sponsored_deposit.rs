@@ -0,0 +1,149 @@
+//! This module contains the logic for depositing the trade, with the rent paid by a relayer.
+use crate::{error::CustomError, event::*, instructions::deposit::*, state::*, ID};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+/// Handles the deposit of either tokens or SOL into the vault, with the rent of the trade accounts paid by the payer.
+///
+/// The [TradeDetail::payer_pubkey] records the payer, which gets the rent back when the trade accounts are closed.
+/// # Arguments
+/// * `ctx` - A [Context] of [SponsoredDepositAccounts] required for the deposit.
+/// * `deposit_args` - An argument [DepositArgs] required for the deposit.
+/// # Errors
+/// Same as the deposit instruction, see [handler_deposit].
+pub fn handler_sponsored_deposit<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SponsoredDepositAccounts<'info>>,
+    deposit_args: DepositArgs,
+) -> Result<()> {
+    // Extract account information
+    let signer = &ctx.accounts.signer.to_account_info();
+    let payer = &ctx.accounts.payer.to_account_info();
+    let vault = &ctx.accounts.vault.to_account_info();
+    let ephemeral_account = &ctx.accounts.ephemeral_account.to_account_info();
+
+    validate_solver_signature(
+        &ctx.accounts.config,
+        ctx.accounts.instructions_sysvar.as_ref(),
+        &deposit_args,
+    )?;
+
+    let (deposited_amount, from_token_pubkey) = deposit_to_vault(
+        signer,
+        vault,
+        ephemeral_account,
+        &mut ctx.accounts.whitelist_token,
+        &ctx.accounts.mpc_registry,
+        ctx.remaining_accounts,
+        &deposit_args,
+    )?;
+
+    // Assign value for user's trade detail, the payer pays the rent
+    ctx.accounts.user_trade_detail.assign_value(
+        &deposit_args,
+        deposited_amount,
+        from_token_pubkey,
+        signer.key(),
+        ephemeral_account.key(),
+        payer.key(),
+    )?;
+
+    // Emit deposit event
+    emit!(Deposited {
+        trade_id: deposit_args.trade_id,
+        from_pubkey: signer.key(),
+        token: from_token_pubkey,
+        amount: deposited_amount,
+        vault: vault.key(),
+    });
+    emit!(DepositSponsored {
+        trade_id: deposit_args.trade_id,
+        payer: payer.key(),
+    });
+
+    Ok(())
+}
+
+/// The context accounts required for the sponsored deposit instruction.
+#[derive(Accounts)]
+#[instruction(deposit_args: DepositArgs)]
+pub struct SponsoredDepositAccounts<'info> {
+    /// The payer account that pays the rent of the trade accounts, usually a relayer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The signer account that is authorized to perform the deposit instruction.
+    /// This is the account that perform the deposit, the amount is transferred from it.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// The trade detail PDA that contains the trade information.
+    /// This PDA will be initialized by the instruction.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TradeDetail::INIT_SPACE,
+        seeds = [&deposit_args.trade_id,],
+        bump
+    )]
+    pub user_trade_detail: Account<'info, TradeDetail>,
+
+    /// CHECK: User ephemeral account, used as nonce account too.
+    #[account(mut)]
+    pub ephemeral_account: Signer<'info>,
+
+    /// CHECK: The nonce check account, used to check the nonce account is being used by another trade, or not yet closed.
+    /// This PDA will be initialized by the instruction.
+    #[account(
+        init,
+        payer = payer,
+        space = 8,
+        seeds = [NonceCheckAccount::SEED, ephemeral_account.key.as_ref()],
+        bump,
+    )]
+    pub nonce_check_account: Account<'info, NonceCheckAccount>,
+
+    /// The trade vault PDA that corresponds to the trade.
+    /// This PDA will be initialized by the instruction.
+    #[account(
+        init,
+        space = 8 + TradeVault::INIT_SPACE,
+        payer = payer,
+        seeds = [TradeVault::SEED, &deposit_args.trade_id],
+        bump,
+    )]
+    pub vault: Account<'info, TradeVault>,
+
+    /// CHECK
+    /// The whitelist token PDA, only token has been whitelisted can be deposited
+    /// The deposited amount is added to its outstanding amount.
+    #[account(
+        mut,
+        owner = ID @ CustomError::NotWhitelistedToken,  // This PDA must come from our smart-contract
+    )]
+    pub whitelist_token: Account<'info, WhitelistToken>,
+
+    /// The config PDA that contains the protocol configuration.
+    /// The deposit instruction must not be paused.
+    #[account(
+        seeds = [Config::SEED],
+        bump,
+        constraint = !config.pause.deposit @ CustomError::InstructionPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK:
+    /// The MPC registry PDA of the [TradeDetailInput::mpc_pubkey].
+    /// The MPC key must be registered and active, see [MpcRegistry::assert_active].
+    #[account(
+        seeds = [MpcRegistry::SEED, deposit_args.data.mpc_pubkey.as_ref()],
+        bump,
+    )]
+    pub mpc_registry: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// The instructions sysvar, to read the secp256k1 program instruction that verifies the solver signature.
+    /// Required when the [Config::solver_signature_required].
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
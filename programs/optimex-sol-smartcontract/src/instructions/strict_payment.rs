@@ -0,0 +1,77 @@
+//! This module contains the logic for the strict payment instruction.
+use anchor_lang::prelude::*;
+
+use crate::{error::CustomError, event::*, payment::*, state::*};
+
+/// Handles the strict payment instruction.
+///
+/// The payment is checked against the [PaymentExpectation] published by the MPC before any funds are moved,
+/// and the amount received by the receiver is checked once transferred.
+///
+/// # Arguments
+/// * `ctx` - A [Context] of [StrictPaymentAccounts] required for the payment.
+/// * `payment_args` - An argument [PaymentArgs] required for the payment.
+/// # Errors
+/// * [CustomError::InvalidTradeId] when the [PaymentArgs::trade_id] is not the [PaymentExpectation::trade_id].
+/// * [CustomError::PaymentAlreadyFulfilled] when the [PaymentExpectation] is already fulfilled.
+/// * [CustomError::DeadlineExceeded] when the current timestamp is greater than the [PaymentExpectation::deadline].
+/// * [CustomError::InvalidUserAccount] when the receiver is not the [PaymentExpectation::to_pubkey].
+/// * [CustomError::InvalidMintKey] when the [PaymentArgs::token] is not the [PaymentExpectation::token].
+/// * [CustomError::InvalidTotalFee] when the [PaymentArgs::total_fee] is greater than the [PaymentExpectation::max_fee].
+/// * [CustomError::InvalidAmount] when the amount received is less than the [PaymentExpectation::min_amount].
+/// * And all errors of the payment instruction.
+pub fn handler_strict_payment<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, StrictPaymentAccounts<'info>>,
+    payment_args: PaymentArgs,
+) -> Result<()> {
+    let payment_expectation = &mut ctx.accounts.payment_expectation;
+    if payment_expectation.trade_id != payment_args.trade_id {
+        return err!(CustomError::InvalidTradeId);
+    }
+    payment_expectation.assert_payment(
+        ctx.accounts.payment.to_user.key,
+        payment_args.token,
+        payment_args.total_fee,
+        Clock::get()?.unix_timestamp,
+    )?;
+    payment_expectation.fulfilled = true;
+
+    // The minimum amount is checked against the amount actually received, net of any Token-2022 transfer fee
+    let received_amount = process_payment(&mut ctx.accounts.payment, ctx.remaining_accounts, &payment_args)?;
+    let payment_expectation = &ctx.accounts.payment_expectation;
+    payment_expectation.assert_received(received_amount)?;
+
+    emit!(PaymentExpectationFulfilled {
+        trade_id: payment_expectation.trade_id,
+        mpc_pubkey: payment_expectation.mpc_pubkey,
+        to_pubkey: payment_expectation.to_pubkey,
+        token: payment_expectation.token,
+        received_amount,
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the strict payment instruction.
+///
+/// The instruction arguments are only deserialized by the nested [PaymentAccounts],
+/// since the arguments deserialized by this struct would not be passed down to it.
+#[derive(Accounts)]
+pub struct StrictPaymentAccounts<'info> {
+    /// The accounts of the payment instruction.
+    pub payment: PaymentAccounts<'info>,
+
+    /// The payment expectation PDA published by the MPC for the trade.
+    /// Must be the expectation of the [PaymentArgs::trade_id], checked by the instruction.
+    /// Will be marked as fulfilled by the instruction.
+    #[account(
+        mut,
+        seeds = [
+            PaymentExpectation::SEED,
+            &payment_expectation.trade_id,
+            payment_expectation.mpc_pubkey.as_ref(),
+        ],
+        bump,
+    )]
+    pub payment_expectation: Account<'info, PaymentExpectation>,
+}
@@ -27,6 +27,7 @@ pub mod optimex_sol_smartcontract {
     /// Initialize the program and some required accounts, setup [Config::admin] if needed
     /// 
     /// This instruction is called after the program is deployed, and is authorized by only the upgrade authority,
+    /// Later admin rotations are done by the admin itself, through [propose_admin] and [accept_admin].
     /// # Arguments
     /// * `ctx` - A [Context] of [Init] required for initialization
     /// * `init_args` - An [InitArgs] required for initialization
@@ -37,54 +38,33 @@ pub mod optimex_sol_smartcontract {
         handler_init(ctx, init_args)
     }
 
-    /// Add or remove an operator for the protocol.
-    /// 
-    /// This instruction is authorized by the [Config::admin].
-    /// # Arguments
-    /// * `ctx` - A [Context] of [AddOrRemoveOperator] required for adding or removing an operator.
-    /// * `operator` - The operator to add or remove.
-    /// * `is_bool` - Whether to add or remove the operator.
-    /// # Errors
-    /// * [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].
-    /// * [CustomError::OperatorAlreadyExists] when add a operator that is already exists.
-    /// * [CustomError::OperatorLimitReached] when add a operator and reach the limit of [Config::OPERATORS_SIZE].
-    /// * [CustomError::OperatorNotFound] when remove a operator that is not exists.
-    pub fn add_or_remove_operator(ctx: Context<AddOrRemoveOperator>, operator: Pubkey, is_add: bool) -> Result<()> {
-        handler_add_or_remove_operator(ctx, operator, is_add)
-    }
-
     /// Add or update whitelist token setup.
     /// 
-    /// This instruction is authorized by the [Config::operators].
+    /// This instruction is authorized by the [Role::WhitelistManager] members.
     /// # Arguments
     /// * `ctx` - A [Context] of [AddOrUpdateWhitelist] required for adding or updating the whitelist.
     /// * `amount` - The minimum amount to set for the whitelisted token.
+    /// * `max_amount` - The maximum amount of a single deposit. Zero means no limit.
+    /// * `max_outstanding` - The maximum amount of the token held by the trade vaults. Zero means no limit.
     /// # Errors
-    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the operator.
-    pub fn add_or_update_whitelist(ctx: Context<AddOrUpdateWhitelist>, amount: u64) -> Result<()> {
-        handler_add_or_update_whitelist(ctx, amount)
-    }
-
-    /// Withdraw the total fee of the protocol to fee receiver.
-    /// 
-    /// This instruction is authorized by anyone.
-    /// However, only account decaled as [FeeReceiver] can receive the fee.
-    /// # Arguments
-    /// * `ctx` - A [Context] of [WithdrawTotalFeeAccounts] required for withdrawing the total fee.
-    /// * `withdraw_total_fee_args` - An argument [WithdrawTotalFeeArgs] required for withdrawing the total fee.
-    /// # Errors
-    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
-    pub fn withdraw_total_fee<'c: 'info, 'info>(ctx: Context<'_, '_, 'c, 'info, WithdrawTotalFeeAccounts<'info>>, withdraw_total_fee_args: WithdrawTotalFeeArgs) -> Result<()> {
-        handler_withdraw_total_fee(ctx, withdraw_total_fee_args)
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not a whitelist manager.
+    /// * [CustomError::UnsupportedMintExtension] - The Token-2022 mint has an extension that can endanger the vault funds, such as a permanent delegate or a transfer hook.
+    pub fn add_or_update_whitelist(
+        ctx: Context<AddOrUpdateWhitelist>,
+        amount: u64,
+        max_amount: u64,
+        max_outstanding: u64,
+    ) -> Result<()> {
+        handler_add_or_update_whitelist(ctx, amount, max_amount, max_outstanding)
     }
 
     /// Remove whitelist token setup.
     /// 
-    /// This instruction is authorized by the operator.
+    /// This instruction is authorized by the [Role::WhitelistManager] members.
     /// # Arguments
     /// * `ctx` - A [Context] of [RemoveWhitelist] required for removing the whitelist.
     /// # Errors
-    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the operator.
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not a whitelist manager.
     pub fn remove_whitelist(ctx: Context<RemoveWhitelist>) -> Result<()> {
         handler_remove_whitelist(ctx)
     }
@@ -94,20 +74,29 @@ pub mod optimex_sol_smartcontract {
     /// Only token that is set whitelisted can be deposited.
     /// 
     /// The [TradeDetail], [TradeVault], [NonceCheckAccount], [anchor_spl::token::TokenAccount] of vault and token mint, are created in this instruction.
+    /// Both SPL Token and Token-2022 tokens are supported. The [TradeDetail::amount] is the amount the vault actually received, after the transfer fee if any.
+    /// When the [Config::solver_signature_required], a previous secp256k1 program instruction must verify the signature
+    /// of the [TradeInput::solver] over the trade id.
     /// # Arguments
     /// * `ctx` - A [Context] of [DepositAccounts] required for the deposit.
     /// * `deposit_args` - An argument [DepositArgs] required for the deposit.
     /// # Errors 
+    /// * [CustomError::InstructionPaused] when the deposit instruction is paused.
     /// * [CustomError::NotWhitelistedToken] when the token is not whitelisted.
     /// * [CustomError::NonceAccountBeingUsed] when the nonce account is being used by another trade, or not yet closed.
     /// * [CustomError::Unauthorized] when the signer is not match with the pubkey in the [DepositArgs]
     /// * [CustomError::InvalidTimeout] when the current timestamp is greater than the deposit timeout.
+    /// * [CustomError::MpcNotRegistered] when the MPC of the trade is not registered, or not active in the [MpcRegistry].
     /// * [CustomError::DepositZeroAmount] when the deposit amount is zero.
     /// * [CustomError::InvalidAmount] when the deposit amount is less than the whitelisted amount.
-    /// * [CustomError::InvalidTradeId] when the calculated trade ID is not match with the trade ID in the [DepositArgs].
+    /// * [CustomError::MaxAmountExceeded] when the deposit amount is greater than the [WhitelistToken::max_amount].
+    /// * [CustomError::OutstandingLimitReached] when the deposit makes the [WhitelistToken::outstanding] greater than the [WhitelistToken::max_outstanding].
+    /// * [CustomError::InvalidTradeId] when the trade ID calculated with the [DepositArgs::trade_id_scheme] is not match with the trade ID in the [DepositArgs].
+    /// * [CustomError::InvalidTokenProgram] when the token program is not the SPL Token or the Token-2022 program.
     /// * [CustomError::InvalidMintKey] when the mint key is not match with the mint of the trade.
     /// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.
     /// * [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the vault and mint.
+    /// * [CustomError::InvalidSolverSignature] when the solver signature is required, and not verified by a previous instruction.
     /// 
     pub fn deposit<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, DepositAccounts<'info>>,
@@ -120,13 +109,18 @@ pub mod optimex_sol_smartcontract {
     /// 
     /// Only token that is set whitelisted can be deposited. The [PaymentReceipt] is created in this instruction.
     /// 
+    /// The payment is not checked against the [PaymentExpectation] of the trade, even when the MPC published one.
+    /// The checks are opt-in, through [strict_payment].
+    /// 
     /// # Arguments
     /// * `ctx` - A [Context] of [PaymentAccounts] required for the payment.
     /// * `payment_args` - An argument [PaymentArgs] required for the payment.
     /// # Errors
+    /// * [CustomError::InstructionPaused] when the payment instruction is paused.
     /// * [CustomError::NotWhitelistedToken] when the token is not whitelisted.
     /// * [CustomError::DeadlineExceeded] when the current timestamp is greater than the [PaymentArgs::deadline].
     /// * [CustomError::InvalidAmount] when the amount [PaymentArgs::amount] is less than the [PaymentArgs::total_fee].
+    /// * [CustomError::MaxFeeExceeded] when the [PaymentArgs::total_fee] is greater than the maximum fee of the token, or the [Config::max_fee_bps].
     /// * [CustomError::InvalidMintKey] when the mint key is not match with the [PaymentReceipt::token].
     /// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.
     /// * [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the [PaymentReceipt::to_pubkey] and mint.
@@ -138,6 +132,68 @@ pub mod optimex_sol_smartcontract {
         handler_payment(ctx, payment_args)
     }
 
+    /// The MPC publishes the expected payment of a specific trade.
+    /// 
+    /// The [PaymentExpectation] is created in this instruction, and is used by the strict payment instruction.
+    /// 
+    /// This instruction is authorized by a registered MPC key, or the threshold of an [MpcGroup].
+    /// 
+    /// # Arguments
+    /// * `ctx` - A [Context] of [CreatePaymentExpectationAccounts] required for publishing the payment expectation.
+    /// * `args` - An argument [CreatePaymentExpectationArgs] required for publishing the payment expectation.
+    /// # Errors
+    /// * [CustomError::Unauthorized] when the `mpc_group` is given, and less than [MpcGroup::threshold] members signed.
+    /// * [CustomError::MpcNotRegistered] when the MPC key, or the address of the `mpc_group`, is not registered and active.
+    /// * [CustomError::InvalidAmount] when the [CreatePaymentExpectationArgs::min_amount] is zero.
+    /// * [CustomError::DeadlineExceeded] when the [CreatePaymentExpectationArgs::deadline] is already passed.
+    pub fn create_payment_expectation<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CreatePaymentExpectationAccounts<'info>>,
+        args: CreatePaymentExpectationArgs,
+    ) -> Result<()> {
+        handler_create_payment_expectation(ctx, args)
+    }
+
+    /// The pmm perform the payment process to a specific trade, checked against the [PaymentExpectation].
+    /// 
+    /// Same as the payment instruction, but the recipient, token, amount, fee and deadline are validated
+    /// against the [PaymentExpectation] published by the MPC before moving any funds.
+    /// The [PaymentExpectation] is marked as fulfilled, and the [PaymentExpectationFulfilled] event is emitted.
+    /// The unchecked payment instruction stays available for the same trade, so only this event proves the expected payment.
+    /// 
+    /// # Arguments
+    /// * `ctx` - A [Context] of [StrictPaymentAccounts] required for the payment.
+    /// * `payment_args` - An argument [PaymentArgs] required for the payment.
+    /// # Errors
+    /// * [CustomError::InvalidTradeId] when the [PaymentArgs::trade_id] is not the [PaymentExpectation::trade_id].
+    /// * [CustomError::PaymentAlreadyFulfilled] when the [PaymentExpectation] is already fulfilled.
+    /// * [CustomError::DeadlineExceeded] when the current timestamp is greater than the [PaymentExpectation::deadline].
+    /// * [CustomError::InvalidUserAccount] when the receiver is not the [PaymentExpectation::to_pubkey].
+    /// * [CustomError::InvalidMintKey] when the [PaymentArgs::token] is not the [PaymentExpectation::token].
+    /// * [CustomError::InvalidTotalFee] when the [PaymentArgs::total_fee] is greater than the [PaymentExpectation::max_fee].
+    /// * [CustomError::InvalidAmount] when the amount received is less than the [PaymentExpectation::min_amount].
+    /// * And all errors of the payment instruction.
+    pub fn strict_payment<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, StrictPaymentAccounts<'info>>,
+        payment_args: PaymentArgs,
+    ) -> Result<()> {
+        handler_strict_payment(ctx, payment_args)
+    }
+
+    /// The MPC closes the [PaymentExpectation] to reclaim the rent fee.
+    /// 
+    /// Only available when the expectation is fulfilled, or the [PaymentExpectation::deadline] is passed.
+    /// 
+    /// # Arguments
+    /// * `ctx` - A [Context] of [ClosePaymentExpectationAccounts] required for closing the payment expectation.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - When the caller is not authorized by the MPC of the expectation, see [PaymentExpectation::is_mpc_authorized].
+    /// * [CustomError::CloseNotAvailable] - When the payment expectation is not fulfilled and the deadline is not passed.
+    pub fn close_payment_expectation<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClosePaymentExpectationAccounts<'info>>,
+    ) -> Result<()> {
+        handler_close_payment_expectation(ctx)
+    }
+
     /// Set the total fee for a specific trade.
     /// 
     /// This instruction is authorized by [TradeDetail::mpc_pubkey]. This fee is deducted from the [TradeDetail::amount] when settling.
@@ -145,10 +201,12 @@ pub mod optimex_sol_smartcontract {
     /// * `ctx` - A [Context] of [SetTotalFee] required for setting the total fee.
     /// * `set_total_fee_args` - An argument [SetTotalFeeArgs] required for setting the total fee.
     /// # Errors
-    /// * [CustomError::Unauthorized] when the caller is not authorized, or not the mpc of the trade.
+    /// * [CustomError::Unauthorized] when the caller is not authorized by the MPC of the trade, see [TradeDetail::is_mpc_authorized].
     /// * [CustomError::TimeOut] when the trade timeout is expired, so we cannot set the total fee anymore.
-    pub fn set_total_fee(
-        ctx: Context<SetTotalFee>,
+    /// * [CustomError::InvalidTotalFee] when the total fee is greater than the [TradeDetail::amount].
+    /// * [CustomError::MaxFeeExceeded] when the total fee is greater than the maximum fee of the token, or the [Config::max_fee_bps].
+    pub fn set_total_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SetTotalFee<'info>>,
         set_total_fee_args: SetTotalFeeArgs,
     ) -> Result<()> {
         handler_set_total_fee(ctx, set_total_fee_args)
@@ -158,13 +216,14 @@ pub mod optimex_sol_smartcontract {
     /// 
     /// This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey].
     /// This instruction is called after the pmm paid to users, and before the [TradeDetail::timeout].
-    /// This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::user_pubkey], and allow the nonce can be used by other trade.
+    /// This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.
     /// # Arguments
     /// * `ctx` - A [Context] of [SettlementAccounts] required for settling the trade.
     /// * `payment_args` - An argument [SettlementArgs] required for settling the trade.
     /// # Errors
-    /// * [CustomError::Unauthorized] when the caller is not authorized by both [TradeDetail::mpc_pubkey] and [TradeDetail::user_ephemeral_pubkey].
-    /// * [CustomError::InvalidUserAccount] when the user account is not match with [TradeDetail::user_pubkey].
+    /// * [CustomError::InstructionPaused] when the settlement instruction is paused.
+    /// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+    /// * [CustomError::InvalidUserAccount] when the user account is not match with [TradeDetail::rent_payer].
     /// * [CustomError::InvalidRefundPubkey] when the refund pubkey is not match with [TradeDetail::refund_pubkey].
     /// * [CustomError::TimeOut] when the trade timeout is expired, so we cannot settle the trade anymore.
     /// * [CustomError::InvalidTradeStatus] when the trade status is not [TradeStatus::Deposited]. We only can settle the trade has deposited status.
@@ -179,15 +238,39 @@ pub mod optimex_sol_smartcontract {
         handler_settlement(ctx, settle_args)
     }
 
+    /// MPC settles the trade to multiple pmms, transfer each leg amount to its pmm and the total fee to the protocol, after the pmms paid to users.
+    /// 
+    /// This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey], like [settlement].
+    /// The sum of the legs must be the settlement amount, [TradeDetail::amount] minus [TradeDetail::total_fee].
+    /// The [TradeDetail::settled_pmm] is set to the pmm of the first leg.
+    /// This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SplitSettlementAccounts] required for settling the trade.
+    /// * `split_settlement_args` - An argument [SplitSettlementArgs] required for settling the trade.
+    /// # Errors
+    /// * [CustomError::InstructionPaused] when the settlement instruction is paused.
+    /// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+    /// * [CustomError::TimeOut] when the trade timeout is expired, so we cannot settle the trade anymore.
+    /// * [CustomError::InvalidTradeStatus] when the trade status is not [TradeStatus::Deposited].
+    /// * [CustomError::InvalidSettlementLegs] when there is no leg, a leg has zero amount, or the sum of the legs is not the settlement amount.
+    /// * [CustomError::InvalidPmmAccount] when the pmm account is not match with the pmm of the leg.
+    /// * [CustomError::InvalidDestinationAta] when the destination to transfer a leg is not the associated token account of the pmm and mint.
+    pub fn split_settlement<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SplitSettlementAccounts<'info>>,
+        split_settlement_args: SplitSettlementArgs,
+    ) -> Result<()> {
+        handler_split_settlement(ctx, split_settlement_args)
+    }
+
     /// Claim the deposited amount after the timeout. This instruction is authorized by anyone.
     /// 
     /// The deposited amount is transferred to the [TradeDetail::refund_pubkey].
-    /// This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::user_pubkey], and allow the nonce can be used by other trade.
+    /// This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.
     /// # Arguments
     /// * `ctx` - A [Context] of [Claim] required for claiming the deposited amount.
     /// * `claim_args` - An argument [ClaimArgs] required for claiming the deposited amount.
     /// # Errors
-    /// * [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::user_pubkey].
+    /// * [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::rent_payer].
     /// * [CustomError::InvalidRefundPubkey] when the refund pubkey address is not match with the [TradeDetail::refund_pubkey].
     /// * [CustomError::CLaimNotAvailable] when the [TradeDetail::timeout] is not expired, so we cannot claim the deposited amount.
     /// * [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited], we only claim the Deposited trade and timed out.
@@ -201,21 +284,46 @@ pub mod optimex_sol_smartcontract {
         handler_claim(ctx, claim_args)
     }
 
-    /// Close the finished trade ([TradeStatus::Settled] or [TradeStatus::Claimed]) to reclaim the rent fee.
+    /// Refund the deposited amount before the timeout, when the trade will not be filled. 
+    /// 
+    /// This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey].
+    /// The deposited amount is transferred to the [TradeDetail::refund_pubkey], and the trade status becomes [TradeStatus::Refunded].
+    /// This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [CooperativeRefundAccounts] required for refunding the deposited amount.
+    /// * `cooperative_refund_args` - An argument [CooperativeRefundArgs] required for refunding the deposited amount.
+    /// # Errors
+    /// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+    /// * [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::rent_payer].
+    /// * [CustomError::InvalidRefundPubkey] when the refund pubkey address is not match with the [TradeDetail::refund_pubkey].
+    /// * [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited].
+    /// * [CustomError::InvalidMintKey] when the mint key is not match with the [TradeDetail::token]
+    /// * [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the vault and mint.
+    /// * [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the refund pubkey.
+    pub fn cooperative_refund<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CooperativeRefundAccounts<'info>>,
+        cooperative_refund_args: CooperativeRefundArgs,
+    ) -> Result<()> {
+        handler_cooperative_refund(ctx, cooperative_refund_args)
+    }
+
+    /// Close the finished trade ([TradeStatus::Settled], [TradeStatus::Claimed] or [TradeStatus::Refunded]) to reclaim the rent fee.
     /// 
-    /// Transfer the rent fee of [TradeDetail], [TradeVault] and [anchor_spl::token::TokenAccount] to the [TradeDetail::user_pubkey].
+    /// Transfer the rent fee of [TradeDetail], [TradeVault] and [anchor_spl::token::TokenAccount] to the [TradeDetail::rent_payer].
     /// 
     /// Depend on the trade status, the close action is different:
     /// * When the trade is [TradeStatus::Deposited], this action is not allowed.
-    /// * When the trade is [TradeStatus::Claimed], this action is allowed for anyone.
+    /// * When the trade is [TradeStatus::Claimed] or [TradeStatus::Refunded], this action is allowed for anyone.
     /// * When the trade is [TradeStatus::Settled], MPC can close the trade right away. Otherwise, anyone can close the trade after the [TradeDetail::timeout] + [Config::close_trade_duration].
     /// # Arguments
     /// * `ctx` - A [Context] of [CloseFinishedTradeAccounts] required for closing the trade.
     /// * `_close_finished_trade_args` - An argument [CloseFinishedTradeArgs] required for closing the trade.
     /// # Errors
-    /// * [CustomError::InvalidUserAccount] when the user account is not match to [TradeDetail::user_pubkey]. This account will receive the claimed rent fee.
+    /// * [CustomError::InvalidUserAccount] when the user account is not match to [TradeDetail::rent_payer]. This account will receive the claimed rent fee.
     /// * [CustomError::InvalidTradeStatus] when the trade status is [TradeStatus::Deposited].
     /// * [CustomError::CloseNotAvailable] when the trade is not the available time to close.
+    /// * [CustomError::InvalidTokenAccount] when the token accounts or the mint are missing or do not match the trade.
+    /// * [CustomError::InvalidMintKey] when the mint is not match with the [TradeDetail::token].
     pub fn close_finished_trade<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, CloseFinishedTradeAccounts<'info>>,
         _close_finished_trade_args: CloseFinishedTradeArgs,
@@ -241,38 +349,460 @@ pub mod optimex_sol_smartcontract {
 
     /// Set the waiting duration for closing a finished trade or close a payment receipt.
     /// 
-    /// This instruction is authorized by the [Config::operators].
+    /// This instruction is authorized by the [Role::DurationManager] members.
     /// # Arguments
     /// * `ctx` - A [Context] of [SetCloseWaitDuration] required for setting the waiting duration.
     /// * `set_close_wait_duration_args` - An argument [SetCloseWaitDurationArgs] required for setting the waiting duration.
     /// # Errors
-    /// * [CustomError::Unauthorized] when the caller is not authorized.
+    /// * [CustomError::Unauthorized] when the caller is not authorized, not a duration manager.
     pub fn set_close_wait_duration(ctx: Context<SetCloseWaitDuration>, set_close_wait_duration_args: SetCloseWaitDurationArgs) -> Result<()> {
         handler_set_close_wait_duration(ctx, set_close_wait_duration_args)
     }
 
     /// Add fee receiver.
     /// 
-    /// This instruction is authorized by the [Config::admin].
+    /// This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].
     /// # Arguments
     /// * `ctx` - A [Context] of [AddFeeReceiver] required for adding the fee receiver.
     /// * `receiver_pubkey` - The pubkey of the fee receiver.
     /// # Errors
     /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::TimelockRequired] - The operation must be queued first.
+    /// * [CustomError::TimelockNotReady] - The pending action cannot be executed yet.
     pub fn add_fee_receiver(ctx: Context<AddFeeReceiverAccounts>, receiver_pubkey: Pubkey) -> Result<()> {
         handler_add_fee_receiver(ctx, receiver_pubkey)
     }
 
     /// Remove fee receiver.
     /// 
-    /// This instruction is authorized by the [Config::admin].
+    /// This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].
     /// This instruction close the [FeeReceiver] account, and transfer rent fee to the signer.
     /// # Arguments
     /// * `ctx` - A [Context] of [RemoveFeeReceiver] required for removing the fee receiver.
     /// * `receiver_pubkey` - The pubkey of the fee receiver.
     /// # Errors
     /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::TimelockRequired] - The operation must be queued first.
+    /// * [CustomError::TimelockNotReady] - The pending action cannot be executed yet.
     pub fn remove_fee_receiver(ctx: Context<RemoveFeeReceiverAccounts>, receiver_pubkey: Pubkey) -> Result<()> {
         handler_remove_fee_receiver(ctx, receiver_pubkey)
     }
+
+    /// Pause or unpause the deposit, payment, settlement and distribute fees instructions.
+    /// 
+    /// This instruction is authorized by the [Config::admin] or the [Role::Guardian] members. The guardians can only pause.
+    /// The claim and close finished trade instructions cannot be paused, so users can always get their funds back.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SetPause] required for setting the pause flags.
+    /// * `set_pause_args` - An argument [SetPauseArgs] required for setting the pause flags.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not the admin or a guardian, or a guardian tries to unpause.
+    pub fn set_pause(ctx: Context<SetPause>, set_pause_args: SetPauseArgs) -> Result<()> {
+        handler_set_pause(ctx, set_pause_args)
+    }
+
+    /// Propose a new admin for the protocol. The proposed admin takes the role after performing [accept_admin].
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// Proposing [Pubkey::default] cancels the pending proposal.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [ProposeAdmin] required for proposing the new admin.
+    /// * `new_admin` - The pubkey of the proposed admin.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        handler_propose_admin(ctx, new_admin)
+    }
+
+    /// Accept the admin role, completes the admin transfer started by [propose_admin].
+    /// 
+    /// This instruction is authorized by the [Config::pending_admin].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [AcceptAdmin] required for accepting the admin role.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the pending admin.
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        handler_accept_admin(ctx)
+    }
+
+    /// Set the maximum total fee of a trade or a payment, in basis points of the amount.
+    /// 
+    /// This instruction is authorized by the [Role::FeeManager] members.
+    /// When the whitelist token is provided, set the override of this token [WhitelistToken::max_fee_bps].
+    /// Otherwise, set the global maximum fee [Config::max_fee_bps].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SetMaxFee] required for setting the maximum fee.
+    /// * `max_fee_bps` - The maximum fee in basis points. None to remove the limit, or the token override.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not a fee manager.
+    /// * [CustomError::InvalidMaxFeeBps] - The maximum fee is greater than 100%.
+    pub fn set_max_fee(ctx: Context<SetMaxFee>, max_fee_bps: Option<u16>) -> Result<()> {
+        handler_set_max_fee(ctx, max_fee_bps)
+    }
+    /// Add an MPC group, a threshold (M-of-N) MPC authority for the trades.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// A trade references the group when its [TradeDetail::mpc_pubkey] is the address of the group PDA.
+    /// Then the instructions authorized by the MPC of the trade require the signatures of [MpcGroup::threshold] members.
+    /// A group is immutable once created: the members are rotated by adding a new group with a new `group_id`,
+    /// and a `group_id` is never reused, even after the group is removed.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [AddMpcGroup] required for adding the MPC group.
+    /// * `group_id` - The identifier of the group, at least the [Config::next_mpc_group_id].
+    /// * `members` - The member keys of the group, up to [MpcGroup::MAX_MEMBERS].
+    /// * `threshold` - The number of member signatures required to authorize an instruction.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::InvalidMpcGroup] - The `group_id` was already used, or the members or the threshold are invalid.
+    pub fn add_mpc_group(
+        ctx: Context<AddMpcGroup>,
+        group_id: u64,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        handler_add_mpc_group(ctx, group_id, members, threshold)
+    }
+
+    /// Remove an MPC group, and reclaim its rent fee.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [RemoveMpcGroup] required for removing the MPC group.
+    /// * `group_id` - The identifier of the group.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    pub fn remove_mpc_group(ctx: Context<RemoveMpcGroup>, group_id: u64) -> Result<()> {
+        handler_remove_mpc_group(ctx, group_id)
+    }
+
+    /// Register or update an MPC key in the [MpcRegistry], the key is active after this instruction.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// The deposits can only reference an MPC key that is registered, and active at the deposit time.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [AddOrUpdateMpcRegistry] required for registering the MPC key.
+    /// * `mpc_pubkey` - The MPC key, or the address of an [MpcGroup].
+    /// * `valid_from` - The time from which the deposits can reference the MPC key.
+    /// * `valid_until` - The time until which the deposits can reference the MPC key. Zero means no limit.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::InvalidPublicKey] - The MPC key is the default pubkey.
+    /// * [CustomError::InvalidValidityWindow] - The `valid_until` is before the `valid_from`.
+    pub fn add_or_update_mpc_registry(
+        ctx: Context<AddOrUpdateMpcRegistry>,
+        mpc_pubkey: Pubkey,
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        handler_add_or_update_mpc_registry(ctx, mpc_pubkey, valid_from, valid_until)
+    }
+
+    /// Retire an MPC key of the [MpcRegistry], the new deposits can no longer reference it.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// The trades deposited before are not affected.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [RetireMpcRegistry] required for retiring the MPC key.
+    /// * `mpc_pubkey` - The MPC key to retire.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    pub fn retire_mpc_registry(ctx: Context<RetireMpcRegistry>, mpc_pubkey: Pubkey) -> Result<()> {
+        handler_retire_mpc_registry(ctx, mpc_pubkey)
+    }
+
+    /// Get the fee accrued, withdrawn and available for a token, from its [FeeLedger].
+    /// 
+    /// This instruction is read only, and can be simulated by anyone for the treasury reporting.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [GetFeeLedgerAccounts] required for the query.
+    pub fn get_fee_ledger(ctx: Context<GetFeeLedgerAccounts>) -> Result<FeeLedgerBalance> {
+        handler_get_fee_ledger(ctx)
+    }
+
+    /// Accrue the fee held by the protocol before the [FeeLedger] was introduced, once per token.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// The balance of the protocol for the token that is not already available in the [FeeLedger] is accrued,
+    /// so the fee collected before the upgrade can be withdrawn.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [AccrueLegacyFee] required for accruing the legacy fee.
+    /// * `token` - The token of the fee, none for SOL.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::LegacyFeeAlreadyAccrued] - The legacy fee of the token was already accrued.
+    /// * [CustomError::InvalidTokenAccount] - The protocol token account is missing for a token, or given for SOL.
+    /// * [CustomError::InvalidDestinationAta] - The protocol token account is not the associated token account of the protocol PDA.
+    pub fn accrue_legacy_fee(ctx: Context<AccrueLegacyFee>, token: Option<Pubkey>) -> Result<()> {
+        handler_accrue_legacy_fee(ctx, token)
+    }
+
+    /// Set the share of a fee receiver in the distributed fee, in basis points.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// The shares of all the fee receivers sum to at most 100%, tracked by [Config::total_fee_share_bps].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SetFeeReceiverShareAccounts] required for setting the share.
+    /// * `receiver_pubkey` - The pubkey of the fee receiver.
+    /// * `share_bps` - The share of the fee receiver, in basis points.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::InvalidFeeShare] - The shares of the fee receivers would sum to more than 100%.
+    pub fn set_fee_receiver_share(
+        ctx: Context<SetFeeReceiverShareAccounts>,
+        receiver_pubkey: Pubkey,
+        share_bps: u16,
+    ) -> Result<()> {
+        handler_set_fee_receiver_share(ctx, receiver_pubkey, share_bps)
+    }
+
+    /// Distribute the available fee of a token to all the fee receivers, pro rata of their [FeeReceiver::share_bps].
+    /// 
+    /// This instruction is authorized by anyone.
+    /// The fee receivers are given as remaining accounts, and must include every fee receiver with a share.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [DistributeFeesAccounts] required for the distribution.
+    /// * `distribute_fees_args` - An argument [DistributeFeesArgs] required for the distribution.
+    /// # Errors
+    /// * [CustomError::InstructionPaused] - The distribute fees instruction is paused.
+    /// * [CustomError::InvalidFeeShare] - The shares of the fee receivers do not sum to 100%, or a fee receiver is missing or duplicated.
+    /// * [CustomError::InvalidFeeReceiver] - A fee receiver account is not a [FeeReceiver] PDA.
+    pub fn distribute_fees<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, DistributeFeesAccounts<'info>>,
+        distribute_fees_args: DistributeFeesArgs,
+    ) -> Result<()> {
+        handler_distribute_fees(ctx, distribute_fees_args)
+    }
+
+    /// Close the finished trades in batch, and send the rent fee to the users.
+    /// 
+    /// Each trade is checked with the same rules as [close_finished_trade], the trades given as groups of remaining accounts.
+    /// The trades of an [MpcGroup] are closed by their MPC when the group is given, with its co-signing members first in the remaining accounts.
+    /// The trades that cannot be closed yet, or are already closed, are skipped and reported by the [BatchCloseSkipped] event.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [BatchCloseFinishedTradesAccounts] required for closing the trades.
+    /// # Errors
+    /// * [CustomError::InvalidBatchAccounts] - The remaining accounts are not valid groups of accounts.
+    /// * [CustomError::InvalidUserAccount] - A user account is not the user of its trade.
+    /// * [CustomError::InvalidTokenProgram] - A token program is not the SPL Token or the Token-2022 program.
+    /// * [CustomError::InvalidTokenAccount] - The token accounts do not match a trade.
+    pub fn batch_close_finished_trades<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, BatchCloseFinishedTradesAccounts<'info>>,
+    ) -> Result<()> {
+        handler_batch_close_finished_trades(ctx)
+    }
+
+    /// Close the payment receipts of the signer in batch, and reclaim their rent fee.
+    /// 
+    /// Each payment receipt is checked with the same rules as [close_payment_receipt], the payment receipts given as remaining accounts.
+    /// The payment receipts that cannot be closed yet, or are already closed, are skipped and reported by the [BatchCloseSkipped] event.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [BatchClosePaymentReceiptsAccounts] required for closing the payment receipts.
+    /// # Errors
+    /// * [CustomError::InvalidBatchAccounts] - There is no payment receipt.
+    /// * [CustomError::InvalidUserAccount] - The signer is not the payer of a payment receipt.
+    pub fn batch_close_payment_receipts<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, BatchClosePaymentReceiptsAccounts<'info>>,
+    ) -> Result<()> {
+        handler_batch_close_payment_receipts(ctx)
+    }
+
+    /// Settle several trades sharing the same MPC, PMM and token in one instruction. This instruction is authorized by the MPC and the ephemeral accounts of the trades.
+    /// 
+    /// Each trade is checked with the same rules as [settlement], the trades given as groups of remaining accounts.
+    /// This instruction close the [NonceCheckAccount] of each trade, transfer rent fee to [TradeDetail::rent_payer], and emit a [Settled] event for each trade.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [BatchSettlementAccounts] required for settling the trades.
+    /// * `batch_settlement_args` - An argument [BatchSettlementArgs] required for settling the trades.
+    /// # Errors
+    /// * [CustomError::InstructionPaused] - The settlement instruction is paused.
+    /// * [CustomError::InvalidBatchAccounts] - There is no trade, or an account is not the PDA of its trade.
+    /// * [CustomError::Unauthorized] - A trade is not authorized by both its MPC and its [TradeDetail::user_ephemeral_pubkey].
+    /// * [CustomError::InvalidMintKey] - A trade is not with the [BatchSettlementArgs::token].
+    /// * [CustomError::TimeOut] - A trade timeout is expired.
+    /// * [CustomError::InvalidTradeStatus] - A trade status is not [TradeStatus::Deposited].
+    pub fn batch_settlement<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, BatchSettlementAccounts<'info>>,
+        batch_settlement_args: BatchSettlementArgs,
+    ) -> Result<()> {
+        handler_batch_settlement(ctx, batch_settlement_args)
+    }
+
+    /// Queue a sensitive admin operation behind the timelock.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// The operation is executed by its instruction, given the [PendingAction], once the [Config::timelock_delay] has passed.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [QueuePendingAction] required for queuing the operation.
+    /// * `action` - The [TimelockAction] to queue.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    pub fn queue_pending_action(ctx: Context<QueuePendingAction>, action: TimelockAction) -> Result<()> {
+        handler_queue_pending_action(ctx, action)
+    }
+
+    /// Cancel a queued sensitive admin operation.
+    /// 
+    /// This instruction is authorized by the [Config::admin] or the [Role::Guardian] members.
+    /// This instruction close the [PendingAction] account, and transfer rent fee to the [PendingAction::admin] who queued it.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [CancelPendingAction] required for cancelling the operation.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not the admin or a guardian.
+    /// * [CustomError::InvalidUserAccount] - The admin account is not the [PendingAction::admin].
+    pub fn cancel_pending_action(ctx: Context<CancelPendingAction>) -> Result<()> {
+        handler_cancel_pending_action(ctx)
+    }
+
+    /// Set the timelock delay of the sensitive admin operations.
+    /// 
+    /// This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SetTimelockDelay] required for setting the delay.
+    /// * `timelock_delay` - The new delay in seconds, zero to disable the timelock.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::TimelockRequired] - The new delay must be queued first.
+    /// * [CustomError::TimelockNotReady] - The pending action cannot be executed yet.
+    pub fn set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: u64) -> Result<()> {
+        handler_set_timelock_delay(ctx, timelock_delay)
+    }
+
+    /// Grant a role of the protocol to a member.
+    /// 
+    /// This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [GrantRole] required for granting the role.
+    /// * `role` - The [Role] to grant.
+    /// * `member` - The member of the role.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::InvalidPublicKey] - The member is the default pubkey.
+    /// * [CustomError::TimelockRequired] - The operation must be queued first.
+    /// * [CustomError::TimelockNotReady] - The pending action cannot be executed yet.
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+        handler_grant_role(ctx, role, member)
+    }
+
+    /// Revoke a role of the protocol from a member.
+    /// 
+    /// This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].
+    /// This instruction close the [RoleMember] account, and transfer rent fee to the signer.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [RevokeRole] required for revoking the role.
+    /// * `role` - The [Role] to revoke.
+    /// * `member` - The member of the role.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::TimelockRequired] - The operation must be queued first.
+    /// * [CustomError::TimelockNotReady] - The pending action cannot be executed yet.
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+        handler_revoke_role(ctx, role, member)
+    }
+
+    /// Migrate a legacy operator to the [Role::WhitelistManager] and [Role::DurationManager] roles.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// The operator is removed from the legacy [Config::operators].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [MigrateOperator] required for migrating the operator.
+    /// * `operator` - The legacy operator to migrate.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::OperatorNotFound] - The operator is not a legacy operator.
+    pub fn migrate_operator(ctx: Context<MigrateOperator>, operator: Pubkey) -> Result<()> {
+        handler_migrate_operator(ctx, operator)
+    }
+
+    /// Migrate the legacy guardian to the [Role::Guardian] role.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// The legacy [Config::guardian] is cleared.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [MigrateGuardian] required for migrating the guardian.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    /// * [CustomError::InvalidPublicKey] - There is no legacy guardian.
+    pub fn migrate_guardian(ctx: Context<MigrateGuardian>) -> Result<()> {
+        handler_migrate_guardian(ctx)
+    }
+
+    /// Extend the timeout of a deposited trade, when the trade is slow but still healthy.
+    /// 
+    /// This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey].
+    /// The total extension of a trade is bounded by the [Config::max_timeout_extension].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [ExtendTimeoutAccounts] required for extending the timeout.
+    /// * `extend_timeout_args` - An argument [ExtendTimeoutArgs] required for extending the timeout.
+    /// # Errors
+    /// * [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].
+    /// * [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited].
+    /// * [CustomError::TimeOut] when the trade is already expired.
+    /// * [CustomError::InvalidTimeout] when the new timeout is not after the current [TradeDetail::timeout].
+    /// * [CustomError::TimeoutExtensionExceeded] when the total extension of the trade exceeds the [Config::max_timeout_extension].
+    pub fn extend_timeout<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ExtendTimeoutAccounts<'info>>,
+        extend_timeout_args: ExtendTimeoutArgs,
+    ) -> Result<()> {
+        handler_extend_timeout(ctx, extend_timeout_args)
+    }
+
+    /// Set the maximum total duration a trade timeout can be extended by.
+    /// 
+    /// This instruction is authorized by the [Role::DurationManager] members.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SetMaxTimeoutExtension] required for setting the maximum timeout extension.
+    /// * `max_timeout_extension` - The maximum timeout extension in seconds, zero disables the extensions.
+    /// # Errors
+    /// * [CustomError::Unauthorized] when the caller is not authorized, not a duration manager.
+    pub fn set_max_timeout_extension(ctx: Context<SetMaxTimeoutExtension>, max_timeout_extension: u64) -> Result<()> {
+        handler_set_max_timeout_extension(ctx, max_timeout_extension)
+    }
+
+    /// Deposit the trade, with the rent of the trade accounts paid by a relayer.
+    /// 
+    /// Same as the [deposit], except that the `payer` pays the rent of the [TradeDetail], [TradeVault] and [NonceCheckAccount].
+    /// The payer is recorded in the [TradeDetail::payer_pubkey], and gets the rent back when these accounts are closed.
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SponsoredDepositAccounts] required for the deposit.
+    /// * `deposit_args` - An argument [DepositArgs] required for the deposit.
+    /// # Errors
+    /// Same as the [deposit] instruction.
+    pub fn sponsored_deposit<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SponsoredDepositAccounts<'info>>,
+        deposit_args: DepositArgs,
+    ) -> Result<()> {
+        handler_sponsored_deposit(ctx, deposit_args)
+    }
+
+    /// Deposit the trade from a deposit intent signed off-chain by the user, and submitted by a relayer.
+    /// 
+    /// The user does not sign the transaction, the instruction must be preceded by an Ed25519 program instruction
+    /// that verifies the user signature over the [DepositIntent::message].
+    /// Only tokens can be deposited, the amount is transferred by the delegate PDA that the user approved on its token account.
+    /// The relayer pays the rent of the trade accounts, like in the [sponsored_deposit].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [IntentDepositAccounts] required for the deposit.
+    /// * `deposit_args` - An argument [DepositArgs] required for the deposit.
+    /// # Errors
+    /// * [CustomError::InvalidDepositIntent] when the deposit is SOL.
+    /// * [CustomError::InvalidSignature] when the user signature of the deposit intent is not verified by the previous instruction.
+    /// * The errors of the [deposit] instruction.
+    pub fn intent_deposit<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, IntentDepositAccounts<'info>>,
+        deposit_args: DepositArgs,
+    ) -> Result<()> {
+        handler_intent_deposit(ctx, deposit_args)
+    }
+
+    /// Set whether the deposits must be authorized by the secp256k1 signature of the solver over the trade id.
+    /// 
+    /// This instruction is authorized by the [Config::admin].
+    /// # Arguments
+    /// * `ctx` - A [Context] of [SetSolverSignatureRequired] required for setting the solver signature requirement.
+    /// * `required` - Whether the solver signature is required.
+    /// # Errors
+    /// * [CustomError::Unauthorized] - The caller is not authorized, or not the admin.
+    pub fn set_solver_signature_required(ctx: Context<SetSolverSignatureRequired>, required: bool) -> Result<()> {
+        handler_set_solver_signature_required(ctx, required)
+    }
 }
@@ -1,37 +1,55 @@
 use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
 use crate::error::CustomError;
+use crate::SetPauseArgs;
+use crate::state::{PendingAction, TimelockAction};
 
 /// The config PDA account that contains the protocol configuration.
 #[account]
 pub struct Config {
+    /// The legacy guardian of the protocol, replaced by the [crate::Role::Guardian] role.
+    /// Only read by the migrate guardian instruction, which clears it.
+    pub guardian: Pubkey,
+    /// The pause state of the protocol instructions. Set by the admin or the guardian.
+    pub pause: PauseFlags,
+    /// The admin candidate proposed by the current admin. Becomes the admin after accepting the role.
+    pub pending_admin: Pubkey,
+    /// The maximum total fee of a trade or a payment, in basis points of the amount. Set by the [crate::Role::FeeManager] members.
+    /// Can be overridden per token by [WhitelistToken::max_fee_bps]. None means no limit.
+    pub max_fee_bps: Option<u16>,
+    /// The lowest identifier of the next [crate::MpcGroup]. Moved past each added group,
+    /// so that the identifier of a removed group is never reused.
+    pub next_mpc_group_id: u64,
+    /// The sum of the [crate::FeeReceiver::share_bps] of all the fee receivers, at most [BPS_DENOMINATOR].
+    /// The fee is only distributed when the shares sum to [BPS_DENOMINATOR].
+    pub total_fee_share_bps: u16,
+    /// The delay in seconds between queuing a [PendingAction] and executing it. Set by the admin through a pending action.
+    /// Zero means the sensitive admin operations take effect immediately.
+    pub timelock_delay: u64,
+    /// The maximum total duration in seconds a trade timeout can be extended by. Set by the duration managers.
+    /// Zero means the timeouts cannot be extended.
+    pub max_timeout_extension: u64,
+    /// Whether the deposits must be authorized by the secp256k1 signature of the [crate::TradeInput::solver] over the trade id.
+    /// Set by the admin.
+    pub solver_signature_required: bool,
     /// The reserve field space, used to upgrade in the future.
-    pub _reserve: [u128; 7],
-    /// The admin account of the protocol. Set by the upgrade authority. Used to manage the operators.
+    pub _reserve: [u8; 14],
+    /// The admin account of the protocol. Set by the upgrade authority, or transferred by the current admin. Used to manage the roles.
     pub admin: Pubkey,
     /// The duration for closing a finished trade.
     pub close_trade_duration: u64,
     /// The duration for closing a payment receipt.
     pub close_payment_duration: u64,
-    /// The operators of the protocol. Set by the admin. Used to manage close wait time and whitelist token.
+    /// The legacy operators of the protocol, replaced by the [crate::Role::WhitelistManager] and [crate::Role::DurationManager] roles.
+    /// Only read by the migrate operator instruction, which removes the migrated operator.
     pub operators: Vec<Pubkey>,
 }
 
 impl Config {
     pub const OPERATORS_SIZE: usize = 3;
-    pub const SPACE: usize = 8 + 16 * 7 + 32 + 8 + 8 + 4 + Config::OPERATORS_SIZE * 32;
+    pub const SPACE: usize = 8 + 32 + PauseFlags::INIT_SPACE + 32 + 3 + 8 + 2 + 8 + 8 + 1 + 14 + 32 + 8 + 8 + 4 + Config::OPERATORS_SIZE * 32;
     pub const SEED: &'static [u8] = b"config";
 
-    pub fn add_operator(&mut self, operator: Pubkey) -> Result<()> {
-        if self.operators.contains(&operator) {
-            return Err(CustomError::OperatorAlreadyExists.into());
-        }
-        if self.operators.len() >= Config::OPERATORS_SIZE {
-            return Err(CustomError::OperatorLimitReached.into());
-        }
-        self.operators.push(operator);
-        Ok(())
-    }
-
     pub fn remove_operator(&mut self, operator: Pubkey) -> Result<()> {
         if !self.operators.contains(&operator) {
             return Err(CustomError::OperatorNotFound.into());
@@ -47,6 +65,68 @@ impl Config {
         Ok(())
     }
 
+    pub fn set_pause(&mut self, set_pause_args: &SetPauseArgs) -> Result<()> {
+        if let Some(deposit) = set_pause_args.deposit {
+            self.pause.deposit = deposit;
+        }
+        if let Some(payment) = set_pause_args.payment {
+            self.pause.payment = payment;
+        }
+        if let Some(settlement) = set_pause_args.settlement {
+            self.pause.settlement = settlement;
+        }
+        if let Some(distribute_fees) = set_pause_args.distribute_fees {
+            self.pause.distribute_fees = distribute_fees;
+        }
+        Ok(())
+    }
+
+    /// Asserts that the total fee does not exceed the maximum fee of the amount.
+    ///
+    /// The maximum fee is the token override `token_max_fee_bps` if any, otherwise the [Config::max_fee_bps].
+    pub fn assert_max_fee(&self, token_max_fee_bps: Option<u16>, amount: u64, total_fee: u64) -> Result<()> {
+        if let Some(max_fee_bps) = token_max_fee_bps.or(self.max_fee_bps) {
+            let max_fee = (amount as u128) * (max_fee_bps as u128) / (BPS_DENOMINATOR as u128);
+            if (total_fee as u128) > max_fee {
+                return Err(CustomError::MaxFeeExceeded.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the `previous_share_bps` of a fee receiver by its `share_bps` in the [Config::total_fee_share_bps].
+    ///
+    /// # Errors
+    /// * [CustomError::InvalidFeeShare] when the shares of the fee receivers would sum to more than [BPS_DENOMINATOR].
+    pub fn update_fee_share(&mut self, previous_share_bps: u16, share_bps: u16) -> Result<()> {
+        let total_fee_share_bps = self.total_fee_share_bps.saturating_sub(previous_share_bps) as u32 + share_bps as u32;
+        if total_fee_share_bps > BPS_DENOMINATOR as u32 {
+            return Err(CustomError::InvalidFeeShare.into());
+        }
+        self.total_fee_share_bps = total_fee_share_bps as u16;
+        Ok(())
+    }
+
+    /// Asserts that the sensitive admin `action` can be performed at the `current_timestamp`.
+    ///
+    /// Without `pending_action`, the action takes effect immediately only when there is no [Config::timelock_delay].
+    /// # Errors
+    /// * [CustomError::TimelockRequired] when the action must be queued first.
+    /// * [CustomError::InvalidPendingAction] when the pending action is not the `action`.
+    /// * [CustomError::TimelockNotReady] when the pending action cannot be executed yet.
+    pub fn assert_timelock(
+        &self,
+        pending_action: Option<&PendingAction>,
+        action: &TimelockAction,
+        current_timestamp: i64,
+    ) -> Result<()> {
+        match pending_action {
+            Some(pending_action) => pending_action.assert_executable(action, current_timestamp),
+            None if self.timelock_delay == 0 => Ok(()),
+            None => Err(CustomError::TimelockRequired.into()),
+        }
+    }
+
     pub fn set_close_payment_duration(&mut self, duration: Option<u64>) -> Result<()> {
         if let Some(duration) = duration {
             self.close_payment_duration = duration;
@@ -54,3 +134,19 @@ impl Config {
         Ok(())
     }
 }
+
+/// The pause state of the protocol instructions.
+///
+/// Only the instructions that move funds into the protocol or to the pmm and fee receivers can be paused.
+/// The `claim` and `close_finished_trade` instructions are always available, so users can get their funds back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct PauseFlags {
+    /// Whether the `deposit` instruction is paused.
+    pub deposit: bool,
+    /// Whether the `payment` instruction is paused.
+    pub payment: bool,
+    /// Whether the `settlement` instruction is paused.
+    pub settlement: bool,
+    /// Whether the `distribute_fees` instruction is paused.
+    pub distribute_fees: bool,
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::CustomError;
+
+/// The fee ledger PDA account that tracks the protocol fee of a token.
+///
+/// There is one PDA for each token, SOL uses the WSOL Pubkey.
+/// The fee is accrued by the settlements and the payments, and can only be withdrawn up to [FeeLedger::available].
+#[account]
+#[derive(InitSpace, Debug, Default)]
+pub struct FeeLedger {
+    /// The token of the fee ledger, WSOL for SOL.
+    pub token: Pubkey,
+    /// The total fee accrued by the protocol, with decimals.
+    pub accrued: u64,
+    /// The total fee withdrawn to the fee receivers, with decimals.
+    pub withdrawn: u64,
+    /// Whether the fee held by the protocol before the fee ledger was introduced has been accrued.
+    pub legacy_fee_accrued: bool,
+    /// The padding of the legacy fee flag, used for future use.
+    pub _padding: [u8; 15],
+    /// The reserve of the fee ledger, used for future use.
+    pub _reserve: [u128; 3],
+}
+
+/// The numbers of a [FeeLedger], returned by the get fee ledger instruction for the treasury reporting.
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeLedgerBalance {
+    /// The token of the fee ledger, WSOL for SOL.
+    pub token: Pubkey,
+    /// The total fee accrued by the protocol.
+    pub accrued: u64,
+    /// The total fee withdrawn to the fee receivers.
+    pub withdrawn: u64,
+    /// The fee that can be withdrawn.
+    pub available: u64,
+}
+
+impl FeeLedger {
+    pub const SEED: &'static [u8] = b"fee_ledger";
+
+    /// The fee that can be withdrawn, accrued and not yet withdrawn.
+    pub fn available(&self) -> u64 {
+        self.accrued.saturating_sub(self.withdrawn)
+    }
+
+    /// Records the fee accrued by a settlement or a payment of the `token`.
+    pub fn accrue(&mut self, token: Pubkey, amount: u64) -> Result<()> {
+        self.token = token;
+        self.accrued = self.accrued.checked_add(amount).ok_or(CustomError::AmountExceeds)?;
+        Ok(())
+    }
+
+    /// Records the fee held by the protocol before the fee ledger was introduced, once per token.
+    ///
+    /// `held` is the balance of the protocol for the `token`, the part of it that is not already [FeeLedger::available] is accrued.
+    /// Returns the accrued amount.
+    ///
+    /// # Errors
+    /// * [CustomError::LegacyFeeAlreadyAccrued] when the legacy fee of the token was already accrued.
+    pub fn accrue_legacy(&mut self, token: Pubkey, held: u64) -> Result<u64> {
+        if self.legacy_fee_accrued {
+            return Err(CustomError::LegacyFeeAlreadyAccrued.into());
+        }
+        let amount = held.saturating_sub(self.available());
+        self.accrue(token, amount)?;
+        self.legacy_fee_accrued = true;
+        Ok(amount)
+    }
+
+    /// Records a withdrawal of the fee.
+    ///
+    /// # Errors
+    /// * [CustomError::InsufficientAvailableFee] when the `amount` is greater than the [FeeLedger::available] fee.
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        if amount > self.available() {
+            return Err(CustomError::InsufficientAvailableFee.into());
+        }
+        self.withdrawn += amount;
+        Ok(())
+    }
+
+    /// The numbers of the fee ledger.
+    pub fn balance(&self) -> FeeLedgerBalance {
+        FeeLedgerBalance {
+            token: self.token,
+            accrued: self.accrued,
+            withdrawn: self.withdrawn,
+            available: self.available(),
+        }
+    }
+}
+
+#[test]
+fn test_fee_ledger() {
+    let token = Pubkey::new_unique();
+    let mut fee_ledger = FeeLedger::default();
+    fee_ledger.accrue(token, 100).unwrap();
+    fee_ledger.accrue(token, 50).unwrap();
+    assert_eq!(fee_ledger.token, token);
+    assert_eq!(fee_ledger.available(), 150);
+
+    fee_ledger.withdraw(120).unwrap();
+    assert_eq!(fee_ledger.withdraw(31).unwrap_err(), CustomError::InsufficientAvailableFee.into());
+    fee_ledger.withdraw(30).unwrap();
+    assert_eq!(
+        fee_ledger.balance(),
+        FeeLedgerBalance {
+            token,
+            accrued: 150,
+            withdrawn: 150,
+            available: 0,
+        }
+    );
+    assert_eq!(fee_ledger.accrue(token, u64::MAX).unwrap_err(), CustomError::AmountExceeds.into());
+}
+
+#[test]
+fn test_accrue_legacy() {
+    let token = Pubkey::new_unique();
+    let mut fee_ledger = FeeLedger::default();
+    fee_ledger.accrue(token, 100).unwrap();
+    fee_ledger.withdraw(40).unwrap();
+
+    // Only the part of the balance that is not already available is accrued
+    assert_eq!(fee_ledger.accrue_legacy(token, 250).unwrap(), 190);
+    assert_eq!(fee_ledger.available(), 250);
+    assert!(fee_ledger.legacy_fee_accrued);
+    assert_eq!(
+        fee_ledger.accrue_legacy(token, 500).unwrap_err(),
+        CustomError::LegacyFeeAlreadyAccrued.into()
+    );
+
+    let mut fee_ledger = FeeLedger::default();
+    fee_ledger.accrue(token, 100).unwrap();
+    assert_eq!(fee_ledger.accrue_legacy(token, 80).unwrap(), 0);
+    assert_eq!(fee_ledger.available(), 100);
+}
@@ -1,15 +1,40 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::BPS_DENOMINATOR;
+
 /// The fee receiver PDA account that contains the fee receiver information.
 #[account]
 #[derive(InitSpace)]
 pub struct FeeReceiver {
     /// The pubkey of the fee receiver.
     pub receiver: Pubkey,
+    /// The share of the fee receiver in the distributed fee, in basis points. Set by the admin.
+    /// The shares of all the fee receivers sum to at most [BPS_DENOMINATOR], see [crate::Config::total_fee_share_bps].
+    pub share_bps: u16,
     /// The reserve of the fee receiver, used for future use.
-    pub _reserve: [u128; 4],
+    pub _reserve: [u8; 62],
 }
 
 impl FeeReceiver {
     pub const SEED: &'static [u8] = b"fee_receiver";
+
+    /// The share of the fee receiver in the `amount`, rounded down.
+    pub fn share_of(&self, amount: u64) -> u64 {
+        ((amount as u128) * (self.share_bps as u128) / (BPS_DENOMINATOR as u128)) as u64
+    }
+}
+
+#[test]
+fn test_share_of() {
+    let mut fee_receiver = FeeReceiver {
+        receiver: Pubkey::new_unique(),
+        share_bps: 2_500,
+        _reserve: [0; 62],
+    };
+    assert_eq!(fee_receiver.share_of(1_000), 250);
+    assert_eq!(fee_receiver.share_of(999), 249);
+    assert_eq!(fee_receiver.share_of(u64::MAX), u64::MAX / 4);
+
+    fee_receiver.share_bps = BPS_DENOMINATOR;
+    assert_eq!(fee_receiver.share_of(u64::MAX), u64::MAX);
 }
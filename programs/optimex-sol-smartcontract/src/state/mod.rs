@@ -4,11 +4,23 @@ pub mod config;
 pub mod whitelist_token;
 pub mod nonce_check_account;
 pub mod payment_receipt;
+pub mod payment_expectation;
 pub mod fee_receiver;
+pub mod mpc_group;
+pub mod mpc_registry;
+pub mod fee_ledger;
+pub mod pending_action;
+pub mod role;
 
 pub use vault::*;
 pub use config::*;
 pub use whitelist_token::*;
 pub use nonce_check_account::*;
 pub use payment_receipt::*;
-pub use fee_receiver::*;
\ No newline at end of file
+pub use payment_expectation::*;
+pub use fee_receiver::*;
+pub use mpc_group::*;
+pub use mpc_registry::*;
+pub use fee_ledger::*;
+pub use pending_action::*;
+pub use role::*;
\ No newline at end of file
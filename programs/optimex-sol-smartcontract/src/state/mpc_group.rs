@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::CustomError;
+
+/// The MPC group PDA account, a threshold (M-of-N) MPC authority for the trades.
+///
+/// A trade references the group by setting its [crate::TradeDetail::mpc_pubkey] to the address of this PDA.
+/// The instructions authorized by the MPC of the trade then require the signatures of at least [MpcGroup::threshold] members.
+#[account]
+#[derive(InitSpace, Debug, Default)]
+pub struct MpcGroup {
+    /// The identifier of the group, used as the seed of the PDA.
+    pub group_id: u64,
+    /// The member keys of the group, up to [MpcGroup::MAX_MEMBERS].
+    #[max_len(10)]
+    pub members: Vec<Pubkey>,
+    /// The number of member signatures required to authorize an instruction.
+    pub threshold: u8,
+    /// The reserve of the MPC group, used for future use.
+    pub _reserve: [u128; 4],
+}
+
+impl MpcGroup {
+    pub const SEED: &'static [u8] = b"mpc_group";
+    /// The maximum number of members of a group.
+    pub const MAX_MEMBERS: usize = 10;
+
+    /// Sets the members and the threshold of the group.
+    ///
+    /// # Errors
+    /// * [CustomError::InvalidMpcGroup] when there is no member or too many members, a member is duplicated or the default pubkey,
+    ///   or the threshold is zero or greater than the number of members.
+    pub fn initialize(&mut self, group_id: u64, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        if members.is_empty() || members.len() > Self::MAX_MEMBERS {
+            return Err(CustomError::InvalidMpcGroup.into());
+        }
+        if threshold == 0 || threshold as usize > members.len() {
+            return Err(CustomError::InvalidMpcGroup.into());
+        }
+        for (index, member) in members.iter().enumerate() {
+            if *member == Pubkey::default() || members[..index].contains(member) {
+                return Err(CustomError::InvalidMpcGroup.into());
+            }
+        }
+        self.group_id = group_id;
+        self.members = members;
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    /// Whether at least [MpcGroup::threshold] distinct members signed among the `accounts`.
+    pub fn is_signed_by<'a, 'info: 'a>(&self, accounts: impl IntoIterator<Item = &'a AccountInfo<'info>>) -> bool {
+        let mut signed: Vec<&Pubkey> = vec![];
+        for account in accounts {
+            if account.is_signer && self.members.contains(account.key) && !signed.contains(&account.key) {
+                signed.push(account.key);
+            }
+        }
+        signed.len() >= self.threshold as usize
+    }
+}
+
+#[test]
+fn test_initialize_mpc_group() {
+    let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let invalid = Err(CustomError::InvalidMpcGroup.into());
+
+    let mut group = MpcGroup::default();
+    assert_eq!(group.initialize(1, vec![], 1), invalid);
+    assert_eq!(group.initialize(1, members.clone(), 0), invalid);
+    assert_eq!(group.initialize(1, members.clone(), 4), invalid);
+    assert_eq!(group.initialize(1, vec![members[0], members[1], members[0]], 2), invalid);
+    assert_eq!(group.initialize(1, vec![members[0], Pubkey::default()], 1), invalid);
+    let too_many: Vec<Pubkey> = (0..=MpcGroup::MAX_MEMBERS).map(|_| Pubkey::new_unique()).collect();
+    assert_eq!(group.initialize(1, too_many, 1), invalid);
+
+    group.initialize(1, members.clone(), 2).unwrap();
+    assert_eq!(group.group_id, 1);
+    assert_eq!(group.members, members);
+    assert_eq!(group.threshold, 2);
+}
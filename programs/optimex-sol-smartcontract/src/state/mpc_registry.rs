@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{CustomError, ID};
+
+/// The MPC registry PDA account, an MPC key approved by the admin to authorize the trades.
+///
+/// There is one PDA for each registered key, a deposit can only reference an MPC key that is active at the deposit time.
+/// The key can be a single MPC key, or the address of an [crate::MpcGroup].
+#[account]
+#[derive(InitSpace, Debug, Default)]
+pub struct MpcRegistry {
+    /// The registered MPC key.
+    pub mpc_pubkey: Pubkey,
+    /// Whether the MPC key is active. False once the key is retired.
+    pub is_active: bool,
+    /// The time from which the deposits can reference the MPC key.
+    pub valid_from: i64,
+    /// The time until which the deposits can reference the MPC key. Zero means no limit.
+    pub valid_until: i64,
+    /// The reserve of the MPC registry, used for future use.
+    pub _reserve: [u128; 4],
+}
+
+impl MpcRegistry {
+    pub const SEED: &'static [u8] = b"mpc_registry";
+
+    /// Whether the deposits can reference the MPC key at the `current_timestamp`.
+    pub fn is_active_at(&self, current_timestamp: i64) -> bool {
+        self.is_active
+            && current_timestamp >= self.valid_from
+            && (self.valid_until == 0 || current_timestamp <= self.valid_until)
+    }
+
+    /// Asserts that the MPC key is registered and active at the `current_timestamp`.
+    ///
+    /// # Errors
+    /// * [CustomError::MpcNotRegistered] when the registry account is not the registry of the `mpc_pubkey`,
+    ///   or the key is retired or outside of its validity window.
+    pub fn assert_active(mpc_registry: &AccountInfo, mpc_pubkey: &Pubkey, current_timestamp: i64) -> Result<()> {
+        if mpc_registry.owner != &ID || mpc_registry.data_is_empty() {
+            return Err(CustomError::MpcNotRegistered.into());
+        }
+        let data = mpc_registry.try_borrow_data()?;
+        let mpc_registry = MpcRegistry::try_deserialize(&mut &data[..])?;
+        if mpc_registry.mpc_pubkey != *mpc_pubkey || !mpc_registry.is_active_at(current_timestamp) {
+            return Err(CustomError::MpcNotRegistered.into());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_is_active_at() {
+    let mut mpc_registry = MpcRegistry {
+        mpc_pubkey: Pubkey::new_unique(),
+        is_active: true,
+        valid_from: 100,
+        valid_until: 0,
+        ..Default::default()
+    };
+    assert!(!mpc_registry.is_active_at(99));
+    assert!(mpc_registry.is_active_at(100));
+    assert!(mpc_registry.is_active_at(i64::MAX));
+
+    mpc_registry.valid_until = 200;
+    assert!(mpc_registry.is_active_at(200));
+    assert!(!mpc_registry.is_active_at(201));
+
+    mpc_registry.is_active = false;
+    assert!(!mpc_registry.is_active_at(150));
+}
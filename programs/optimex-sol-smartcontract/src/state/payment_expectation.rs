@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+use crate::{CustomError, MpcGroup};
+
+/// The payment expectation PDA account that contains the payment the MPC expects for a trade.
+///
+/// Only checked by the strict payment instruction, the payment instruction ignores it.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PaymentExpectation {
+    /// The trade id of the payment expectation.
+    pub trade_id: [u8; 32],
+    /// The mpc pubkey who published the payment expectation, a single MPC key or the address of an [MpcGroup].
+    pub mpc_pubkey: Pubkey,
+    /// The pubkey that must receive the payment.
+    pub to_pubkey: Pubkey,
+    /// The token of the payment. None if the payment is SOL.
+    pub token: Option<Pubkey>,
+    /// The minimum amount the receiver must get, excluded fee, with decimals.
+    pub min_amount: u64,
+    /// The maximum total fee of the payment, with decimals.
+    pub max_fee: u64,
+    /// The latest time that the payment can be made.
+    pub deadline: i64,
+    /// Whether the payment has been made.
+    pub fulfilled: bool,
+    /// The reserve field space, used to upgrade in the future.
+    pub _reserve: [u128; 4],
+}
+
+impl PaymentExpectation {
+    pub const SEED: &'static [u8] = b"payment_expectation";
+
+    /// The MPC key of the expectation: the address of the `mpc_group` if any, otherwise the `signer`.
+    pub fn mpc_key<'a, 'info>(signer: &'a Pubkey, mpc_group: Option<&'a Account<'info, MpcGroup>>) -> &'a Pubkey {
+        mpc_group.map_or(signer, |mpc_group| AsRef::<AccountInfo>::as_ref(mpc_group).key)
+    }
+
+    /// The [PaymentExpectation::mpc_key] as a PDA seed.
+    ///
+    /// A plain function call, so the IDL account resolution skips the seeds instead of emitting them.
+    pub fn mpc_seed<'a, 'info>(signer: &'a Pubkey, mpc_group: Option<&'a Account<'info, MpcGroup>>) -> &'a [u8] {
+        Self::mpc_key(signer, mpc_group).as_ref()
+    }
+
+    /// Whether the MPC of the expectation authorized the instruction.
+    ///
+    /// Without `mpc_group`, the `signer` must be the [PaymentExpectation::mpc_pubkey].
+    /// When the expectation was published by an [MpcGroup], `mpc_group` is this group, and at least [MpcGroup::threshold] members
+    /// must have signed among the `signer` and the `co_signers`.
+    pub fn is_mpc_authorized<'a, 'info: 'a>(
+        &self,
+        mpc_group: Option<&MpcGroup>,
+        signer: &'a AccountInfo<'info>,
+        co_signers: &'a [AccountInfo<'info>],
+    ) -> bool {
+        match mpc_group {
+            Some(mpc_group) => mpc_group.is_signed_by(std::iter::once(signer).chain(co_signers)),
+            None => signer.is_signer && *signer.key == self.mpc_pubkey,
+        }
+    }
+
+    /// Check the payment against the expectation, before any funds are moved.
+    ///
+    /// The amount received is checked by [PaymentExpectation::assert_received] once the payment is transferred.
+    pub fn assert_payment(
+        &self,
+        to_pubkey: &Pubkey,
+        token: Option<Pubkey>,
+        total_fee: u64,
+        current_timestamp: i64,
+    ) -> Result<()> {
+        if self.fulfilled {
+            return Err(CustomError::PaymentAlreadyFulfilled.into());
+        }
+        if current_timestamp > self.deadline {
+            return Err(CustomError::DeadlineExceeded.into());
+        }
+        if self.to_pubkey != *to_pubkey {
+            return Err(CustomError::InvalidUserAccount.into());
+        }
+        if self.token != token {
+            return Err(CustomError::InvalidMintKey.into());
+        }
+        if total_fee > self.max_fee {
+            return Err(CustomError::InvalidTotalFee.into());
+        }
+        Ok(())
+    }
+
+    /// Check the amount actually received by the [PaymentExpectation::to_pubkey], net of any token transfer fee.
+    pub fn assert_received(&self, received_amount: u64) -> Result<()> {
+        if received_amount < self.min_amount {
+            return Err(CustomError::InvalidAmount.into());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_assert_payment() {
+    let to_pubkey = Pubkey::new_unique();
+    let token = Some(Pubkey::new_unique());
+    let mut expectation = PaymentExpectation {
+        trade_id: [1; 32],
+        mpc_pubkey: Pubkey::new_unique(),
+        to_pubkey,
+        token,
+        min_amount: 1_000,
+        max_fee: 10,
+        deadline: 100,
+        fulfilled: false,
+        _reserve: [0; 4],
+    };
+    assert!(expectation.assert_payment(&to_pubkey, token, 10, 100).is_ok());
+    assert!(expectation.assert_payment(&to_pubkey, token, 0, 0).is_ok());
+
+    let err = |result: Result<()>| result.unwrap_err();
+    assert_eq!(
+        err(expectation.assert_payment(&to_pubkey, token, 10, 101)),
+        CustomError::DeadlineExceeded.into()
+    );
+    assert_eq!(
+        err(expectation.assert_payment(&Pubkey::new_unique(), token, 10, 0)),
+        CustomError::InvalidUserAccount.into()
+    );
+    assert_eq!(
+        err(expectation.assert_payment(&to_pubkey, None, 10, 0)),
+        CustomError::InvalidMintKey.into()
+    );
+    assert_eq!(
+        err(expectation.assert_payment(&to_pubkey, token, 11, 0)),
+        CustomError::InvalidTotalFee.into()
+    );
+    assert!(expectation.assert_received(1_000).is_ok());
+    assert_eq!(err(expectation.assert_received(999)), CustomError::InvalidAmount.into());
+
+    expectation.fulfilled = true;
+    assert_eq!(
+        err(expectation.assert_payment(&to_pubkey, token, 10, 0)),
+        CustomError::PaymentAlreadyFulfilled.into()
+    );
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::{CustomError, Role};
+
+/// A sensitive admin operation, which only takes effect after the [crate::Config::timelock_delay].
+#[derive(Debug, Clone, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub enum TimelockAction {
+    /// Grant the role to the member, see the `grant_role` instruction.
+    GrantRole(Role, Pubkey),
+    /// Revoke the role of the member, see the `revoke_role` instruction.
+    RevokeRole(Role, Pubkey),
+    /// Add the fee receiver, see the `add_fee_receiver` instruction.
+    AddFeeReceiver(Pubkey),
+    /// Remove the fee receiver, see the `remove_fee_receiver` instruction.
+    RemoveFeeReceiver(Pubkey),
+    /// Set the timelock delay, see the `set_timelock_delay` instruction.
+    SetTimelockDelay(u64),
+}
+
+impl TimelockAction {
+    /// The identifier of the action, the hash of its serialization. Used as the seed of its [PendingAction] PDA.
+    pub fn id(&self) -> [u8; 32] {
+        let mut data = vec![];
+        self.serialize(&mut data).unwrap();
+        hash(&data).to_bytes()
+    }
+}
+
+/// The pending action PDA account, a [TimelockAction] queued by the admin.
+///
+/// There is one PDA for each action, see [TimelockAction::id]. The action is executed by its instruction,
+/// given the pending action, once the [PendingAction::eta] is reached. The admin or the guardian can cancel it before.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct PendingAction {
+    /// The queued action.
+    pub action: TimelockAction,
+    /// The admin who queued the action.
+    pub admin: Pubkey,
+    /// The time from which the action can be executed.
+    pub eta: i64,
+    /// The reserve of the pending action, used for future use.
+    pub _reserve: [u128; 2],
+}
+
+impl PendingAction {
+    pub const SEED: &'static [u8] = b"pending_action";
+
+    /// Asserts that the pending action is the `action`, and can be executed at the `current_timestamp`.
+    ///
+    /// # Errors
+    /// * [CustomError::InvalidPendingAction] when the pending action is not the `action`.
+    /// * [CustomError::TimelockNotReady] when the [PendingAction::eta] is not reached.
+    pub fn assert_executable(&self, action: &TimelockAction, current_timestamp: i64) -> Result<()> {
+        if self.action != *action {
+            return Err(CustomError::InvalidPendingAction.into());
+        }
+        if current_timestamp < self.eta {
+            return Err(CustomError::TimelockNotReady.into());
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_assert_executable() {
+    let operator = Pubkey::new_unique();
+    let pending_action = PendingAction {
+        action: TimelockAction::GrantRole(Role::WhitelistManager, operator),
+        admin: Pubkey::new_unique(),
+        eta: 100,
+        _reserve: [0; 2],
+    };
+    let action = TimelockAction::GrantRole(Role::WhitelistManager, operator);
+    assert_eq!(
+        pending_action.assert_executable(&action, 99).unwrap_err(),
+        CustomError::TimelockNotReady.into()
+    );
+    pending_action.assert_executable(&action, 100).unwrap();
+    assert_eq!(
+        pending_action
+            .assert_executable(&TimelockAction::RevokeRole(Role::WhitelistManager, operator), 100)
+            .unwrap_err(),
+        CustomError::InvalidPendingAction.into()
+    );
+    assert_ne!(action.id(), TimelockAction::RevokeRole(Role::WhitelistManager, operator).id());
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::ID;
+
+/// A role of the protocol, granted by the admin to any number of members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub enum Role {
+    /// Manages the whitelisted tokens, see the `add_or_update_whitelist` and `remove_whitelist` instructions.
+    WhitelistManager,
+    /// Manages the waiting durations for closing, see the `set_close_wait_duration` instruction.
+    DurationManager,
+    /// Manages the maximum fee, see the `set_max_fee` instruction.
+    FeeManager,
+    /// Pauses the protocol instructions and cancels the pending actions during an incident, along with the admin.
+    Guardian,
+}
+
+/// The role member PDA account, a [Role] granted to a member.
+///
+/// There is one PDA for each role and member, it is closed when the role is revoked.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct RoleMember {
+    /// The granted role.
+    pub role: Role,
+    /// The member of the role.
+    pub member: Pubkey,
+    /// The reserve of the role member, used for future use.
+    pub _reserve: [u128; 2],
+}
+
+impl RoleMember {
+    pub const SEED: &'static [u8] = b"role";
+
+    /// Whether the `role_member` account grants the `role` to the `member`.
+    pub fn has_role(role_member: &AccountInfo, role: Role, member: &Pubkey) -> bool {
+        if role_member.owner != &ID || role_member.data_is_empty() {
+            return false;
+        }
+        let Ok(data) = role_member.try_borrow_data() else {
+            return false;
+        };
+        match RoleMember::try_deserialize(&mut &data[..]) {
+            Ok(role_member) => role_member.role == role && role_member.member == *member,
+            Err(_) => false,
+        }
+    }
+}
+
+#[test]
+fn test_has_role() {
+    let member = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let mut data = vec![];
+    RoleMember {
+        role: Role::WhitelistManager,
+        member,
+        _reserve: [0; 2],
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    let mut lamports = 1;
+    let mut owner = ID;
+    let role_member = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    assert!(RoleMember::has_role(&role_member, Role::WhitelistManager, &member));
+    assert!(!RoleMember::has_role(&role_member, Role::DurationManager, &member));
+    assert!(!RoleMember::has_role(&role_member, Role::WhitelistManager, &key));
+
+    owner = Pubkey::new_unique();
+    let role_member = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    assert!(!RoleMember::has_role(&role_member, Role::WhitelistManager, &member));
+}
@@ -1,7 +1,8 @@
 use crate::CustomError;
 use crate::DepositArgs;
+use crate::MpcGroup;
 
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::keccak};
 use ethabi::ethereum_types::{H160, U256};
 use ethabi::{encode, Token};
 use sha2::{Digest, Sha256};
@@ -31,7 +32,9 @@ pub struct TradeInfo {
 }
 
 impl TradeInput {
-    fn encode_trade_input(&self) -> Vec<u8> {
+    /// ABI encodes the trade input as `abi.encode(sessionId, solver, (amountIn, fromChain, toChain))`,
+    /// the same encoding as the EVM contracts, so that the trade id matches on every chain.
+    pub fn encode_trade_input(&self) -> Vec<u8> {
         // 1. Session ID: uint256 (left-padded)
         let session_id = U256::from_big_endian(&self.session_id);
 
@@ -81,20 +84,53 @@ impl TradeInput {
         encoded
     }
 
+    /// Calculates the trade id with the default [TradeIdScheme::Sha256Abi] scheme, the SHA-256 hash of the [TradeInput::encode_trade_input].
     pub fn calculate_trade_id(&self) -> [u8; 32] {
-        let encoded = self.encode_trade_input();
+        Self::hash_trade_input(TradeIdScheme::Sha256Abi, &self.encode_trade_input())
+    }
+
+    /// Calculates the trade id with the hashing and encoding of the `scheme`.
+    pub fn calculate_trade_id_with_scheme(&self, scheme: TradeIdScheme) -> Result<[u8; 32]> {
+        let encoded = match scheme {
+            TradeIdScheme::Sha256Abi | TradeIdScheme::Keccak256Abi => self.encode_trade_input(),
+            TradeIdScheme::Sha256Borsh => self.try_to_vec()?,
+        };
+
+        Ok(Self::hash_trade_input(scheme, &encoded))
+    }
 
-        let mut hasher = Sha256::new();
-        hasher.update(&encoded);
-        let result = hasher.finalize();
+    /// Hashes the encoded trade input with the hash function of the `scheme`.
+    fn hash_trade_input(scheme: TradeIdScheme, encoded: &[u8]) -> [u8; 32] {
+        match scheme {
+            TradeIdScheme::Keccak256Abi => keccak::hash(encoded).to_bytes(),
+            TradeIdScheme::Sha256Abi | TradeIdScheme::Sha256Borsh => {
+                let mut hasher = Sha256::new();
+                hasher.update(encoded);
+                let result = hasher.finalize();
 
-        let mut trade_id = [0u8; 32];
-        trade_id.copy_from_slice(&result);
+                let mut trade_id = [0u8; 32];
+                trade_id.copy_from_slice(&result);
 
-        trade_id
+                trade_id
+            }
+        }
     }
 }
 
+/// The scheme of the trade id, the hashing and encoding of the [TradeInput] it is derived from.
+///
+/// Chosen by the depositor in the [DepositArgs::trade_id_scheme], and recorded in the [TradeDetail::trade_id_scheme].
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Default)]
+pub enum TradeIdScheme {
+    /// The SHA-256 hash of the ABI encoding, see [TradeInput::encode_trade_input]. The scheme of the trades deposited before it was recorded.
+    #[default]
+    Sha256Abi,
+    /// The keccak256 hash of the ABI encoding, see [TradeInput::encode_trade_input].
+    Keccak256Abi,
+    /// The SHA-256 hash of the Borsh serialization of the [TradeInput].
+    Sha256Borsh,
+}
+
 /// The trade detail PDA account that contains the trade detail information.
 #[account]
 #[derive(Debug, InitSpace, Default)]
@@ -106,6 +142,7 @@ pub struct TradeDetail {
     /// The token of the trade. None if the trade is SOL.
     pub token: Option<Pubkey>,
     /// The amount of the trade, with decimals.
+    /// This is the amount the vault actually received, after the Token-2022 transfer fee if any.
     pub amount: u64,
     /// The timeout of the trade. After this time, the trade cannot be settled, only claimed.
     pub timeout: i64,
@@ -121,8 +158,18 @@ pub struct TradeDetail {
     pub status: TradeStatus,
     /// The pmm that settled the trade.
     pub settled_pmm: Pubkey,
+    /// The total duration in seconds the [TradeDetail::timeout] was extended by since the deposit.
+    pub timeout_extension: u64,
+    /// The account that paid the rent of the trade accounts, the depositor or a relayer that sponsored the deposit.
+    /// The default pubkey for the trades deposited before it was recorded, see [TradeDetail::rent_payer].
+    pub payer_pubkey: Pubkey,
+    /// The scheme the [TradeDetail::trade_id] was derived with.
+    /// [TradeIdScheme::Sha256Abi] for the trades deposited before it was recorded.
+    pub trade_id_scheme: TradeIdScheme,
+    /// The padding of the [TradeDetail::trade_id_scheme] in the reserve space.
+    pub _padding: [u8; 7],
     /// The reserve space, used to upgrade in the future.
-    pub _reserve: [u128; 8],
+    pub _reserve: [u64; 10],
 }
 
 /// The trade status of the trade.
@@ -135,6 +182,8 @@ pub enum TradeStatus {
     Settled,
     /// When the trade is claimed by the user.
     Claimed,
+    /// When the trade is refunded to the user before the timeout, agreed by the mpc and the user.
+    Refunded,
 }
 
 impl TradeDetail {
@@ -145,6 +194,7 @@ impl TradeDetail {
         token: Option<Pubkey>,
         user_pubkey: Pubkey,
         user_ephemeral_pubkey: Pubkey,
+        payer_pubkey: Pubkey,
     ) -> Result<()> {
         self.trade_id = deposit_args.trade_id;
         self.amount = amount;
@@ -154,12 +204,24 @@ impl TradeDetail {
         self.user_ephemeral_pubkey = user_ephemeral_pubkey;
         self.refund_pubkey = deposit_args.data.refund_pubkey;
         self.user_pubkey = user_pubkey;
+        self.payer_pubkey = payer_pubkey;
+        self.trade_id_scheme = deposit_args.trade_id_scheme;
         self.total_fee = None;
         self.status = TradeStatus::Deposited;
 
         Ok(())
     }
 
+    /// The account that receives the rent of the trade accounts when they are closed.
+    ///
+    /// The [TradeDetail::payer_pubkey], or the [TradeDetail::user_pubkey] for the trades deposited before it was recorded.
+    pub fn rent_payer(&self) -> Pubkey {
+        match self.payer_pubkey == Pubkey::default() {
+            true => self.user_pubkey,
+            false => self.payer_pubkey,
+        }
+    }
+
     pub fn assert_claim(&self) -> Result<()> {
         let clock = Clock::get()?;
         let current_timestamp = clock.unix_timestamp;
@@ -173,6 +235,44 @@ impl TradeDetail {
         Ok(())
     }
 
+    pub fn assert_refund(&self) -> Result<()> {
+        if self.status != TradeStatus::Deposited {
+            return Err(CustomError::InvalidTradeStatus.into());
+        }
+
+        Ok(())
+    }
+
+    /// Whether the MPC of the trade authorized the instruction.
+    ///
+    /// Without `mpc_group`, the `signer` must be the [TradeDetail::mpc_pubkey].
+    /// When the trade references an [MpcGroup], `mpc_group` is this group, and at least [MpcGroup::threshold] members
+    /// must have signed among the `signer` and the `co_signers`.
+    pub fn is_mpc_authorized<'a, 'info: 'a>(
+        &self,
+        mpc_group: Option<&MpcGroup>,
+        signer: &'a AccountInfo<'info>,
+        co_signers: &'a [AccountInfo<'info>],
+    ) -> bool {
+        match mpc_group {
+            Some(mpc_group) => mpc_group.is_signed_by(std::iter::once(signer).chain(co_signers)),
+            None => signer.is_signer && *signer.key == self.mpc_pubkey,
+        }
+    }
+
+    /// Asserts that the MPC of the trade authorized the instruction, see [TradeDetail::is_mpc_authorized].
+    pub fn assert_mpc_authorized<'a, 'info: 'a>(
+        &self,
+        mpc_group: Option<&MpcGroup>,
+        signer: &'a AccountInfo<'info>,
+        co_signers: &'a [AccountInfo<'info>],
+    ) -> Result<()> {
+        if !self.is_mpc_authorized(mpc_group, signer, co_signers) {
+            return Err(CustomError::Unauthorized.into());
+        }
+        Ok(())
+    }
+
     pub fn assert_settlement(&self) -> Result<()> {
         let clock = Clock::get()?;
         let current_timestamp = clock.unix_timestamp;
@@ -186,6 +286,34 @@ impl TradeDetail {
         Ok(())
     }
 
+    /// Extends the [TradeDetail::timeout] of a deposited trade to the `new_timeout`.
+    ///
+    /// The total extension since the deposit is at most the `max_timeout_extension`.
+    /// # Errors
+    /// * [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited].
+    /// * [CustomError::TimeOut] when the trade is already expired at the `current_timestamp`.
+    /// * [CustomError::InvalidTimeout] when the `new_timeout` is not after the current timeout.
+    /// * [CustomError::TimeoutExtensionExceeded] when the total extension would exceed the `max_timeout_extension`.
+    pub fn extend_timeout(&mut self, new_timeout: i64, max_timeout_extension: u64, current_timestamp: i64) -> Result<()> {
+        if self.status != TradeStatus::Deposited {
+            return Err(CustomError::InvalidTradeStatus.into());
+        }
+        if current_timestamp > self.timeout {
+            return Err(CustomError::TimeOut.into());
+        }
+        if new_timeout <= self.timeout {
+            return Err(CustomError::InvalidTimeout.into());
+        }
+        let timeout_extension = self
+            .timeout_extension
+            .checked_add(new_timeout.abs_diff(self.timeout))
+            .filter(|extension| *extension <= max_timeout_extension)
+            .ok_or(CustomError::TimeoutExtensionExceeded)?;
+        self.timeout = new_timeout;
+        self.timeout_extension = timeout_extension;
+        Ok(())
+    }
+
     pub fn assert_close_finished_trade(
         &self,
         current_timestamp: u64,
@@ -197,8 +325,8 @@ impl TradeDetail {
             TradeStatus::Deposited => {
                 return Err(CustomError::InvalidTradeStatus.into());
             }
-            // When the trade is claimed, this action is allowed for anyone
-            TradeStatus::Claimed => {
+            // When the trade is claimed or refunded, this action is allowed for anyone
+            TradeStatus::Claimed | TradeStatus::Refunded => {
                 return Ok(());
             }
             // When the trade is settled:
@@ -265,3 +393,107 @@ pub fn test_assert_close_claimed_trade() {
     let result = trade_detail.assert_close_finished_trade(3, 100, false);
     assert!(result.is_ok());
 }
+
+#[test]
+pub fn test_assert_close_refunded_trade() {
+    let trade_detail = TradeDetail {
+        status: TradeStatus::Refunded,
+        ..Default::default()
+    };
+    let result = trade_detail.assert_close_finished_trade(0, 0, true);
+    assert!(result.is_ok());
+
+    let result = trade_detail.assert_close_finished_trade(3, 100, false);
+    assert!(result.is_ok());
+}
+
+#[test]
+pub fn test_assert_refund() {
+    let mut trade_detail = TradeDetail {
+        status: TradeStatus::Deposited,
+        ..Default::default()
+    };
+    assert!(trade_detail.assert_refund().is_ok());
+
+    for status in [TradeStatus::Settled, TradeStatus::Claimed, TradeStatus::Refunded] {
+        trade_detail.status = status;
+        assert_eq!(trade_detail.assert_refund().unwrap_err(), CustomError::InvalidTradeStatus.into());
+    }
+}
+
+#[test]
+pub fn test_is_mpc_authorized() {
+    let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let mut lamports = vec![0u64; 4];
+    let mut data = vec![[0u8; 0]; 4];
+    let owner = Pubkey::default();
+    let mut accounts: Vec<AccountInfo> = keys
+        .iter()
+        .zip(lamports.iter_mut())
+        .zip(data.iter_mut())
+        .map(|((key, lamports), data)| AccountInfo::new(key, true, false, lamports, data, &owner, false, 0))
+        .collect();
+    let (mpc, members) = (keys[0], &keys[1..]);
+
+    // A single MPC key
+    let trade_detail = TradeDetail {
+        mpc_pubkey: mpc,
+        ..Default::default()
+    };
+    assert!(trade_detail.is_mpc_authorized(None, &accounts[0], &[]));
+    assert!(!trade_detail.is_mpc_authorized(None, &accounts[1], &accounts[..1]));
+
+    // A 2-of-3 MPC group
+    let mut mpc_group = MpcGroup::default();
+    mpc_group.initialize(1, members.to_vec(), 2).unwrap();
+    let group = Some(&mpc_group);
+    assert!(!trade_detail.is_mpc_authorized(group, &accounts[1], &[]));
+    assert!(!trade_detail.is_mpc_authorized(group, &accounts[0], &accounts[1..2]));
+    // The same member is only counted once
+    assert!(!trade_detail.is_mpc_authorized(group, &accounts[1], &accounts[1..2]));
+    assert!(trade_detail.is_mpc_authorized(group, &accounts[1], &accounts[3..]));
+    assert!(trade_detail.is_mpc_authorized(group, &accounts[0], &accounts[1..]));
+
+    // Only the members who signed are counted
+    accounts[3].is_signer = false;
+    assert!(!trade_detail.is_mpc_authorized(group, &accounts[1], &accounts[3..]));
+    assert_eq!(
+        trade_detail.assert_mpc_authorized(group, &accounts[1], &accounts[3..]),
+        Err(CustomError::Unauthorized.into())
+    );
+}
+
+#[test]
+pub fn test_extend_timeout() {
+    let mut trade_detail = TradeDetail {
+        status: TradeStatus::Deposited,
+        timeout: 100,
+        ..Default::default()
+    };
+    assert_eq!(trade_detail.extend_timeout(100, 50, 0).unwrap_err(), CustomError::InvalidTimeout.into());
+    assert_eq!(trade_detail.extend_timeout(151, 50, 0).unwrap_err(), CustomError::TimeoutExtensionExceeded.into());
+
+    // The extensions add up to the maximum
+    trade_detail.extend_timeout(130, 50, 100).unwrap();
+    trade_detail.extend_timeout(150, 50, 0).unwrap();
+    assert_eq!(trade_detail.timeout, 150);
+    assert_eq!(trade_detail.timeout_extension, 50);
+    assert_eq!(trade_detail.extend_timeout(151, 50, 0).unwrap_err(), CustomError::TimeoutExtensionExceeded.into());
+
+    assert_eq!(trade_detail.extend_timeout(200, 100, 151).unwrap_err(), CustomError::TimeOut.into());
+    trade_detail.status = TradeStatus::Settled;
+    assert_eq!(trade_detail.extend_timeout(200, 100, 0).unwrap_err(), CustomError::InvalidTradeStatus.into());
+}
+
+#[test]
+pub fn test_rent_payer() {
+    let mut trade_detail = TradeDetail {
+        user_pubkey: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    // A trade deposited before the payer was recorded
+    assert_eq!(trade_detail.rent_payer(), trade_detail.user_pubkey);
+
+    trade_detail.payer_pubkey = Pubkey::new_unique();
+    assert_eq!(trade_detail.rent_payer(), trade_detail.payer_pubkey);
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{CustomError, ID};
+
 /// The whitelist token PDA account that contains the whitelist token information.
 #[account()]
 #[derive(InitSpace)]
@@ -9,17 +11,101 @@ pub struct WhitelistToken {
     pub token: Pubkey,
     /// The minimum amount of the whitelist token.
     pub amount: u64,
+    /// The maximum amount of a single deposit, with decimals. Zero means no limit.
+    pub max_amount: u64,
+    /// The maximum amount of the token held by the trade vaults, with decimals. Zero means no limit.
+    pub max_outstanding: u64,
+    /// The amount of the token currently held by the trade vaults, with decimals.
+    /// Increased by deposits, decreased when the trades are settled or claimed.
+    pub outstanding: u64,
+    /// The maximum total fee for this token, in basis points of the amount. Set by the admin.
+    /// Overrides the [crate::Config::max_fee_bps] when it is not none.
+    pub max_fee_bps: Option<u16>,
     /// The reserve field space, used to upgrade in the future.
-    pub _reserve: [u128; 4],
+    pub _reserve: [u8; 37],
 }
 
 impl WhitelistToken {
     pub const SEED: &'static [u8] = b"whitelist";
 
-    pub fn initialize(&mut self, token: Pubkey, amount: u64) -> Result<()> {
+    pub fn initialize(&mut self, token: Pubkey, amount: u64, max_amount: u64, max_outstanding: u64) -> Result<()> {
         self.token = token;
         self.amount = amount;
+        self.max_amount = max_amount;
+        self.max_outstanding = max_outstanding;
+
+        Ok(())
+    }
 
+    /// Checks the deposit against the limits of the whitelist token, and adds it to the outstanding amount.
+    /// # Arguments
+    /// * `amount` - The amount of the deposit, checked against [WhitelistToken::max_amount].
+    /// * `deposited_amount` - The amount actually received by the vault, added to [WhitelistToken::outstanding].
+    pub fn add_outstanding(&mut self, amount: u64, deposited_amount: u64) -> Result<()> {
+        if self.max_amount != 0 && amount > self.max_amount {
+            return Err(CustomError::MaxAmountExceeded.into());
+        }
+        let outstanding = self
+            .outstanding
+            .checked_add(deposited_amount)
+            .ok_or(CustomError::OutstandingLimitReached)?;
+        if self.max_outstanding != 0 && outstanding > self.max_outstanding {
+            return Err(CustomError::OutstandingLimitReached.into());
+        }
+        self.outstanding = outstanding;
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Loads the whitelist token from the account, none if the whitelist token has been removed.
+    pub fn try_load(whitelist_token: &AccountInfo) -> Result<Option<WhitelistToken>> {
+        if whitelist_token.owner != &ID || whitelist_token.data_is_empty() {
+            return Ok(None);
+        }
+        let data = whitelist_token.try_borrow_data()?;
+        Ok(Some(WhitelistToken::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Removes the amount leaving the trade vaults from the outstanding amount.
+    ///
+    /// The whitelist token may have been removed after the deposit, in this case there is nothing to update.
+    /// So this never fails because of the whitelist token, and the funds can always leave the vaults.
+    pub fn release_outstanding(whitelist_token: &AccountInfo, amount: u64) -> Result<()> {
+        if whitelist_token.owner != &ID || whitelist_token.data_is_empty() {
+            return Ok(());
+        }
+        let mut data = whitelist_token.try_borrow_mut_data()?;
+        let mut whitelist_token = WhitelistToken::try_deserialize(&mut &data[..])?;
+        whitelist_token.outstanding = whitelist_token.outstanding.saturating_sub(amount);
+        whitelist_token.try_serialize(&mut &mut data[..])
+    }
+}
+
+#[test]
+pub fn test_add_outstanding() {
+    let mut whitelist_token = WhitelistToken {
+        token: Pubkey::default(),
+        amount: 0,
+        max_amount: 0,
+        max_outstanding: 0,
+        outstanding: 0,
+        max_fee_bps: None,
+        _reserve: [0; 37],
+    };
+    // No limit
+    assert!(whitelist_token.add_outstanding(u64::MAX / 2, u64::MAX / 2).is_ok());
+    whitelist_token.outstanding = 0;
+
+    whitelist_token.max_amount = 100;
+    whitelist_token.max_outstanding = 150;
+    let result = whitelist_token.add_outstanding(101, 101);
+    assert_eq!(result.unwrap_err(), CustomError::MaxAmountExceeded.into());
+
+    // The outstanding amount is tracked with the amount received by the vault
+    assert!(whitelist_token.add_outstanding(100, 99).is_ok());
+    assert_eq!(whitelist_token.outstanding, 99);
+    let result = whitelist_token.add_outstanding(52, 52);
+    assert_eq!(result.unwrap_err(), CustomError::OutstandingLimitReached.into());
+    assert_eq!(whitelist_token.outstanding, 99);
+    assert!(whitelist_token.add_outstanding(51, 51).is_ok());
+    assert_eq!(whitelist_token.outstanding, 150);
+}
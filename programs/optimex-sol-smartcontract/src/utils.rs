@@ -4,13 +4,26 @@ use std::str::FromStr;
 
 use crate::{CustomError, ID};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, secp256k1_program};
 use anchor_lang::solana_program::program::{invoke, invoke_signed};
 use anchor_lang::solana_program::program_memory::sol_memcmp;
 use anchor_lang::solana_program::pubkey::PUBKEY_BYTES;
 use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use anchor_spl::associated_token::get_associated_token_address_with_program_id;
-use anchor_spl::token::spl_token::instruction::transfer_checked;
-use anchor_spl::token::Mint;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::extension::default_account_state::DefaultAccountState;
+use anchor_spl::token_2022::spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeAmount;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::instruction::transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::{Account as SplAccount, AccountState, Mint as SplMint};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::Mint;
 
 /// Compares two public keys for equality.
 pub fn cmp_pubkeys(a: &Pubkey, b: &Pubkey) -> bool {
@@ -49,6 +62,10 @@ pub fn bytes_to_u64_number(amount_in_bytes: &[u8]) -> Result<u64> {
 }
 
 /// Transfers SPL tokens from one account to another, optionally including a protocol fee.
+///
+/// Supports both the SPL Token and the Token-2022 programs.
+/// Returns the amounts actually received by the destination and by the protocol, zero without `fee_amount`.
+/// They are less than `amount` and `fee_amount` when the mint charges a transfer fee.
 pub fn transfer_spl_token<'c: 'info, 'info>(
     list_remaining_accounts: &mut Iter<'info, AccountInfo<'info>>,
     token_pubkey: &Pubkey,
@@ -58,14 +75,15 @@ pub fn transfer_spl_token<'c: 'info, 'info>(
     amount: u64,
     seeds: &[&[&[u8]]],
     fee_amount: Option<u64>,
-) -> Result<()> {
+) -> Result<(u64, u64)> {
     let token_program = next_account_info(list_remaining_accounts)?;
     let mint = next_account_info(list_remaining_accounts)?;
     let source = next_account_info(list_remaining_accounts)?;
     let destination = next_account_info(list_remaining_accounts)?;
 
+    assert_token_program(token_program.key)?;
     assert_keys_equal(&mint.key(), &token_pubkey, CustomError::InvalidMintKey)?;
-    let mint_data: Account<Mint> = Account::try_from(mint)?;
+    let mint_data: InterfaceAccount<Mint> = InterfaceAccount::try_from(mint)?;
 
     // Validate source key (vault ata)
     let source_key =
@@ -81,6 +99,7 @@ pub fn transfer_spl_token<'c: 'info, 'info>(
         CustomError::InvalidDestinationAta,
     )?;
 
+    let destination_amount_before = get_token_account_amount(destination)?;
     transfer_spl_token_internal(
         token_program,
         mint,
@@ -91,8 +110,9 @@ pub fn transfer_spl_token<'c: 'info, 'info>(
         mint_data.decimals,
         seeds,
     )?;
+    let received_amount = get_token_account_amount(destination)? - destination_amount_before;
 
-    match fee_amount {
+    let received_fee = match fee_amount {
         Some(fee) => {
             let (protocol_pubkey, _) = Pubkey::find_program_address(&[b"protocol"], &ID);
 
@@ -112,6 +132,7 @@ pub fn transfer_spl_token<'c: 'info, 'info>(
                 CustomError::InvalidDestinationAta,
             )?;
 
+            let protocol_amount_before = get_token_account_amount(protocol_ata)?;
             transfer_spl_token_internal(
                 token_program,
                 mint,
@@ -122,10 +143,106 @@ pub fn transfer_spl_token<'c: 'info, 'info>(
                 mint_data.decimals,
                 seeds,
             )?;
+            get_token_account_amount(protocol_ata)? - protocol_amount_before
         }
-        None => {}
+        None => 0,
+    };
+
+    Ok((received_amount, received_fee))
+}
+
+/// Asserts that the program is the SPL Token or the Token-2022 program.
+pub fn assert_token_program(token_program: &Pubkey) -> Result<()> {
+    if !cmp_pubkeys(token_program, &Token::id()) && !cmp_pubkeys(token_program, &Token2022::id()) {
+        return Err(CustomError::InvalidTokenProgram.into());
     }
+    Ok(())
+}
 
+/// Returns the amount of a SPL Token or Token-2022 token account.
+pub fn get_token_account_amount(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    let account = StateWithExtensions::<SplAccount>::unpack(&data)?;
+    Ok(account.base.amount)
+}
+
+/// Asserts that the mint is safe to be held by the protocol.
+///
+/// SPL Token mints are always supported. For Token-2022 mints, only the extensions that cannot move or lock the vault funds are allowed:
+/// * Transfer fee, the received amount is recorded when depositing.
+/// * Metadata, group and interest bearing extensions, which are informational.
+/// * Transfer hook, only when there is no hook program and no authority that can set one.
+/// * Mint close authority, only when there is no close authority.
+/// * Default account state, only when new accounts are not frozen.
+///
+/// Other extensions, such as permanent delegate, non-transferable or confidential transfer, are rejected.
+pub fn assert_supported_mint(mint: &AccountInfo) -> Result<()> {
+    if !cmp_pubkeys(mint.owner, &Token2022::id()) {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<SplMint>::unpack(&data)?;
+    for extension_type in mint_state.get_extension_types()? {
+        let is_supported = match extension_type {
+            ExtensionType::TransferFeeConfig
+            | ExtensionType::MetadataPointer
+            | ExtensionType::TokenMetadata
+            | ExtensionType::InterestBearingConfig
+            | ExtensionType::GroupPointer
+            | ExtensionType::TokenGroup
+            | ExtensionType::GroupMemberPointer
+            | ExtensionType::TokenGroupMember => true,
+            ExtensionType::TransferHook => {
+                let transfer_hook = mint_state.get_extension::<TransferHook>()?;
+                Option::<Pubkey>::from(transfer_hook.program_id).is_none()
+                    && Option::<Pubkey>::from(transfer_hook.authority).is_none()
+            }
+            ExtensionType::MintCloseAuthority => {
+                let mint_close_authority = mint_state.get_extension::<MintCloseAuthority>()?;
+                Option::<Pubkey>::from(mint_close_authority.close_authority).is_none()
+            }
+            ExtensionType::DefaultAccountState => {
+                let default_account_state = mint_state.get_extension::<DefaultAccountState>()?;
+                default_account_state.state == AccountState::Initialized as u8
+            }
+            _ => false,
+        };
+        if !is_supported {
+            return Err(CustomError::UnsupportedMintExtension.into());
+        }
+    }
+    Ok(())
+}
+
+/// Moves the transfer fees withheld in a Token-2022 token account to its mint, so that the token account can be closed.
+///
+/// Does nothing for SPL Token accounts, or when there is no withheld fee.
+pub fn harvest_withheld_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+) -> Result<()> {
+    if !cmp_pubkeys(token_program.key, &Token2022::id()) {
+        return Ok(());
+    }
+    let withheld_amount = {
+        let data = token_account.try_borrow_data()?;
+        let account = StateWithExtensions::<SplAccount>::unpack(&data)?;
+        match account.get_extension::<TransferFeeAmount>() {
+            Ok(transfer_fee_amount) => u64::from(transfer_fee_amount.withheld_amount),
+            Err(_) => 0,
+        }
+    };
+    if withheld_amount == 0 {
+        return Ok(());
+    }
+
+    let harvest_instruction =
+        harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[token_account.key])?;
+    invoke(
+        &harvest_instruction,
+        &[token_program.clone(), mint.clone(), token_account.clone()],
+    )?;
     Ok(())
 }
 
@@ -236,4 +353,132 @@ fn test_bytes_to_u64_number_error() {
     ];
     let x = bytes_to_u64_number(&v);
     assert_eq!(x, Err(CustomError::AmountExceeds.into()));
-}
\ No newline at end of file
+}
+#[cfg(test)]
+fn token_2022_mint_data(extension_types: &[ExtensionType]) -> Vec<u8> {
+    use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, StateWithExtensionsMut};
+    use anchor_spl::token_2022::spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+    use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+
+    let space = ExtensionType::try_calculate_account_len::<SplMint>(extension_types).unwrap();
+    let mut data = vec![0u8; space];
+    let mut state = StateWithExtensionsMut::<SplMint>::unpack_uninitialized(&mut data).unwrap();
+    for extension_type in extension_types {
+        match extension_type {
+            ExtensionType::TransferFeeConfig => {
+                state.init_extension::<TransferFeeConfig>(true).unwrap();
+            }
+            ExtensionType::PermanentDelegate => {
+                let extension = state.init_extension::<PermanentDelegate>(true).unwrap();
+                extension.delegate = Some(Pubkey::new_unique()).try_into().unwrap();
+            }
+            ExtensionType::TransferHook => {
+                let extension = state.init_extension::<TransferHook>(true).unwrap();
+                extension.program_id = Some(Pubkey::new_unique()).try_into().unwrap();
+            }
+            _ => unreachable!(),
+        }
+    }
+    state.base.is_initialized = true;
+    state.pack_base();
+    state.init_account_type().unwrap();
+    data
+}
+
+#[test]
+fn test_assert_supported_mint() {
+    let key = Pubkey::new_unique();
+    let owner = Token2022::id();
+    let cases = [
+        (vec![], true),
+        (vec![ExtensionType::TransferFeeConfig], true),
+        (vec![ExtensionType::PermanentDelegate], false),
+        (vec![ExtensionType::TransferHook], false),
+        (vec![ExtensionType::TransferFeeConfig, ExtensionType::PermanentDelegate], false),
+    ];
+    for (extension_types, is_supported) in cases {
+        let mut lamports = 0;
+        let mut data = token_2022_mint_data(&extension_types);
+        let mint = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        let result = assert_supported_mint(&mint);
+        if is_supported {
+            assert!(result.is_ok());
+        } else {
+            assert_eq!(result, Err(CustomError::UnsupportedMintExtension.into()));
+        }
+    }
+}
+
+/// The size of the offsets of a signature in the Ed25519 program instruction data.
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Asserts that the instruction before the current one is an Ed25519 program instruction,
+/// that verifies the signature of the `signer` over the `message`.
+///
+/// The Ed25519 program fails the transaction when the signature is invalid, so it is only checked that
+/// the verified public key and message are the expected ones, and are part of the Ed25519 instruction itself.
+/// # Errors
+/// * [CustomError::InvalidSignature] when there is no such Ed25519 instruction.
+pub fn assert_ed25519_signature(instructions_sysvar: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        return Err(CustomError::InvalidSignature.into());
+    }
+    let instruction = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    let data = &instruction.data;
+    // A single signature: the count, a padding byte, then the offsets
+    if instruction.program_id != ed25519_program::ID || data.len() < 2 + ED25519_OFFSETS_SIZE || data[0] != 1 {
+        return Err(CustomError::InvalidSignature.into());
+    }
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    // The signature, public key and message must be read from the Ed25519 instruction, not from another one
+    let from_ed25519_instruction = [read_u16(4), read_u16(8), read_u16(14)]
+        .iter()
+        .all(|instruction_index| *instruction_index == u16::MAX);
+    let public_key = data.get(public_key_offset..public_key_offset + PUBKEY_BYTES);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+    if !from_ed25519_instruction || public_key != Some(signer.as_ref()) || signed_message != Some(message) {
+        return Err(CustomError::InvalidSignature.into());
+    }
+    Ok(())
+}
+
+/// The size of the offsets of a signature in the secp256k1 program instruction data.
+const SECP256K1_OFFSETS_SIZE: usize = 11;
+
+/// Asserts that an instruction before the current one is a secp256k1 program instruction,
+/// that verifies the signature of the `eth_address` over the `message`.
+///
+/// The secp256k1 program fails the transaction when the signature is invalid, it recovers the signer of the keccak256 hash
+/// of the message and compares it with the Ethereum address. So it is only checked that the verified address and message
+/// are the expected ones, and are part of the secp256k1 instruction itself.
+/// # Errors
+/// * [CustomError::InvalidSolverSignature] when there is no such secp256k1 instruction.
+pub fn assert_secp256k1_signature(instructions_sysvar: &AccountInfo, eth_address: &[u8; 20], message: &[u8]) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+        let data = &instruction.data;
+        // A single signature: the count, then the offsets
+        if instruction.program_id != secp256k1_program::ID || data.len() < 1 + SECP256K1_OFFSETS_SIZE || data[0] != 1 {
+            continue;
+        }
+        let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]) as usize;
+        let eth_address_offset = read_u16(4);
+        let message_offset = read_u16(7);
+        let message_size = read_u16(9);
+        // The signature, address and message must be read from the secp256k1 instruction, not from another one
+        let from_secp256k1_instruction = [data[3], data[6], data[11]]
+            .iter()
+            .all(|instruction_index| *instruction_index as u16 == index);
+        let verified_address = data.get(eth_address_offset..eth_address_offset + 20);
+        let signed_message = data.get(message_offset..message_offset + message_size);
+        if from_secp256k1_instruction && verified_address == Some(eth_address) && signed_message == Some(message) {
+            return Ok(());
+        }
+    }
+    Err(CustomError::InvalidSolverSignature.into())
+}
@@ -0,0 +1,427 @@
+mod common;
+
+use anchor_lang::AccountSerialize;
+use common::*;
+use optimex_sol_sdk::{
+    instructions, pda, Config, FeeReceiver, MpcRegistry, PendingAction, Role, RoleMember, SetPauseArgs, TimelockAction,
+    WhitelistToken, WSOL_MINT,
+};
+use optimex_sol_smartcontract::CustomError;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[tokio::test]
+async fn test_init() {
+    let mut test = TestContext::new().await;
+    if test.native {
+        // The upgrade authority is only known to a program deployed by the upgradeable loader
+        eprintln!("test_init requires the compiled program, set SBF_OUT_DIR to run it");
+        return;
+    }
+    let admin = test.admin.pubkey();
+
+    // Only the upgrade authority can init
+    let stranger = test.user.insecure_clone();
+    let result = test
+        .process(&[instructions::init(&stranger.pubkey(), Some(admin))], &[&stranger])
+        .await;
+    assert!(result.is_err());
+
+    let deployer = test.deployer.insecure_clone();
+    test.process(&[instructions::init(&deployer.pubkey(), Some(admin))], &[&deployer])
+        .await
+        .unwrap();
+    let config: Config = test.data(&pda::get_config_pda()).await;
+    assert_eq!(config.admin, admin);
+    assert!(config.operators.is_empty());
+    assert!(test.account(&pda::get_vault_pda()).await.is_some());
+    assert!(test.account(&pda::get_protocol_pda()).await.is_some());
+}
+
+#[tokio::test]
+async fn test_role_and_fee_receiver() {
+    let mut test = TestContext::initialized().await;
+    let whitelist_manager = pda::get_role_pda(Role::WhitelistManager, &test.operator.pubkey());
+    let role_member: RoleMember = test.data(&whitelist_manager).await;
+    assert_eq!(role_member.role, Role::WhitelistManager);
+    assert_eq!(role_member.member, test.operator.pubkey());
+    let fee_receiver: FeeReceiver = test
+        .data(&pda::get_fee_receiver_pda(&test.fee_receiver.pubkey()))
+        .await;
+    assert_eq!(fee_receiver.receiver, test.fee_receiver.pubkey());
+
+    test.setup_asset(Asset::Sol).await;
+
+    // Only the admin manages the roles
+    let operator = test.operator.insecure_clone();
+    let result = test
+        .process(
+            &[instructions::grant_role(&operator.pubkey(), Role::FeeManager, operator.pubkey(), false)],
+            &[&operator],
+        )
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+
+    let admin = test.admin.insecure_clone();
+    test.process(
+        &[
+            instructions::revoke_role(&admin.pubkey(), Role::WhitelistManager, operator.pubkey(), false),
+            instructions::remove_fee_receiver(&admin.pubkey(), test.fee_receiver.pubkey(), false),
+        ],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    assert!(test.account(&whitelist_manager).await.is_none());
+    let result = test
+        .process(&[instructions::remove_whitelist(&operator.pubkey(), &WSOL_MINT)], &[&operator])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+    assert!(test
+        .account(&pda::get_fee_receiver_pda(&test.fee_receiver.pubkey()))
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_whitelist() {
+    let mut test = TestContext::initialized().await;
+    let operator = test.operator.insecure_clone();
+
+    for asset in [Asset::Spl, Asset::Token2022] {
+        let token = test.create_mint(asset).await.unwrap();
+        let whitelist = pda::get_whitelist_pda(&token.mint);
+
+        // Only the operators manage the whitelist
+        let admin = test.admin.insecure_clone();
+        let result = test
+            .process(
+                &[instructions::add_or_update_whitelist(&admin.pubkey(), &token.mint, 10, 0, 0)],
+                &[&admin],
+            )
+            .await;
+        assert_custom_error(result, CustomError::Unauthorized);
+
+        test.process(
+            &[instructions::add_or_update_whitelist(&operator.pubkey(), &token.mint, 10, 100, 1_000)],
+            &[&operator],
+        )
+        .await
+        .unwrap();
+        let whitelist_token: WhitelistToken = test.data(&whitelist).await;
+        assert_eq!(whitelist_token.token, token.mint);
+        assert_eq!(whitelist_token.amount, 10);
+        assert_eq!(whitelist_token.max_amount, 100);
+        assert_eq!(whitelist_token.max_outstanding, 1_000);
+
+        test.process(
+            &[instructions::add_or_update_whitelist(&operator.pubkey(), &token.mint, 20, 0, 0)],
+            &[&operator],
+        )
+        .await
+        .unwrap();
+        let whitelist_token: WhitelistToken = test.data(&whitelist).await;
+        assert_eq!(whitelist_token.amount, 20);
+
+        test.process(
+            &[instructions::remove_whitelist(&operator.pubkey(), &token.mint)],
+            &[&operator],
+        )
+        .await
+        .unwrap();
+        assert!(test.account(&whitelist).await.is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_pause() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Sol).await;
+    let admin = test.admin.insecure_clone();
+    let guardian = Keypair::new();
+    test.process(
+        &[instructions::grant_role(&admin.pubkey(), Role::Guardian, guardian.pubkey(), false)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    let pause_deposit = |deposit| SetPauseArgs {
+        deposit: Some(deposit),
+        payment: None,
+        settlement: None,
+        distribute_fees: None,
+    };
+    test.process(&[instructions::set_pause(&guardian.pubkey(), pause_deposit(true))], &[&guardian])
+        .await
+        .unwrap();
+    let config: Config = test.data(&pda::get_config_pda()).await;
+    assert!(config.pause.deposit);
+    assert!(!config.pause.payment);
+
+    let (ix, trade) = test.deposit_instruction(token.as_ref(), 1_000_000, 3_600).await;
+    let user = test.user.insecure_clone();
+    let result = test.process(&[ix], &[&user, &trade.ephemeral]).await;
+    assert_custom_error(result, CustomError::InstructionPaused);
+
+    // Only the admin can unpause
+    let result = test
+        .process(&[instructions::set_pause(&guardian.pubkey(), pause_deposit(false))], &[&guardian])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+    test.process(&[instructions::set_pause(&admin.pubkey(), pause_deposit(false))], &[&admin])
+        .await
+        .unwrap();
+    test.deposit(token.as_ref(), 1_000_000, 3_600).await;
+}
+
+#[tokio::test]
+async fn test_propose_and_accept_admin() {
+    let mut test = TestContext::initialized().await;
+    let admin = test.admin.insecure_clone();
+    let new_admin = Keypair::new();
+
+    // Only the proposed admin can accept
+    let result = test.process(&[instructions::accept_admin(&new_admin.pubkey())], &[&new_admin]).await;
+    assert!(result.is_err());
+
+    test.process(&[instructions::propose_admin(&admin.pubkey(), new_admin.pubkey())], &[&admin])
+        .await
+        .unwrap();
+    let config: Config = test.data(&pda::get_config_pda()).await;
+    assert_eq!(config.admin, admin.pubkey());
+    assert_eq!(config.pending_admin, new_admin.pubkey());
+
+    test.process(&[instructions::accept_admin(&new_admin.pubkey())], &[&new_admin])
+        .await
+        .unwrap();
+    let config: Config = test.data(&pda::get_config_pda()).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, Default::default());
+
+    // The previous admin has no more authority
+    let result = test
+        .process(
+            &[instructions::grant_role(&admin.pubkey(), Role::Guardian, Keypair::new().pubkey(), false)],
+            &[&admin],
+        )
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+}
+
+#[tokio::test]
+async fn test_mpc_registry() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Sol).await;
+    let admin = test.admin.insecure_clone();
+    let user = test.user.insecure_clone();
+    let mpc = Keypair::new().pubkey();
+    test.trade_mpc = mpc;
+
+    // A deposit cannot reference an unregistered MPC
+    let (ix, trade) = test.deposit_instruction(token.as_ref(), 1_000_000, 3_600).await;
+    let result = test.process(&[ix], &[&user, &trade.ephemeral]).await;
+    assert_custom_error(result, CustomError::MpcNotRegistered);
+
+    // Only the admin manages the registry, with a valid window
+    let operator = test.operator.insecure_clone();
+    let result = test
+        .process(&[instructions::add_or_update_mpc_registry(&operator.pubkey(), mpc, 0, 0)], &[&operator])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+    let result = test
+        .process(&[instructions::add_or_update_mpc_registry(&admin.pubkey(), mpc, 200, 100)], &[&admin])
+        .await;
+    assert_custom_error(result, CustomError::InvalidValidityWindow);
+
+    // Not yet valid
+    let now = test.now().await;
+    test.process(&[instructions::add_or_update_mpc_registry(&admin.pubkey(), mpc, now + 100, 0)], &[&admin])
+        .await
+        .unwrap();
+    let (ix, trade) = test.deposit_instruction(token.as_ref(), 1_000_000, 3_600).await;
+    let result = test.process(&[ix], &[&user, &trade.ephemeral]).await;
+    assert_custom_error(result, CustomError::MpcNotRegistered);
+
+    test.process(&[instructions::add_or_update_mpc_registry(&admin.pubkey(), mpc, 0, now + 100)], &[&admin])
+        .await
+        .unwrap();
+    let mpc_registry: MpcRegistry = test.data(&pda::get_mpc_registry_pda(&mpc)).await;
+    assert_eq!(mpc_registry.mpc_pubkey, mpc);
+    assert!(mpc_registry.is_active);
+    assert_eq!(mpc_registry.valid_until, now + 100);
+    test.deposit(token.as_ref(), 1_000_000, 3_600).await;
+
+    // Expired
+    test.warp_to_timestamp(now + 101).await;
+    let (ix, trade) = test.deposit_instruction(token.as_ref(), 1_000_000, 3_600).await;
+    let result = test.process(&[ix], &[&user, &trade.ephemeral]).await;
+    assert_custom_error(result, CustomError::MpcNotRegistered);
+
+    // Retired
+    test.process(&[instructions::add_or_update_mpc_registry(&admin.pubkey(), mpc, 0, 0)], &[&admin])
+        .await
+        .unwrap();
+    test.deposit(token.as_ref(), 1_000_000, 3_600).await;
+    test.process(&[instructions::retire_mpc_registry(&admin.pubkey(), mpc)], &[&admin])
+        .await
+        .unwrap();
+    let mpc_registry: MpcRegistry = test.data(&pda::get_mpc_registry_pda(&mpc)).await;
+    assert!(!mpc_registry.is_active);
+    let (ix, trade) = test.deposit_instruction(token.as_ref(), 1_000_000, 3_600).await;
+    let result = test.process(&[ix], &[&user, &trade.ephemeral]).await;
+    assert_custom_error(result, CustomError::MpcNotRegistered);
+}
+
+#[tokio::test]
+async fn test_timelock() {
+    let mut test = TestContext::initialized().await;
+    let admin = test.admin.insecure_clone();
+    let receiver = Keypair::new().pubkey();
+    let guardian = Keypair::new();
+    const DELAY: u64 = 3_600;
+    test.process(
+        &[instructions::grant_role(&admin.pubkey(), Role::Guardian, guardian.pubkey(), false)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    // Without a delay, the operations take effect immediately, including setting the delay
+    test.process(&[instructions::set_timelock_delay(&admin.pubkey(), DELAY, false)], &[&admin])
+        .await
+        .unwrap();
+    let config: Config = test.data(&pda::get_config_pda()).await;
+    assert_eq!(config.timelock_delay, DELAY);
+    let result = test
+        .process(&[instructions::add_fee_receiver(&admin.pubkey(), receiver, false)], &[&admin])
+        .await;
+    assert_custom_error(result, CustomError::TimelockRequired);
+
+    // The queued operation is executed after the delay
+    let action = TimelockAction::AddFeeReceiver(receiver);
+    let now = test.now().await;
+    test.process(&[instructions::queue_pending_action(&admin.pubkey(), action.clone())], &[&admin])
+        .await
+        .unwrap();
+    let pending_action_pda = pda::get_pending_action_pda(&action);
+    let pending_action: PendingAction = test.data(&pending_action_pda).await;
+    assert_eq!(pending_action.action, action);
+    assert_eq!(pending_action.admin, admin.pubkey());
+    assert!(pending_action.eta >= now + DELAY as i64);
+
+    let ix = instructions::add_fee_receiver(&admin.pubkey(), receiver, true);
+    let result = test.process(&[ix.clone()], &[&admin]).await;
+    assert_custom_error(result, CustomError::TimelockNotReady);
+
+    // A pending action only executes its own operation
+    test.warp_to_timestamp(pending_action.eta).await;
+    let mut other = instructions::grant_role(&admin.pubkey(), Role::FeeManager, receiver, true);
+    other.accounts.last_mut().unwrap().pubkey = pending_action_pda;
+    let result = test.process(&[other], &[&admin]).await;
+    assert_custom_error(result, CustomError::InvalidPendingAction);
+
+    test.process(&[ix], &[&admin]).await.unwrap();
+    let fee_receiver: FeeReceiver = test.data(&pda::get_fee_receiver_pda(&receiver)).await;
+    assert_eq!(fee_receiver.receiver, receiver);
+    assert!(test.account(&pending_action_pda).await.is_none());
+
+    // A guardian cancels a queued operation
+    let action = TimelockAction::RemoveFeeReceiver(receiver);
+    test.process(&[instructions::queue_pending_action(&admin.pubkey(), action.clone())], &[&admin])
+        .await
+        .unwrap();
+    let user = test.user.insecure_clone();
+    let result = test
+        .process(&[instructions::cancel_pending_action(&user.pubkey(), &admin.pubkey(), &action)], &[&user])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+
+    // The rent fee goes back to the admin who queued the operation, not to the guardian
+    let result = test
+        .process(
+            &[instructions::cancel_pending_action(&guardian.pubkey(), &guardian.pubkey(), &action)],
+            &[&guardian],
+        )
+        .await;
+    assert_custom_error(result, CustomError::InvalidUserAccount);
+    let pending_action_pda = pda::get_pending_action_pda(&action);
+    let rent = test.lamports(&pending_action_pda).await;
+    let admin_lamports = test.lamports(&admin.pubkey()).await;
+    test.process(
+        &[instructions::cancel_pending_action(&guardian.pubkey(), &admin.pubkey(), &action)],
+        &[&guardian],
+    )
+    .await
+    .unwrap();
+    assert!(test.account(&pending_action_pda).await.is_none());
+    assert_eq!(test.lamports(&admin.pubkey()).await, admin_lamports + rent);
+}
+
+#[tokio::test]
+async fn test_migrate_legacy_roles() {
+    let mut test = TestContext::initialized().await;
+    let admin = test.admin.insecure_clone();
+    let operator = Keypair::new();
+    let guardian = Keypair::new();
+
+    // Set the legacy operator and guardian of a config created before the roles
+    let config_pda = pda::get_config_pda();
+    let mut config: Config = test.data(&config_pda).await;
+    config.operators = vec![operator.pubkey()];
+    config.guardian = guardian.pubkey();
+    let mut account = test.account(&config_pda).await.unwrap();
+    config.try_serialize(&mut account.data.as_mut_slice()).unwrap();
+    test.context.set_account(&config_pda, &account.into());
+
+    // The legacy guardian has no permission until migrated
+    let pause_all = SetPauseArgs {
+        deposit: Some(true),
+        payment: Some(true),
+        settlement: Some(true),
+        distribute_fees: Some(true),
+    };
+    let result = test
+        .process(&[instructions::set_pause(&guardian.pubkey(), pause_all.clone())], &[&guardian])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+    test.fund(&operator.pubkey());
+    let result = test
+        .process(&[instructions::migrate_operator(&operator.pubkey(), operator.pubkey())], &[&operator])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+
+    test.process(
+        &[
+            instructions::migrate_operator(&admin.pubkey(), operator.pubkey()),
+            instructions::migrate_guardian(&admin.pubkey(), &guardian.pubkey()),
+        ],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    let config: Config = test.data(&config_pda).await;
+    assert!(config.operators.is_empty());
+    assert_eq!(config.guardian, Default::default());
+    for (role, member) in [
+        (Role::WhitelistManager, operator.pubkey()),
+        (Role::DurationManager, operator.pubkey()),
+        (Role::Guardian, guardian.pubkey()),
+    ] {
+        let role_member: RoleMember = test.data(&pda::get_role_pda(role, &member)).await;
+        assert_eq!(role_member.role, role);
+        assert_eq!(role_member.member, member);
+    }
+    test.process(&[instructions::set_pause(&guardian.pubkey(), pause_all)], &[&guardian])
+        .await
+        .unwrap();
+
+    // A migration only happens once
+    let result = test
+        .process(&[instructions::migrate_operator(&admin.pubkey(), operator.pubkey())], &[&admin])
+        .await;
+    assert!(result.is_err());
+    let result = test
+        .process(&[instructions::migrate_guardian(&admin.pubkey(), &guardian.pubkey())], &[&admin])
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,549 @@
+//! In-process harness for the integration tests.
+//!
+//! The program runs natively through its entrypoint by default. When `SBF_OUT_DIR` (or `BPF_OUT_DIR`)
+//! points to the compiled `optimex_sol_smartcontract.so`, the compiled program is deployed as an upgradeable
+//! program instead, so that `init` can check the upgrade authority.
+#![allow(dead_code)]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        entrypoint::ProgramResult,
+        native_token::LAMPORTS_PER_SOL,
+        program_pack::Pack,
+        system_instruction,
+    },
+    AccountDeserialize, AccountSerialize, Discriminator,
+};
+use anchor_spl::{
+    associated_token::spl_associated_token_account,
+    token::spl_token,
+    token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_fee, ExtensionType, StateWithExtensions},
+    },
+};
+use optimex_sol_sdk::{
+    instructions, pda, remaining_accounts::TokenMint, Role, TradeDetail, TradeDetailInput, TradeIdScheme, TradeInfo,
+    TradeInput, WSOL_MINT,
+};
+use optimex_sol_smartcontract::{Config, CustomError, ID};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+/// The transfer fee of the Token-2022 mints, in basis points.
+pub const TRANSFER_FEE_BPS: u16 = 100;
+/// The maximum transfer fee of the Token-2022 mints.
+pub const MAX_TRANSFER_FEE: u64 = 1_000_000;
+/// The decimals of the test mints.
+pub const DECIMALS: u8 = 6;
+
+/// The asset of a trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Asset {
+    Sol,
+    Spl,
+    /// A Token-2022 mint with a transfer fee.
+    Token2022,
+}
+
+impl Asset {
+    pub const ALL: [Asset; 3] = [Asset::Sol, Asset::Spl, Asset::Token2022];
+
+    /// The amount received by the destination of a transfer of `amount`.
+    pub fn received(&self, amount: u64) -> u64 {
+        match self {
+            Asset::Token2022 => {
+                let fee = (amount as u128 * TRANSFER_FEE_BPS as u128).div_ceil(10_000) as u64;
+                amount - fee.min(MAX_TRANSFER_FEE)
+            }
+            _ => amount,
+        }
+    }
+}
+
+/// A deposited trade.
+pub struct Trade {
+    pub trade_id: [u8; 32],
+    pub ephemeral: Keypair,
+    pub timeout: i64,
+}
+
+fn process_instruction<'a, 'b, 'c, 'd>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'c>],
+    data: &'d [u8],
+) -> ProgramResult {
+    // The anchor entrypoint requires the accounts slice to live as long as the accounts themselves
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    optimex_sol_smartcontract::entry(program_id, accounts, data)
+}
+
+fn funded_account() -> Account {
+    Account {
+        lamports: 1_000 * LAMPORTS_PER_SOL,
+        ..Account::default()
+    }
+}
+
+pub struct TestContext {
+    pub context: ProgramTestContext,
+    pub deployer: Keypair,
+    pub admin: Keypair,
+    pub operator: Keypair,
+    pub mpc: Keypair,
+    /// The MPC of the deposited trades, the `mpc` key by default.
+    pub trade_mpc: Pubkey,
+    pub pmm: Keypair,
+    pub user: Keypair,
+    pub fee_receiver: Keypair,
+    /// Whether the program runs natively, rather than the compiled program.
+    pub native: bool,
+    session_id: u64,
+}
+
+impl TestContext {
+    /// Start the test validator with the program and funded participants.
+    pub async fn new() -> Self {
+        let deployer = Keypair::new();
+        let admin = Keypair::new();
+        let operator = Keypair::new();
+        let mpc = Keypair::new();
+        let pmm = Keypair::new();
+        let user = Keypair::new();
+        let fee_receiver = Keypair::new();
+
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(false);
+        let elf = std::env::var("SBF_OUT_DIR")
+            .or_else(|_| std::env::var("BPF_OUT_DIR"))
+            .map(|dir| {
+                std::fs::read(format!("{dir}/optimex_sol_smartcontract.so"))
+                    .expect("the compiled program is not found")
+            })
+            .ok();
+        match &elf {
+            // Deploy the compiled program as an upgradeable program, owned by the deployer
+            Some(elf) => {
+                let program_data = pda::get_program_data();
+                program_test.add_account(
+                    ID,
+                    Account {
+                        lamports: LAMPORTS_PER_SOL,
+                        data: bincode::serialize(&UpgradeableLoaderState::Program {
+                            programdata_address: program_data,
+                        })
+                        .unwrap(),
+                        owner: bpf_loader_upgradeable::ID,
+                        executable: true,
+                        rent_epoch: 0,
+                    },
+                );
+                let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+                    slot: 0,
+                    upgrade_authority_address: Some(deployer.pubkey()),
+                })
+                .unwrap();
+                data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+                data.extend_from_slice(elf);
+                program_test.add_account(
+                    program_data,
+                    Account {
+                        lamports: LAMPORTS_PER_SOL,
+                        data,
+                        owner: bpf_loader_upgradeable::ID,
+                        executable: false,
+                        rent_epoch: 0,
+                    },
+                );
+            }
+            None => program_test.add_program(
+                "optimex_sol_smartcontract",
+                ID,
+                processor!(process_instruction),
+            ),
+        }
+
+        // The native mint, whitelisted for SOL trades
+        let mut native_mint = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            decimals: 9,
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut native_mint);
+        program_test.add_account(
+            WSOL_MINT,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: native_mint,
+                owner: spl_token::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        for keypair in [&deployer, &admin, &operator, &mpc, &pmm, &user, &fee_receiver] {
+            program_test.add_account(keypair.pubkey(), funded_account());
+        }
+
+        let context = program_test.start_with_context().await;
+        Self {
+            context,
+            deployer,
+            admin,
+            operator,
+            trade_mpc: mpc.pubkey(),
+            mpc,
+            pmm,
+            user,
+            fee_receiver,
+            native: elf.is_none(),
+            session_id: 0,
+        }
+    }
+
+    /// Start the test validator, then initialize the protocol with an operator, a fee receiver and the registered MPC.
+    pub async fn initialized() -> Self {
+        let mut test = Self::new().await;
+        let admin = test.admin.pubkey();
+        if test.native {
+            test.seed_init(&admin);
+        } else {
+            test.process(
+                &[instructions::init(&test.deployer.pubkey(), Some(admin))],
+                &[&test.deployer.insecure_clone()],
+            )
+            .await
+            .unwrap();
+        }
+        test.process(
+            &[
+                instructions::grant_role(&admin, Role::WhitelistManager, test.operator.pubkey(), false),
+                instructions::grant_role(&admin, Role::DurationManager, test.operator.pubkey(), false),
+                instructions::add_fee_receiver(&admin, test.fee_receiver.pubkey(), false),
+                instructions::add_or_update_mpc_registry(&admin, test.mpc.pubkey(), 0, 0),
+            ],
+            &[&test.admin.insecure_clone()],
+        )
+        .await
+        .unwrap();
+        test
+    }
+
+    /// Create the accounts of the init instruction.
+    ///
+    /// A native program is not owned by the upgradeable loader, so the upgrade authority check of init cannot pass.
+    fn seed_init(&mut self, admin: &Pubkey) {
+        let mut data = vec![0; Config::SPACE];
+        data[..8].copy_from_slice(&Config::DISCRIMINATOR);
+        let mut config = Config::try_deserialize(&mut data.as_slice()).unwrap();
+        config.admin = *admin;
+        config.try_serialize(&mut data.as_mut_slice()).unwrap();
+
+        let rent = Rent::default();
+        let accounts = [
+            (pda::get_config_pda(), data),
+            (pda::get_vault_pda(), vec![]),
+            (pda::get_protocol_pda(), vec![]),
+        ];
+        for (address, data) in accounts {
+            let account = Account {
+                lamports: rent.minimum_balance(data.len()),
+                data,
+                owner: ID,
+                executable: false,
+                rent_epoch: 0,
+            };
+            self.context.set_account(&address, &account.into());
+        }
+    }
+
+    /// Process the instructions in a transaction paid by the payer, with a fresh blockhash.
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let payer = self.context.payer.insecure_clone();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Simulate the instruction in a transaction paid by the payer, and return its return data.
+    pub async fn simulate(&mut self, instruction: Instruction) -> Vec<u8> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let payer = self.context.payer.insecure_clone();
+        let transaction =
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], blockhash);
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        simulation.result.unwrap().unwrap();
+        simulation.simulation_details.unwrap().return_data.unwrap().data
+    }
+
+    /// Fund an account created during the test, so it can pay the transaction fees and rents.
+    pub fn fund(&mut self, address: &Pubkey) {
+        self.context.set_account(address, &funded_account().into());
+    }
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context.banks_client.get_account(*address).await.unwrap()
+    }
+
+    pub async fn data<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.account(address).await.expect("account not found");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn trade_detail(&mut self, trade_id: &[u8; 32]) -> TradeDetail {
+        self.data(&pda::get_user_trade_detail_pda(trade_id)).await
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.account(address).await.map_or(0, |account| account.lamports)
+    }
+
+    /// The balance of an owner, in lamports for SOL or in the associated token account otherwise.
+    pub async fn balance(&mut self, token: Option<&TokenMint>, owner: &Pubkey) -> u64 {
+        match token {
+            Some(token) => {
+                let account = self.account(&token.ata(owner)).await.unwrap();
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+                    .unwrap()
+                    .base
+                    .amount
+            }
+            None => self.lamports(owner).await,
+        }
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    /// Move the clock to the timestamp.
+    pub async fn warp_to_timestamp(&mut self, unix_timestamp: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Create a mint of the asset, with the payer as mint authority. None for SOL.
+    pub async fn create_mint(&mut self, asset: Asset) -> Option<TokenMint> {
+        let (token_program, extensions) = match asset {
+            Asset::Sol => return None,
+            Asset::Spl => (spl_token::ID, vec![]),
+            Asset::Token2022 => (spl_token_2022::ID, vec![ExtensionType::TransferFeeConfig]),
+        };
+        let mint = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &extensions,
+        )
+        .unwrap();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+
+        let mut ixs = vec![system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            &token_program,
+        )];
+        if asset == Asset::Token2022 {
+            ixs.push(
+                transfer_fee::instruction::initialize_transfer_fee_config(
+                    &token_program,
+                    &mint.pubkey(),
+                    Some(&payer),
+                    Some(&payer),
+                    TRANSFER_FEE_BPS,
+                    MAX_TRANSFER_FEE,
+                )
+                .unwrap(),
+            );
+        }
+        ixs.push(
+            spl_token_2022::instruction::initialize_mint2(
+                &token_program,
+                &mint.pubkey(),
+                &payer,
+                None,
+                DECIMALS,
+            )
+            .unwrap(),
+        );
+        self.process(&ixs, &[&mint]).await.unwrap();
+
+        Some(TokenMint {
+            mint: mint.pubkey(),
+            token_program,
+        })
+    }
+
+    /// Create the associated token account of the owner, paid by the payer.
+    pub async fn create_ata(&mut self, token: &TokenMint, owner: &Pubkey) -> Pubkey {
+        let payer = self.context.payer.pubkey();
+        self.process(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer,
+                    owner,
+                    &token.mint,
+                    &token.token_program,
+                ),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+        token.ata(owner)
+    }
+
+    /// Mint tokens to the associated token account of the owner, creating it if needed.
+    pub async fn mint_to(&mut self, token: &TokenMint, owner: &Pubkey, amount: u64) {
+        let ata = token.ata(owner);
+        if self.account(&ata).await.is_none() {
+            self.create_ata(token, owner).await;
+        }
+        let payer = self.context.payer.pubkey();
+        self.process(
+            &[spl_token_2022::instruction::mint_to(
+                &token.token_program,
+                &token.mint,
+                &ata,
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap()],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Whitelist the asset and fund the participants. Returns the mint, None for SOL.
+    pub async fn setup_asset(&mut self, asset: Asset) -> Option<TokenMint> {
+        let token = self.create_mint(asset).await;
+        let mint = token.map_or(WSOL_MINT, |token| token.mint);
+        let operator = self.operator.insecure_clone();
+        self.process(
+            &[instructions::add_or_update_whitelist(&operator.pubkey(), &mint, 1_000, 0, 0)],
+            &[&operator],
+        )
+        .await
+        .unwrap();
+
+        if let Some(token) = &token {
+            let amount = 1_000 * 10u64.pow(DECIMALS as u32);
+            for owner in [self.user.pubkey(), self.pmm.pubkey()] {
+                self.mint_to(token, &owner, amount).await;
+            }
+            for owner in [pda::get_protocol_pda(), self.fee_receiver.pubkey()] {
+                self.create_ata(token, &owner).await;
+            }
+        }
+        token
+    }
+
+    /// The trade input of a deposit of the user.
+    pub fn trade_input(&mut self, token: Option<&TokenMint>, amount: u64) -> TradeInput {
+        self.session_id += 1;
+        let mut session_id = [0u8; 32];
+        session_id[24..].copy_from_slice(&self.session_id.to_be_bytes());
+        let mut amount_in = [0u8; 32];
+        amount_in[24..].copy_from_slice(&amount.to_be_bytes());
+        let from_token = token.map_or("native".to_string(), |token| token.mint.to_string());
+
+        TradeInput {
+            session_id,
+            solver: [1; 20],
+            trade_info: TradeInfo {
+                amount_in,
+                from_chain: [
+                    self.user.pubkey().to_string().into_bytes(),
+                    b"solana".to_vec(),
+                    from_token.into_bytes(),
+                ],
+                to_chain: [
+                    b"0x0000000000000000000000000000000000000001".to_vec(),
+                    b"ethereum".to_vec(),
+                    b"native".to_vec(),
+                ],
+            },
+        }
+    }
+
+    /// The deposit instruction of the user, with a new ephemeral account, timing out after `duration` seconds.
+    pub async fn deposit_instruction(
+        &mut self,
+        token: Option<&TokenMint>,
+        amount: u64,
+        duration: i64,
+    ) -> (Instruction, Trade) {
+        let input = self.trade_input(token, amount);
+        let trade_id = input.calculate_trade_id();
+        let timeout = self.now().await + duration;
+        let ephemeral = Keypair::new();
+        let ix = instructions::deposit(
+            &self.user.pubkey(),
+            &ephemeral.pubkey(),
+            input,
+            TradeIdScheme::Sha256Abi,
+            TradeDetailInput {
+                timeout,
+                mpc_pubkey: self.trade_mpc,
+                refund_pubkey: self.user.pubkey(),
+            },
+            token,
+        ).unwrap();
+        (
+            ix,
+            Trade {
+                trade_id,
+                ephemeral,
+                timeout,
+            },
+        )
+    }
+
+    /// Deposit a trade of the user, timing out after `duration` seconds. The vault token account is created first.
+    pub async fn deposit(&mut self, token: Option<&TokenMint>, amount: u64, duration: i64) -> Trade {
+        let (ix, trade) = self.deposit_instruction(token, amount, duration).await;
+        if let Some(token) = token {
+            self.create_ata(token, &pda::get_trade_vault_pda(&trade.trade_id)).await;
+        }
+        let user = self.user.insecure_clone();
+        let ephemeral = trade.ephemeral.insecure_clone();
+        self.process(&[ix], &[&user, &ephemeral]).await.unwrap();
+        trade
+    }
+}
+
+/// Assert that the transaction failed with the custom error of the program.
+pub fn assert_custom_error(result: std::result::Result<(), BanksClientError>, error: CustomError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(error), "expected {error:?}")
+        }
+        other => panic!("expected {error:?}, got {other:?}"),
+    }
+}
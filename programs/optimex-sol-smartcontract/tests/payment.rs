@@ -0,0 +1,458 @@
+mod common;
+
+use common::*;
+use optimex_sol_sdk::{
+    decode, instructions, pda, Config, CreatePaymentExpectationArgs, FeeLedger, FeeLedgerBalance, PaymentArgs,
+    PaymentExpectation, PaymentReceipt, SetCloseWaitDurationArgs, WSOL_MINT,
+};
+use optimex_sol_smartcontract::CustomError;
+use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+
+const AMOUNT: u64 = 1_000_000;
+const TOTAL_FEE: u64 = 10_000;
+
+#[tokio::test]
+async fn test_payment_and_close_payment_receipt() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let pmm = test.pmm.insecure_clone();
+        let to_user = Keypair::new().pubkey();
+        let protocol = pda::get_protocol_pda();
+        if let Some(token) = &token {
+            test.create_ata(token, &to_user).await;
+        }
+
+        let payment_args = PaymentArgs {
+            trade_id: [9; 32],
+            token: token.map(|token| token.mint),
+            amount: AMOUNT,
+            total_fee: TOTAL_FEE,
+            deadline: test.now().await + 60,
+        };
+        let token_program = token.map_or(anchor_spl::token::ID, |token| token.token_program);
+        let protocol_balance = test.balance(token.as_ref(), &protocol).await;
+        test.process(
+            &[instructions::payment(&pmm.pubkey(), &to_user, payment_args.clone(), &token_program)],
+            &[&pmm],
+        )
+        .await
+        .unwrap();
+
+        let to_user_balance = match &token {
+            Some(_) => test.balance(token.as_ref(), &to_user).await,
+            None => test.lamports(&to_user).await,
+        };
+        assert_eq!(to_user_balance, asset.received(AMOUNT - TOTAL_FEE), "{asset:?}");
+        assert_eq!(
+            test.balance(token.as_ref(), &protocol).await,
+            protocol_balance + asset.received(TOTAL_FEE),
+            "{asset:?}"
+        );
+        let payment_receipt = pda::get_payment_receipt_pda(
+            &payment_args.trade_id,
+            &pmm.pubkey(),
+            &to_user,
+            AMOUNT,
+            TOTAL_FEE,
+            payment_args.token,
+        );
+        let receipt: PaymentReceipt = test.data(&payment_receipt).await;
+        assert_eq!(receipt.trade_id, payment_args.trade_id);
+        assert_eq!(receipt.from_pubkey, pmm.pubkey());
+        assert_eq!(receipt.to_pubkey, to_user);
+        assert_eq!(receipt.payment_amount, AMOUNT);
+        assert_eq!(receipt.total_fee, TOTAL_FEE);
+
+        // The receipt is closable after the close payment duration
+        let operator = test.operator.insecure_clone();
+        test.process(
+            &[instructions::set_close_wait_duration(
+                &operator.pubkey(),
+                SetCloseWaitDurationArgs {
+                    close_trade_duration: None,
+                    close_payment_duration: Some(100),
+                },
+            )],
+            &[&operator],
+        )
+        .await
+        .unwrap();
+        let ix = instructions::close_payment_receipt(
+            &pmm.pubkey(),
+            &payment_args.trade_id,
+            &to_user,
+            AMOUNT,
+            TOTAL_FEE,
+            payment_args.token,
+        );
+        let result = test.process(&[ix.clone()], &[&pmm]).await;
+        assert_custom_error(result, CustomError::CloseNotAvailable);
+
+        test.warp_to_timestamp(receipt.payment_time as i64 + 101).await;
+        test.process(&[ix], &[&pmm]).await.unwrap();
+        assert!(test.account(&payment_receipt).await.is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_batch_close_payment_receipts() {
+    let mut test = TestContext::initialized().await;
+    test.setup_asset(Asset::Sol).await;
+    let pmm = test.pmm.insecure_clone();
+    let to_user = Keypair::new().pubkey();
+    let operator = test.operator.insecure_clone();
+    test.process(
+        &[instructions::set_close_wait_duration(
+            &operator.pubkey(),
+            SetCloseWaitDurationArgs {
+                close_trade_duration: None,
+                close_payment_duration: Some(100),
+            },
+        )],
+        &[&operator],
+    )
+    .await
+    .unwrap();
+
+    // Two payments, the second one is not closable when the first one is
+    let mut payment_receipts = vec![];
+    for trade_id in [[1; 32], [2; 32]] {
+        let payment_args = PaymentArgs {
+            trade_id,
+            token: None,
+            amount: AMOUNT,
+            total_fee: TOTAL_FEE,
+            deadline: test.now().await + 60,
+        };
+        test.process(
+            &[instructions::payment(&pmm.pubkey(), &to_user, payment_args, &anchor_spl::token::ID)],
+            &[&pmm],
+        )
+        .await
+        .unwrap();
+        payment_receipts.push(pda::get_payment_receipt_pda(
+            &trade_id,
+            &pmm.pubkey(),
+            &to_user,
+            AMOUNT,
+            TOTAL_FEE,
+            None,
+        ));
+        let now = test.now().await;
+        test.warp_to_timestamp(now + 50).await;
+    }
+
+    // Only the payer closes its payment receipts
+    let user = test.user.insecure_clone();
+    let result = test
+        .process(&[instructions::batch_close_payment_receipts(&user.pubkey(), &payment_receipts)], &[&user])
+        .await;
+    assert_custom_error(result, CustomError::InvalidUserAccount);
+
+    let receipt: PaymentReceipt = test.data(&payment_receipts[0]).await;
+    test.warp_to_timestamp(receipt.payment_time as i64 + 101).await;
+    let pmm_lamports = test.lamports(&pmm.pubkey()).await;
+    test.process(&[instructions::batch_close_payment_receipts(&pmm.pubkey(), &payment_receipts)], &[&pmm])
+        .await
+        .unwrap();
+    assert!(test.account(&payment_receipts[0]).await.is_none());
+    assert!(test.account(&payment_receipts[1]).await.is_some());
+    assert!(test.lamports(&pmm.pubkey()).await > pmm_lamports);
+
+    // The closed payment receipt is skipped
+    let now = test.now().await;
+    test.warp_to_timestamp(now + 100).await;
+    test.process(&[instructions::batch_close_payment_receipts(&pmm.pubkey(), &payment_receipts)], &[&pmm])
+        .await
+        .unwrap();
+    assert!(test.account(&payment_receipts[1]).await.is_none());
+}
+
+#[tokio::test]
+async fn test_payment_deadline_exceeded() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Sol).await;
+    let pmm = test.pmm.insecure_clone();
+    let payment_args = PaymentArgs {
+        trade_id: [9; 32],
+        token: None,
+        amount: AMOUNT,
+        total_fee: TOTAL_FEE,
+        deadline: test.now().await - 1,
+    };
+    assert!(token.is_none());
+    let result = test
+        .process(
+            &[instructions::payment(
+                &pmm.pubkey(),
+                &Keypair::new().pubkey(),
+                payment_args,
+                &anchor_spl::token::ID,
+            )],
+            &[&pmm],
+        )
+        .await;
+    assert_custom_error(result, CustomError::DeadlineExceeded);
+}
+
+#[tokio::test]
+async fn test_accrue_legacy_fee() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let admin = test.admin.insecure_clone();
+        let pmm = test.pmm.insecure_clone();
+        let to_user = test.user.pubkey();
+        let token_program = token.map_or(anchor_spl::token::ID, |token| token.token_program);
+        let mint = token.map_or(WSOL_MINT, |token| token.mint);
+        let fee_ledger_pda = pda::get_fee_ledger_pda(&mint);
+
+        // The protocol holds some fee collected before the fee ledger, then a payment accrues its fee
+        let legacy_fee = 5_000;
+        match &token {
+            Some(token) => test.mint_to(token, &pda::get_protocol_pda(), legacy_fee).await,
+            None => test
+                .process(
+                    &[system_instruction::transfer(&pmm.pubkey(), &pda::get_protocol_pda(), legacy_fee)],
+                    &[&pmm],
+                )
+                .await
+                .unwrap(),
+        }
+        let payment_args = PaymentArgs {
+            trade_id: [9; 32],
+            token: token.map(|token| token.mint),
+            amount: AMOUNT,
+            total_fee: TOTAL_FEE,
+            deadline: test.now().await + 60,
+        };
+        test.process(
+            &[instructions::payment(&pmm.pubkey(), &to_user, payment_args, &token_program)],
+            &[&pmm],
+        )
+        .await
+        .unwrap();
+
+        // Only the admin accrues the legacy fee
+        let result = test
+            .process(&[instructions::accrue_legacy_fee(&pmm.pubkey(), token.as_ref())], &[&pmm])
+            .await;
+        assert_custom_error(result, CustomError::Unauthorized);
+
+        test.process(&[instructions::accrue_legacy_fee(&admin.pubkey(), token.as_ref())], &[&admin])
+            .await
+            .unwrap();
+        let fee_ledger: FeeLedger = test.data(&fee_ledger_pda).await;
+        assert_eq!(fee_ledger.accrued, asset.received(TOTAL_FEE) + legacy_fee, "{asset:?}");
+        assert!(fee_ledger.legacy_fee_accrued);
+
+        // Only once per token
+        let result = test
+            .process(&[instructions::accrue_legacy_fee(&admin.pubkey(), token.as_ref())], &[&admin])
+            .await;
+        assert_custom_error(result, CustomError::LegacyFeeAlreadyAccrued);
+    }
+}
+
+#[tokio::test]
+async fn test_distribute_fees() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let admin = test.admin.insecure_clone();
+        let pmm = test.pmm.insecure_clone();
+        let to_user = test.user.pubkey();
+        let token_program = token.map_or(anchor_spl::token::ID, |token| token.token_program);
+        // The operator is funded, so it can receive a small SOL share
+        let receivers = [test.fee_receiver.pubkey(), test.operator.pubkey()];
+        if let Some(token) = &token {
+            test.create_ata(token, &receivers[1]).await;
+        }
+
+        let payment_args = PaymentArgs {
+            trade_id: [9; 32],
+            token: token.map(|token| token.mint),
+            amount: AMOUNT,
+            total_fee: TOTAL_FEE,
+            deadline: test.now().await + 60,
+        };
+        test.process(
+            &[instructions::payment(&pmm.pubkey(), &to_user, payment_args, &token_program)],
+            &[&pmm],
+        )
+        .await
+        .unwrap();
+        let mint = token.map_or(WSOL_MINT, |token| token.mint);
+        let fee_ledger: FeeLedger = test.data(&pda::get_fee_ledger_pda(&mint)).await;
+        assert_eq!(fee_ledger.token, mint);
+        // The fee actually received by the protocol is accrued, net of the Token-2022 transfer fee
+        assert_eq!(fee_ledger.accrued, asset.received(TOTAL_FEE), "{asset:?}");
+        assert_eq!(fee_ledger.withdrawn, 0);
+
+        // The shares must sum to 100%
+        let anyone = test.pmm.insecure_clone();
+        let result = test
+            .process(&[instructions::distribute_fees(&anyone.pubkey(), &receivers[..1], token.as_ref())], &[&anyone])
+            .await;
+        assert_custom_error(result, CustomError::InvalidFeeShare);
+
+        test.process(
+            &[
+                instructions::add_fee_receiver(&admin.pubkey(), receivers[1], false),
+                instructions::set_fee_receiver_share(&admin.pubkey(), receivers[0], 7_500),
+            ],
+            &[&admin],
+        )
+        .await
+        .unwrap();
+        let result = test
+            .process(&[instructions::set_fee_receiver_share(&admin.pubkey(), receivers[1], 2_501)], &[&admin])
+            .await;
+        assert_custom_error(result, CustomError::InvalidFeeShare);
+        test.process(&[instructions::set_fee_receiver_share(&admin.pubkey(), receivers[1], 2_500)], &[&admin])
+            .await
+            .unwrap();
+        let config: Config = test.data(&pda::get_config_pda()).await;
+        assert_eq!(config.total_fee_share_bps, 10_000);
+
+        // Every fee receiver with a share must be paid
+        let result = test
+            .process(&[instructions::distribute_fees(&anyone.pubkey(), &receivers[..1], token.as_ref())], &[&anyone])
+            .await;
+        assert_custom_error(result, CustomError::InvalidFeeShare);
+        let result = test
+            .process(
+                &[instructions::distribute_fees(&anyone.pubkey(), &[receivers[0], receivers[0]], token.as_ref())],
+                &[&anyone],
+            )
+            .await;
+        assert_custom_error(result, CustomError::InvalidFeeShare);
+
+        let mut balances = vec![];
+        for receiver in &receivers {
+            balances.push(test.balance(token.as_ref(), receiver).await);
+        }
+        test.process(&[instructions::distribute_fees(&anyone.pubkey(), &receivers, token.as_ref())], &[&anyone])
+            .await
+            .unwrap();
+
+        // The distributed fee is what the protocol received
+        let distributable = asset.received(TOTAL_FEE);
+        let shares = [distributable * 3 / 4, distributable / 4];
+        for ((receiver, balance), share) in receivers.iter().zip(balances).zip(shares) {
+            assert_eq!(
+                test.balance(token.as_ref(), receiver).await,
+                balance + asset.received(share),
+                "{asset:?}"
+            );
+        }
+        let return_data = test.simulate(instructions::get_fee_ledger(&mint)).await;
+        assert_eq!(
+            decode::decode_fee_ledger_balance(&return_data).unwrap(),
+            FeeLedgerBalance {
+                token: mint,
+                accrued: distributable,
+                withdrawn: shares[0] + shares[1],
+                available: distributable - shares[0] - shares[1],
+            }
+        );
+
+        // Removing a fee receiver removes its share
+        test.process(&[instructions::remove_fee_receiver(&admin.pubkey(), receivers[1], false)], &[&admin])
+            .await
+            .unwrap();
+        let config: Config = test.data(&pda::get_config_pda()).await;
+        assert_eq!(config.total_fee_share_bps, 7_500);
+        let result = test
+            .process(&[instructions::distribute_fees(&anyone.pubkey(), &receivers[..1], token.as_ref())], &[&anyone])
+            .await;
+        assert_custom_error(result, CustomError::InvalidFeeShare);
+    }
+}
+
+#[tokio::test]
+async fn test_strict_payment() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let pmm = test.pmm.insecure_clone();
+        let mpc = test.mpc.insecure_clone();
+        let to_user = test.user.pubkey();
+        let token_program = token.map_or(anchor_spl::token::ID, |token| token.token_program);
+        let trade_id = [7; 32];
+        let deadline = test.now().await + 60;
+        let expectation_args = CreatePaymentExpectationArgs {
+            trade_id,
+            to_pubkey: to_user,
+            token: token.map(|token| token.mint),
+            min_amount: asset.received(AMOUNT - TOTAL_FEE),
+            max_fee: TOTAL_FEE,
+            deadline,
+        };
+
+        // Only a registered MPC key can publish an expectation
+        let result = test
+            .process(
+                &[instructions::create_payment_expectation(&pmm.pubkey(), expectation_args.clone(), None)],
+                &[&pmm],
+            )
+            .await;
+        assert_custom_error(result, CustomError::MpcNotRegistered);
+
+        test.process(
+            &[instructions::create_payment_expectation(&mpc.pubkey(), expectation_args, None)],
+            &[&mpc],
+        )
+        .await
+        .unwrap();
+        let payment_expectation = pda::get_payment_expectation_pda(&trade_id, &mpc.pubkey());
+
+        // The expectation cannot be closed before it is fulfilled or expired
+        let close = instructions::close_payment_expectation(&mpc.pubkey(), &trade_id, None);
+        assert!(test.process(&[close.clone()], &[&mpc]).await.is_err());
+
+        // The payment must not fall short of the expectation,
+        // the amount received net of the Token-2022 transfer fee is checked
+        let payment_args = PaymentArgs {
+            trade_id,
+            token: token.map(|token| token.mint),
+            amount: AMOUNT - 1,
+            total_fee: TOTAL_FEE,
+            deadline,
+        };
+        let result = test
+            .process(
+                &[instructions::strict_payment(
+                    &pmm.pubkey(),
+                    &to_user,
+                    &mpc.pubkey(),
+                    payment_args.clone(),
+                    &token_program,
+                )],
+                &[&pmm],
+            )
+            .await;
+        assert_custom_error(result, CustomError::InvalidAmount);
+
+        let strict_payment = instructions::strict_payment(
+            &pmm.pubkey(),
+            &to_user,
+            &mpc.pubkey(),
+            PaymentArgs {
+                amount: AMOUNT,
+                ..payment_args
+            },
+            &token_program,
+        );
+        test.process(&[strict_payment.clone()], &[&pmm]).await.unwrap();
+        let expectation: PaymentExpectation = test.data(&payment_expectation).await;
+        assert!(expectation.fulfilled);
+
+        // The expectation is fulfilled only once
+        assert!(test.process(&[strict_payment], &[&pmm]).await.is_err());
+
+        test.process(&[close], &[&mpc]).await.unwrap();
+        assert!(test.account(&payment_expectation).await.is_none());
+    }
+}
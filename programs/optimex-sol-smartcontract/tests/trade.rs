@@ -0,0 +1,1048 @@
+mod common;
+
+use anchor_spl::token::spl_token;
+use common::*;
+use optimex_sol_sdk::{
+    instructions, pda, Config, CreatePaymentExpectationArgs, DepositIntent, FeeLedger, MpcGroup, PaymentExpectation, Role, SettlementLeg, TradeDetailInput, TradeIdScheme, TradeStatus, WhitelistToken,
+    WSOL_MINT,
+};
+use optimex_sol_smartcontract::CustomError;
+use solana_sdk::{keccak, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const AMOUNT: u64 = 1_000_000;
+const TOTAL_FEE: u64 = 10_000;
+const DURATION: i64 = 3_600;
+
+#[tokio::test]
+async fn test_deposit() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let user = test.user.pubkey();
+        let user_balance = test.balance(token.as_ref(), &user).await;
+
+        let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let vault = pda::get_trade_vault_pda(&trade.trade_id);
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.trade_id, trade.trade_id);
+        assert_eq!(trade_detail.user_pubkey, user);
+        assert_eq!(trade_detail.token, token.map(|token| token.mint));
+        assert_eq!(trade_detail.amount, asset.received(AMOUNT), "{asset:?}");
+        assert_eq!(trade_detail.timeout, trade.timeout);
+        assert_eq!(trade_detail.mpc_pubkey, test.mpc.pubkey());
+        assert_eq!(trade_detail.user_ephemeral_pubkey, trade.ephemeral.pubkey());
+        assert_eq!(trade_detail.status, TradeStatus::Deposited);
+        if token.is_some() {
+            assert_eq!(test.balance(token.as_ref(), &vault).await, asset.received(AMOUNT));
+            assert_eq!(test.balance(token.as_ref(), &user).await, user_balance - AMOUNT);
+        } else {
+            assert!(test.lamports(&vault).await >= AMOUNT);
+        }
+        let mint = token.map_or(WSOL_MINT, |token| token.mint);
+        let whitelist_token: WhitelistToken = test.data(&pda::get_whitelist_pda(&mint)).await;
+        assert_eq!(whitelist_token.outstanding, asset.received(AMOUNT));
+
+        // The ephemeral account cannot be reused while the trade is not finished
+        let user = test.user.insecure_clone();
+        let ix = instructions::deposit(
+            &user.pubkey(),
+            &trade.ephemeral.pubkey(),
+            test.trade_input(token.as_ref(), AMOUNT),
+            TradeIdScheme::Sha256Abi,
+            TradeDetailInput {
+                timeout: trade.timeout,
+                mpc_pubkey: test.mpc.pubkey(),
+                refund_pubkey: user.pubkey(),
+            },
+            token.as_ref(),
+        ).unwrap();
+        let result = test.process(&[ix], &[&user, &trade.ephemeral]).await;
+        assert!(result.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_deposit_trade_id_scheme() {
+    let mut test = TestContext::initialized().await;
+    test.setup_asset(Asset::Sol).await;
+    let user = test.user.insecure_clone();
+    for scheme in [TradeIdScheme::Sha256Abi, TradeIdScheme::Keccak256Abi, TradeIdScheme::Sha256Borsh] {
+        let input = test.trade_input(None, AMOUNT);
+        let trade_id = input.calculate_trade_id_with_scheme(scheme).unwrap();
+        let ephemeral = Keypair::new();
+        let mut ix = instructions::deposit(
+            &user.pubkey(),
+            &ephemeral.pubkey(),
+            input,
+            scheme,
+            TradeDetailInput {
+                timeout: test.now().await + DURATION,
+                mpc_pubkey: test.trade_mpc,
+                refund_pubkey: user.pubkey(),
+            },
+            None,
+        ).unwrap();
+
+        // The trade id must be derived with the scheme of the deposit, the last byte of the instruction data
+        let scheme_byte = ix.data.last_mut().unwrap();
+        *scheme_byte = (*scheme_byte + 1) % 3;
+        let result = test.process(&[ix.clone()], &[&user, &ephemeral]).await;
+        assert_custom_error(result, CustomError::InvalidTradeId);
+
+        *ix.data.last_mut().unwrap() = scheme as u8;
+        test.process(&[ix], &[&user, &ephemeral]).await.unwrap();
+        let trade_detail = test.trade_detail(&trade_id).await;
+        assert_eq!(trade_detail.trade_id, trade_id);
+        assert_eq!(trade_detail.trade_id_scheme, scheme);
+    }
+}
+
+#[tokio::test]
+async fn test_deposit_expired_timeout() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Sol).await;
+    let (ix, trade) = test.deposit_instruction(token.as_ref(), AMOUNT, -1).await;
+    let user = test.user.insecure_clone();
+    let result = test.process(&[ix], &[&user, &trade.ephemeral]).await;
+    assert_custom_error(result, CustomError::InvalidTimeout);
+}
+
+#[tokio::test]
+async fn test_set_total_fee_and_settlement() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let mpc = test.mpc.insecure_clone();
+        let pmm = test.pmm.pubkey();
+        let protocol = pda::get_protocol_pda();
+
+        // Only the MPC of the trade sets the total fee
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        let user = test.user.insecure_clone();
+        let mut ix = instructions::set_total_fee(&trade_detail, TOTAL_FEE, None).unwrap();
+        ix.accounts[0].pubkey = user.pubkey();
+        let result = test.process(&[ix], &[&user]).await;
+        assert_custom_error(result, CustomError::Unauthorized);
+
+        test.process(&[instructions::set_total_fee(&trade_detail, TOTAL_FEE, None).unwrap()], &[&mpc])
+            .await
+            .unwrap();
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.total_fee, Some(TOTAL_FEE));
+
+        let pmm_balance = test.balance(token.as_ref(), &pmm).await;
+        let protocol_balance = test.balance(token.as_ref(), &protocol).await;
+        test.process(
+            &[instructions::settlement(&trade_detail, &pmm, token.as_ref(), None).unwrap()],
+            &[&mpc, &trade.ephemeral],
+        )
+        .await
+        .unwrap();
+
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.status, TradeStatus::Settled);
+        assert_eq!(trade_detail.settled_pmm, pmm);
+        let settled_amount = trade_detail.amount - TOTAL_FEE;
+        assert_eq!(
+            test.balance(token.as_ref(), &pmm).await,
+            pmm_balance + asset.received(settled_amount),
+            "{asset:?}"
+        );
+        assert_eq!(
+            test.balance(token.as_ref(), &protocol).await,
+            protocol_balance + asset.received(TOTAL_FEE),
+            "{asset:?}"
+        );
+        // The nonce check account is closed, so the ephemeral account can be used again
+        assert!(test
+            .account(&pda::get_nonce_check_pda(&trade.ephemeral.pubkey()))
+            .await
+            .is_none());
+        let mint = token.map_or(WSOL_MINT, |token| token.mint);
+        let whitelist_token: WhitelistToken = test.data(&pda::get_whitelist_pda(&mint)).await;
+        assert_eq!(whitelist_token.outstanding, 0);
+        let fee_ledger: FeeLedger = test.data(&pda::get_fee_ledger_pda(&mint)).await;
+        assert_eq!(fee_ledger.accrued, asset.received(TOTAL_FEE), "{asset:?}");
+
+        // The MPC closes the settled trade right away
+        let user = test.user.pubkey();
+        let user_lamports = test.lamports(&user).await;
+        test.process(
+            &[instructions::close_finished_trade(&mpc.pubkey(), &trade_detail, token.as_ref(), None).unwrap()],
+            &[&mpc],
+        )
+        .await
+        .unwrap();
+        let vault = pda::get_trade_vault_pda(&trade.trade_id);
+        assert!(test
+            .account(&pda::get_user_trade_detail_pda(&trade.trade_id))
+            .await
+            .is_none());
+        assert!(test.account(&vault).await.is_none());
+        if let Some(token) = &token {
+            assert!(test.account(&token.ata(&vault)).await.is_none());
+        }
+        assert!(test.lamports(&user).await > user_lamports);
+    }
+}
+
+#[tokio::test]
+async fn test_batch_settlement() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let mpc = test.mpc.insecure_clone();
+        let pmm = test.pmm.pubkey();
+        let with_fee = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let without_fee = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let trade_detail = test.trade_detail(&with_fee.trade_id).await;
+        test.process(&[instructions::set_total_fee(&trade_detail, TOTAL_FEE, None).unwrap()], &[&mpc])
+            .await
+            .unwrap();
+        let trade_details = vec![
+            test.trade_detail(&with_fee.trade_id).await,
+            test.trade_detail(&without_fee.trade_id).await,
+        ];
+
+        // A trade cannot be settled twice in the same batch
+        let duplicated = vec![trade_details[0].clone(), trade_details[0].clone()];
+        let result = test
+            .process(
+                &[instructions::batch_settlement(&duplicated, &pmm, token.as_ref(), None).unwrap()],
+                &[&mpc, &with_fee.ephemeral],
+            )
+            .await;
+        assert_custom_error(result, CustomError::InvalidTradeStatus);
+
+        let pmm_balance = test.balance(token.as_ref(), &pmm).await;
+        test.process(
+            &[instructions::batch_settlement(&trade_details, &pmm, token.as_ref(), None).unwrap()],
+            &[&mpc, &with_fee.ephemeral, &without_fee.ephemeral],
+        )
+        .await
+        .unwrap();
+
+        for trade in [&with_fee, &without_fee] {
+            let trade_detail = test.trade_detail(&trade.trade_id).await;
+            assert_eq!(trade_detail.status, TradeStatus::Settled);
+            assert_eq!(trade_detail.settled_pmm, pmm);
+            assert!(test
+                .account(&pda::get_nonce_check_pda(&trade.ephemeral.pubkey()))
+                .await
+                .is_none());
+        }
+        let settled_amount = trade_details[0].amount - TOTAL_FEE + trade_details[1].amount;
+        match asset {
+            Asset::Token2022 => assert!(test.balance(token.as_ref(), &pmm).await > pmm_balance),
+            _ => assert_eq!(test.balance(token.as_ref(), &pmm).await, pmm_balance + settled_amount, "{asset:?}"),
+        }
+        let mint = token.map_or(WSOL_MINT, |token| token.mint);
+        let whitelist_token: WhitelistToken = test.data(&pda::get_whitelist_pda(&mint)).await;
+        assert_eq!(whitelist_token.outstanding, 0);
+        let fee_ledger: FeeLedger = test.data(&pda::get_fee_ledger_pda(&mint)).await;
+        assert_eq!(fee_ledger.accrued, asset.received(TOTAL_FEE), "{asset:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_settlement_after_timeout() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Spl).await;
+    let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+    let trade_detail = test.trade_detail(&trade.trade_id).await;
+    let mpc = test.mpc.insecure_clone();
+    let pmm = test.pmm.pubkey();
+
+    test.warp_to_timestamp(trade.timeout + 1).await;
+    let result = test
+        .process(
+            &[instructions::settlement(&trade_detail, &pmm, token.as_ref(), None).unwrap()],
+            &[&mpc, &trade.ephemeral],
+        )
+        .await;
+    assert_custom_error(result, CustomError::TimeOut);
+}
+
+#[tokio::test]
+async fn test_sponsored_deposit() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Spl).await;
+    let mint = token.clone().unwrap();
+    let relayer = Keypair::new();
+    test.fund(&relayer.pubkey());
+    let user = test.user.insecure_clone();
+    let ephemeral = Keypair::new();
+    let input = test.trade_input(token.as_ref(), AMOUNT);
+    let trade_id = input.calculate_trade_id();
+    let data = TradeDetailInput {
+        timeout: test.now().await + DURATION,
+        mpc_pubkey: test.trade_mpc,
+        refund_pubkey: user.pubkey(),
+    };
+    test.create_ata(&mint, &pda::get_trade_vault_pda(&trade_id)).await;
+
+    // The relayer pays the rent, the user only transfers the tokens
+    let user_balance = test.balance(token.as_ref(), &user.pubkey()).await;
+    let user_lamports = test.lamports(&user.pubkey()).await;
+    let relayer_lamports = test.lamports(&relayer.pubkey()).await;
+    let ix = instructions::sponsored_deposit(
+        &relayer.pubkey(),
+        &user.pubkey(),
+        &ephemeral.pubkey(),
+        input,
+        TradeIdScheme::Sha256Abi,
+        data.clone(),
+        token.as_ref(),
+    ).unwrap();
+    test.process(&[ix], &[&relayer, &user, &ephemeral]).await.unwrap();
+    let trade_detail = test.trade_detail(&trade_id).await;
+    assert_eq!(trade_detail.user_pubkey, user.pubkey());
+    assert_eq!(trade_detail.payer_pubkey, relayer.pubkey());
+    assert_eq!(trade_detail.rent_payer(), relayer.pubkey());
+    assert_eq!(test.lamports(&user.pubkey()).await, user_lamports);
+    assert!(test.lamports(&relayer.pubkey()).await < relayer_lamports);
+
+    // The rents go back to the relayer, not the user
+    test.warp_to_timestamp(data.timeout + 1).await;
+    let mut claim = instructions::claim(&relayer.pubkey(), &trade_detail, token.as_ref()).unwrap();
+    claim.accounts[1].pubkey = user.pubkey();
+    let result = test.process(&[claim], &[&relayer]).await;
+    assert_custom_error(result, CustomError::InvalidUserAccount);
+    test.process(
+        &[instructions::claim(&relayer.pubkey(), &trade_detail, token.as_ref()).unwrap()],
+        &[&relayer],
+    )
+    .await
+    .unwrap();
+    // The tokens sent to the vault after the claim belong to the user
+    let leftover = 1_000;
+    test.mint_to(&mint, &pda::get_trade_vault_pda(&trade_id), leftover).await;
+    test.process(
+        &[instructions::close_finished_trade(&relayer.pubkey(), &trade_detail, token.as_ref(), None).unwrap()],
+        &[&relayer],
+    )
+    .await
+    .unwrap();
+    assert_eq!(test.balance(token.as_ref(), &user.pubkey()).await, user_balance + leftover);
+    assert_eq!(test.lamports(&user.pubkey()).await, user_lamports);
+    // The relayer also gets the rent of the vault token account, created by the test payer
+    assert!(test.lamports(&relayer.pubkey()).await > relayer_lamports);
+}
+
+#[tokio::test]
+async fn test_intent_deposit() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Spl).await.unwrap();
+    let relayer = Keypair::new();
+    test.fund(&relayer.pubkey());
+    let user = test.user.insecure_clone();
+    let ephemeral = Keypair::new();
+    let input = test.trade_input(Some(&token), AMOUNT);
+    let trade_id = input.calculate_trade_id();
+    let data = TradeDetailInput {
+        timeout: test.now().await + DURATION,
+        mpc_pubkey: test.trade_mpc,
+        refund_pubkey: user.pubkey(),
+    };
+    test.create_ata(&token, &pda::get_trade_vault_pda(&trade_id)).await;
+
+    // The user approves the delegate PDA once, then only signs the deposit intents off-chain
+    let approve = spl_token::instruction::approve(
+        &spl_token::ID,
+        &token.ata(&user.pubkey()),
+        &pda::get_intent_delegate_pda(),
+        &user.pubkey(),
+        &[],
+        AMOUNT,
+    )
+    .unwrap();
+    test.process(&[approve], &[&user]).await.unwrap();
+    let intent = DepositIntent {
+        trade_id,
+        amount: AMOUNT,
+        mint: token.mint,
+        timeout: data.timeout,
+        mpc_pubkey: data.mpc_pubkey,
+        refund_pubkey: data.refund_pubkey,
+        user_ephemeral_pubkey: ephemeral.pubkey(),
+    };
+    let signature: [u8; 64] = user.sign_message(&intent.message().unwrap()).into();
+    let deposit = instructions::intent_deposit(
+        &relayer.pubkey(),
+        &user.pubkey(),
+        &ephemeral.pubkey(),
+        input,
+        TradeIdScheme::Sha256Abi,
+        data.clone(),
+        &token,
+    ).unwrap();
+
+    // The signature must be verified by the previous instruction, over the exact intent
+    let result = test.process(&[deposit.clone()], &[&relayer]).await;
+    assert_custom_error(result, CustomError::InvalidSignature);
+    let tampered = DepositIntent {
+        refund_pubkey: relayer.pubkey(),
+        ..intent.clone()
+    };
+    let tampered_signature: [u8; 64] = user.sign_message(&tampered.message().unwrap()).into();
+    let result = test
+        .process(
+            &[
+                instructions::ed25519_verify(&user.pubkey(), &tampered_signature, &tampered.message().unwrap()),
+                deposit.clone(),
+            ],
+            &[&relayer],
+        )
+        .await;
+    assert_custom_error(result, CustomError::InvalidSignature);
+    let forged: [u8; 64] = relayer.sign_message(&intent.message().unwrap()).into();
+    let result = test
+        .process(
+            &[
+                instructions::ed25519_verify(&relayer.pubkey(), &forged, &intent.message().unwrap()),
+                deposit.clone(),
+            ],
+            &[&relayer],
+        )
+        .await;
+    assert_custom_error(result, CustomError::InvalidSignature);
+
+    // The relayer submits and pays, the delegate PDA pulls the tokens of the user
+    let user_balance = test.balance(Some(&token), &user.pubkey()).await;
+    let user_lamports = test.lamports(&user.pubkey()).await;
+    test.process(
+        &[
+            instructions::ed25519_verify(&user.pubkey(), &signature, &intent.message().unwrap()),
+            deposit.clone(),
+        ],
+        &[&relayer],
+    )
+    .await
+    .unwrap();
+    let trade_detail = test.trade_detail(&trade_id).await;
+    assert_eq!(trade_detail.user_pubkey, user.pubkey());
+    assert_eq!(trade_detail.user_ephemeral_pubkey, ephemeral.pubkey());
+    assert_eq!(trade_detail.rent_payer(), relayer.pubkey());
+    assert_eq!(trade_detail.amount, AMOUNT);
+    assert_eq!(test.balance(Some(&token), &user.pubkey()).await, user_balance - AMOUNT);
+    assert_eq!(test.lamports(&user.pubkey()).await, user_lamports);
+
+    // The intent cannot be replayed
+    test.context.get_new_latest_blockhash().await.unwrap();
+    let result = test
+        .process(
+            &[instructions::ed25519_verify(&user.pubkey(), &signature, &intent.message().unwrap()), deposit],
+            &[&relayer],
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_solver_signature() {
+    let mut test = TestContext::initialized().await;
+    test.setup_asset(Asset::Sol).await;
+    let user = test.user.insecure_clone();
+    let admin = test.admin.insecure_clone();
+    let operator = test.operator.insecure_clone();
+    let solver = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+    let forger = libsecp256k1::SecretKey::parse(&[8; 32]).unwrap();
+    let eth_address = |secret_key: &libsecp256k1::SecretKey| -> [u8; 20] {
+        let public_key = libsecp256k1::PublicKey::from_secret_key(secret_key).serialize();
+        keccak::hash(&public_key[1..]).to_bytes()[12..].try_into().unwrap()
+    };
+    let sign = |secret_key: &libsecp256k1::SecretKey, trade_id: &[u8; 32]| -> ([u8; 64], u8) {
+        let message = libsecp256k1::Message::parse(&keccak::hash(trade_id).to_bytes());
+        let (signature, recovery_id) = libsecp256k1::sign(&message, secret_key);
+        (signature.serialize(), recovery_id.serialize())
+    };
+
+    // Only the admin sets the solver signature requirement
+    let result = test
+        .process(&[instructions::set_solver_signature_required(&operator.pubkey(), true)], &[&operator])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+    test.process(&[instructions::set_solver_signature_required(&admin.pubkey(), true)], &[&admin])
+        .await
+        .unwrap();
+    let config: Config = test.data(&pda::get_config_pda()).await;
+    assert!(config.solver_signature_required);
+
+    let mut input = test.trade_input(None, AMOUNT);
+    input.solver = eth_address(&solver);
+    let trade_id = input.calculate_trade_id();
+    let ephemeral = Keypair::new();
+    let deposit = instructions::deposit(
+        &user.pubkey(),
+        &ephemeral.pubkey(),
+        input,
+        TradeIdScheme::Sha256Abi,
+        TradeDetailInput {
+            timeout: test.now().await + DURATION,
+            mpc_pubkey: test.trade_mpc,
+            refund_pubkey: user.pubkey(),
+        },
+        None,
+    ).unwrap();
+
+    // A trade with a forged solver is rejected, without signature or signed by another key
+    let result = test.process(&[deposit.clone()], &[&user, &ephemeral]).await;
+    assert_custom_error(result, CustomError::InvalidSolverSignature);
+    let (signature, recovery_id) = sign(&forger, &trade_id);
+    let forged = instructions::secp256k1_verify(&eth_address(&forger), &signature, recovery_id, &trade_id, 0);
+    let result = test.process(&[forged, deposit.clone()], &[&user, &ephemeral]).await;
+    assert_custom_error(result, CustomError::InvalidSolverSignature);
+    let forged = instructions::secp256k1_verify(&eth_address(&solver), &signature, recovery_id, &trade_id, 0);
+    let result = test.process(&[forged, deposit.clone()], &[&user, &ephemeral]).await;
+    assert!(result.is_err());
+
+    let (signature, recovery_id) = sign(&solver, &trade_id);
+    let verify = instructions::secp256k1_verify(&eth_address(&solver), &signature, recovery_id, &trade_id, 0);
+    test.process(&[verify, deposit], &[&user, &ephemeral]).await.unwrap();
+    assert_eq!(test.trade_detail(&trade_id).await.amount, AMOUNT);
+
+    // Not required anymore
+    test.process(&[instructions::set_solver_signature_required(&admin.pubkey(), false)], &[&admin])
+        .await
+        .unwrap();
+    test.deposit(None, AMOUNT, DURATION).await;
+}
+
+#[tokio::test]
+async fn test_claim() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        let anyone = test.pmm.insecure_clone();
+        let user = test.user.pubkey();
+
+        // Not claimable before the timeout
+        let result = test
+            .process(
+                &[instructions::claim(&anyone.pubkey(), &trade_detail, token.as_ref()).unwrap()],
+                &[&anyone],
+            )
+            .await;
+        assert_custom_error(result, CustomError::ClaimNotAvailable);
+
+        test.warp_to_timestamp(trade.timeout + 1).await;
+        let user_balance = test.balance(token.as_ref(), &user).await;
+        test.process(
+            &[instructions::claim(&anyone.pubkey(), &trade_detail, token.as_ref()).unwrap()],
+            &[&anyone],
+        )
+        .await
+        .unwrap();
+        let claimed = test.balance(token.as_ref(), &user).await - user_balance;
+        match asset {
+            // The user also gets the rent of the nonce check account back
+            Asset::Sol => assert!(claimed > trade_detail.amount),
+            _ => assert_eq!(claimed, asset.received(trade_detail.amount), "{asset:?}"),
+        }
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.status, TradeStatus::Claimed);
+
+        // A claimed trade can be closed by anyone
+        test.process(
+            &[instructions::close_finished_trade(&anyone.pubkey(), &trade_detail, token.as_ref(), None).unwrap()],
+            &[&anyone],
+        )
+        .await
+        .unwrap();
+        assert!(test
+            .account(&pda::get_user_trade_detail_pda(&trade.trade_id))
+            .await
+            .is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_close_settled_trade_by_anyone() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Sol).await;
+    let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+    let trade_detail = test.trade_detail(&trade.trade_id).await;
+    let mpc = test.mpc.insecure_clone();
+    let pmm = test.pmm.insecure_clone();
+    test.process(
+        &[instructions::settlement(&trade_detail, &pmm.pubkey(), None, None).unwrap()],
+        &[&mpc, &trade.ephemeral],
+    )
+    .await
+    .unwrap();
+
+    // Anyone else waits for the timeout and the close trade duration
+    let trade_detail = test.trade_detail(&trade.trade_id).await;
+    let ix = instructions::close_finished_trade(&pmm.pubkey(), &trade_detail, None, None).unwrap();
+    let result = test.process(&[ix.clone()], &[&pmm]).await;
+    assert_custom_error(result, CustomError::CloseNotAvailable);
+
+    test.warp_to_timestamp(trade.timeout + 1).await;
+    test.process(&[ix], &[&pmm]).await.unwrap();
+    assert!(test
+        .account(&pda::get_user_trade_detail_pda(&trade.trade_id))
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_batch_close_finished_trades() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let settled = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let deposited = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let mpc = test.mpc.insecure_clone();
+        let pmm = test.pmm.insecure_clone();
+        let trade_detail = test.trade_detail(&settled.trade_id).await;
+        test.process(
+            &[instructions::settlement(&trade_detail, &pmm.pubkey(), token.as_ref(), None).unwrap()],
+            &[&mpc, &settled.ephemeral],
+        )
+        .await
+        .unwrap();
+
+        let trades = vec![
+            (test.trade_detail(&settled.trade_id).await, token),
+            (test.trade_detail(&deposited.trade_id).await, token),
+        ];
+        let settled_pda = pda::get_user_trade_detail_pda(&settled.trade_id);
+        let deposited_pda = pda::get_user_trade_detail_pda(&deposited.trade_id);
+
+        // Anyone else waits for the timeout, both trades are skipped
+        test.process(&[instructions::batch_close_finished_trades(&pmm.pubkey(), &trades, None).unwrap()], &[&pmm])
+            .await
+            .unwrap();
+        assert!(test.account(&settled_pda).await.is_some());
+        assert!(test.account(&deposited_pda).await.is_some());
+
+        // The token program gets the signature of the vault, it must be a token program
+        if token.is_some() {
+            let mut ix = instructions::batch_close_finished_trades(&mpc.pubkey(), &trades, None).unwrap();
+            ix.accounts[6].pubkey = Pubkey::new_unique();
+            let result = test.process(&[ix], &[&mpc]).await;
+            assert_custom_error(result, CustomError::InvalidTokenProgram);
+        }
+
+        // The MPC closes the settled trade right away, the deposited trade is skipped
+        let user = test.user.pubkey();
+        let user_lamports = test.lamports(&user).await;
+        test.process(&[instructions::batch_close_finished_trades(&mpc.pubkey(), &trades, None).unwrap()], &[&mpc])
+            .await
+            .unwrap();
+        let vault = pda::get_trade_vault_pda(&settled.trade_id);
+        assert!(test.account(&settled_pda).await.is_none());
+        assert!(test.account(&vault).await.is_none());
+        if let Some(token) = &token {
+            assert!(test.account(&token.ata(&vault)).await.is_none());
+        }
+        assert!(test.lamports(&user).await > user_lamports);
+        assert!(test.account(&deposited_pda).await.is_some());
+        assert!(test
+            .account(&pda::get_trade_vault_pda(&deposited.trade_id))
+            .await
+            .is_some());
+
+        // The already closed trade is skipped too
+        test.warp_to_timestamp(deposited.timeout + 1).await;
+        let trade_detail = test.trade_detail(&deposited.trade_id).await;
+        test.process(
+            &[instructions::claim(&pmm.pubkey(), &trade_detail, token.as_ref()).unwrap()],
+            &[&pmm],
+        )
+        .await
+        .unwrap();
+        let trades = vec![trades[0].clone(), (test.trade_detail(&deposited.trade_id).await, token)];
+        test.process(&[instructions::batch_close_finished_trades(&pmm.pubkey(), &trades, None).unwrap()], &[&pmm])
+            .await
+            .unwrap();
+        assert!(test.account(&deposited_pda).await.is_none());
+
+        // The remaining accounts must be groups of accounts
+        let mut ix = instructions::batch_close_finished_trades(&pmm.pubkey(), &trades, None).unwrap();
+        ix.accounts.pop();
+        let result = test.process(&[ix], &[&pmm]).await;
+        assert_custom_error(result, CustomError::InvalidBatchAccounts);
+    }
+}
+
+#[tokio::test]
+async fn test_split_settlement() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let mpc = test.mpc.insecure_clone();
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        test.process(&[instructions::set_total_fee(&trade_detail, TOTAL_FEE, None).unwrap()], &[&mpc])
+            .await
+            .unwrap();
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        let settlement_amount = trade_detail.amount - TOTAL_FEE;
+        let pmms = [test.pmm.pubkey(), test.fee_receiver.pubkey()];
+        let legs = vec![
+            SettlementLeg {
+                pmm: pmms[0],
+                amount: settlement_amount / 2,
+            },
+            SettlementLeg {
+                pmm: pmms[1],
+                amount: settlement_amount - settlement_amount / 2,
+            },
+        ];
+
+        // The legs must cover the settlement amount exactly
+        let mut invalid_legs = legs.clone();
+        invalid_legs[1].amount -= 1;
+        let result = test
+            .process(
+                &[instructions::split_settlement(&trade_detail, invalid_legs, token.as_ref(), None).unwrap()],
+                &[&mpc, &trade.ephemeral],
+            )
+            .await;
+        assert_custom_error(result, CustomError::InvalidSettlementLegs);
+
+        let mut balances = vec![];
+        for pmm in &pmms {
+            balances.push(test.balance(token.as_ref(), pmm).await);
+        }
+        test.process(
+            &[instructions::split_settlement(&trade_detail, legs.clone(), token.as_ref(), None).unwrap()],
+            &[&mpc, &trade.ephemeral],
+        )
+        .await
+        .unwrap();
+
+        for (leg, balance) in legs.iter().zip(balances) {
+            assert_eq!(
+                test.balance(token.as_ref(), &leg.pmm).await,
+                balance + asset.received(leg.amount),
+                "{asset:?}"
+            );
+        }
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.status, TradeStatus::Settled);
+        assert_eq!(trade_detail.settled_pmm, pmms[0]);
+        let mint = token.map_or(WSOL_MINT, |token| token.mint);
+        let fee_ledger: FeeLedger = test.data(&pda::get_fee_ledger_pda(&mint)).await;
+        assert_eq!(fee_ledger.accrued, asset.received(TOTAL_FEE), "{asset:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_cooperative_refund() {
+    for asset in Asset::ALL {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        let mpc = test.mpc.insecure_clone();
+        let user = test.user.pubkey();
+        let pmm = test.pmm.pubkey();
+
+        // The refund needs the agreement of the user through the ephemeral account
+        let mut ix = instructions::cooperative_refund(&trade_detail, token.as_ref(), None).unwrap();
+        ix.accounts[2].is_signer = false;
+        let result = test.process(&[ix], &[&mpc]).await;
+        assert!(result.is_err());
+
+        let user_balance = test.balance(token.as_ref(), &user).await;
+        test.process(
+            &[instructions::cooperative_refund(&trade_detail, token.as_ref(), None).unwrap()],
+            &[&mpc, &trade.ephemeral],
+        )
+        .await
+        .unwrap();
+        let refunded = test.balance(token.as_ref(), &user).await - user_balance;
+        match asset {
+            // The user also gets the rent of the nonce check account back
+            Asset::Sol => assert!(refunded > trade_detail.amount),
+            _ => assert_eq!(refunded, asset.received(trade_detail.amount), "{asset:?}"),
+        }
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.status, TradeStatus::Refunded);
+
+        // A refunded trade can be settled no more
+        let result = test
+            .process(
+                &[instructions::settlement(&trade_detail, &pmm, token.as_ref(), None).unwrap()],
+                &[&mpc, &trade.ephemeral],
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_extend_timeout() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Sol).await;
+    let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+    let trade_detail = test.trade_detail(&trade.trade_id).await;
+    let mpc = test.mpc.insecure_clone();
+    let operator = test.operator.insecure_clone();
+    let new_timeout = trade.timeout + DURATION;
+
+    // The timeouts cannot be extended until a duration manager sets the maximum extension
+    let ix = instructions::extend_timeout(&trade_detail, new_timeout, None).unwrap();
+    let result = test.process(&[ix.clone()], &[&mpc, &trade.ephemeral]).await;
+    assert_custom_error(result, CustomError::TimeoutExtensionExceeded);
+    test.process(
+        &[instructions::set_max_timeout_extension(&operator.pubkey(), DURATION as u64)],
+        &[&operator],
+    )
+    .await
+    .unwrap();
+
+    // The extension needs the agreement of the user through the ephemeral account
+    let mut unsigned = ix.clone();
+    unsigned.accounts[1].is_signer = false;
+    let result = test.process(&[unsigned], &[&mpc]).await;
+    assert!(result.is_err());
+
+    test.process(&[ix], &[&mpc, &trade.ephemeral]).await.unwrap();
+    let trade_detail = test.trade_detail(&trade.trade_id).await;
+    assert_eq!(trade_detail.timeout, new_timeout);
+    assert_eq!(trade_detail.timeout_extension, DURATION as u64);
+    let result = test
+        .process(
+            &[instructions::extend_timeout(&trade_detail, new_timeout + 1, None).unwrap()],
+            &[&mpc, &trade.ephemeral],
+        )
+        .await;
+    assert_custom_error(result, CustomError::TimeoutExtensionExceeded);
+
+    // The trade cannot be claimed before the new timeout, nor extended after it
+    test.warp_to_timestamp(trade.timeout + 1).await;
+    let user = test.user.insecure_clone();
+    let result = test
+        .process(&[instructions::claim(&user.pubkey(), &trade_detail, token.as_ref()).unwrap()], &[&user])
+        .await;
+    assert_custom_error(result, CustomError::ClaimNotAvailable);
+    test.warp_to_timestamp(new_timeout + 1).await;
+    let result = test
+        .process(
+            &[instructions::extend_timeout(&trade_detail, new_timeout + 1, None).unwrap()],
+            &[&mpc, &trade.ephemeral],
+        )
+        .await;
+    assert_custom_error(result, CustomError::TimeOut);
+}
+
+#[tokio::test]
+async fn test_max_fee() {
+    let mut test = TestContext::initialized().await;
+    let token = test.setup_asset(Asset::Spl).await;
+    let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+    let trade_detail = test.trade_detail(&trade.trade_id).await;
+    let admin = test.admin.insecure_clone();
+    let mpc = test.mpc.insecure_clone();
+
+    // 0.5% of the amount at most, set by a fee manager
+    let result = test
+        .process(&[instructions::set_max_fee(&admin.pubkey(), None, Some(50))], &[&admin])
+        .await;
+    assert_custom_error(result, CustomError::Unauthorized);
+    test.process(
+        &[
+            instructions::grant_role(&admin.pubkey(), Role::FeeManager, admin.pubkey(), false),
+            instructions::set_max_fee(&admin.pubkey(), None, Some(50)),
+        ],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    let result = test
+        .process(&[instructions::set_total_fee(&trade_detail, AMOUNT / 200 + 1, None).unwrap()], &[&mpc])
+        .await;
+    assert_custom_error(result, CustomError::MaxFeeExceeded);
+    test.process(&[instructions::set_total_fee(&trade_detail, AMOUNT / 200, None).unwrap()], &[&mpc])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_mpc_group() {
+    for asset in [Asset::Sol, Asset::Spl] {
+        let mut test = TestContext::initialized().await;
+        let token = test.setup_asset(asset).await;
+        let admin = test.admin.insecure_clone();
+        let members: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+        let keys: Vec<Pubkey> = members.iter().map(|member| member.pubkey()).collect();
+        keys.iter().for_each(|key| test.fund(key));
+        let mpc_group = pda::get_mpc_group_pda(1);
+
+        // Only the admin manages the groups, with a valid threshold
+        let operator = test.operator.insecure_clone();
+        let result = test
+            .process(
+                &[instructions::add_mpc_group(&operator.pubkey(), 1, keys.clone(), 2)],
+                &[&operator],
+            )
+            .await;
+        assert_custom_error(result, CustomError::Unauthorized);
+        let result = test
+            .process(&[instructions::add_mpc_group(&admin.pubkey(), 1, keys.clone(), 4)], &[&admin])
+            .await;
+        assert_custom_error(result, CustomError::InvalidMpcGroup);
+        test.process(&[instructions::add_mpc_group(&admin.pubkey(), 1, keys.clone(), 2)], &[&admin])
+            .await
+            .unwrap();
+        let group: MpcGroup = test.data(&mpc_group).await;
+        assert_eq!(group.members, keys);
+        assert_eq!(group.threshold, 2);
+
+        // A group is immutable once created, the members are rotated with a new group id
+        let result = test
+            .process(&[instructions::add_mpc_group(&admin.pubkey(), 1, keys[..2].to_vec(), 1)], &[&admin])
+            .await;
+        assert!(result.is_err());
+        let group: MpcGroup = test.data(&mpc_group).await;
+        assert_eq!(group.members, keys);
+
+        // The trade references the group as its MPC, once registered
+        test.process(&[instructions::add_or_update_mpc_registry(&admin.pubkey(), mpc_group, 0, 0)], &[&admin])
+            .await
+            .unwrap();
+        test.trade_mpc = mpc_group;
+        let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.mpc_pubkey, mpc_group);
+
+        // A single member is not enough, neither is the MPC key
+        let result = test
+            .process(
+                &[instructions::set_total_fee(&trade_detail, TOTAL_FEE, Some(&keys[..1])).unwrap()],
+                &[&members[0]],
+            )
+            .await;
+        assert_custom_error(result, CustomError::Unauthorized);
+        let mut ix = instructions::set_total_fee(&trade_detail, TOTAL_FEE, None).unwrap();
+        let mpc = test.mpc.insecure_clone();
+        ix.accounts[0].pubkey = mpc.pubkey();
+        let result = test.process(&[ix], &[&mpc]).await;
+        assert_custom_error(result, CustomError::Unauthorized);
+
+        test.process(
+            &[instructions::set_total_fee(&trade_detail, TOTAL_FEE, Some(&[keys[0], keys[2]])).unwrap()],
+            &[&members[0], &members[2]],
+        )
+        .await
+        .unwrap();
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.total_fee, Some(TOTAL_FEE));
+
+        let pmm = test.pmm.pubkey();
+        test.process(
+            &[instructions::settlement(&trade_detail, &pmm, token.as_ref(), Some(&keys[1..])).unwrap()],
+            &[&members[1], &members[2], &trade.ephemeral],
+        )
+        .await
+        .unwrap();
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        assert_eq!(trade_detail.status, TradeStatus::Settled);
+
+        // The group closes the settled trade right away, like a single MPC
+        let anyone = test.pmm.insecure_clone();
+        test.process(
+            &[instructions::close_finished_trade(
+                &anyone.pubkey(),
+                &trade_detail,
+                token.as_ref(),
+                Some(&keys[..2]),
+            ).unwrap()],
+            &[&members[0], &members[1]],
+        )
+        .await
+        .unwrap();
+        assert!(test
+            .account(&pda::get_user_trade_detail_pda(&trade.trade_id))
+            .await
+            .is_none());
+
+        // In batch, the group closes its settled trades with the co-signing members
+        let trade = test.deposit(token.as_ref(), AMOUNT, DURATION).await;
+        let trade_detail = test.trade_detail(&trade.trade_id).await;
+        test.process(
+            &[instructions::settlement(&trade_detail, &pmm, token.as_ref(), Some(&keys[1..])).unwrap()],
+            &[&members[1], &members[2], &trade.ephemeral],
+        )
+        .await
+        .unwrap();
+        let trades = vec![(test.trade_detail(&trade.trade_id).await, token)];
+        let trade_pda = pda::get_user_trade_detail_pda(&trade.trade_id);
+        test.process(
+            &[instructions::batch_close_finished_trades(&keys[0], &trades, Some((mpc_group, &[]))).unwrap()],
+            &[&members[0]],
+        )
+        .await
+        .unwrap();
+        assert!(test.account(&trade_pda).await.is_some());
+        test.process(
+            &[instructions::batch_close_finished_trades(&keys[0], &trades, Some((mpc_group, &keys[2..]))).unwrap()],
+            &[&members[0], &members[2]],
+        )
+        .await
+        .unwrap();
+        assert!(test.account(&trade_pda).await.is_none());
+
+        // The group publishes and closes its payment expectations with the threshold of members
+        let args = CreatePaymentExpectationArgs {
+            trade_id: [9; 32],
+            to_pubkey: test.user.pubkey(),
+            token: token.map(|token| token.mint),
+            min_amount: AMOUNT,
+            max_fee: TOTAL_FEE,
+            deadline: test.now().await + 60,
+        };
+        let result = test
+            .process(
+                &[instructions::create_payment_expectation(&keys[0], args.clone(), Some((mpc_group, &[])))],
+                &[&members[0]],
+            )
+            .await;
+        assert_custom_error(result, CustomError::Unauthorized);
+        test.process(
+            &[instructions::create_payment_expectation(&keys[0], args, Some((mpc_group, &keys[1..2])))],
+            &[&members[0], &members[1]],
+        )
+        .await
+        .unwrap();
+        let expectation: PaymentExpectation =
+            test.data(&pda::get_payment_expectation_pda(&[9; 32], &mpc_group)).await;
+        assert_eq!(expectation.mpc_pubkey, mpc_group);
+        let result = test
+            .process(
+                &[instructions::close_payment_expectation(&keys[0], &[9; 32], Some((mpc_group, &[])))],
+                &[&members[0]],
+            )
+            .await;
+        assert_custom_error(result, CustomError::Unauthorized);
+        let result = test
+            .process(
+                &[instructions::close_payment_expectation(&keys[0], &[9; 32], Some((mpc_group, &keys[1..2])))],
+                &[&members[0], &members[1]],
+            )
+            .await;
+        assert_custom_error(result, CustomError::CloseNotAvailable);
+
+        test.process(&[instructions::remove_mpc_group(&admin.pubkey(), 1)], &[&admin])
+            .await
+            .unwrap();
+        assert!(test.account(&mpc_group).await.is_none());
+
+        // The id of a removed group is never reused, so its trades cannot be taken over
+        let result = test
+            .process(&[instructions::add_mpc_group(&admin.pubkey(), 1, keys[..2].to_vec(), 1)], &[&admin])
+            .await;
+        assert_custom_error(result, CustomError::InvalidMpcGroup);
+        test.process(&[instructions::add_mpc_group(&admin.pubkey(), 2, keys[..2].to_vec(), 1)], &[&admin])
+            .await
+            .unwrap();
+        let config: Config = test.data(&pda::get_config_pda()).await;
+        assert_eq!(config.next_mpc_group_id, 3);
+    }
+}
@@ -0,0 +1,237 @@
+//! Conformance of the trade id with the EVM contracts.
+//!
+//! The golden vectors in `fixtures/trade_id_vectors.json` are ABI encodings of
+//! `abi.encode(uint256 sessionId, address solver, (uint256 amountIn, bytes[3] fromChain, bytes[3] toChain))`,
+//! as computed by `getTradeId` of `solana-js/utils/parse_utils.ts` and the EVM contracts.
+//! They are generated by `solana-js/scripts/trade_id_vectors.ts`, run with `yarn vectors:trade-id`.
+use std::str::FromStr;
+
+use anchor_lang::{prelude::Pubkey, AnchorSerialize};
+use ethabi::{
+    decode,
+    ethereum_types::{H160, U256},
+    ParamType, Token,
+};
+use optimex_sol_smartcontract::{
+    bytes_to_u64_number, vec_u8_to_address, CustomError, TradeIdScheme, TradeInfo, TradeInput,
+};
+use proptest::prelude::*;
+use serde_json::Value;
+use solana_sdk::keccak;
+use sha2::{Digest, Sha256};
+
+fn hex_bytes(value: &Value) -> Vec<u8> {
+    hex::decode(value.as_str().unwrap()).unwrap()
+}
+
+fn hex_chain(value: &Value) -> [Vec<u8>; 3] {
+    let chain: Vec<Vec<u8>> = value.as_array().unwrap().iter().map(hex_bytes).collect();
+    chain.try_into().unwrap()
+}
+
+fn trade_input_param_types() -> Vec<ParamType> {
+    let chain = ParamType::FixedArray(Box::new(ParamType::Bytes), 3);
+    vec![
+        ParamType::Uint(256),
+        ParamType::Address,
+        ParamType::Tuple(vec![ParamType::Uint(256), chain.clone(), chain]),
+    ]
+}
+
+/// The length of the ABI encoding: the head of the input and the trade info, then each element of the chains.
+fn encoded_len(input: &TradeInput) -> usize {
+    let chains = [&input.trade_info.from_chain, &input.trade_info.to_chain];
+    let elements: usize = chains
+        .iter()
+        .flat_map(|chain| chain.iter())
+        .map(|bytes| 32 + bytes.len().div_ceil(32) * 32)
+        .sum();
+    3 * 32 + 3 * 32 + 2 * 3 * 32 + elements
+}
+
+#[test]
+fn test_trade_id_golden_vectors() {
+    let vectors: Value =
+        serde_json::from_str(include_str!("fixtures/trade_id_vectors.json")).unwrap();
+    let vectors = vectors.as_array().unwrap();
+    assert!(!vectors.is_empty());
+
+    for vector in vectors {
+        let name = vector["name"].as_str().unwrap();
+        let input = TradeInput {
+            session_id: hex_bytes(&vector["session_id"]).try_into().unwrap(),
+            solver: hex_bytes(&vector["solver"]).try_into().unwrap(),
+            trade_info: TradeInfo {
+                amount_in: hex_bytes(&vector["amount_in"]).try_into().unwrap(),
+                from_chain: hex_chain(&vector["from_chain"]),
+                to_chain: hex_chain(&vector["to_chain"]),
+            },
+        };
+        assert_eq!(
+            hex::encode(input.encode_trade_input()),
+            vector["encoded"].as_str().unwrap(),
+            "{name}"
+        );
+        assert_eq!(
+            hex::encode(input.calculate_trade_id()),
+            vector["trade_id"].as_str().unwrap(),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_bytes_to_u64_number_edges() {
+    assert_eq!(bytes_to_u64_number(&[]), Ok(0));
+    assert_eq!(bytes_to_u64_number(&[0; 32]), Ok(0));
+    assert_eq!(bytes_to_u64_number(&[0xff; 8]), Ok(u64::MAX));
+
+    let mut amount_in = [0u8; 32];
+    amount_in[24..].copy_from_slice(&u64::MAX.to_be_bytes());
+    assert_eq!(bytes_to_u64_number(&amount_in), Ok(u64::MAX));
+
+    // u64::MAX + 1
+    let mut amount_in = [0u8; 32];
+    amount_in[23] = 1;
+    assert_eq!(bytes_to_u64_number(&amount_in), Err(CustomError::AmountExceeds.into()));
+    assert_eq!(bytes_to_u64_number(&[0xff; 32]), Err(CustomError::AmountExceeds.into()));
+}
+
+#[test]
+fn test_vec_u8_to_address_malformed() {
+    let invalid = Err(CustomError::InvalidPublicKey.into());
+    assert_eq!(vec_u8_to_address(&b"native".to_vec()), Ok(None));
+    assert_eq!(vec_u8_to_address(&b"NATIVE".to_vec()), invalid);
+    assert_eq!(vec_u8_to_address(&vec![]), invalid);
+
+    // Invalid UTF-8
+    assert_eq!(vec_u8_to_address(&vec![0xff, 0xfe]), invalid);
+    assert_eq!(vec_u8_to_address(&vec![0xc3, 0x28]), invalid);
+
+    // The characters 0, O, I and l are not in the base58 alphabet
+    let pubkey = "3DYbLvuRV6tZN7iEBxAgHbpgv3AvWmAJGtWmrkp24Vew";
+    for c in ['0', 'O', 'I', 'l'] {
+        let key = format!("{c}{}", &pubkey[1..]);
+        assert_eq!(vec_u8_to_address(&key.into_bytes()), invalid, "{c}");
+    }
+
+    // Not 32 bytes once decoded
+    assert_eq!(vec_u8_to_address(&b"1111".to_vec()), invalid);
+    assert_eq!(vec_u8_to_address(&format!("{pubkey}1").into_bytes()), invalid);
+    assert_eq!(vec_u8_to_address(&format!(" {pubkey}").into_bytes()), invalid);
+
+    // An EVM address is not a Solana address
+    let evm_address = b"0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_vec();
+    assert_eq!(vec_u8_to_address(&evm_address), invalid);
+}
+
+fn trade_input_strategy() -> impl Strategy<Value = TradeInput> {
+    let bytes = || prop::collection::vec(any::<u8>(), 0..160);
+    let chain = move || [bytes(), bytes(), bytes()];
+    (any::<[u8; 32]>(), any::<[u8; 20]>(), any::<[u8; 32]>(), chain(), chain()).prop_map(
+        |(session_id, solver, amount_in, from_chain, to_chain)| TradeInput {
+            session_id,
+            solver,
+            trade_info: TradeInfo {
+                amount_in,
+                from_chain,
+                to_chain,
+            },
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn prop_encode_trade_input_round_trip(input in trade_input_strategy()) {
+        let encoded = input.encode_trade_input();
+        prop_assert_eq!(encoded.len(), encoded_len(&input));
+        prop_assert_eq!(encoded.len() % 32, 0);
+
+        let chain = |chain: &[Vec<u8>; 3]| {
+            Token::FixedArray(chain.iter().cloned().map(Token::Bytes).collect())
+        };
+        let expected = vec![
+            Token::Uint(U256::from_big_endian(&input.session_id)),
+            Token::Address(H160::from(input.solver)),
+            Token::Tuple(vec![
+                Token::Uint(U256::from_big_endian(&input.trade_info.amount_in)),
+                chain(&input.trade_info.from_chain),
+                chain(&input.trade_info.to_chain),
+            ]),
+        ];
+        prop_assert_eq!(decode(&trade_input_param_types(), &encoded).unwrap(), expected);
+
+        let trade_id: [u8; 32] = Sha256::digest(&encoded).into();
+        prop_assert_eq!(input.calculate_trade_id(), trade_id);
+    }
+
+    #[test]
+    fn prop_trade_id_schemes(input in trade_input_strategy()) {
+        let encoded = input.encode_trade_input();
+        prop_assert_eq!(input.calculate_trade_id_with_scheme(TradeIdScheme::Sha256Abi).unwrap(), input.calculate_trade_id());
+        prop_assert_eq!(
+            input.calculate_trade_id_with_scheme(TradeIdScheme::Keccak256Abi).unwrap(),
+            keccak::hash(&encoded).to_bytes()
+        );
+        let trade_id: [u8; 32] = Sha256::digest(input.try_to_vec().unwrap()).into();
+        prop_assert_eq!(input.calculate_trade_id_with_scheme(TradeIdScheme::Sha256Borsh).unwrap(), trade_id);
+    }
+
+    #[test]
+    fn prop_trade_id_changes_with_chains(input in trade_input_strategy(), index in 0usize..6, byte in any::<u8>()) {
+        let mut changed = input.clone();
+        let chain = match index / 3 {
+            0 => &mut changed.trade_info.from_chain,
+            _ => &mut changed.trade_info.to_chain,
+        };
+        chain[index % 3].push(byte);
+        prop_assert_ne!(input.calculate_trade_id(), changed.calculate_trade_id());
+    }
+
+    #[test]
+    fn prop_bytes_to_u64_number_padded(value in any::<u64>(), padding in 0usize..=32) {
+        let mut bytes = vec![0u8; padding];
+        bytes.extend_from_slice(&value.to_be_bytes());
+        prop_assert_eq!(bytes_to_u64_number(&bytes), Ok(value));
+    }
+
+    #[test]
+    fn prop_bytes_to_u64_number_matches_u256(bytes in prop::collection::vec(any::<u8>(), 0..=32)) {
+        let value = U256::from_big_endian(&bytes);
+        match bytes_to_u64_number(&bytes) {
+            Ok(amount) => prop_assert_eq!(U256::from(amount), value),
+            Err(err) => {
+                prop_assert!(value > U256::from(u64::MAX));
+                prop_assert_eq!(err, CustomError::AmountExceeds.into());
+            }
+        }
+    }
+
+    #[test]
+    fn prop_vec_u8_to_address_round_trip(key in any::<[u8; 32]>()) {
+        let pubkey = Pubkey::new_from_array(key);
+        prop_assert_eq!(vec_u8_to_address(&pubkey.to_string().into_bytes()), Ok(Some(pubkey)));
+    }
+
+    #[test]
+    fn prop_vec_u8_to_address_arbitrary(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+        // Never panics, and only accepts the canonical base58 of a public key
+        match vec_u8_to_address(&bytes) {
+            Ok(Some(pubkey)) => prop_assert_eq!(pubkey.to_string().into_bytes(), bytes),
+            Ok(None) => prop_assert_eq!(bytes, b"native".to_vec()),
+            Err(err) => {
+                prop_assert_eq!(err, CustomError::InvalidPublicKey.into());
+                let key = std::str::from_utf8(&bytes).ok().and_then(|key| Pubkey::from_str(key).ok());
+                prop_assert!(key.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn prop_vec_u8_to_address_invalid_utf8(mut bytes in prop::collection::vec(any::<u8>(), 0..44), index in any::<prop::sample::Index>()) {
+        let index = index.index(bytes.len() + 1);
+        bytes.insert(index, 0xff);
+        prop_assert_eq!(vec_u8_to_address(&bytes), Err(CustomError::InvalidPublicKey.into()));
+    }
+}
@@ -1,6 +1,6 @@
 import { BorshCoder, Instruction, ProgramAccount } from '@coral-xyz/anchor';
 import { Connection, PublicKey, sendAndConfirmTransaction, Transaction } from '@solana/web3.js';
-import { createAddFeeReceiverInstruction, createAddOrUpdateWhitelistInstruction, createAssociatedTokenAccountInstructionIfNeeded, createRemoveFeeReceiverInstruction, createRemoveWhitelistInstruction, createWithdrawTotalFeeInstruction, getOptimexProgram, getConfigPda, getProtocolPda, getWhitelistPda, PaymentReceipt, TradeDetail, tradeIdBytesToString, createInitializeProgramInstructions, createAddOperatorInstruction, createRemoveOperatorInstruction, createSetCloseWaitDurationInstruction } from "../../solana-js";
+import { createAddFeeReceiverInstruction, createAddOrUpdateWhitelistInstruction, createAssociatedTokenAccountInstructionIfNeeded, createRemoveFeeReceiverInstruction, createRemoveWhitelistInstruction, createReceiverAtasIfNeededAndDistributeFeesInstruction, createSetFeeReceiverShareInstruction, getOptimexProgram, getConfigPda, getProtocolPda, getWhitelistPda, PaymentReceipt, TradeDetail, tradeIdBytesToString, createInitializeProgramInstructions, createGrantRoleInstruction, createRevokeRoleInstruction, createMigrateOperatorInstruction, createSetCloseWaitDurationInstruction, RoleName, ROLES } from "../../solana-js";
 import { Command, Option } from 'commander';
 import { getKeypairFromFile } from '../utils/helper';
 import bs58 from 'bs58';
@@ -207,6 +207,8 @@ program.command('update-whitelist')
 .requiredOption('--operator <string>', 'The path to operator keypair')
 .requiredOption('--token <string>', 'The token to add or update')
 .requiredOption('--amount <string>', 'The amount to set for the whitelisted token, without decimals')
+.option('--max-amount <string>', 'The maximum amount of a single deposit, zero means no limit', '0')
+.option('--max-outstanding <string>', 'The maximum amount held by the trade vaults, zero means no limit', '0')
 .action(async (options) => {
     const commitment = options.commitment || 'confirmed';
     const connection = new Connection(options.url, commitment);
@@ -234,6 +236,8 @@ program.command('update-whitelist')
         operator: operator.publicKey,
         token,
         amount: BigInt(options.amount),
+        maxAmount: BigInt(options.maxAmount),
+        maxOutstanding: BigInt(options.maxOutstanding),
         connection: connection,
     })
 
@@ -253,6 +257,7 @@ program.command('add-receiver')
 .addOption(commonOptions[1])
 .requiredOption('--admin <string>', 'The path to admin keypair')
 .requiredOption('--fee-receiver <string>', 'The fee receiver address')
+.option('--pending-action <string>', 'The pending action queued for the addition, required once the timelock delay is set')
 .action(async (options) => {
     const commitment = options.commitment || 'confirmed';
     const connection = new Connection(options.url, commitment);
@@ -262,6 +267,7 @@ program.command('add-receiver')
         connection, 
         signer: admin.publicKey,
         receiver: new PublicKey(options.feeReceiver),
+        pendingAction: options.pendingAction ? new PublicKey(options.pendingAction) : undefined,
     })
 
     try {
@@ -280,6 +286,7 @@ program.command('remove-receiver')
 .addOption(commonOptions[1])
 .requiredOption('--admin <string>', 'The path to admin keypair')
 .requiredOption('--fee-receiver <string>', 'The fee receiver address')
+.option('--pending-action <string>', 'The pending action queued for the removal, required once the timelock delay is set')
 .action(async (options) => {
     const commitment = options.commitment || 'confirmed';
     const connection = new Connection(options.url, commitment);
@@ -289,6 +296,7 @@ program.command('remove-receiver')
         connection, 
         signer: admin.publicKey,
         receiver: new PublicKey(options.feeReceiver),
+        pendingAction: options.pendingAction ? new PublicKey(options.pendingAction) : undefined,
     })
 
     try {
@@ -338,98 +346,154 @@ program.command('remove-whitelist')
     }
 })
 
-program.command('withdraw-fee')
-.description('Withdraw total fee')
+program.command('set-receiver-share')
+.description('Set the share of the receiver in the distributed fee')
 .addOption(commonOptions[0])
 .addOption(commonOptions[1])
-.requiredOption('--token <string>', 'The asset address we want to withdraw, native if SOL')
-.requiredOption('--authorizer <string>', 'The path to the authorizer')
+.requiredOption('--admin <string>', 'The path to admin keypair')
 .requiredOption('--fee-receiver <string>', 'The fee receiver address')
+.requiredOption('--share-bps <number>', 'The share of the fee receiver, in basis points')
+.action(async (options) => {
+    const commitment = options.commitment || 'confirmed';
+    const connection = new Connection(options.url, commitment);
+    const admin = await getKeypairFromFile(options.admin);
+
+    const setFeeReceiverShareInstruction = await createSetFeeReceiverShareInstruction({
+        connection,
+        signer: admin.publicKey,
+        receiver: new PublicKey(options.feeReceiver),
+        shareBps: Number(options.shareBps),
+    })
+
+    try {
+        const transaction = new Transaction().add(...setFeeReceiverShareInstruction);
+        const txHash = await sendAndConfirmTransaction(connection, transaction, [admin], commitment);
+        console.log(`Set fee receiver share success tx hash: ${txHash}`);
+    } catch (error) {
+        console.error('Set fee receiver share failed');
+        throw error;
+    }
+})
+
+program.command('distribute-fees')
+.description('Distribute the available fee to all the fee receivers, pro rata of their shares')
+.addOption(commonOptions[0])
+.addOption(commonOptions[1])
+.requiredOption('--token <string>', 'The asset address we want to distribute, native if SOL')
+.requiredOption('--authorizer <string>', 'The path to the authorizer')
 .action(async (options) => {
     console.log('Options ', options);
     const commitment = options.commitment || 'confirmed';
     const connection = new Connection(options.url, commitment);
     const authorizer = await getKeypairFromFile(options.authorizer);
-    const feeReceiver = new PublicKey(options.feeReceiver);
     const token = options.token === 'native' ? null : new PublicKey(options.token)
 
-    const createFeeReceiverAtaInstruction = await createAssociatedTokenAccountInstructionIfNeeded(
-        connection,
-        authorizer.publicKey,
-        token,
-        feeReceiver, 
-    )
-    const withdrawFeeInstruction = await createWithdrawTotalFeeInstruction({
+    const distributeFeesInstruction = await createReceiverAtasIfNeededAndDistributeFeesInstruction({
         connection,
         signer: authorizer.publicKey,
         token,
-        receiverPubkey: feeReceiver,
-        amount: null,
+        commitment,
     })
 
-    const transaction = new Transaction().add(...createFeeReceiverAtaInstruction, ...withdrawFeeInstruction);
+    const transaction = new Transaction().add(...distributeFeesInstruction);
 
     try {
         const txHash = await sendAndConfirmTransaction(connection, transaction, [authorizer], commitment);
-        console.log(`Withdraw fee success tx hash: ${txHash}`);
+        console.log(`Distribute fees success tx hash: ${txHash}`);
     } catch (error) {
-        console.error('Withdraw fee failed');
+        console.error('Distribute fees failed');
         throw error;
     }
 })
 
-program.command('add-operator')
-.description('Add the operator')
+program.command('grant-role')
+.description(`Grant a role to a member, one of ${ROLES.join(', ')}`)
 .addOption(commonOptions[0])
 .addOption(commonOptions[1])
 .requiredOption('--admin <string>', 'The path to admin keypair')
-.requiredOption('--operator <string>', 'The operator address')
+.requiredOption('--role <string>', 'The role to grant')
+.requiredOption('--member <string>', 'The member address')
+.option('--pending-action <string>', 'The pending action queued for the grant, required once the timelock delay is set')
 .action(async (options) => {
     const commitment = options.commitment || 'confirmed';
     const connection = new Connection(options.url, commitment);
     const admin = await getKeypairFromFile(options.admin);
-    const operator = new PublicKey(options.operator);
+    const member = new PublicKey(options.member);
 
-    const addOperatorInstruction = await createAddOperatorInstruction({
+    const grantRoleInstruction = await createGrantRoleInstruction({
         connection,
         signer: admin.publicKey,
-        operator,
+        role: options.role as RoleName,
+        member,
+        pendingAction: options.pendingAction ? new PublicKey(options.pendingAction) : undefined,
+    })
+
+    try {
+        const transaction = new Transaction().add(...grantRoleInstruction);
+        const txHash = await sendAndConfirmTransaction(connection, transaction, [admin], commitment);
+        console.log(`Grant role success tx hash: ${txHash}`);
+    } catch (error) {
+        console.error('Grant role failed');
+        throw error;
+    }
+})
+
+program.command('revoke-role')
+.description(`Revoke a role from a member, one of ${ROLES.join(', ')}`)
+.addOption(commonOptions[0])
+.addOption(commonOptions[1])
+.requiredOption('--admin <string>', 'The path to admin keypair')
+.requiredOption('--role <string>', 'The role to revoke')
+.requiredOption('--member <string>', 'The member address')
+.option('--pending-action <string>', 'The pending action queued for the revoke, required once the timelock delay is set')
+.action(async (options) => {
+    const commitment = options.commitment || 'confirmed';
+    const connection = new Connection(options.url, commitment);
+    const admin = await getKeypairFromFile(options.admin);
+    const member = new PublicKey(options.member);
+
+    const revokeRoleInstruction = await createRevokeRoleInstruction({
+        connection,
+        signer: admin.publicKey,
+        role: options.role as RoleName,
+        member,
+        pendingAction: options.pendingAction ? new PublicKey(options.pendingAction) : undefined,
     })
 
     try {
-        const transaction = new Transaction().add(...addOperatorInstruction);
+        const transaction = new Transaction().add(...revokeRoleInstruction);
         const txHash = await sendAndConfirmTransaction(connection, transaction, [admin], commitment);
-        console.log(`Add operator success tx hash: ${txHash}`);
+        console.log(`Revoke role success tx hash: ${txHash}`);
     } catch (error) {
-        console.error('Add operator failed');
+        console.error('Revoke role failed');
         throw error;
     }
 })
 
-program.command('remove-operator')
-.description('Remove the operator')
+program.command('migrate-operator')
+.description('Migrate a legacy operator to the whitelist manager and duration manager roles')
 .addOption(commonOptions[0])
 .addOption(commonOptions[1])
 .requiredOption('--admin <string>', 'The path to admin keypair')
-.requiredOption('--operator <string>', 'The operator address')
+.requiredOption('--operator <string>', 'The legacy operator address')
 .action(async (options) => {
     const commitment = options.commitment || 'confirmed';
     const connection = new Connection(options.url, commitment);
     const admin = await getKeypairFromFile(options.admin);
     const operator = new PublicKey(options.operator);
 
-    const removeOperatorInstruction = await createRemoveOperatorInstruction({
+    const migrateOperatorInstruction = await createMigrateOperatorInstruction({
         connection,
         signer: admin.publicKey,
         operator,
     })
 
     try {
-        const transaction = new Transaction().add(...removeOperatorInstruction);
+        const transaction = new Transaction().add(...migrateOperatorInstruction);
         const txHash = await sendAndConfirmTransaction(connection, transaction, [admin], commitment);
-        console.log(`Remove operator success tx hash: ${txHash}`);
+        console.log(`Migrate operator success tx hash: ${txHash}`);
     } catch (error) {
-        console.error('Remove operator failed');
+        console.error('Migrate operator failed');
         throw error;
     }
 })
@@ -10,7 +10,9 @@ import { WSOL_MINT } from "../../solana-js/constants";
     const addWhitelistIns = await createAddOrUpdateWhitelistInstruction({
         operator: deployer.publicKey,
         token: WSOL_MINT,
-        amount: 0.00001 * LAMPORTS_PER_SOL,
+        amount: BigInt(0.00001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection,
     });
     try {
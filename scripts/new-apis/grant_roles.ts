@@ -1,5 +1,5 @@
 import { clusterApiUrl, Connection, PublicKey, sendAndConfirmTransaction, Transaction } from "@solana/web3.js";
-import { createAddOperatorInstruction } from "../../solana-js";
+import { createGrantRoleInstruction } from "../../solana-js";
 import path from 'path';
 import { getKeypairFromFile } from "../utils/helper";
 
@@ -7,15 +7,23 @@ import { getKeypairFromFile } from "../utils/helper";
     const connection = new Connection(clusterApiUrl('devnet'), 'confirmed');
     const currentDir = __dirname;
     const deployer = await getKeypairFromFile(path.join(currentDir, '../../.wallets/deployer.json'));
-    const addOperatorIns = await createAddOperatorInstruction({
+    const operator = new PublicKey('6MsXYrd6iLhfuwP3DnLkq56UsCzS89bSL1VmepWKznRg');
+    const grantWhitelistManagerIns = await createGrantRoleInstruction({
         signer: deployer.publicKey,
-        operator: new PublicKey('6MsXYrd6iLhfuwP3DnLkq56UsCzS89bSL1VmepWKznRg'),
+        role: 'whitelistManager',
+        member: operator,
+        connection,
+    });
+    const grantDurationManagerIns = await createGrantRoleInstruction({
+        signer: deployer.publicKey,
+        role: 'durationManager',
+        member: operator,
         connection,
     });
     try {
-        const transaction = new Transaction().add(...addOperatorIns);
+        const transaction = new Transaction().add(...grantWhitelistManagerIns, ...grantDurationManagerIns);
         const signature = await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
-        console.log(`Add operator success at ${signature}`);
+        console.log(`Grant roles success at ${signature}`);
     } catch (error) {
         console.log('Error: ', error);
         throw error;
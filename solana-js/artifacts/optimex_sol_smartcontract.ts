@@ -16,17 +16,211 @@ export type OptimexSolSmartcontract = {
     "This is the main module of the Optimex protocol. Contains the instructions that are performed by the protocol."
   ],
   "instructions": [
+    {
+      "name": "acceptAdmin",
+      "docs": [
+        "Accept the admin role, completes the admin transfer started by [propose_admin].",
+        "",
+        "This instruction is authorized by the [Config::pending_admin].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [AcceptAdmin] required for accepting the admin role.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the pending admin."
+      ],
+      "discriminator": [
+        112,
+        42,
+        45,
+        90,
+        116,
+        181,
+        13,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the accept admin instruction.",
+            "Must be the [Config::pending_admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "accrueLegacyFee",
+      "docs": [
+        "Accrue the fee held by the protocol before the [FeeLedger] was introduced, once per token.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "The balance of the protocol for the token that is not already available in the [FeeLedger] is accrued,",
+        "so the fee collected before the upgrade can be withdrawn.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [AccrueLegacyFee] required for accruing the legacy fee.",
+        "* `token` - The token of the fee, none for SOL.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::LegacyFeeAlreadyAccrued] - The legacy fee of the token was already accrued.",
+        "* [CustomError::InvalidTokenAccount] - The protocol token account is missing for a token, or given for SOL.",
+        "* [CustomError::InvalidDestinationAta] - The protocol token account is not the associated token account of the protocol PDA."
+      ],
+      "discriminator": [
+        58,
+        14,
+        190,
+        74,
+        73,
+        3,
+        70,
+        250
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the accrue legacy fee instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "protocol",
+          "docs": [
+            "The protocol PDA account which own the protocol fee."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "protocolTokenAccount",
+          "docs": [
+            "The associated token account of the protocol PDA for the token, none for SOL. Checked by the instruction."
+          ],
+          "optional": true
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "The fee ledger PDA of the token, WSOL for SOL.",
+            "The legacy fee is added to its accrued fee. Will be initialized by the signer if needed."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "token.unwrap_or(WSOL_MINT)"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "token",
+          "type": {
+            "option": "pubkey"
+          }
+        }
+      ]
+    },
     {
       "name": "addFeeReceiver",
       "docs": [
         "Add fee receiver.",
         "",
-        "This instruction is authorized by the [Config::admin].",
+        "This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].",
         "# Arguments",
         "* `ctx` - A [Context] of [AddFeeReceiver] required for adding the fee receiver.",
         "* `receiver_pubkey` - The pubkey of the fee receiver.",
         "# Errors",
-        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::TimelockRequired] - The operation must be queued first.",
+        "* [CustomError::TimelockNotReady] - The pending action cannot be executed yet."
       ],
       "discriminator": [
         142,
@@ -108,6 +302,15 @@ export type OptimexSolSmartcontract = {
             "System program."
           ],
           "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "pendingAction",
+          "docs": [
+            "The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.",
+            "Will be closed and transferred rent fee to the signer."
+          ],
+          "writable": true,
+          "optional": true
         }
       ],
       "args": [
@@ -118,36 +321,39 @@ export type OptimexSolSmartcontract = {
       ]
     },
     {
-      "name": "addOrRemoveOperator",
+      "name": "addMpcGroup",
       "docs": [
-        "Add or remove an operator for the protocol.",
+        "Add an MPC group, a threshold (M-of-N) MPC authority for the trades.",
         "",
         "This instruction is authorized by the [Config::admin].",
+        "A trade references the group when its [TradeDetail::mpc_pubkey] is the address of the group PDA.",
+        "Then the instructions authorized by the MPC of the trade require the signatures of [MpcGroup::threshold] members.",
+        "A group is immutable once created: the members are rotated by adding a new group with a new `group_id`,",
+        "and a `group_id` is never reused, even after the group is removed.",
         "# Arguments",
-        "* `ctx` - A [Context] of [AddOrRemoveOperator] required for adding or removing an operator.",
-        "* `operator` - The operator to add or remove.",
-        "* `is_bool` - Whether to add or remove the operator.",
+        "* `ctx` - A [Context] of [AddMpcGroup] required for adding the MPC group.",
+        "* `group_id` - The identifier of the group, at least the [Config::next_mpc_group_id].",
+        "* `members` - The member keys of the group, up to [MpcGroup::MAX_MEMBERS].",
+        "* `threshold` - The number of member signatures required to authorize an instruction.",
         "# Errors",
-        "* [CustomError::Unauthorized] when the caller is not authorized, not the [Config::admin].",
-        "* [CustomError::OperatorAlreadyExists] when add a operator that is already exists.",
-        "* [CustomError::OperatorLimitReached] when add a operator and reach the limit of [Config::OPERATORS_SIZE].",
-        "* [CustomError::OperatorNotFound] when remove a operator that is not exists."
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::InvalidMpcGroup] - The `group_id` was already used, or the members or the threshold are invalid."
       ],
       "discriminator": [
+        21,
+        206,
+        175,
+        56,
         242,
-        151,
-        243,
-        85,
-        157,
-        174,
-        36,
-        182
+        6,
+        4,
+        209
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer account that is authorized to perform the add or remove operator instruction.",
+            "The admin that is authorized to perform the add MPC group instruction.",
             "Must be the [Config::admin]"
           ],
           "writable": true,
@@ -156,7 +362,8 @@ export type OptimexSolSmartcontract = {
         {
           "name": "config",
           "docs": [
-            "The config PDA account that contains the protocol configuration."
+            "The config PDA account that contains the protocol configuration.",
+            "The [Config::next_mpc_group_id] is moved past the `group_id`."
           ],
           "writable": true,
           "pda": {
@@ -174,47 +381,96 @@ export type OptimexSolSmartcontract = {
               }
             ]
           }
+        },
+        {
+          "name": "mpcGroup",
+          "docs": [
+            "The MPC group PDA account that contains the members and the threshold.",
+            "Will be initialized by the signer. A group is immutable once created,",
+            "its members are rotated by adding a new group with a new `group_id`."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  112,
+                  99,
+                  95,
+                  103,
+                  114,
+                  111,
+                  117,
+                  112
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "groupId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "operator",
-          "type": "pubkey"
+          "name": "groupId",
+          "type": "u64"
         },
         {
-          "name": "isAdd",
-          "type": "bool"
+          "name": "members",
+          "type": {
+            "vec": "pubkey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "addOrUpdateWhitelist",
+      "name": "addOrUpdateMpcRegistry",
       "docs": [
-        "Add or update whitelist token setup.",
+        "Register or update an MPC key in the [MpcRegistry], the key is active after this instruction.",
         "",
-        "This instruction is authorized by the [Config::operators].",
+        "This instruction is authorized by the [Config::admin].",
+        "The deposits can only reference an MPC key that is registered, and active at the deposit time.",
         "# Arguments",
-        "* `ctx` - A [Context] of [AddOrUpdateWhitelist] required for adding or updating the whitelist.",
-        "* `amount` - The minimum amount to set for the whitelisted token.",
+        "* `ctx` - A [Context] of [AddOrUpdateMpcRegistry] required for registering the MPC key.",
+        "* `mpc_pubkey` - The MPC key, or the address of an [MpcGroup].",
+        "* `valid_from` - The time from which the deposits can reference the MPC key.",
+        "* `valid_until` - The time until which the deposits can reference the MPC key. Zero means no limit.",
         "# Errors",
-        "* [CustomError::Unauthorized] - The caller is not authorized, or not the operator."
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::InvalidPublicKey] - The MPC key is the default pubkey.",
+        "* [CustomError::InvalidValidityWindow] - The `valid_until` is before the `valid_from`."
       ],
       "discriminator": [
+        140,
+        145,
+        5,
+        205,
+        146,
         37,
-        38,
-        2,
-        162,
-        195,
-        182,
-        21,
-        30
+        109,
+        80
       ],
       "accounts": [
         {
-          "name": "operator",
+          "name": "signer",
           "docs": [
-            "The operator that is authorized to perform the add or update whitelist instruction.",
-            "Must be the [Config::operators]"
+            "The admin that is authorized to perform the add or update MPC registry instruction.",
+            "Must be the [Config::admin]"
           ],
           "writable": true,
           "signer": true
@@ -241,10 +497,10 @@ export type OptimexSolSmartcontract = {
           }
         },
         {
-          "name": "whitelistToken",
+          "name": "mpcRegistry",
           "docs": [
-            "The whitelist token PDA account that contains the whitelist token information.",
-            "If SOL native, used WSOL PDA account."
+            "The MPC registry PDA account of the MPC key.",
+            "Will be initialized by the signer if needed."
           ],
           "writable": true,
           "pda": {
@@ -252,30 +508,27 @@ export type OptimexSolSmartcontract = {
               {
                 "kind": "const",
                 "value": [
-                  119,
-                  104,
-                  105,
-                  116,
+                  109,
+                  112,
+                  99,
+                  95,
+                  114,
                   101,
-                  108,
+                  103,
                   105,
                   115,
-                  116
+                  116,
+                  114,
+                  121
                 ]
               },
               {
-                "kind": "account",
-                "path": "token"
+                "kind": "arg",
+                "path": "mpcPubkey"
               }
             ]
           }
         },
-        {
-          "name": "token",
-          "docs": [
-            "The mint token account that we want to set whitelist."
-          ]
-        },
         {
           "name": "systemProgram",
           "docs": [
@@ -286,64 +539,80 @@ export type OptimexSolSmartcontract = {
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "mpcPubkey",
+          "type": "pubkey"
+        },
+        {
+          "name": "validFrom",
+          "type": "i64"
+        },
+        {
+          "name": "validUntil",
+          "type": "i64"
         }
       ]
     },
     {
-      "name": "claim",
+      "name": "addOrUpdateWhitelist",
       "docs": [
-        "Claim the deposited amount after the timeout. This instruction is authorized by anyone.",
+        "Add or update whitelist token setup.",
         "",
-        "The deposited amount is transferred to the [TradeDetail::refund_pubkey].",
-        "This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::user_pubkey], and allow the nonce can be used by other trade.",
+        "This instruction is authorized by the [Role::WhitelistManager] members.",
         "# Arguments",
-        "* `ctx` - A [Context] of [Claim] required for claiming the deposited amount.",
-        "* `claim_args` - An argument [ClaimArgs] required for claiming the deposited amount.",
+        "* `ctx` - A [Context] of [AddOrUpdateWhitelist] required for adding or updating the whitelist.",
+        "* `amount` - The minimum amount to set for the whitelisted token.",
+        "* `max_amount` - The maximum amount of a single deposit. Zero means no limit.",
+        "* `max_outstanding` - The maximum amount of the token held by the trade vaults. Zero means no limit.",
         "# Errors",
-        "* [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::user_pubkey].",
-        "* [CustomError::InvalidRefundPubkey] when the refund pubkey address is not match with the [TradeDetail::refund_pubkey].",
-        "* [CustomError::CLaimNotAvailable] when the [TradeDetail::timeout] is not expired, so we cannot claim the deposited amount.",
-        "* [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited], we only claim the Deposited trade and timed out.",
-        "* [CustomError::InvalidMintKey] when the mint key is not match with the [TradeDetail::token]",
-        "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the vault and mint.",
-        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the refund pubkey."
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not a whitelist manager.",
+        "* [CustomError::UnsupportedMintExtension] - The Token-2022 mint has an extension that can endanger the vault funds, such as a permanent delegate or a transfer hook."
       ],
       "discriminator": [
-        62,
-        198,
-        214,
-        193,
-        213,
-        159,
-        108,
-        210
+        37,
+        38,
+        2,
+        162,
+        195,
+        182,
+        21,
+        30
       ],
       "accounts": [
         {
-          "name": "signer",
+          "name": "operator",
           "docs": [
-            "The signer account that is authorized to perform the claim instruction.",
-            "Can be anyone."
+            "The operator that is authorized to perform the add or update whitelist instruction.",
+            "Must have the [Role::WhitelistManager] role."
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "userAccount",
+          "name": "config",
           "docs": [
-            "",
-            "The user account that is the depositor of the trade.",
-            "Must be the [TradeDetail::user_pubkey]"
+            "The config PDA account that contains the protocol configuration."
           ],
-          "writable": true
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "nonceCheckAccount",
+          "name": "whitelistToken",
           "docs": [
-            "The nonce check account PDA that flag whether the nonce is currently active or not.",
-            "Will be closed and transferred rent to the user_account."
+            "The whitelist token PDA account that contains the whitelist token information.",
+            "If SOL native, used WSOL PDA account."
           ],
           "writable": true,
           "pda": {
@@ -351,43 +620,30 @@ export type OptimexSolSmartcontract = {
               {
                 "kind": "const",
                 "value": [
-                  110,
-                  111,
-                  110,
-                  99,
-                  101
+                  119,
+                  104,
+                  105,
+                  116,
+                  101,
+                  108,
+                  105,
+                  115,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "user_trade_detail.user_ephemeral_pubkey",
-                "account": "tradeDetail"
+                "path": "token"
               }
             ]
           }
         },
         {
-          "name": "userTradeDetail",
-          "docs": [
-            "The trade detail PDA that contains the trade information."
-          ],
-          "writable": true
-        },
-        {
-          "name": "vault",
-          "docs": [
-            "The trade vault PDA that corresponds to the trade."
-          ],
-          "writable": true
-        },
-        {
-          "name": "refundAccount",
+          "name": "token",
           "docs": [
-            "",
-            "The refund account of the trade.",
-            "Must be the [TradeDetail::refund_pubkey]"
-          ],
-          "writable": true
+            "The mint token account that we want to set whitelist.",
+            "Can be a SPL Token or a Token-2022 mint, see [assert_supported_mint] for the supported extensions."
+          ]
         },
         {
           "name": "systemProgram",
@@ -395,82 +651,64 @@ export type OptimexSolSmartcontract = {
             "System program."
           ],
           "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the operator, which must grant the [Role::WhitelistManager] role."
+          ]
         }
       ],
       "args": [
         {
-          "name": "claimArgs",
-          "type": {
-            "defined": {
-              "name": "claimArgs"
-            }
-          }
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "maxAmount",
+          "type": "u64"
+        },
+        {
+          "name": "maxOutstanding",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "closeFinishedTrade",
+      "name": "batchCloseFinishedTrades",
       "docs": [
-        "Close the finished trade ([TradeStatus::Settled] or [TradeStatus::Claimed]) to reclaim the rent fee.",
-        "",
-        "Transfer the rent fee of [TradeDetail], [TradeVault] and [anchor_spl::token::TokenAccount] to the [TradeDetail::user_pubkey].",
+        "Close the finished trades in batch, and send the rent fee to the users.",
         "",
-        "Depend on the trade status, the close action is different:",
-        "* When the trade is [TradeStatus::Deposited], this action is not allowed.",
-        "* When the trade is [TradeStatus::Claimed], this action is allowed for anyone.",
-        "* When the trade is [TradeStatus::Settled], MPC can close the trade right away. Otherwise, anyone can close the trade after the [TradeDetail::timeout] + [Config::close_trade_duration].",
+        "Each trade is checked with the same rules as [close_finished_trade], the trades given as groups of remaining accounts.",
+        "The trades of an [MpcGroup] are closed by their MPC when the group is given, with its co-signing members first in the remaining accounts.",
+        "The trades that cannot be closed yet, or are already closed, are skipped and reported by the [BatchCloseSkipped] event.",
         "# Arguments",
-        "* `ctx` - A [Context] of [CloseFinishedTradeAccounts] required for closing the trade.",
-        "* `_close_finished_trade_args` - An argument [CloseFinishedTradeArgs] required for closing the trade.",
+        "* `ctx` - A [Context] of [BatchCloseFinishedTradesAccounts] required for closing the trades.",
         "# Errors",
-        "* [CustomError::InvalidUserAccount] when the user account is not match to [TradeDetail::user_pubkey]. This account will receive the claimed rent fee.",
-        "* [CustomError::InvalidTradeStatus] when the trade status is [TradeStatus::Deposited].",
-        "* [CustomError::CloseNotAvailable] when the trade is not the available time to close."
+        "* [CustomError::InvalidBatchAccounts] - The remaining accounts are not valid groups of accounts.",
+        "* [CustomError::InvalidUserAccount] - A user account is not the user of its trade.",
+        "* [CustomError::InvalidTokenProgram] - A token program is not the SPL Token or the Token-2022 program.",
+        "* [CustomError::InvalidTokenAccount] - The token accounts do not match a trade."
       ],
       "discriminator": [
-        176,
-        51,
-        115,
-        198,
-        119,
-        249,
-        227,
-        63
+        209,
+        56,
+        148,
+        112,
+        232,
+        7,
+        120,
+        156
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer account that is authorized to perform the close finished trade instruction.",
+            "The signer account that performs the batch close finished trades instruction.",
             "Depends on the trade status and timeout, the signer can be the MPC or anyone."
           ],
-          "writable": true,
           "signer": true
         },
-        {
-          "name": "userAccount",
-          "docs": [
-            "The user_account that receive the rent fee of closed account.",
-            "Must be the [TradeDetail::user_pubkey]"
-          ],
-          "writable": true
-        },
-        {
-          "name": "userTradeDetail",
-          "docs": [
-            "The trade detail PDA that contains the trade information.",
-            "This PDA will be closed by the instruction."
-          ],
-          "writable": true
-        },
-        {
-          "name": "vault",
-          "docs": [
-            "The trade vault PDA that corresponds to the trade.",
-            "This PDA will be closed by the instruction."
-          ],
-          "writable": true
-        },
         {
           "name": "config",
           "docs": [
@@ -493,83 +731,48 @@ export type OptimexSolSmartcontract = {
           }
         },
         {
-          "name": "vaultTokenAccount",
-          "docs": [
-            "The token account of the trade.",
-            "This account will be closed by the instruction."
-          ],
-          "writable": true,
-          "optional": true
-        },
-        {
-          "name": "userTokenAccount",
+          "name": "mpcGroup",
           "docs": [
-            "The user token account that is used to receive the amount if someone transfer the token after closed the trade."
+            "The MPC group of the trades closed by their MPC group, if any."
           ],
-          "writable": true,
           "optional": true
-        },
-        {
-          "name": "tokenProgram",
-          "docs": [
-            "The token program."
-          ],
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         }
       ],
-      "args": [
-        {
-          "name": "closeFinishedTradeArgs",
-          "type": {
-            "defined": {
-              "name": "closeFinishedTradeArgs"
-            }
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "closePaymentReceipt",
+      "name": "batchClosePaymentReceipts",
       "docs": [
-        "Close a [PaymentReceipt] account, reclaim the rent fee.",
+        "Close the payment receipts of the signer in batch, and reclaim their rent fee.",
         "",
-        "The [PaymentReceiptrent fee is transferred to the [PaymentReceipt::from_pubkey] account.",
-        "This instruction is authorized by the [PaymentReceipt::from_pubkey] account.",
-        "Can close after [PaymentReceipt::payment_time] + [Config::close_payment_duration].",
+        "Each payment receipt is checked with the same rules as [close_payment_receipt], the payment receipts given as remaining accounts.",
+        "The payment receipts that cannot be closed yet, or are already closed, are skipped and reported by the [BatchCloseSkipped] event.",
         "# Arguments",
-        "* `ctx` - A [Context] of [ClosePaymentReceiptAccounts] required for closing the payment receipt.",
+        "* `ctx` - A [Context] of [BatchClosePaymentReceiptsAccounts] required for closing the payment receipts.",
         "# Errors",
-        "* [CustomError::InvalidUserAccount] - When the signer is not match to [PaymentReceipt::from_pubkey].",
-        "* [CustomError::CloseNotAvailable] - Not the available time to close the payment receipt."
+        "* [CustomError::InvalidBatchAccounts] - There is no payment receipt.",
+        "* [CustomError::InvalidUserAccount] - The signer is not the payer of a payment receipt."
       ],
       "discriminator": [
-        192,
-        42,
-        180,
-        252,
-        51,
-        166,
-        11,
-        158
+        45,
+        52,
+        18,
+        83,
+        98,
+        83,
+        181,
+        98
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer account, which is authorized to perform the close payment receipt instruction.",
-            "Must be the same as the [PaymentReceipt::from_pubkey]."
+            "The signer account, which is authorized to perform the batch close payment receipts instruction.",
+            "Must be the [PaymentReceipt::from_pubkey] of all the payment receipts."
           ],
           "writable": true,
           "signer": true
         },
-        {
-          "name": "paymentReceipt",
-          "docs": [
-            "The payment receipt PDA that contains the payment information.",
-            "This PDA will be closed by the instruction."
-          ],
-          "writable": true
-        },
         {
           "name": "config",
           "docs": [
@@ -590,78 +793,52 @@ export type OptimexSolSmartcontract = {
               }
             ]
           }
-        },
-        {
-          "name": "systemProgram",
-          "docs": [
-            "System program."
-          ],
-          "address": "11111111111111111111111111111111"
         }
       ],
       "args": []
     },
     {
-      "name": "deposit",
+      "name": "batchSettlement",
       "docs": [
-        "Handles the deposit of either tokens or SOL into the vault.",
-        "",
-        "Only token that is set whitelisted can be deposited.",
+        "Settle several trades sharing the same MPC, PMM and token in one instruction. This instruction is authorized by the MPC and the ephemeral accounts of the trades.",
         "",
-        "The [TradeDetail], [TradeVault], [NonceCheckAccount], [anchor_spl::token::TokenAccount] of vault and token mint, are created in this instruction.",
+        "Each trade is checked with the same rules as [settlement], the trades given as groups of remaining accounts.",
+        "This instruction close the [NonceCheckAccount] of each trade, transfer rent fee to [TradeDetail::rent_payer], and emit a [Settled] event for each trade.",
         "# Arguments",
-        "* `ctx` - A [Context] of [DepositAccounts] required for the deposit.",
-        "* `deposit_args` - An argument [DepositArgs] required for the deposit.",
+        "* `ctx` - A [Context] of [BatchSettlementAccounts] required for settling the trades.",
+        "* `batch_settlement_args` - An argument [BatchSettlementArgs] required for settling the trades.",
         "# Errors",
-        "* [CustomError::NotWhitelistedToken] when the token is not whitelisted.",
-        "* [CustomError::NonceAccountBeingUsed] when the nonce account is being used by another trade, or not yet closed.",
-        "* [CustomError::Unauthorized] when the signer is not match with the pubkey in the [DepositArgs]",
-        "* [CustomError::InvalidTimeout] when the current timestamp is greater than the deposit timeout.",
-        "* [CustomError::DepositZeroAmount] when the deposit amount is zero.",
-        "* [CustomError::InvalidAmount] when the deposit amount is less than the whitelisted amount.",
-        "* [CustomError::InvalidTradeId] when the calculated trade ID is not match with the trade ID in the [DepositArgs].",
-        "* [CustomError::InvalidMintKey] when the mint key is not match with the mint of the trade.",
-        "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.",
-        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the vault and mint.",
-        ""
+        "* [CustomError::InstructionPaused] - The settlement instruction is paused.",
+        "* [CustomError::InvalidBatchAccounts] - There is no trade, or an account is not the PDA of its trade.",
+        "* [CustomError::Unauthorized] - A trade is not authorized by both its MPC and its [TradeDetail::user_ephemeral_pubkey].",
+        "* [CustomError::InvalidMintKey] - A trade is not with the [BatchSettlementArgs::token].",
+        "* [CustomError::TimeOut] - A trade timeout is expired.",
+        "* [CustomError::InvalidTradeStatus] - A trade status is not [TradeStatus::Deposited]."
       ],
       "discriminator": [
-        242,
-        35,
-        198,
-        137,
-        82,
-        225,
-        242,
-        182
+        232,
+        21,
+        230,
+        250,
+        9,
+        182,
+        231,
+        37
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer account that is authorized to perform the deposit instruction.",
-            "This is the account that perform the deposit."
-          ],
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "userTradeDetail",
-          "docs": [
-            "The trade detail PDA that contains the trade information.",
-            "This PDA will be initialized by the instruction."
+            "The signer who is authorized to settle the trades.",
+            "Must be the [TradeDetail::mpc_pubkey] of all the trades, or a member of their [MpcGroup], see [TradeDetail::is_mpc_authorized]."
           ],
-          "writable": true
-        },
-        {
-          "name": "ephemeralAccount",
           "writable": true,
           "signer": true
         },
         {
-          "name": "nonceCheckAccount",
+          "name": "protocol",
           "docs": [
-            "This PDA will be initialized by the instruction."
+            "The protocol PDA account."
           ],
           "writable": true,
           "pda": {
@@ -669,109 +846,53 @@ export type OptimexSolSmartcontract = {
               {
                 "kind": "const",
                 "value": [
-                  110,
+                  112,
+                  114,
+                  111,
+                  116,
                   111,
-                  110,
                   99,
-                  101
+                  111,
+                  108
                 ]
-              },
-              {
-                "kind": "account",
-                "path": "ephemeralAccount"
               }
             ]
           }
         },
         {
-          "name": "vault",
+          "name": "pmm",
           "docs": [
-            "The trade vault PDA that corresponds to the trade.",
-            "This PDA will be initialized by the instruction."
+            "The pmm account, which receives the settlement amount of all the trades."
           ],
           "writable": true
         },
         {
-          "name": "whitelistToken",
-          "docs": [
-            "CHECK",
-            "The whitelist token PDA, only token has been whitelisted can be deposited"
-          ]
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "depositArgs",
-          "type": {
-            "defined": {
-              "name": "depositArgs"
-            }
-          }
-        }
-      ]
-    },
-    {
-      "name": "init",
-      "docs": [
-        "Initialize the program and some required accounts, setup [Config::admin] if needed",
-        "",
-        "This instruction is called after the program is deployed, and is authorized by only the upgrade authority,",
-        "# Arguments",
-        "* `ctx` - A [Context] of [Init] required for initialization",
-        "* `init_args` - An [InitArgs] required for initialization",
-        "",
-        "# Errors",
-        "* [CustomError::Unauthorized] when the caller is not the upgrade authority."
-      ],
-      "discriminator": [
-        220,
-        59,
-        207,
-        236,
-        108,
-        250,
-        47,
-        100
-      ],
-      "accounts": [
-        {
-          "name": "signer",
-          "docs": [
-            "The signer account that is authorized to perform the init instruction.",
-            "Must be the upgrade authority."
-          ],
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "vault",
+          "name": "config",
           "docs": [
-            "The vault PDA account."
+            "The config PDA that contains the protocol configuration.",
+            "The settlement instruction must not be paused."
           ],
-          "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  118,
-                  97,
-                  117,
-                  108,
-                  116
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
                 ]
               }
             ]
           }
         },
         {
-          "name": "protocol",
+          "name": "whitelistToken",
           "docs": [
-            "The protocol PDA account."
+            "The whitelist token PDA of the token, WSOL if the trades are with SOL.",
+            "The trade amounts are removed from its outstanding amount. It may have been removed since the deposits, then nothing is updated."
           ],
           "writable": true,
           "pda": {
@@ -779,23 +900,29 @@ export type OptimexSolSmartcontract = {
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  114,
-                  111,
+                  119,
+                  104,
+                  105,
                   116,
-                  111,
-                  99,
-                  111,
-                  108
+                  101,
+                  108,
+                  105,
+                  115,
+                  116
                 ]
+              },
+              {
+                "kind": "arg",
+                "path": "batch_settlement_args.token.unwrap_or(WSOL_MINT)"
               }
             ]
           }
         },
         {
-          "name": "config",
+          "name": "feeLedger",
           "docs": [
-            "The config PDA account that contains the protocol configuration."
+            "The fee ledger PDA of the token, WSOL if the trades are with SOL.",
+            "The total fees are added to its accrued fee. Will be initialized by the signer if needed."
           ],
           "writable": true,
           "pda": {
@@ -803,13 +930,21 @@ export type OptimexSolSmartcontract = {
               {
                 "kind": "const",
                 "value": [
-                  99,
-                  111,
-                  110,
                   102,
-                  105,
-                  103
+                  101,
+                  101,
+                  95,
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
                 ]
+              },
+              {
+                "kind": "arg",
+                "path": "batch_settlement_args.token.unwrap_or(WSOL_MINT)"
               }
             ]
           }
@@ -822,81 +957,84 @@ export type OptimexSolSmartcontract = {
           "address": "11111111111111111111111111111111"
         },
         {
-          "name": "program",
+          "name": "mpcGroup",
           "docs": [
-            "The program account that contains the protocol program data.",
-            "Must be the program account."
+            "The MPC group of the trades, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].",
+            "The other signing members are appended to the remaining accounts."
           ],
-          "address": "E2pt2s1vZjgf1eBzWhe69qDWawdFKD2u4FbLEFijSMJP"
-        },
-        {
-          "name": "programData",
-          "docs": [
-            "The program data account that contains metadata about the program.",
-            "Must be the program data account."
-          ]
+          "optional": true
         }
       ],
       "args": [
         {
-          "name": "initArgs",
+          "name": "batchSettlementArgs",
           "type": {
             "defined": {
-              "name": "initArgs"
+              "name": "batchSettlementArgs"
             }
           }
         }
       ]
     },
     {
-      "name": "payment",
+      "name": "cancelPendingAction",
       "docs": [
-        "The pmm perform the payment process to a specific trade.",
-        "",
-        "Only token that is set whitelisted can be deposited. The [PaymentReceipt] is created in this instruction.",
+        "Cancel a queued sensitive admin operation.",
         "",
+        "This instruction is authorized by the [Config::admin] or the [Role::Guardian] members.",
+        "This instruction close the [PendingAction] account, and transfer rent fee to the [PendingAction::admin] who queued it.",
         "# Arguments",
-        "* `ctx` - A [Context] of [PaymentAccounts] required for the payment.",
-        "* `payment_args` - An argument [PaymentArgs] required for the payment.",
+        "* `ctx` - A [Context] of [CancelPendingAction] required for cancelling the operation.",
         "# Errors",
-        "* [CustomError::NotWhitelistedToken] when the token is not whitelisted.",
-        "* [CustomError::DeadlineExceeded] when the current timestamp is greater than the [PaymentArgs::deadline].",
-        "* [CustomError::InvalidAmount] when the amount [PaymentArgs::amount] is less than the [PaymentArgs::total_fee].",
-        "* [CustomError::InvalidMintKey] when the mint key is not match with the [PaymentReceipt::token].",
-        "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.",
-        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the [PaymentReceipt::to_pubkey] and mint.",
-        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the protocol PDA and mint."
+        "* [CustomError::Unauthorized] - The caller is not the admin or a guardian.",
+        "* [CustomError::InvalidUserAccount] - The admin account is not the [PendingAction::admin]."
       ],
       "discriminator": [
-        156,
-        226,
-        80,
-        91,
-        104,
-        252,
-        49,
-        142
+        218,
+        126,
+        76,
+        224,
+        30,
+        9,
+        86,
+        114
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer account who perform the payment."
+            "The signer account that is authorized to perform the cancel pending action instruction.",
+            "Must be the [Config::admin] or have the [Role::Guardian] role."
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "toUser",
+          "name": "config",
           "docs": [
-            "The account to which the payment sent to."
+            "The config PDA account that contains the protocol configuration."
           ],
-          "writable": true
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "protocol",
+          "name": "pendingAction",
           "docs": [
-            "The protocol PDA account to which the total fee will be sent."
+            "The pending action PDA account to cancel.",
+            "Will be closed and transferred rent fee to the admin who queued it."
           ],
           "writable": true,
           "pda": {
@@ -905,110 +1043,149 @@ export type OptimexSolSmartcontract = {
                 "kind": "const",
                 "value": [
                   112,
-                  114,
-                  111,
-                  116,
-                  111,
+                  101,
+                  110,
+                  100,
+                  105,
+                  110,
+                  103,
+                  95,
+                  97,
                   99,
+                  116,
+                  105,
                   111,
-                  108
+                  110
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "pending_action.action",
+                "account": "pendingAction"
               }
             ]
           }
         },
         {
-          "name": "whitelistToken",
-          "docs": [
-            "The whitelist token PDA, only token has been whitelisted can be payment."
-          ]
-        },
-        {
-          "name": "paymentReceipt",
+          "name": "admin",
           "docs": [
-            "The payment receipt PDA that contains the payment information.",
-            "This PDA will be initialized by the instruction."
+            "The admin who queued the action and paid its rent fee, refunded on cancellation.",
+            "Must be the [PendingAction::admin]."
           ],
           "writable": true
         },
         {
-          "name": "systemProgram",
+          "name": "guardian",
           "docs": [
-            "System program."
-          ],
-          "address": "11111111111111111111111111111111"
+            "The role member PDA account of the signer as [Role::Guardian], unless the signer is the [Config::admin]."
+          ]
         }
       ],
-      "args": [
-        {
-          "name": "paymentArgs",
-          "type": {
-            "defined": {
-              "name": "paymentArgs"
-            }
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "removeFeeReceiver",
+      "name": "claim",
       "docs": [
-        "Remove fee receiver.",
+        "Claim the deposited amount after the timeout. This instruction is authorized by anyone.",
         "",
-        "This instruction is authorized by the [Config::admin].",
-        "This instruction close the [FeeReceiver] account, and transfer rent fee to the signer.",
+        "The deposited amount is transferred to the [TradeDetail::refund_pubkey].",
+        "This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.",
         "# Arguments",
-        "* `ctx` - A [Context] of [RemoveFeeReceiver] required for removing the fee receiver.",
-        "* `receiver_pubkey` - The pubkey of the fee receiver.",
+        "* `ctx` - A [Context] of [Claim] required for claiming the deposited amount.",
+        "* `claim_args` - An argument [ClaimArgs] required for claiming the deposited amount.",
         "# Errors",
-        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+        "* [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::rent_payer].",
+        "* [CustomError::InvalidRefundPubkey] when the refund pubkey address is not match with the [TradeDetail::refund_pubkey].",
+        "* [CustomError::CLaimNotAvailable] when the [TradeDetail::timeout] is not expired, so we cannot claim the deposited amount.",
+        "* [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited], we only claim the Deposited trade and timed out.",
+        "* [CustomError::InvalidMintKey] when the mint key is not match with the [TradeDetail::token]",
+        "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the vault and mint.",
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the refund pubkey."
       ],
       "discriminator": [
-        137,
-        155,
-        52,
-        88,
-        218,
-        163,
-        184,
-        76
+        62,
+        198,
+        214,
+        193,
+        213,
+        159,
+        108,
+        210
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The admin that is authorized to perform the remove fee receiver instruction.",
-            "Must be the [Config::admin]"
+            "The signer account that is authorized to perform the claim instruction.",
+            "Can be anyone."
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "config",
+          "name": "userAccount",
           "docs": [
-            "The config PDA account that contains the protocol configuration."
+            "",
+            "The user account that paid the rent of the trade.",
+            "Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored."
+          ],
+          "writable": true
+        },
+        {
+          "name": "nonceCheckAccount",
+          "docs": [
+            "The nonce check account PDA that flag whether the nonce is currently active or not.",
+            "Will be closed and transferred rent to the user_account."
           ],
+          "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  99,
+                  110,
                   111,
                   110,
-                  102,
-                  105,
-                  103
+                  99,
+                  101
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "user_trade_detail.user_ephemeral_pubkey",
+                "account": "tradeDetail"
               }
             ]
           }
         },
         {
-          "name": "feeReceiverAccount",
+          "name": "userTradeDetail",
           "docs": [
-            "The fee receiver PDA account that contains the fee receiver information.",
-            "Will be closed and transferred rent fee to the signer."
+            "The trade detail PDA that contains the trade information."
+          ],
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The trade vault PDA that corresponds to the trade."
+          ],
+          "writable": true
+        },
+        {
+          "name": "refundAccount",
+          "docs": [
+            "",
+            "The refund account of the trade.",
+            "Must be the [TradeDetail::refund_pubkey]"
+          ],
+          "writable": true
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "The whitelist token PDA of the trade token, WSOL if the trade is with SOL.",
+            "The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated."
           ],
           "writable": true,
           "pda": {
@@ -1016,23 +1193,21 @@ export type OptimexSolSmartcontract = {
               {
                 "kind": "const",
                 "value": [
-                  102,
-                  101,
-                  101,
-                  95,
-                  114,
-                  101,
-                  99,
-                  101,
+                  119,
+                  104,
                   105,
-                  118,
+                  116,
                   101,
-                  114
+                  108,
+                  105,
+                  115,
+                  116
                 ]
               },
               {
-                "kind": "arg",
-                "path": "receiverPubkey"
+                "kind": "account",
+                "path": "user_trade_detail.token.unwrap_or(WSOL_MINT)",
+                "account": "tradeDetail"
               }
             ]
           }
@@ -1047,46 +1222,84 @@ export type OptimexSolSmartcontract = {
       ],
       "args": [
         {
-          "name": "receiverPubkey",
-          "type": "pubkey"
+          "name": "claimArgs",
+          "type": {
+            "defined": {
+              "name": "claimArgs"
+            }
+          }
         }
       ]
     },
     {
-      "name": "removeWhitelist",
+      "name": "closeFinishedTrade",
       "docs": [
-        "Remove whitelist token setup.",
+        "Close the finished trade ([TradeStatus::Settled], [TradeStatus::Claimed] or [TradeStatus::Refunded]) to reclaim the rent fee.",
         "",
-        "This instruction is authorized by the operator.",
+        "Transfer the rent fee of [TradeDetail], [TradeVault] and [anchor_spl::token::TokenAccount] to the [TradeDetail::rent_payer].",
+        "",
+        "Depend on the trade status, the close action is different:",
+        "* When the trade is [TradeStatus::Deposited], this action is not allowed.",
+        "* When the trade is [TradeStatus::Claimed] or [TradeStatus::Refunded], this action is allowed for anyone.",
+        "* When the trade is [TradeStatus::Settled], MPC can close the trade right away. Otherwise, anyone can close the trade after the [TradeDetail::timeout] + [Config::close_trade_duration].",
         "# Arguments",
-        "* `ctx` - A [Context] of [RemoveWhitelist] required for removing the whitelist.",
+        "* `ctx` - A [Context] of [CloseFinishedTradeAccounts] required for closing the trade.",
+        "* `_close_finished_trade_args` - An argument [CloseFinishedTradeArgs] required for closing the trade.",
         "# Errors",
-        "* [CustomError::Unauthorized] - The caller is not authorized, or not the operator."
+        "* [CustomError::InvalidUserAccount] when the user account is not match to [TradeDetail::rent_payer]. This account will receive the claimed rent fee.",
+        "* [CustomError::InvalidTradeStatus] when the trade status is [TradeStatus::Deposited].",
+        "* [CustomError::CloseNotAvailable] when the trade is not the available time to close.",
+        "* [CustomError::InvalidTokenAccount] when the token accounts or the mint are missing or do not match the trade.",
+        "* [CustomError::InvalidMintKey] when the mint is not match with the [TradeDetail::token]."
       ],
       "discriminator": [
-        148,
-        244,
-        73,
-        234,
-        131,
-        55,
-        247,
-        90
+        176,
+        51,
+        115,
+        198,
+        119,
+        249,
+        227,
+        63
       ],
       "accounts": [
         {
-          "name": "operator",
+          "name": "signer",
           "docs": [
-            "The operator that is authorized to perform the remove whitelist instruction.",
-            "Must be the [Config::operators]"
+            "The signer account that is authorized to perform the close finished trade instruction.",
+            "Depends on the trade status and timeout, the signer can be the MPC or anyone."
           ],
           "writable": true,
           "signer": true
         },
+        {
+          "name": "userAccount",
+          "docs": [
+            "The user_account that receive the rent fee of closed account.",
+            "Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored."
+          ],
+          "writable": true
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The trade detail PDA that contains the trade information.",
+            "This PDA will be closed by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The trade vault PDA that corresponds to the trade.",
+            "This PDA will be closed by the instruction."
+          ],
+          "writable": true
+        },
         {
           "name": "config",
           "docs": [
-            "The config PDA account that contains the protocol configuration."
+            "The config PDA that contains the protocol configuration."
           ],
           "pda": {
             "seeds": [
@@ -1105,185 +1318,219 @@ export type OptimexSolSmartcontract = {
           }
         },
         {
-          "name": "whitelistToken",
+          "name": "vaultTokenAccount",
           "docs": [
-            "The whitelist token PDA account that contains the whitelist token information.",
-            "If SOL native, used WSOL PDA account."
+            "The token account of the trade.",
+            "This account will be closed by the instruction."
           ],
-          "writable": true
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "token",
+          "name": "userTokenAccount",
           "docs": [
-            "The mint token account that we want to remove whitelist."
+            "The user token account that is used to receive the amount if someone transfer the token after closed the trade.",
+            "Must be the associated token account of the [TradeDetail::user_pubkey], even when the deposit was sponsored."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "mint",
+          "docs": [
+            "The mint of the trade token. Required when the trade is with token.",
+            "Must be the [TradeDetail::token]."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "docs": [
+            "The token program, either the SPL Token or the Token-2022 program."
           ]
         },
         {
-          "name": "systemProgram",
+          "name": "mpcGroup",
           "docs": [
-            "System program."
+            "The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup] that closes the trade.",
+            "The other signing members are appended to the remaining accounts."
           ],
-          "address": "11111111111111111111111111111111"
+          "optional": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "closeFinishedTradeArgs",
+          "type": {
+            "defined": {
+              "name": "closeFinishedTradeArgs"
+            }
+          }
+        }
+      ]
     },
     {
-      "name": "setCloseWaitDuration",
+      "name": "closePaymentExpectation",
       "docs": [
-        "Set the waiting duration for closing a finished trade or close a payment receipt.",
+        "The MPC closes the [PaymentExpectation] to reclaim the rent fee.",
+        "",
+        "Only available when the expectation is fulfilled, or the [PaymentExpectation::deadline] is passed.",
         "",
-        "This instruction is authorized by the [Config::operators].",
         "# Arguments",
-        "* `ctx` - A [Context] of [SetCloseWaitDuration] required for setting the waiting duration.",
-        "* `set_close_wait_duration_args` - An argument [SetCloseWaitDurationArgs] required for setting the waiting duration.",
+        "* `ctx` - A [Context] of [ClosePaymentExpectationAccounts] required for closing the payment expectation.",
         "# Errors",
-        "* [CustomError::Unauthorized] when the caller is not authorized."
+        "* [CustomError::Unauthorized] - When the caller is not authorized by the MPC of the expectation, see [PaymentExpectation::is_mpc_authorized].",
+        "* [CustomError::CloseNotAvailable] - When the payment expectation is not fulfilled and the deadline is not passed."
       ],
       "discriminator": [
-        14,
-        233,
-        71,
-        143,
-        55,
-        182,
-        177,
-        231
+        175,
+        91,
+        223,
+        165,
+        171,
+        172,
+        3,
+        139
       ],
       "accounts": [
         {
-          "name": "operator",
+          "name": "signer",
           "docs": [
-            "The operator that is authorized to perform the set close wait duration instruction.",
-            "Must be [Config::operators]"
+            "The MPC account who published the payment expectation, and receive the rent fee.",
+            "Must be the [PaymentExpectation::mpc_pubkey], or a member of its MPC group."
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "config",
+          "name": "paymentExpectation",
           "docs": [
-            "The config PDA account that contains the protocol configuration."
+            "The payment expectation PDA.",
+            "This PDA will be closed by the instruction."
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  99,
-                  111,
-                  110,
-                  102,
-                  105,
-                  103
-                ]
-              }
-            ]
-          }
-        }
-      ],
-      "args": [
+          "writable": true
+        },
         {
-          "name": "setCloseWaitDurationArgs",
-          "type": {
-            "defined": {
-              "name": "setCloseWaitDurationArgs"
-            }
-          }
+          "name": "mpcGroup",
+          "docs": [
+            "The MPC group of the expectation, only when the [PaymentExpectation::mpc_pubkey] is an [MpcGroup].",
+            "The other signing members are appended to the remaining accounts."
+          ],
+          "optional": true
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "setTotalFee",
+      "name": "closePaymentReceipt",
       "docs": [
-        "Set the total fee for a specific trade.",
+        "Close a [PaymentReceipt] account, reclaim the rent fee.",
         "",
-        "This instruction is authorized by [TradeDetail::mpc_pubkey]. This fee is deducted from the [TradeDetail::amount] when settling.",
+        "The [PaymentReceiptrent fee is transferred to the [PaymentReceipt::from_pubkey] account.",
+        "This instruction is authorized by the [PaymentReceipt::from_pubkey] account.",
+        "Can close after [PaymentReceipt::payment_time] + [Config::close_payment_duration].",
         "# Arguments",
-        "* `ctx` - A [Context] of [SetTotalFee] required for setting the total fee.",
-        "* `set_total_fee_args` - An argument [SetTotalFeeArgs] required for setting the total fee.",
+        "* `ctx` - A [Context] of [ClosePaymentReceiptAccounts] required for closing the payment receipt.",
         "# Errors",
-        "* [CustomError::Unauthorized] when the caller is not authorized, or not the mpc of the trade.",
-        "* [CustomError::TimeOut] when the trade timeout is expired, so we cannot set the total fee anymore."
+        "* [CustomError::InvalidUserAccount] - When the signer is not match to [PaymentReceipt::from_pubkey].",
+        "* [CustomError::CloseNotAvailable] - Not the available time to close the payment receipt."
       ],
       "discriminator": [
-        4,
-        250,
-        240,
-        112,
-        5,
-        249,
-        79,
-        109
+        192,
+        42,
+        180,
+        252,
+        51,
+        166,
+        11,
+        158
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer account who is authorized to set the total fee.",
-            "Must be the [TradeDetail::mpc_pubkey]."
+            "The signer account, which is authorized to perform the close payment receipt instruction.",
+            "Must be the same as the [PaymentReceipt::from_pubkey]."
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "userTradeDetail",
+          "name": "paymentReceipt",
           "docs": [
-            "The user trade detail account that contains the trade information."
+            "The payment receipt PDA that contains the payment information.",
+            "This PDA will be closed by the instruction."
           ],
           "writable": true
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "setTotalFeeArgs",
-          "type": {
-            "defined": {
-              "name": "setTotalFeeArgs"
-            }
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
           }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "settlement",
+      "name": "cooperativeRefund",
       "docs": [
-        "MPC settles the trade, tranfer the settlement amount to the pmm and the total fee to the protocol, after the pmm paid to users.",
+        "Refund the deposited amount before the timeout, when the trade will not be filled.",
         "",
         "This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey].",
-        "This instruction is called after the pmm paid to users, and before the [TradeDetail::timeout].",
-        "This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::user_pubkey], and allow the nonce can be used by other trade.",
+        "The deposited amount is transferred to the [TradeDetail::refund_pubkey], and the trade status becomes [TradeStatus::Refunded].",
+        "This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.",
         "# Arguments",
-        "* `ctx` - A [Context] of [SettlementAccounts] required for settling the trade.",
-        "* `payment_args` - An argument [SettlementArgs] required for settling the trade.",
+        "* `ctx` - A [Context] of [CooperativeRefundAccounts] required for refunding the deposited amount.",
+        "* `cooperative_refund_args` - An argument [CooperativeRefundArgs] required for refunding the deposited amount.",
         "# Errors",
-        "* [CustomError::Unauthorized] when the caller is not authorized by both [TradeDetail::mpc_pubkey] and [TradeDetail::user_ephemeral_pubkey].",
-        "* [CustomError::InvalidUserAccount] when the user account is not match with [TradeDetail::user_pubkey].",
-        "* [CustomError::InvalidRefundPubkey] when the refund pubkey is not match with [TradeDetail::refund_pubkey].",
-        "* [CustomError::TimeOut] when the trade timeout is expired, so we cannot settle the trade anymore.",
-        "* [CustomError::InvalidTradeStatus] when the trade status is not [TradeStatus::Deposited]. We only can settle the trade has deposited status.",
-        "* [CustomError::InvalidMintKey] when the mint key is not match with the mint of the trade.",
+        "* [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].",
+        "* [CustomError::InvalidUserAccount] when the user account not match with [TradeDetail::rent_payer].",
+        "* [CustomError::InvalidRefundPubkey] when the refund pubkey address is not match with the [TradeDetail::refund_pubkey].",
+        "* [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited].",
+        "* [CustomError::InvalidMintKey] when the mint key is not match with the [TradeDetail::token]",
         "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the vault and mint.",
-        "* [CustomError::InvalidDestinationAta] when the destination to transfer settlement amount is not the associated token account of the pmm and mint.",
-        "* [CustomError::InvalidDestinationAta] when the destination to transfer total fee is not the associated token account of the protocol and mint."
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the refund pubkey."
       ],
       "discriminator": [
-        128,
-        21,
-        174,
-        60,
-        47,
-        86,
-        130,
-        108
+        20,
+        74,
+        239,
+        231,
+        14,
+        3,
+        112,
+        38
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer who is authorized to settle the trade.",
-            "Must be the [TradeDetail::mpc_pubkey]"
+            "The signer who is authorized to refund the trade.",
+            "Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized]."
           ],
           "writable": true,
           "signer": true
@@ -1291,8 +1538,9 @@ export type OptimexSolSmartcontract = {
         {
           "name": "userAccount",
           "docs": [
-            "The user account that is the depositor of the trade. This account will receive the rent fee of the nonce check account PDA.",
-            "Must be the [TradeDetail::user_pubkey]."
+            "",
+            "The user account that paid the rent of the trade. This account will receive the rent fee of the nonce check account PDA.",
+            "Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored."
           ],
           "writable": true
         },
@@ -1304,18 +1552,11 @@ export type OptimexSolSmartcontract = {
           ],
           "signer": true
         },
-        {
-          "name": "userTradeDetail",
-          "docs": [
-            "The user trade detail PDA that contains the trade information."
-          ],
-          "writable": true
-        },
         {
           "name": "nonceCheckAccount",
           "docs": [
-            "The nonce check account PDA, used to check the nonce account is being used by another trade, or not yet closed.",
-            "This PDA will be closed by the instruction."
+            "The nonce check account PDA that flag whether the nonce is currently active or not.",
+            "Will be closed and transferred rent to the user_account."
           ],
           "writable": true,
           "pda": {
@@ -1337,6 +1578,13 @@ export type OptimexSolSmartcontract = {
             ]
           }
         },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The trade detail PDA that contains the trade information."
+          ],
+          "writable": true
+        },
         {
           "name": "vault",
           "docs": [
@@ -1347,15 +1595,17 @@ export type OptimexSolSmartcontract = {
         {
           "name": "refundAccount",
           "docs": [
+            "",
             "The refund account of the trade.",
-            "Must be the [TradeDetail::refund_pubkey]."
+            "Must be the [TradeDetail::refund_pubkey]"
           ],
           "writable": true
         },
         {
-          "name": "protocol",
+          "name": "whitelistToken",
           "docs": [
-            "The protocol PDA account."
+            "The whitelist token PDA of the trade token, WSOL if the trade is with SOL.",
+            "The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated."
           ],
           "writable": true,
           "pda": {
@@ -1363,418 +1613,6055 @@ export type OptimexSolSmartcontract = {
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  114,
-                  111,
+                  119,
+                  104,
+                  105,
                   116,
-                  111,
-                  99,
-                  111,
-                  108
+                  101,
+                  108,
+                  105,
+                  115,
+                  116
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "user_trade_detail.token.unwrap_or(WSOL_MINT)",
+                "account": "tradeDetail"
               }
             ]
           }
         },
         {
-          "name": "pmm",
+          "name": "systemProgram",
           "docs": [
-            "The pmm account."
+            "System program."
           ],
-          "writable": true
+          "address": "11111111111111111111111111111111"
         },
         {
-          "name": "systemProgram",
+          "name": "mpcGroup",
           "docs": [
-            "System program."
+            "The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].",
+            "The other signing members are appended to the remaining accounts."
           ],
-          "address": "11111111111111111111111111111111"
+          "optional": true
         }
       ],
       "args": [
         {
-          "name": "settleArgs",
+          "name": "cooperativeRefundArgs",
           "type": {
             "defined": {
-              "name": "settlementArgs"
+              "name": "cooperativeRefundArgs"
             }
           }
         }
       ]
     },
     {
-      "name": "withdrawTotalFee",
+      "name": "createPaymentExpectation",
       "docs": [
-        "Withdraw the total fee of the protocol to fee receiver.",
+        "The MPC publishes the expected payment of a specific trade.",
+        "",
+        "The [PaymentExpectation] is created in this instruction, and is used by the strict payment instruction.",
+        "",
+        "This instruction is authorized by a registered MPC key, or the threshold of an [MpcGroup].",
         "",
-        "This instruction is authorized by anyone.",
-        "However, only account decaled as [FeeReceiver] can receive the fee.",
         "# Arguments",
-        "* `ctx` - A [Context] of [WithdrawTotalFeeAccounts] required for withdrawing the total fee.",
-        "* `withdraw_total_fee_args` - An argument [WithdrawTotalFeeArgs] required for withdrawing the total fee.",
+        "* `ctx` - A [Context] of [CreatePaymentExpectationAccounts] required for publishing the payment expectation.",
+        "* `args` - An argument [CreatePaymentExpectationArgs] required for publishing the payment expectation.",
         "# Errors",
-        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+        "* [CustomError::Unauthorized] when the `mpc_group` is given, and less than [MpcGroup::threshold] members signed.",
+        "* [CustomError::MpcNotRegistered] when the MPC key, or the address of the `mpc_group`, is not registered and active.",
+        "* [CustomError::InvalidAmount] when the [CreatePaymentExpectationArgs::min_amount] is zero.",
+        "* [CustomError::DeadlineExceeded] when the [CreatePaymentExpectationArgs::deadline] is already passed."
       ],
       "discriminator": [
-        39,
-        248,
-        168,
-        154,
-        219,
-        50,
-        222,
-        110
+        61,
+        232,
+        74,
+        234,
+        17,
+        169,
+        188,
+        118
       ],
       "accounts": [
         {
           "name": "signer",
           "docs": [
-            "The signer account who perform the withdraw total fee.",
-            "Can be anyone"
+            "The MPC account who publishes the payment expectation, and pays the rent fee.",
+            "A member of the `mpc_group` when the expectation is published by a group."
           ],
           "writable": true,
           "signer": true
         },
         {
-          "name": "toUser",
+          "name": "mpcGroup",
           "docs": [
-            "The account to which the total fee will be sent. Must be decaled as a [FeeReceiver]."
+            "The MPC group publishing the expectation, none if published by the signer's own MPC key.",
+            "The other signing members are appended to the remaining accounts."
           ],
-          "writable": true
+          "optional": true
         },
         {
-          "name": "feeReceiver",
+          "name": "mpcRegistry",
           "docs": [
-            "The fee receiver PDA account that contains the fee receiver information."
+            "The MPC registry PDA of the MPC key of the expectation, see [PaymentExpectation::mpc_key].",
+            "The MPC key must be registered and active, see [MpcRegistry::assert_active]."
+          ]
+        },
+        {
+          "name": "paymentExpectation",
+          "docs": [
+            "The payment expectation PDA that contains the expected payment of the trade.",
+            "This PDA will be initialized by the instruction."
           ],
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  102,
-                  101,
-                  101,
-                  95,
-                  114,
-                  101,
-                  99,
-                  101,
-                  105,
-                  118,
-                  101,
-                  114
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "toUser"
-              }
-            ]
-          }
+          "writable": true
         },
         {
-          "name": "protocol",
+          "name": "systemProgram",
           "docs": [
-            "The protocol PDA account which own the protocol fee."
+            "System program."
           ],
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  112,
-                  114,
-                  111,
-                  116,
-                  111,
-                  99,
-                  111,
-                  108
-                ]
-              }
-            ]
-          }
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "withdrawTotalFeeArgs",
+          "name": "args",
           "type": {
             "defined": {
-              "name": "withdrawTotalFeeArgs"
+              "name": "createPaymentExpectationArgs"
             }
           }
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "config",
-      "discriminator": [
-        155,
-        12,
-        170,
-        224,
-        30,
-        250,
-        204,
-        130
-      ]
-    },
-    {
-      "name": "feeReceiver",
+      "name": "deposit",
+      "docs": [
+        "Handles the deposit of either tokens or SOL into the vault.",
+        "",
+        "Only token that is set whitelisted can be deposited.",
+        "",
+        "The [TradeDetail], [TradeVault], [NonceCheckAccount], [anchor_spl::token::TokenAccount] of vault and token mint, are created in this instruction.",
+        "Both SPL Token and Token-2022 tokens are supported. The [TradeDetail::amount] is the amount the vault actually received, after the transfer fee if any.",
+        "When the [Config::solver_signature_required], a previous secp256k1 program instruction must verify the signature",
+        "of the [TradeInput::solver] over the trade id.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [DepositAccounts] required for the deposit.",
+        "* `deposit_args` - An argument [DepositArgs] required for the deposit.",
+        "# Errors",
+        "* [CustomError::InstructionPaused] when the deposit instruction is paused.",
+        "* [CustomError::NotWhitelistedToken] when the token is not whitelisted.",
+        "* [CustomError::NonceAccountBeingUsed] when the nonce account is being used by another trade, or not yet closed.",
+        "* [CustomError::Unauthorized] when the signer is not match with the pubkey in the [DepositArgs]",
+        "* [CustomError::InvalidTimeout] when the current timestamp is greater than the deposit timeout.",
+        "* [CustomError::MpcNotRegistered] when the MPC of the trade is not registered, or not active in the [MpcRegistry].",
+        "* [CustomError::DepositZeroAmount] when the deposit amount is zero.",
+        "* [CustomError::InvalidAmount] when the deposit amount is less than the whitelisted amount.",
+        "* [CustomError::MaxAmountExceeded] when the deposit amount is greater than the [WhitelistToken::max_amount].",
+        "* [CustomError::OutstandingLimitReached] when the deposit makes the [WhitelistToken::outstanding] greater than the [WhitelistToken::max_outstanding].",
+        "* [CustomError::InvalidTradeId] when the trade ID calculated with the [DepositArgs::trade_id_scheme] is not match with the trade ID in the [DepositArgs].",
+        "* [CustomError::InvalidTokenProgram] when the token program is not the SPL Token or the Token-2022 program.",
+        "* [CustomError::InvalidMintKey] when the mint key is not match with the mint of the trade.",
+        "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.",
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the vault and mint.",
+        "* [CustomError::InvalidSolverSignature] when the solver signature is required, and not verified by a previous instruction.",
+        ""
+      ],
       "discriminator": [
-        217,
-        123,
-        124,
-        75,
-        212,
-        179,
-        171,
-        135
+        242,
+        35,
+        198,
+        137,
+        82,
+        225,
+        242,
+        182
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the deposit instruction.",
+            "This is the account that perform the deposit."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The trade detail PDA that contains the trade information.",
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "ephemeralAccount",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "nonceCheckAccount",
+          "docs": [
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "ephemeralAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The trade vault PDA that corresponds to the trade.",
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "CHECK",
+            "The whitelist token PDA, only token has been whitelisted can be deposited",
+            "The deposited amount is added to its outstanding amount."
+          ],
+          "writable": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration.",
+            "The deposit instruction must not be paused."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "mpcRegistry",
+          "docs": [
+            "The MPC registry PDA of the [TradeDetailInput::mpc_pubkey].",
+            "The MPC key must be registered and active, see [MpcRegistry::assert_active]."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  112,
+                  99,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deposit_args.data.mpc_pubkey"
+              }
+            ]
+          }
+        },
+        {
+          "name": "instructionsSysvar",
+          "docs": [
+            "The instructions sysvar, to read the secp256k1 program instruction that verifies the solver signature.",
+            "Required when the [Config::solver_signature_required]."
+          ],
+          "optional": true,
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "depositArgs",
+          "type": {
+            "defined": {
+              "name": "depositArgs"
+            }
+          }
+        }
       ]
     },
     {
-      "name": "nonceCheckAccount",
+      "name": "distributeFees",
+      "docs": [
+        "Distribute the available fee of a token to all the fee receivers, pro rata of their [FeeReceiver::share_bps].",
+        "",
+        "This instruction is authorized by anyone.",
+        "The fee receivers are given as remaining accounts, and must include every fee receiver with a share.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [DistributeFeesAccounts] required for the distribution.",
+        "* `distribute_fees_args` - An argument [DistributeFeesArgs] required for the distribution.",
+        "# Errors",
+        "* [CustomError::InstructionPaused] - The distribute fees instruction is paused.",
+        "* [CustomError::InvalidFeeShare] - The shares of the fee receivers do not sum to 100%, or a fee receiver is missing or duplicated.",
+        "* [CustomError::InvalidFeeReceiver] - A fee receiver account is not a [FeeReceiver] PDA."
+      ],
       "discriminator": [
-        191,
-        217,
-        36,
-        242,
-        192,
-        98,
-        193,
-        237
-      ]
+        120,
+        56,
+        27,
+        7,
+        53,
+        176,
+        113,
+        186
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account who perform the distribution.",
+            "Can be anyone"
+          ],
+          "signer": true
+        },
+        {
+          "name": "protocol",
+          "docs": [
+            "The protocol PDA account which own the protocol fee."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration.",
+            "The distribute fees instruction must not be paused."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "The fee ledger PDA of the token, WSOL if the fee is SOL.",
+            "The distributed fee is added to its withdrawn fee."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "distribute_fees_args.token.unwrap_or(WSOL_MINT)"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "distributeFeesArgs",
+          "type": {
+            "defined": {
+              "name": "distributeFeesArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "extendTimeout",
+      "docs": [
+        "Extend the timeout of a deposited trade, when the trade is slow but still healthy.",
+        "",
+        "This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey].",
+        "The total extension of a trade is bounded by the [Config::max_timeout_extension].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [ExtendTimeoutAccounts] required for extending the timeout.",
+        "* `extend_timeout_args` - An argument [ExtendTimeoutArgs] required for extending the timeout.",
+        "# Errors",
+        "* [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].",
+        "* [CustomError::InvalidTradeStatus] when the [TradeDetail::status] is not [TradeStatus::Deposited].",
+        "* [CustomError::TimeOut] when the trade is already expired.",
+        "* [CustomError::InvalidTimeout] when the new timeout is not after the current [TradeDetail::timeout].",
+        "* [CustomError::TimeoutExtensionExceeded] when the total extension of the trade exceeds the [Config::max_timeout_extension]."
+      ],
+      "discriminator": [
+        246,
+        84,
+        96,
+        134,
+        76,
+        55,
+        57,
+        33
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer who is authorized to extend the timeout of the trade.",
+            "Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized]."
+          ],
+          "signer": true
+        },
+        {
+          "name": "userEphemeralAccount",
+          "docs": [
+            "The user ephemeral account of the trade, need to sign this transaction too.",
+            "Must be the [TradeDetail::user_ephemeral_pubkey]."
+          ],
+          "signer": true
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The trade detail PDA that contains the trade information."
+          ],
+          "writable": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration, with the [Config::max_timeout_extension]."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mpcGroup",
+          "docs": [
+            "The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].",
+            "The other signing members are appended to the remaining accounts."
+          ],
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "extendTimeoutArgs",
+          "type": {
+            "defined": {
+              "name": "extendTimeoutArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "getFeeLedger",
+      "docs": [
+        "Get the fee accrued, withdrawn and available for a token, from its [FeeLedger].",
+        "",
+        "This instruction is read only, and can be simulated by anyone for the treasury reporting.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [GetFeeLedgerAccounts] required for the query."
+      ],
+      "discriminator": [
+        172,
+        108,
+        78,
+        104,
+        78,
+        164,
+        68,
+        53
+      ],
+      "accounts": [
+        {
+          "name": "feeLedger",
+          "docs": [
+            "The fee ledger PDA of the token, WSOL for SOL."
+          ]
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": {
+          "name": "feeLedgerBalance"
+        }
+      }
+    },
+    {
+      "name": "grantRole",
+      "docs": [
+        "Grant a role of the protocol to a member.",
+        "",
+        "This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [GrantRole] required for granting the role.",
+        "* `role` - The [Role] to grant.",
+        "* `member` - The member of the role.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::InvalidPublicKey] - The member is the default pubkey.",
+        "* [CustomError::TimelockRequired] - The operation must be queued first.",
+        "* [CustomError::TimelockNotReady] - The pending action cannot be executed yet."
+      ],
+      "discriminator": [
+        218,
+        234,
+        128,
+        15,
+        82,
+        33,
+        236,
+        253
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the grant role instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the role and member.",
+            "Will be initialized by the signer."
+          ],
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "pendingAction",
+          "docs": [
+            "The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.",
+            "Will be closed and transferred rent fee to the signer."
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "role",
+          "type": {
+            "defined": {
+              "name": "role"
+            }
+          }
+        },
+        {
+          "name": "member",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "init",
+      "docs": [
+        "Initialize the program and some required accounts, setup [Config::admin] if needed",
+        "",
+        "This instruction is called after the program is deployed, and is authorized by only the upgrade authority,",
+        "Later admin rotations are done by the admin itself, through [propose_admin] and [accept_admin].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [Init] required for initialization",
+        "* `init_args` - An [InitArgs] required for initialization",
+        "",
+        "# Errors",
+        "* [CustomError::Unauthorized] when the caller is not the upgrade authority."
+      ],
+      "discriminator": [
+        220,
+        59,
+        207,
+        236,
+        108,
+        250,
+        47,
+        100
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the init instruction.",
+            "Must be the upgrade authority."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The vault PDA account."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "protocol",
+          "docs": [
+            "The protocol PDA account."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "program",
+          "docs": [
+            "The program account that contains the protocol program data.",
+            "Must be the program account."
+          ],
+          "address": "E2pt2s1vZjgf1eBzWhe69qDWawdFKD2u4FbLEFijSMJP"
+        },
+        {
+          "name": "programData",
+          "docs": [
+            "The program data account that contains metadata about the program.",
+            "Must be the program data account."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "initArgs",
+          "type": {
+            "defined": {
+              "name": "initArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "intentDeposit",
+      "docs": [
+        "Deposit the trade from a deposit intent signed off-chain by the user, and submitted by a relayer.",
+        "",
+        "The user does not sign the transaction, the instruction must be preceded by an Ed25519 program instruction",
+        "that verifies the user signature over the [DepositIntent::message].",
+        "Only tokens can be deposited, the amount is transferred by the delegate PDA that the user approved on its token account.",
+        "The relayer pays the rent of the trade accounts, like in the [sponsored_deposit].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [IntentDepositAccounts] required for the deposit.",
+        "* `deposit_args` - An argument [DepositArgs] required for the deposit.",
+        "# Errors",
+        "* [CustomError::InvalidDepositIntent] when the deposit is SOL.",
+        "* [CustomError::InvalidSignature] when the user signature of the deposit intent is not verified by the previous instruction.",
+        "* The errors of the [deposit] instruction."
+      ],
+      "discriminator": [
+        68,
+        136,
+        105,
+        226,
+        27,
+        45,
+        138,
+        76
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "The payer account that submits the deposit intent and pays the rent of the trade accounts, usually a relayer."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "user",
+          "docs": [
+            "The user account that performs the deposit, authorized by its signature of the [DepositIntent]."
+          ]
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The trade detail PDA that contains the trade information.",
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "ephemeralAccount",
+          "docs": [
+            "Part of the [DepositIntent], so it does not need to sign."
+          ]
+        },
+        {
+          "name": "nonceCheckAccount",
+          "docs": [
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "ephemeralAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The trade vault PDA that corresponds to the trade.",
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "CHECK",
+            "The whitelist token PDA, only token has been whitelisted can be deposited",
+            "The deposited amount is added to its outstanding amount."
+          ],
+          "writable": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration.",
+            "The deposit instruction must not be paused."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "mpcRegistry",
+          "docs": [
+            "The MPC registry PDA of the [TradeDetailInput::mpc_pubkey].",
+            "The MPC key must be registered and active, see [MpcRegistry::assert_active]."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  112,
+                  99,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deposit_args.data.mpc_pubkey"
+              }
+            ]
+          }
+        },
+        {
+          "name": "delegate",
+          "docs": [
+            "The delegate PDA that the user approved on its token account, which transfers the deposited amount."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  105,
+                  110,
+                  116,
+                  101,
+                  110,
+                  116,
+                  95,
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "instructionsSysvar",
+          "docs": [
+            "The instructions sysvar, to read the Ed25519 program instruction that verifies the user signature,",
+            "and the secp256k1 program instruction that verifies the solver signature when the [Config::solver_signature_required]."
+          ],
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "depositArgs",
+          "type": {
+            "defined": {
+              "name": "depositArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "migrateGuardian",
+      "docs": [
+        "Migrate the legacy guardian to the [Role::Guardian] role.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "The legacy [Config::guardian] is cleared.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [MigrateGuardian] required for migrating the guardian.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::InvalidPublicKey] - There is no legacy guardian."
+      ],
+      "discriminator": [
+        226,
+        93,
+        184,
+        126,
+        13,
+        255,
+        34,
+        17
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the migrate guardian instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration.",
+            "Its legacy guardian is cleared."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the legacy guardian as [Role::Guardian].",
+            "Will be initialized by the signer."
+          ],
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateOperator",
+      "docs": [
+        "Migrate a legacy operator to the [Role::WhitelistManager] and [Role::DurationManager] roles.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "The operator is removed from the legacy [Config::operators].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [MigrateOperator] required for migrating the operator.",
+        "* `operator` - The legacy operator to migrate.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::OperatorNotFound] - The operator is not a legacy operator."
+      ],
+      "discriminator": [
+        170,
+        225,
+        177,
+        253,
+        83,
+        108,
+        18,
+        227
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the migrate operator instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration.",
+            "The operator is removed from its legacy operators."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "whitelistManager",
+          "docs": [
+            "The role member PDA account of the operator as [Role::WhitelistManager].",
+            "Will be initialized by the signer."
+          ],
+          "writable": true
+        },
+        {
+          "name": "durationManager",
+          "docs": [
+            "The role member PDA account of the operator as [Role::DurationManager].",
+            "Will be initialized by the signer."
+          ],
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "payment",
+      "docs": [
+        "The pmm perform the payment process to a specific trade.",
+        "",
+        "Only token that is set whitelisted can be deposited. The [PaymentReceipt] is created in this instruction.",
+        "",
+        "The payment is not checked against the [PaymentExpectation] of the trade, even when the MPC published one.",
+        "The checks are opt-in, through [strict_payment].",
+        "",
+        "# Arguments",
+        "* `ctx` - A [Context] of [PaymentAccounts] required for the payment.",
+        "* `payment_args` - An argument [PaymentArgs] required for the payment.",
+        "# Errors",
+        "* [CustomError::InstructionPaused] when the payment instruction is paused.",
+        "* [CustomError::NotWhitelistedToken] when the token is not whitelisted.",
+        "* [CustomError::DeadlineExceeded] when the current timestamp is greater than the [PaymentArgs::deadline].",
+        "* [CustomError::InvalidAmount] when the amount [PaymentArgs::amount] is less than the [PaymentArgs::total_fee].",
+        "* [CustomError::MaxFeeExceeded] when the [PaymentArgs::total_fee] is greater than the maximum fee of the token, or the [Config::max_fee_bps].",
+        "* [CustomError::InvalidMintKey] when the mint key is not match with the [PaymentReceipt::token].",
+        "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the signer and mint.",
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the [PaymentReceipt::to_pubkey] and mint.",
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer to is not the associated token account of the protocol PDA and mint."
+      ],
+      "discriminator": [
+        156,
+        226,
+        80,
+        91,
+        104,
+        252,
+        49,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account who perform the payment."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "toUser",
+          "docs": [
+            "The account to which the payment sent to."
+          ],
+          "writable": true
+        },
+        {
+          "name": "protocol",
+          "docs": [
+            "The protocol PDA account to which the total fee will be sent."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "The whitelist token PDA, only token has been whitelisted can be payment."
+          ]
+        },
+        {
+          "name": "paymentReceipt",
+          "docs": [
+            "The payment receipt PDA that contains the payment information.",
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration.",
+            "The payment instruction must not be paused."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "The fee ledger PDA of the payment token, WSOL if the payment is with SOL.",
+            "The total fee is added to its accrued fee. Will be initialized by the signer if needed."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "payment_args.token.unwrap_or(WSOL_MINT)"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "paymentArgs",
+          "type": {
+            "defined": {
+              "name": "paymentArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "proposeAdmin",
+      "docs": [
+        "Propose a new admin for the protocol. The proposed admin takes the role after performing [accept_admin].",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "Proposing [Pubkey::default] cancels the pending proposal.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [ProposeAdmin] required for proposing the new admin.",
+        "* `new_admin` - The pubkey of the proposed admin.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+      ],
+      "discriminator": [
+        121,
+        214,
+        199,
+        212,
+        87,
+        39,
+        117,
+        234
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the propose admin instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "queuePendingAction",
+      "docs": [
+        "Queue a sensitive admin operation behind the timelock.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "The operation is executed by its instruction, given the [PendingAction], once the [Config::timelock_delay] has passed.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [QueuePendingAction] required for queuing the operation.",
+        "* `action` - The [TimelockAction] to queue.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+      ],
+      "discriminator": [
+        148,
+        29,
+        85,
+        46,
+        25,
+        31,
+        194,
+        244
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the queue pending action instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pendingAction",
+          "docs": [
+            "The pending action PDA account of the action.",
+            "Will be initialized by the signer, the same action cannot be queued twice."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  101,
+                  110,
+                  100,
+                  105,
+                  110,
+                  103,
+                  95,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "action"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "action",
+          "type": {
+            "defined": {
+              "name": "timelockAction"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "removeFeeReceiver",
+      "docs": [
+        "Remove fee receiver.",
+        "",
+        "This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].",
+        "This instruction close the [FeeReceiver] account, and transfer rent fee to the signer.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [RemoveFeeReceiver] required for removing the fee receiver.",
+        "* `receiver_pubkey` - The pubkey of the fee receiver.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::TimelockRequired] - The operation must be queued first.",
+        "* [CustomError::TimelockNotReady] - The pending action cannot be executed yet."
+      ],
+      "discriminator": [
+        137,
+        155,
+        52,
+        88,
+        218,
+        163,
+        184,
+        76
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The admin that is authorized to perform the remove fee receiver instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration.",
+            "The share of the fee receiver is removed from its [Config::total_fee_share_bps]."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeReceiverAccount",
+          "docs": [
+            "The fee receiver PDA account that contains the fee receiver information.",
+            "Will be closed and transferred rent fee to the signer."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  114,
+                  101,
+                  99,
+                  101,
+                  105,
+                  118,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "receiverPubkey"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "pendingAction",
+          "docs": [
+            "The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.",
+            "Will be closed and transferred rent fee to the signer."
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "receiverPubkey",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "removeMpcGroup",
+      "docs": [
+        "Remove an MPC group, and reclaim its rent fee.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [RemoveMpcGroup] required for removing the MPC group.",
+        "* `group_id` - The identifier of the group.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+      ],
+      "discriminator": [
+        178,
+        190,
+        248,
+        188,
+        140,
+        140,
+        141,
+        251
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The admin that is authorized to perform the remove MPC group instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mpcGroup",
+          "docs": [
+            "The MPC group PDA account that contains the members and the threshold.",
+            "Will be closed and transferred rent fee to the signer.",
+            "The trades referencing this group can no longer be authorized by the MPC, only claimed after the timeout."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  112,
+                  99,
+                  95,
+                  103,
+                  114,
+                  111,
+                  117,
+                  112
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "groupId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "groupId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeWhitelist",
+      "docs": [
+        "Remove whitelist token setup.",
+        "",
+        "This instruction is authorized by the [Role::WhitelistManager] members.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [RemoveWhitelist] required for removing the whitelist.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not a whitelist manager."
+      ],
+      "discriminator": [
+        148,
+        244,
+        73,
+        234,
+        131,
+        55,
+        247,
+        90
+      ],
+      "accounts": [
+        {
+          "name": "operator",
+          "docs": [
+            "The operator that is authorized to perform the remove whitelist instruction.",
+            "Must have the [Role::WhitelistManager] role."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "The whitelist token PDA account that contains the whitelist token information.",
+            "If SOL native, used WSOL PDA account."
+          ],
+          "writable": true
+        },
+        {
+          "name": "token",
+          "docs": [
+            "The mint token account that we want to remove whitelist."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the operator, which must grant the [Role::WhitelistManager] role."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "retireMpcRegistry",
+      "docs": [
+        "Retire an MPC key of the [MpcRegistry], the new deposits can no longer reference it.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "The trades deposited before are not affected.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [RetireMpcRegistry] required for retiring the MPC key.",
+        "* `mpc_pubkey` - The MPC key to retire.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+      ],
+      "discriminator": [
+        192,
+        156,
+        102,
+        181,
+        37,
+        164,
+        166,
+        227
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The admin that is authorized to perform the retire MPC registry instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mpcRegistry",
+          "docs": [
+            "The MPC registry PDA account of the MPC key.",
+            "Is kept after the retirement, so the key can be registered again with [AddOrUpdateMpcRegistry]."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  112,
+                  99,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "mpcPubkey"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "mpcPubkey",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "revokeRole",
+      "docs": [
+        "Revoke a role of the protocol from a member.",
+        "",
+        "This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].",
+        "This instruction close the [RoleMember] account, and transfer rent fee to the signer.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [RevokeRole] required for revoking the role.",
+        "* `role` - The [Role] to revoke.",
+        "* `member` - The member of the role.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::TimelockRequired] - The operation must be queued first.",
+        "* [CustomError::TimelockNotReady] - The pending action cannot be executed yet."
+      ],
+      "discriminator": [
+        179,
+        232,
+        2,
+        180,
+        48,
+        227,
+        82,
+        7
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the revoke role instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the role and member.",
+            "Will be closed and transferred rent fee to the signer."
+          ],
+          "writable": true
+        },
+        {
+          "name": "pendingAction",
+          "docs": [
+            "The pending action PDA account of the operation, required when the [Config::timelock_delay] is set.",
+            "Will be closed and transferred rent fee to the signer."
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "role",
+          "type": {
+            "defined": {
+              "name": "role"
+            }
+          }
+        },
+        {
+          "name": "member",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setCloseWaitDuration",
+      "docs": [
+        "Set the waiting duration for closing a finished trade or close a payment receipt.",
+        "",
+        "This instruction is authorized by the [Role::DurationManager] members.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetCloseWaitDuration] required for setting the waiting duration.",
+        "* `set_close_wait_duration_args` - An argument [SetCloseWaitDurationArgs] required for setting the waiting duration.",
+        "# Errors",
+        "* [CustomError::Unauthorized] when the caller is not authorized, not a duration manager."
+      ],
+      "discriminator": [
+        14,
+        233,
+        71,
+        143,
+        55,
+        182,
+        177,
+        231
+      ],
+      "accounts": [
+        {
+          "name": "operator",
+          "docs": [
+            "The operator that is authorized to perform the set close wait duration instruction.",
+            "Must have the [Role::DurationManager] role."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the operator, which must grant the [Role::DurationManager] role."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "setCloseWaitDurationArgs",
+          "type": {
+            "defined": {
+              "name": "setCloseWaitDurationArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setFeeReceiverShare",
+      "docs": [
+        "Set the share of a fee receiver in the distributed fee, in basis points.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "The shares of all the fee receivers sum to at most 100%, tracked by [Config::total_fee_share_bps].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetFeeReceiverShareAccounts] required for setting the share.",
+        "* `receiver_pubkey` - The pubkey of the fee receiver.",
+        "* `share_bps` - The share of the fee receiver, in basis points.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::InvalidFeeShare] - The shares of the fee receivers would sum to more than 100%."
+      ],
+      "discriminator": [
+        79,
+        58,
+        214,
+        3,
+        51,
+        129,
+        197,
+        254
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The admin that is authorized to perform the set fee receiver share instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration.",
+            "The [Config::total_fee_share_bps] is updated with the new share."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeReceiver",
+          "docs": [
+            "The fee receiver PDA account that contains the fee receiver information."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  114,
+                  101,
+                  99,
+                  101,
+                  105,
+                  118,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "receiverPubkey"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "receiverPubkey",
+          "type": "pubkey"
+        },
+        {
+          "name": "shareBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "setMaxFee",
+      "docs": [
+        "Set the maximum total fee of a trade or a payment, in basis points of the amount.",
+        "",
+        "This instruction is authorized by the [Role::FeeManager] members.",
+        "When the whitelist token is provided, set the override of this token [WhitelistToken::max_fee_bps].",
+        "Otherwise, set the global maximum fee [Config::max_fee_bps].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetMaxFee] required for setting the maximum fee.",
+        "* `max_fee_bps` - The maximum fee in basis points. None to remove the limit, or the token override.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not a fee manager.",
+        "* [CustomError::InvalidMaxFeeBps] - The maximum fee is greater than 100%."
+      ],
+      "discriminator": [
+        35,
+        55,
+        192,
+        242,
+        72,
+        189,
+        57,
+        178
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the set max fee instruction.",
+            "Must have the [Role::FeeManager] role."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "The whitelist token PDA account to set the maximum fee override for.",
+            "If it is none, the global maximum fee [Config::max_fee_bps] is set."
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  104,
+                  105,
+                  116,
+                  101,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "whitelist_token.token",
+                "account": "whitelistToken"
+              }
+            ]
+          }
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the signer, which must grant the [Role::FeeManager] role."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "maxFeeBps",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setMaxTimeoutExtension",
+      "docs": [
+        "Set the maximum total duration a trade timeout can be extended by.",
+        "",
+        "This instruction is authorized by the [Role::DurationManager] members.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetMaxTimeoutExtension] required for setting the maximum timeout extension.",
+        "* `max_timeout_extension` - The maximum timeout extension in seconds, zero disables the extensions.",
+        "# Errors",
+        "* [CustomError::Unauthorized] when the caller is not authorized, not a duration manager."
+      ],
+      "discriminator": [
+        243,
+        93,
+        37,
+        32,
+        134,
+        31,
+        248,
+        135
+      ],
+      "accounts": [
+        {
+          "name": "operator",
+          "docs": [
+            "The operator that is authorized to perform the set max timeout extension instruction.",
+            "Must have the [Role::DurationManager] role."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "roleMember",
+          "docs": [
+            "The role member PDA account of the operator, which must grant the [Role::DurationManager] role."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "maxTimeoutExtension",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setPause",
+      "docs": [
+        "Pause or unpause the deposit, payment, settlement and distribute fees instructions.",
+        "",
+        "This instruction is authorized by the [Config::admin] or the [Role::Guardian] members. The guardians can only pause.",
+        "The claim and close finished trade instructions cannot be paused, so users can always get their funds back.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetPause] required for setting the pause flags.",
+        "* `set_pause_args` - An argument [SetPauseArgs] required for setting the pause flags.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not the admin or a guardian, or a guardian tries to unpause."
+      ],
+      "discriminator": [
+        63,
+        32,
+        154,
+        2,
+        56,
+        103,
+        79,
+        45
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the set pause instruction.",
+            "Must be the [Config::admin] or have the [Role::Guardian] role."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "guardian",
+          "docs": [
+            "The role member PDA account of the signer as [Role::Guardian], unless the signer is the [Config::admin]."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "setPauseArgs",
+          "type": {
+            "defined": {
+              "name": "setPauseArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setSolverSignatureRequired",
+      "docs": [
+        "Set whether the deposits must be authorized by the secp256k1 signature of the solver over the trade id.",
+        "",
+        "This instruction is authorized by the [Config::admin].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetSolverSignatureRequired] required for setting the solver signature requirement.",
+        "* `required` - Whether the solver signature is required.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin."
+      ],
+      "discriminator": [
+        196,
+        191,
+        27,
+        157,
+        88,
+        18,
+        228,
+        21
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the set solver signature required instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "required",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setTimelockDelay",
+      "docs": [
+        "Set the timelock delay of the sensitive admin operations.",
+        "",
+        "This instruction is authorized by the [Config::admin]. When the [Config::timelock_delay] is set, it must be queued first, see [queue_pending_action].",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetTimelockDelay] required for setting the delay.",
+        "* `timelock_delay` - The new delay in seconds, zero to disable the timelock.",
+        "# Errors",
+        "* [CustomError::Unauthorized] - The caller is not authorized, or not the admin.",
+        "* [CustomError::TimelockRequired] - The new delay must be queued first.",
+        "* [CustomError::TimelockNotReady] - The pending action cannot be executed yet."
+      ],
+      "discriminator": [
+        47,
+        45,
+        87,
+        12,
+        19,
+        247,
+        83,
+        95
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the set timelock delay instruction.",
+            "Must be the [Config::admin]"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA account that contains the protocol configuration."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pendingAction",
+          "docs": [
+            "The pending action PDA account of the new delay, required when the [Config::timelock_delay] is set.",
+            "Will be closed and transferred rent fee to the signer."
+          ],
+          "writable": true,
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "timelockDelay",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setTotalFee",
+      "docs": [
+        "Set the total fee for a specific trade.",
+        "",
+        "This instruction is authorized by [TradeDetail::mpc_pubkey]. This fee is deducted from the [TradeDetail::amount] when settling.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SetTotalFee] required for setting the total fee.",
+        "* `set_total_fee_args` - An argument [SetTotalFeeArgs] required for setting the total fee.",
+        "# Errors",
+        "* [CustomError::Unauthorized] when the caller is not authorized by the MPC of the trade, see [TradeDetail::is_mpc_authorized].",
+        "* [CustomError::TimeOut] when the trade timeout is expired, so we cannot set the total fee anymore.",
+        "* [CustomError::InvalidTotalFee] when the total fee is greater than the [TradeDetail::amount].",
+        "* [CustomError::MaxFeeExceeded] when the total fee is greater than the maximum fee of the token, or the [Config::max_fee_bps]."
+      ],
+      "discriminator": [
+        4,
+        250,
+        240,
+        112,
+        5,
+        249,
+        79,
+        109
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account who is authorized to set the total fee.",
+            "Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized]."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The user trade detail account that contains the trade information."
+          ],
+          "writable": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "The whitelist token PDA of the trade token, WSOL if the trade is with SOL. Used for the maximum fee override.",
+            "It may have been removed since the deposit, then the [Config::max_fee_bps] is used."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  104,
+                  105,
+                  116,
+                  101,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_trade_detail.token.unwrap_or(WSOL_MINT)",
+                "account": "tradeDetail"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mpcGroup",
+          "docs": [
+            "The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].",
+            "The other signing members are appended to the remaining accounts."
+          ],
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "setTotalFeeArgs",
+          "type": {
+            "defined": {
+              "name": "setTotalFeeArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "settlement",
+      "docs": [
+        "MPC settles the trade, tranfer the settlement amount to the pmm and the total fee to the protocol, after the pmm paid to users.",
+        "",
+        "This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey].",
+        "This instruction is called after the pmm paid to users, and before the [TradeDetail::timeout].",
+        "This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SettlementAccounts] required for settling the trade.",
+        "* `payment_args` - An argument [SettlementArgs] required for settling the trade.",
+        "# Errors",
+        "* [CustomError::InstructionPaused] when the settlement instruction is paused.",
+        "* [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].",
+        "* [CustomError::InvalidUserAccount] when the user account is not match with [TradeDetail::rent_payer].",
+        "* [CustomError::InvalidRefundPubkey] when the refund pubkey is not match with [TradeDetail::refund_pubkey].",
+        "* [CustomError::TimeOut] when the trade timeout is expired, so we cannot settle the trade anymore.",
+        "* [CustomError::InvalidTradeStatus] when the trade status is not [TradeStatus::Deposited]. We only can settle the trade has deposited status.",
+        "* [CustomError::InvalidMintKey] when the mint key is not match with the mint of the trade.",
+        "* [CustomError::InvalidSourceAta] when the source to transfer from is not the associated token account of the vault and mint.",
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer settlement amount is not the associated token account of the pmm and mint.",
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer total fee is not the associated token account of the protocol and mint."
+      ],
+      "discriminator": [
+        128,
+        21,
+        174,
+        60,
+        47,
+        86,
+        130,
+        108
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer who is authorized to settle the trade.",
+            "Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized]."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userAccount",
+          "docs": [
+            "The user account that paid the rent of the trade. This account will receive the rent fee of the nonce check account PDA.",
+            "Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored."
+          ],
+          "writable": true
+        },
+        {
+          "name": "userEphemeralAccount",
+          "docs": [
+            "The user ephemeral account of the trade, need to sign this transaction too.",
+            "Must be the [TradeDetail::user_ephemeral_pubkey]."
+          ],
+          "signer": true
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The user trade detail PDA that contains the trade information."
+          ],
+          "writable": true
+        },
+        {
+          "name": "nonceCheckAccount",
+          "docs": [
+            "The nonce check account PDA, used to check the nonce account is being used by another trade, or not yet closed.",
+            "This PDA will be closed by the instruction."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "userEphemeralAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The trade vault PDA that corresponds to the trade."
+          ],
+          "writable": true
+        },
+        {
+          "name": "refundAccount",
+          "docs": [
+            "The refund account of the trade.",
+            "Must be the [TradeDetail::refund_pubkey]."
+          ],
+          "writable": true
+        },
+        {
+          "name": "protocol",
+          "docs": [
+            "The protocol PDA account."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pmm",
+          "docs": [
+            "The pmm account."
+          ],
+          "writable": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration.",
+            "The settlement instruction must not be paused."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "The whitelist token PDA of the trade token, WSOL if the trade is with SOL.",
+            "The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  104,
+                  105,
+                  116,
+                  101,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_trade_detail.token.unwrap_or(WSOL_MINT)",
+                "account": "tradeDetail"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "The fee ledger PDA of the trade token, WSOL if the trade is with SOL.",
+            "The total fee is added to its accrued fee. Will be initialized by the signer if needed."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_trade_detail.token.unwrap_or(WSOL_MINT)",
+                "account": "tradeDetail"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "mpcGroup",
+          "docs": [
+            "The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].",
+            "The other signing members are appended to the remaining accounts."
+          ],
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "settleArgs",
+          "type": {
+            "defined": {
+              "name": "settlementArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "splitSettlement",
+      "docs": [
+        "MPC settles the trade to multiple pmms, transfer each leg amount to its pmm and the total fee to the protocol, after the pmms paid to users.",
+        "",
+        "This instruction is authorized by both the [TradeDetail::mpc_pubkey] and the [TradeDetail::user_ephemeral_pubkey], like [settlement].",
+        "The sum of the legs must be the settlement amount, [TradeDetail::amount] minus [TradeDetail::total_fee].",
+        "The [TradeDetail::settled_pmm] is set to the pmm of the first leg.",
+        "This instruction close the [NonceCheckAccount], transfer rent fee to [TradeDetail::rent_payer], and allow the nonce can be used by other trade.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SplitSettlementAccounts] required for settling the trade.",
+        "* `split_settlement_args` - An argument [SplitSettlementArgs] required for settling the trade.",
+        "# Errors",
+        "* [CustomError::InstructionPaused] when the settlement instruction is paused.",
+        "* [CustomError::Unauthorized] when the caller is not authorized by both the MPC of the trade, see [TradeDetail::is_mpc_authorized], and the [TradeDetail::user_ephemeral_pubkey].",
+        "* [CustomError::TimeOut] when the trade timeout is expired, so we cannot settle the trade anymore.",
+        "* [CustomError::InvalidTradeStatus] when the trade status is not [TradeStatus::Deposited].",
+        "* [CustomError::InvalidSettlementLegs] when there is no leg, a leg has zero amount, or the sum of the legs is not the settlement amount.",
+        "* [CustomError::InvalidPmmAccount] when the pmm account is not match with the pmm of the leg.",
+        "* [CustomError::InvalidDestinationAta] when the destination to transfer a leg is not the associated token account of the pmm and mint."
+      ],
+      "discriminator": [
+        22,
+        122,
+        186,
+        123,
+        169,
+        55,
+        48,
+        184
+      ],
+      "accounts": [
+        {
+          "name": "signer",
+          "docs": [
+            "The signer who is authorized to settle the trade.",
+            "Must be the [TradeDetail::mpc_pubkey], or a member of its [MpcGroup], see [TradeDetail::is_mpc_authorized]."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userAccount",
+          "docs": [
+            "The user account that paid the rent of the trade. This account will receive the rent fee of the nonce check account PDA.",
+            "Must be the [TradeDetail::rent_payer], the depositor unless the deposit was sponsored."
+          ],
+          "writable": true
+        },
+        {
+          "name": "userEphemeralAccount",
+          "docs": [
+            "The user ephemeral account of the trade, need to sign this transaction too.",
+            "Must be the [TradeDetail::user_ephemeral_pubkey]."
+          ],
+          "signer": true
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The user trade detail PDA that contains the trade information."
+          ],
+          "writable": true
+        },
+        {
+          "name": "nonceCheckAccount",
+          "docs": [
+            "The nonce check account PDA, used to check the nonce account is being used by another trade, or not yet closed.",
+            "This PDA will be closed by the instruction."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "userEphemeralAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The trade vault PDA that corresponds to the trade."
+          ],
+          "writable": true
+        },
+        {
+          "name": "refundAccount",
+          "docs": [
+            "The refund account of the trade.",
+            "Must be the [TradeDetail::refund_pubkey]."
+          ],
+          "writable": true
+        },
+        {
+          "name": "protocol",
+          "docs": [
+            "The protocol PDA account."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration.",
+            "The settlement instruction must not be paused."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "The whitelist token PDA of the trade token, WSOL if the trade is with SOL.",
+            "The trade amount is removed from its outstanding amount. It may have been removed since the deposit, then nothing is updated."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  104,
+                  105,
+                  116,
+                  101,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_trade_detail.token.unwrap_or(WSOL_MINT)",
+                "account": "tradeDetail"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeLedger",
+          "docs": [
+            "The fee ledger PDA of the trade token, WSOL if the trade is with SOL.",
+            "The total fee is added to its accrued fee. Will be initialized by the signer if needed."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  108,
+                  101,
+                  100,
+                  103,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user_trade_detail.token.unwrap_or(WSOL_MINT)",
+                "account": "tradeDetail"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program."
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "mpcGroup",
+          "docs": [
+            "The MPC group of the trade, only when the [TradeDetail::mpc_pubkey] is an [MpcGroup].",
+            "The other signing members are appended to the remaining accounts."
+          ],
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "splitSettlementArgs",
+          "type": {
+            "defined": {
+              "name": "splitSettlementArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "sponsoredDeposit",
+      "docs": [
+        "Deposit the trade, with the rent of the trade accounts paid by a relayer.",
+        "",
+        "Same as the [deposit], except that the `payer` pays the rent of the [TradeDetail], [TradeVault] and [NonceCheckAccount].",
+        "The payer is recorded in the [TradeDetail::payer_pubkey], and gets the rent back when these accounts are closed.",
+        "# Arguments",
+        "* `ctx` - A [Context] of [SponsoredDepositAccounts] required for the deposit.",
+        "* `deposit_args` - An argument [DepositArgs] required for the deposit.",
+        "# Errors",
+        "Same as the [deposit] instruction."
+      ],
+      "discriminator": [
+        43,
+        8,
+        64,
+        19,
+        185,
+        76,
+        34,
+        105
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "The payer account that pays the rent of the trade accounts, usually a relayer."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "signer",
+          "docs": [
+            "The signer account that is authorized to perform the deposit instruction.",
+            "This is the account that perform the deposit, the amount is transferred from it."
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "userTradeDetail",
+          "docs": [
+            "The trade detail PDA that contains the trade information.",
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "ephemeralAccount",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "nonceCheckAccount",
+          "docs": [
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "ephemeralAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vault",
+          "docs": [
+            "The trade vault PDA that corresponds to the trade.",
+            "This PDA will be initialized by the instruction."
+          ],
+          "writable": true
+        },
+        {
+          "name": "whitelistToken",
+          "docs": [
+            "CHECK",
+            "The whitelist token PDA, only token has been whitelisted can be deposited",
+            "The deposited amount is added to its outstanding amount."
+          ],
+          "writable": true
+        },
+        {
+          "name": "config",
+          "docs": [
+            "The config PDA that contains the protocol configuration.",
+            "The deposit instruction must not be paused."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "mpcRegistry",
+          "docs": [
+            "The MPC registry PDA of the [TradeDetailInput::mpc_pubkey].",
+            "The MPC key must be registered and active, see [MpcRegistry::assert_active]."
+          ],
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  112,
+                  99,
+                  95,
+                  114,
+                  101,
+                  103,
+                  105,
+                  115,
+                  116,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deposit_args.data.mpc_pubkey"
+              }
+            ]
+          }
+        },
+        {
+          "name": "instructionsSysvar",
+          "docs": [
+            "The instructions sysvar, to read the secp256k1 program instruction that verifies the solver signature.",
+            "Required when the [Config::solver_signature_required]."
+          ],
+          "optional": true,
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "depositArgs",
+          "type": {
+            "defined": {
+              "name": "depositArgs"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "strictPayment",
+      "docs": [
+        "The pmm perform the payment process to a specific trade, checked against the [PaymentExpectation].",
+        "",
+        "Same as the payment instruction, but the recipient, token, amount, fee and deadline are validated",
+        "against the [PaymentExpectation] published by the MPC before moving any funds.",
+        "The [PaymentExpectation] is marked as fulfilled, and the [PaymentExpectationFulfilled] event is emitted.",
+        "The unchecked payment instruction stays available for the same trade, so only this event proves the expected payment.",
+        "",
+        "# Arguments",
+        "* `ctx` - A [Context] of [StrictPaymentAccounts] required for the payment.",
+        "* `payment_args` - An argument [PaymentArgs] required for the payment.",
+        "# Errors",
+        "* [CustomError::InvalidTradeId] when the [PaymentArgs::trade_id] is not the [PaymentExpectation::trade_id].",
+        "* [CustomError::PaymentAlreadyFulfilled] when the [PaymentExpectation] is already fulfilled.",
+        "* [CustomError::DeadlineExceeded] when the current timestamp is greater than the [PaymentExpectation::deadline].",
+        "* [CustomError::InvalidUserAccount] when the receiver is not the [PaymentExpectation::to_pubkey].",
+        "* [CustomError::InvalidMintKey] when the [PaymentArgs::token] is not the [PaymentExpectation::token].",
+        "* [CustomError::InvalidTotalFee] when the [PaymentArgs::total_fee] is greater than the [PaymentExpectation::max_fee].",
+        "* [CustomError::InvalidAmount] when the amount received is less than the [PaymentExpectation::min_amount].",
+        "* And all errors of the payment instruction."
+      ],
+      "discriminator": [
+        233,
+        244,
+        39,
+        123,
+        166,
+        22,
+        242,
+        228
+      ],
+      "accounts": [
+        {
+          "name": "payment",
+          "accounts": [
+            {
+              "name": "signer",
+              "docs": [
+                "The signer account who perform the payment."
+              ],
+              "writable": true,
+              "signer": true
+            },
+            {
+              "name": "toUser",
+              "docs": [
+                "The account to which the payment sent to."
+              ],
+              "writable": true
+            },
+            {
+              "name": "protocol",
+              "docs": [
+                "The protocol PDA account to which the total fee will be sent."
+              ],
+              "writable": true,
+              "pda": {
+                "seeds": [
+                  {
+                    "kind": "const",
+                    "value": [
+                      112,
+                      114,
+                      111,
+                      116,
+                      111,
+                      99,
+                      111,
+                      108
+                    ]
+                  }
+                ]
+              }
+            },
+            {
+              "name": "whitelistToken",
+              "docs": [
+                "The whitelist token PDA, only token has been whitelisted can be payment."
+              ]
+            },
+            {
+              "name": "paymentReceipt",
+              "docs": [
+                "The payment receipt PDA that contains the payment information.",
+                "This PDA will be initialized by the instruction."
+              ],
+              "writable": true
+            },
+            {
+              "name": "config",
+              "docs": [
+                "The config PDA that contains the protocol configuration.",
+                "The payment instruction must not be paused."
+              ],
+              "pda": {
+                "seeds": [
+                  {
+                    "kind": "const",
+                    "value": [
+                      99,
+                      111,
+                      110,
+                      102,
+                      105,
+                      103
+                    ]
+                  }
+                ]
+              }
+            },
+            {
+              "name": "feeLedger",
+              "docs": [
+                "The fee ledger PDA of the payment token, WSOL if the payment is with SOL.",
+                "The total fee is added to its accrued fee. Will be initialized by the signer if needed."
+              ],
+              "writable": true,
+              "pda": {
+                "seeds": [
+                  {
+                    "kind": "const",
+                    "value": [
+                      102,
+                      101,
+                      101,
+                      95,
+                      108,
+                      101,
+                      100,
+                      103,
+                      101,
+                      114
+                    ]
+                  },
+                  {
+                    "kind": "arg",
+                    "path": "payment_args.token.unwrap_or(WSOL_MINT)"
+                  }
+                ]
+              }
+            },
+            {
+              "name": "systemProgram",
+              "docs": [
+                "System program."
+              ],
+              "address": "11111111111111111111111111111111"
+            }
+          ]
+        },
+        {
+          "name": "paymentExpectation",
+          "docs": [
+            "The payment expectation PDA published by the MPC for the trade.",
+            "Must be the expectation of the [PaymentArgs::trade_id], checked by the instruction.",
+            "Will be marked as fulfilled by the instruction."
+          ],
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paymentArgs",
+          "type": {
+            "defined": {
+              "name": "paymentArgs"
+            }
+          }
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "config",
+      "discriminator": [
+        155,
+        12,
+        170,
+        224,
+        30,
+        250,
+        204,
+        130
+      ]
+    },
+    {
+      "name": "feeLedger",
+      "discriminator": [
+        224,
+        34,
+        151,
+        237,
+        107,
+        206,
+        212,
+        70
+      ]
+    },
+    {
+      "name": "feeReceiver",
+      "discriminator": [
+        217,
+        123,
+        124,
+        75,
+        212,
+        179,
+        171,
+        135
+      ]
+    },
+    {
+      "name": "mpcGroup",
+      "discriminator": [
+        72,
+        133,
+        53,
+        129,
+        255,
+        198,
+        253,
+        166
+      ]
+    },
+    {
+      "name": "mpcRegistry",
+      "discriminator": [
+        49,
+        168,
+        97,
+        5,
+        33,
+        51,
+        141,
+        133
+      ]
+    },
+    {
+      "name": "nonceCheckAccount",
+      "discriminator": [
+        191,
+        217,
+        36,
+        242,
+        192,
+        98,
+        193,
+        237
+      ]
+    },
+    {
+      "name": "paymentExpectation",
+      "discriminator": [
+        141,
+        83,
+        22,
+        194,
+        20,
+        4,
+        184,
+        164
+      ]
+    },
+    {
+      "name": "paymentReceipt",
+      "discriminator": [
+        168,
+        198,
+        209,
+        4,
+        60,
+        235,
+        126,
+        109
+      ]
+    },
+    {
+      "name": "pendingAction",
+      "discriminator": [
+        10,
+        76,
+        29,
+        155,
+        104,
+        63,
+        34,
+        51
+      ]
+    },
+    {
+      "name": "roleMember",
+      "discriminator": [
+        33,
+        119,
+        164,
+        224,
+        146,
+        251,
+        202,
+        109
+      ]
+    },
+    {
+      "name": "tradeDetail",
+      "discriminator": [
+        241,
+        58,
+        83,
+        75,
+        150,
+        155,
+        85,
+        205
+      ]
+    },
+    {
+      "name": "tradeVault",
+      "discriminator": [
+        233,
+        99,
+        74,
+        124,
+        61,
+        226,
+        5,
+        175
+      ]
+    },
+    {
+      "name": "whitelistToken",
+      "discriminator": [
+        179,
+        42,
+        207,
+        134,
+        155,
+        42,
+        77,
+        114
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "adminChanged",
+      "discriminator": [
+        232,
+        34,
+        31,
+        226,
+        62,
+        18,
+        19,
+        114
+      ]
+    },
+    {
+      "name": "adminProposed",
+      "discriminator": [
+        129,
+        249,
+        226,
+        227,
+        199,
+        82,
+        110,
+        243
+      ]
+    },
+    {
+      "name": "batchCloseSkipped",
+      "discriminator": [
+        139,
+        171,
+        124,
+        169,
+        180,
+        77,
+        23,
+        4
+      ]
+    },
+    {
+      "name": "claimed",
+      "discriminator": [
+        217,
+        192,
+        123,
+        72,
+        108,
+        150,
+        248,
+        33
+      ]
+    },
+    {
+      "name": "closeDurationChanged",
+      "discriminator": [
+        19,
+        192,
+        66,
+        200,
+        159,
+        168,
+        105,
+        162
+      ]
+    },
+    {
+      "name": "depositSponsored",
+      "discriminator": [
+        124,
+        13,
+        230,
+        7,
+        188,
+        85,
+        255,
+        174
+      ]
+    },
+    {
+      "name": "deposited",
+      "discriminator": [
+        111,
+        141,
+        26,
+        45,
+        161,
+        35,
+        100,
+        57
+      ]
+    },
+    {
+      "name": "feeReceiverChanged",
+      "discriminator": [
+        180,
+        170,
+        104,
+        102,
+        73,
+        46,
+        125,
+        121
+      ]
+    },
+    {
+      "name": "feeShareChanged",
+      "discriminator": [
+        151,
+        95,
+        146,
+        35,
+        79,
+        225,
+        253,
+        160
+      ]
+    },
+    {
+      "name": "feeWithdrawn",
+      "discriminator": [
+        167,
+        107,
+        0,
+        35,
+        67,
+        79,
+        125,
+        118
+      ]
+    },
+    {
+      "name": "initialized",
+      "discriminator": [
+        208,
+        213,
+        115,
+        98,
+        115,
+        82,
+        201,
+        209
+      ]
+    },
+    {
+      "name": "legacyFeeAccrued",
+      "discriminator": [
+        205,
+        230,
+        233,
+        199,
+        161,
+        141,
+        186,
+        240
+      ]
+    },
+    {
+      "name": "maxFeeChanged",
+      "discriminator": [
+        190,
+        152,
+        144,
+        188,
+        92,
+        58,
+        3,
+        6
+      ]
+    },
+    {
+      "name": "maxTimeoutExtensionChanged",
+      "discriminator": [
+        4,
+        205,
+        63,
+        236,
+        211,
+        164,
+        116,
+        168
+      ]
+    },
+    {
+      "name": "mpcGroupUpdated",
+      "discriminator": [
+        116,
+        199,
+        184,
+        245,
+        149,
+        73,
+        99,
+        248
+      ]
+    },
+    {
+      "name": "mpcRegistryUpdated",
+      "discriminator": [
+        82,
+        215,
+        134,
+        112,
+        74,
+        196,
+        14,
+        107
+      ]
+    },
+    {
+      "name": "pauseChanged",
+      "discriminator": [
+        238,
+        188,
+        213,
+        78,
+        134,
+        209,
+        178,
+        218
+      ]
+    },
+    {
+      "name": "paymentExpectationClosed",
+      "discriminator": [
+        97,
+        190,
+        84,
+        252,
+        48,
+        195,
+        16,
+        182
+      ]
+    },
+    {
+      "name": "paymentExpectationCreated",
+      "discriminator": [
+        214,
+        131,
+        244,
+        176,
+        121,
+        248,
+        201,
+        194
+      ]
+    },
+    {
+      "name": "paymentExpectationFulfilled",
+      "discriminator": [
+        171,
+        105,
+        175,
+        239,
+        189,
+        251,
+        188,
+        58
+      ]
+    },
+    {
+      "name": "paymentReceiptClosed",
+      "discriminator": [
+        173,
+        157,
+        135,
+        124,
+        130,
+        118,
+        36,
+        27
+      ]
+    },
+    {
+      "name": "paymentTransferred",
+      "discriminator": [
+        206,
+        116,
+        224,
+        136,
+        100,
+        105,
+        246,
+        173
+      ]
+    },
+    {
+      "name": "pendingActionCancelled",
+      "discriminator": [
+        15,
+        36,
+        68,
+        64,
+        244,
+        8,
+        190,
+        99
+      ]
+    },
+    {
+      "name": "pendingActionExecuted",
+      "discriminator": [
+        25,
+        147,
+        85,
+        232,
+        230,
+        167,
+        212,
+        121
+      ]
+    },
+    {
+      "name": "pendingActionQueued",
+      "discriminator": [
+        232,
+        91,
+        122,
+        212,
+        250,
+        131,
+        220,
+        24
+      ]
+    },
+    {
+      "name": "refunded",
+      "discriminator": [
+        35,
+        103,
+        149,
+        246,
+        196,
+        123,
+        221,
+        99
+      ]
+    },
+    {
+      "name": "roleChanged",
+      "discriminator": [
+        85,
+        88,
+        130,
+        5,
+        125,
+        143,
+        206,
+        240
+      ]
+    },
+    {
+      "name": "settled",
+      "discriminator": [
+        232,
+        210,
+        40,
+        17,
+        142,
+        124,
+        145,
+        238
+      ]
+    },
+    {
+      "name": "solverSignatureRequiredChanged",
+      "discriminator": [
+        180,
+        159,
+        193,
+        19,
+        80,
+        1,
+        176,
+        24
+      ]
+    },
+    {
+      "name": "splitSettled",
+      "discriminator": [
+        233,
+        242,
+        76,
+        2,
+        61,
+        42,
+        68,
+        122
+      ]
+    },
+    {
+      "name": "timelockDelayChanged",
+      "discriminator": [
+        25,
+        93,
+        100,
+        194,
+        12,
+        10,
+        227,
+        104
+      ]
+    },
+    {
+      "name": "timeoutExtended",
+      "discriminator": [
+        232,
+        159,
+        176,
+        38,
+        80,
+        125,
+        93,
+        154
+      ]
+    },
+    {
+      "name": "totalFeeSet",
+      "discriminator": [
+        18,
+        20,
+        5,
+        132,
+        230,
+        101,
+        182,
+        119
+      ]
+    },
+    {
+      "name": "tradeClosed",
+      "discriminator": [
+        65,
+        245,
+        176,
+        171,
+        79,
+        161,
+        47,
+        121
+      ]
+    },
+    {
+      "name": "whitelistUpdated",
+      "discriminator": [
+        205,
+        110,
+        205,
+        193,
+        238,
+        237,
+        220,
+        22
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidTradeId"
+    },
+    {
+      "code": 6001,
+      "name": "invalidTimeout"
+    },
+    {
+      "code": 6002,
+      "name": "unauthorized"
+    },
+    {
+      "code": 6003,
+      "name": "invalidPublicKey"
+    },
+    {
+      "code": 6004,
+      "name": "depositZeroAmount"
+    },
+    {
+      "code": 6005,
+      "name": "invalidAmount"
+    },
+    {
+      "code": 6006,
+      "name": "invalidMintKey"
+    },
+    {
+      "code": 6007,
+      "name": "invalidSourceAta"
+    },
+    {
+      "code": 6008,
+      "name": "invalidDestinationAta"
+    },
+    {
+      "code": 6009,
+      "name": "timeOut"
+    },
+    {
+      "code": 6010,
+      "name": "invalidRefundPubkey"
+    },
+    {
+      "code": 6011,
+      "name": "claimNotAvailable"
+    },
+    {
+      "code": 6012,
+      "name": "deadlineExceeded"
+    },
+    {
+      "code": 6013,
+      "name": "invalidUserAccount"
+    },
+    {
+      "code": 6014,
+      "name": "nonceAccountBeingUsed"
+    },
+    {
+      "code": 6015,
+      "name": "operatorAlreadyExists"
+    },
+    {
+      "code": 6016,
+      "name": "operatorNotFound"
+    },
+    {
+      "code": 6017,
+      "name": "operatorLimitReached"
+    },
+    {
+      "code": 6018,
+      "name": "notWhitelistedToken"
+    },
+    {
+      "code": 6019,
+      "name": "invalidTradeStatus"
+    },
+    {
+      "code": 6020,
+      "name": "closeNotAvailable"
+    },
+    {
+      "code": 6021,
+      "name": "invalidTokenAccount"
+    },
+    {
+      "code": 6022,
+      "name": "invalidTotalFee"
+    },
+    {
+      "code": 6023,
+      "name": "invalidFeeReceiver"
+    },
+    {
+      "code": 6024,
+      "name": "amountExceeds"
+    },
+    {
+      "code": 6025,
+      "name": "instructionPaused"
+    },
+    {
+      "code": 6026,
+      "name": "invalidTokenProgram"
+    },
+    {
+      "code": 6027,
+      "name": "unsupportedMintExtension"
+    },
+    {
+      "code": 6028,
+      "name": "maxAmountExceeded"
+    },
+    {
+      "code": 6029,
+      "name": "outstandingLimitReached"
+    },
+    {
+      "code": 6030,
+      "name": "maxFeeExceeded"
+    },
+    {
+      "code": 6031,
+      "name": "invalidMaxFeeBps"
+    },
+    {
+      "code": 6032,
+      "name": "invalidSettlementLegs"
+    },
+    {
+      "code": 6033,
+      "name": "invalidPmmAccount"
+    },
+    {
+      "code": 6034,
+      "name": "paymentAlreadyFulfilled"
+    },
+    {
+      "code": 6035,
+      "name": "invalidMpcGroup"
+    },
+    {
+      "code": 6036,
+      "name": "mpcNotRegistered"
+    },
+    {
+      "code": 6037,
+      "name": "invalidValidityWindow"
+    },
+    {
+      "code": 6038,
+      "name": "insufficientAvailableFee"
+    },
+    {
+      "code": 6039,
+      "name": "legacyFeeAlreadyAccrued"
+    },
+    {
+      "code": 6040,
+      "name": "invalidFeeShare"
+    },
+    {
+      "code": 6041,
+      "name": "invalidBatchAccounts"
+    },
+    {
+      "code": 6042,
+      "name": "timelockRequired"
+    },
+    {
+      "code": 6043,
+      "name": "timelockNotReady"
+    },
+    {
+      "code": 6044,
+      "name": "invalidPendingAction"
+    },
+    {
+      "code": 6045,
+      "name": "timeoutExtensionExceeded"
+    },
+    {
+      "code": 6046,
+      "name": "invalidSignature"
+    },
+    {
+      "code": 6047,
+      "name": "invalidDepositIntent"
+    },
+    {
+      "code": 6048,
+      "name": "invalidSolverSignature"
+    }
+  ],
+  "types": [
+    {
+      "name": "adminChanged",
+      "docs": [
+        "- @dev Event emitted when the admin of the protocol is changed\n    - Related function: init(), accept_admin()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "previousAdmin",
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adminProposed",
+      "docs": [
+        "- @dev Event emitted when admin proposes a new admin\n    - Related function: propose_admin()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingAdmin",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "batchCloseSkipped",
+      "docs": [
+        "- @dev Event emitted when a batch close skips the accounts that cannot be closed yet, or are already closed\n    - Related function: batch_close_finished_trades(), batch_close_payment_receipts()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "accounts",
+            "type": {
+              "vec": "pubkey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "batchSettlementArgs",
+      "docs": [
+        "Parameters required for the batch settlement instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "docs": [
+              "Token public key of all the trades, none if the trades are with SOL."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "tradeIds",
+            "docs": [
+              "The tradeIds of the trades to settle, in the order of the remaining accounts."
+            ],
+            "type": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "claimArgs",
+      "docs": [
+        "Parameters required for the claim function."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "The tradeId, unique identifier for the trade."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "claimed",
+      "docs": [
+        "- @dev Event emitted when a user successfully claims the deposit after timeout\n    - Related function: claim()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "closeDurationChanged",
+      "docs": [
+        "- @dev Event emitted when operator changes the waiting durations for closing trades and payment receipts\n    - Related function: set_close_wait_duration()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "closeTradeDuration",
+            "type": "u64"
+          },
+          {
+            "name": "closePaymentDuration",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "closeFinishedTradeArgs",
+      "docs": [
+        "Parameters rquired for the deposit function."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "The tradeId, unique identifier for the trade."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "config",
+      "docs": [
+        "The config PDA account that contains the protocol configuration."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "guardian",
+            "docs": [
+              "The legacy guardian of the protocol, replaced by the [crate::Role::Guardian] role.",
+              "Only read by the migrate guardian instruction, which clears it."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pause",
+            "docs": [
+              "The pause state of the protocol instructions. Set by the admin or the guardian."
+            ],
+            "type": {
+              "defined": {
+                "name": "pauseFlags"
+              }
+            }
+          },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "The admin candidate proposed by the current admin. Becomes the admin after accepting the role."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "maxFeeBps",
+            "docs": [
+              "The maximum total fee of a trade or a payment, in basis points of the amount. Set by the [crate::Role::FeeManager] members.",
+              "Can be overridden per token by [WhitelistToken::max_fee_bps]. None means no limit."
+            ],
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "nextMpcGroupId",
+            "docs": [
+              "The lowest identifier of the next [crate::MpcGroup]. Moved past each added group,",
+              "so that the identifier of a removed group is never reused."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalFeeShareBps",
+            "docs": [
+              "The sum of the [crate::FeeReceiver::share_bps] of all the fee receivers, at most [BPS_DENOMINATOR].",
+              "The fee is only distributed when the shares sum to [BPS_DENOMINATOR]."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "timelockDelay",
+            "docs": [
+              "The delay in seconds between queuing a [PendingAction] and executing it. Set by the admin through a pending action.",
+              "Zero means the sensitive admin operations take effect immediately."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxTimeoutExtension",
+            "docs": [
+              "The maximum total duration in seconds a trade timeout can be extended by. Set by the duration managers.",
+              "Zero means the timeouts cannot be extended."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "solverSignatureRequired",
+            "docs": [
+              "Whether the deposits must be authorized by the secp256k1 signature of the [crate::TradeInput::solver] over the trade id.",
+              "Set by the admin."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve field space, used to upgrade in the future."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                14
+              ]
+            }
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "The admin account of the protocol. Set by the upgrade authority, or transferred by the current admin. Used to manage the roles."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "closeTradeDuration",
+            "docs": [
+              "The duration for closing a finished trade."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "closePaymentDuration",
+            "docs": [
+              "The duration for closing a payment receipt."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "operators",
+            "docs": [
+              "The legacy operators of the protocol, replaced by the [crate::Role::WhitelistManager] and [crate::Role::DurationManager] roles.",
+              "Only read by the migrate operator instruction, which removes the migrated operator."
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "cooperativeRefundArgs",
+      "docs": [
+        "Parameters required for the cooperative refund function."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "The tradeId, unique identifier for the trade."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "createPaymentExpectationArgs",
+      "docs": [
+        "Parameters required for the create payment expectation instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "Unique identifier for the trade."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "toPubkey",
+            "docs": [
+              "The pubkey that must receive the payment."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "docs": [
+              "Token public key for SPL token payments, none if SOL payment."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "minAmount",
+            "docs": [
+              "The minimum amount the receiver must get, excluded fee."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxFee",
+            "docs": [
+              "The maximum total fee of the payment."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "docs": [
+              "The latest time that the payment can be made."
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositArgs",
+      "docs": [
+        "Parameters rquired for the deposit function"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "input",
+            "docs": [
+              "Input trade information."
+            ],
+            "type": {
+              "defined": {
+                "name": "tradeInput"
+              }
+            }
+          },
+          {
+            "name": "data",
+            "docs": [
+              "Detailed trade data."
+            ],
+            "type": {
+              "defined": {
+                "name": "tradeDetailInput"
+              }
+            }
+          },
+          {
+            "name": "tradeId",
+            "docs": [
+              "The tradeId, unique identifier for the trade."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "tradeIdScheme",
+            "docs": [
+              "The scheme the [DepositArgs::trade_id] is derived with from the [DepositArgs::input]."
+            ],
+            "type": {
+              "defined": {
+                "name": "tradeIdScheme"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositSponsored",
+      "docs": [
+        "- @dev Event emitted when a relayer pays the rent of a deposit, emitted with the `Deposited` event\n    - Related function: sponsored_deposit()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "payer",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "deposited",
+      "docs": [
+        "- @dev Event emitted when a user successfully deposits tokens or SOL\n    - Related function: deposit()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "fromPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "vault",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "distributeFeesArgs",
+      "docs": [
+        "Parameters required for the distribute fees instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "docs": [
+              "Token public key for SPL token fee, none if SOL fee."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "extendTimeoutArgs",
+      "docs": [
+        "Parameters required for the extend timeout function."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "The tradeId, unique identifier for the trade."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "newTimeout",
+            "docs": [
+              "The new timeout of the trade, after the current [TradeDetail::timeout]."
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeLedger",
+      "docs": [
+        "The fee ledger PDA account that tracks the protocol fee of a token.",
+        "",
+        "There is one PDA for each token, SOL uses the WSOL Pubkey.",
+        "The fee is accrued by the settlements and the payments, and can only be withdrawn up to [FeeLedger::available]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "docs": [
+              "The token of the fee ledger, WSOL for SOL."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "accrued",
+            "docs": [
+              "The total fee accrued by the protocol, with decimals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawn",
+            "docs": [
+              "The total fee withdrawn to the fee receivers, with decimals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "legacyFeeAccrued",
+            "docs": [
+              "Whether the fee held by the protocol before the fee ledger was introduced has been accrued."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "padding",
+            "docs": [
+              "The padding of the legacy fee flag, used for future use."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                15
+              ]
+            }
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve of the fee ledger, used for future use."
+            ],
+            "type": {
+              "array": [
+                "u128",
+                3
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "name": "paymentReceipt",
-      "discriminator": [
-        168,
-        198,
-        209,
-        4,
-        60,
-        235,
-        126,
-        109
-      ]
+      "name": "feeLedgerBalance",
+      "docs": [
+        "The numbers of a [FeeLedger], returned by the get fee ledger instruction for the treasury reporting."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "docs": [
+              "The token of the fee ledger, WSOL for SOL."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "accrued",
+            "docs": [
+              "The total fee accrued by the protocol."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawn",
+            "docs": [
+              "The total fee withdrawn to the fee receivers."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "available",
+            "docs": [
+              "The fee that can be withdrawn."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "name": "tradeDetail",
-      "discriminator": [
-        241,
-        58,
-        83,
-        75,
-        150,
-        155,
-        85,
-        205
-      ]
+      "name": "feeReceiver",
+      "docs": [
+        "The fee receiver PDA account that contains the fee receiver information."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "receiver",
+            "docs": [
+              "The pubkey of the fee receiver."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "shareBps",
+            "docs": [
+              "The share of the fee receiver in the distributed fee, in basis points. Set by the admin.",
+              "The shares of all the fee receivers sum to at most [BPS_DENOMINATOR], see [crate::Config::total_fee_share_bps]."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve of the fee receiver, used for future use."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                62
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "name": "tradeVault",
-      "discriminator": [
-        233,
-        99,
-        74,
-        124,
-        61,
-        226,
-        5,
-        175
-      ]
+      "name": "feeReceiverChanged",
+      "docs": [
+        "- @dev Event emitted when admin adds or removes a fee receiver\n    - Related function: add_fee_receiver(), remove_fee_receiver()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "receiver",
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "isAdd",
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "name": "whitelistToken",
-      "discriminator": [
-        179,
-        42,
-        207,
-        134,
-        155,
-        42,
-        77,
-        114
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "claimed",
-      "discriminator": [
-        217,
-        192,
-        123,
-        72,
-        108,
-        150,
-        248,
-        33
-      ]
+      "name": "feeShareChanged",
+      "docs": [
+        "- @dev Event emitted when admin sets the share of a fee receiver\n    - Related function: set_fee_receiver_share()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "receiver",
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "shareBps",
+            "type": "u16"
+          },
+          {
+            "name": "totalFeeShareBps",
+            "type": "u16"
+          }
+        ]
+      }
     },
     {
-      "name": "deposited",
-      "discriminator": [
-        111,
-        141,
-        26,
-        45,
-        161,
-        35,
-        100,
-        57
-      ]
+      "name": "feeWithdrawn",
+      "docs": [
+        "- @dev Event emitted when the protocol fee is withdrawn to a fee receiver\n    - Related function: distribute_fees()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "protocol",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "name": "paymentTransferred",
-      "discriminator": [
-        206,
-        116,
-        224,
-        136,
-        100,
-        105,
-        246,
-        173
-      ]
+      "name": "initArgs",
+      "docs": [
+        "Parameters required for the init function."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "The admin of the protocol. If this is not none, the instruction will set the admin."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          }
+        ]
+      }
     },
     {
-      "name": "settled",
-      "discriminator": [
-        232,
-        210,
-        40,
-        17,
-        142,
-        124,
-        145,
-        238
-      ]
-    }
-  ],
-  "errors": [
+      "name": "initialized",
+      "docs": [
+        "- @dev Event emitted when the protocol is initialized\n    - Related function: init()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "operator",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
     {
-      "code": 6000,
-      "name": "invalidTradeId"
+      "name": "legacyFeeAccrued",
+      "docs": [
+        "- @dev Event emitted when admin accrues the fee held by the protocol before the fee ledger\n    - Related function: accrue_legacy_fee()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6001,
-      "name": "invalidTimeout"
+      "name": "maxFeeChanged",
+      "docs": [
+        "- @dev Event emitted when a fee manager sets the maximum total fee, globally or for a token\n    - Related function: set_max_fee()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "maxFeeBps",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6002,
-      "name": "unauthorized"
+      "name": "maxTimeoutExtensionChanged",
+      "docs": [
+        "- @dev Event emitted when operator changes the maximum timeout extension of the trades\n    - Related function: set_max_timeout_extension()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "maxTimeoutExtension",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6003,
-      "name": "invalidPublicKey"
+      "name": "mpcGroup",
+      "docs": [
+        "The MPC group PDA account, a threshold (M-of-N) MPC authority for the trades.",
+        "",
+        "A trade references the group by setting its [crate::TradeDetail::mpc_pubkey] to the address of this PDA.",
+        "The instructions authorized by the MPC of the trade then require the signatures of at least [MpcGroup::threshold] members."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "groupId",
+            "docs": [
+              "The identifier of the group, used as the seed of the PDA."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "members",
+            "docs": [
+              "The member keys of the group, up to [MpcGroup::MAX_MEMBERS]."
+            ],
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "threshold",
+            "docs": [
+              "The number of member signatures required to authorize an instruction."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve of the MPC group, used for future use."
+            ],
+            "type": {
+              "array": [
+                "u128",
+                4
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "depositZeroAmount"
+      "name": "mpcGroupUpdated",
+      "docs": [
+        "- @dev Event emitted when Admin adds or removes an MPC group\n    - Related function: add_mpc_group(), remove_mpc_group()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mpcGroup",
+            "type": "pubkey"
+          },
+          {
+            "name": "groupId",
+            "type": "u64"
+          },
+          {
+            "name": "members",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "isAdd",
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "invalidAmount"
+      "name": "mpcRegistry",
+      "docs": [
+        "The MPC registry PDA account, an MPC key approved by the admin to authorize the trades.",
+        "",
+        "There is one PDA for each registered key, a deposit can only reference an MPC key that is active at the deposit time.",
+        "The key can be a single MPC key, or the address of an [crate::MpcGroup]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mpcPubkey",
+            "docs": [
+              "The registered MPC key."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "isActive",
+            "docs": [
+              "Whether the MPC key is active. False once the key is retired."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "validFrom",
+            "docs": [
+              "The time from which the deposits can reference the MPC key."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "validUntil",
+            "docs": [
+              "The time until which the deposits can reference the MPC key. Zero means no limit."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve of the MPC registry, used for future use."
+            ],
+            "type": {
+              "array": [
+                "u128",
+                4
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "invalidMintKey"
+      "name": "mpcRegistryUpdated",
+      "docs": [
+        "- @dev Event emitted when Admin registers, updates or retires an MPC key\n    - Related function: add_or_update_mpc_registry(), retire_mpc_registry()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mpcPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "validFrom",
+            "type": "i64"
+          },
+          {
+            "name": "validUntil",
+            "type": "i64"
+          },
+          {
+            "name": "isActive",
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "invalidSourceAta"
+      "name": "nonceCheckAccount",
+      "docs": [
+        "The nonce check PDA account that contains the nonce check information."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": []
+      }
     },
     {
-      "code": 6008,
-      "name": "invalidDestinationAta"
+      "name": "pauseChanged",
+      "docs": [
+        "- @dev Event emitted when admin or guardian pauses or unpauses the protocol instructions\n    - Related function: set_pause()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "deposit",
+            "type": "bool"
+          },
+          {
+            "name": "payment",
+            "type": "bool"
+          },
+          {
+            "name": "settlement",
+            "type": "bool"
+          },
+          {
+            "name": "distributeFees",
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "timeOut"
+      "name": "pauseFlags",
+      "docs": [
+        "The pause state of the protocol instructions.",
+        "",
+        "Only the instructions that move funds into the protocol or to the pmm and fee receivers can be paused.",
+        "The `claim` and `close_finished_trade` instructions are always available, so users can get their funds back."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "deposit",
+            "docs": [
+              "Whether the `deposit` instruction is paused."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "payment",
+            "docs": [
+              "Whether the `payment` instruction is paused."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "settlement",
+            "docs": [
+              "Whether the `settlement` instruction is paused."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "distributeFees",
+            "docs": [
+              "Whether the `distribute_fees` instruction is paused."
+            ],
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "invalidRefundPubkey"
+      "name": "paymentArgs",
+      "docs": [
+        "Parameters rquired for the payment instruction."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "Unique identifier for the trade."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "docs": [
+              "Token public key for SPL token payments, none if SOL payment."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Amount to be transferred."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalFee",
+            "docs": [
+              "Total fee to be deducted from the amount, and transferred to the protocol."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "docs": [
+              "Deadline for the payment transaction."
+            ],
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "claimNotAvailable"
+      "name": "paymentExpectation",
+      "docs": [
+        "The payment expectation PDA account that contains the payment the MPC expects for a trade.",
+        "",
+        "Only checked by the strict payment instruction, the payment instruction ignores it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "The trade id of the payment expectation."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "mpcPubkey",
+            "docs": [
+              "The mpc pubkey who published the payment expectation, a single MPC key or the address of an [MpcGroup]."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "docs": [
+              "The pubkey that must receive the payment."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "docs": [
+              "The token of the payment. None if the payment is SOL."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "minAmount",
+            "docs": [
+              "The minimum amount the receiver must get, excluded fee, with decimals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxFee",
+            "docs": [
+              "The maximum total fee of the payment, with decimals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "docs": [
+              "The latest time that the payment can be made."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "fulfilled",
+            "docs": [
+              "Whether the payment has been made."
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve field space, used to upgrade in the future."
+            ],
+            "type": {
+              "array": [
+                "u128",
+                4
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "deadlineExceeded"
+      "name": "paymentExpectationClosed",
+      "docs": [
+        "- @dev Event emitted when MPC closes the expected payment of a trade\n    - Related function: close_payment_expectation()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "mpcPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "fulfilled",
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6013,
-      "name": "invalidUserAccount"
+      "name": "paymentExpectationCreated",
+      "docs": [
+        "- @dev Event emitted when MPC publishes the expected payment of a trade\n    - Related function: create_payment_expectation()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "mpcPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "minAmount",
+            "type": "u64"
+          },
+          {
+            "name": "maxFee",
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6014,
-      "name": "nonceAccountBeingUsed"
+      "name": "paymentExpectationFulfilled",
+      "docs": [
+        "- @dev Event emitted when a payment fulfills the expected payment of a trade\n    - Related function: strict_payment()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "mpcPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "receivedAmount",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "operatorAlreadyExists"
+      "name": "paymentReceipt",
+      "docs": [
+        "The payment receipt PDA account that contains the payment receipt information."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "docs": [
+              "The trade id of the payment receipt."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "fromPubkey",
+            "docs": [
+              "The from pubkey of the payment receipt. Who paid the payment."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "docs": [
+              "The to pubkey of the payment receipt. Who received the payment."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "docs": [
+              "The token of the payment receipt. None if the payment is SOL."
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "paymentAmount",
+            "docs": [
+              "The payment amount of the payment receipt, included fee, with decimals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalFee",
+            "docs": [
+              "The total fee of the payment receipt, with decimals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "paymentTime",
+            "docs": [
+              "The time that the payment is made."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve field space, used to upgrade in the future."
+            ],
+            "type": {
+              "array": [
+                "u128",
+                8
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6016,
-      "name": "operatorNotFound"
+      "name": "paymentReceiptClosed",
+      "docs": [
+        "- @dev Event emitted when a payment receipt is closed and its rent is reclaimed\n    - Related function: close_payment_receipt()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "paymentReceipt",
+            "type": "pubkey"
+          },
+          {
+            "name": "fromPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6017,
-      "name": "operatorLimitReached"
+      "name": "paymentTransferred",
+      "docs": [
+        "- @dev Event emitted when PMM successfully settle the payment\n    - Related function: payment();"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "fromPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "paymentAmount",
+            "type": "u64"
+          },
+          {
+            "name": "totalFee",
+            "type": "u64"
+          },
+          {
+            "name": "protocol",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6018,
-      "name": "notWhitelistedToken"
+      "name": "pendingAction",
+      "docs": [
+        "The pending action PDA account, a [TimelockAction] queued by the admin.",
+        "",
+        "There is one PDA for each action, see [TimelockAction::id]. The action is executed by its instruction,",
+        "given the pending action, once the [PendingAction::eta] is reached. The admin or the guardian can cancel it before."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "action",
+            "docs": [
+              "The queued action."
+            ],
+            "type": {
+              "defined": {
+                "name": "timelockAction"
+              }
+            }
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "The admin who queued the action."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "eta",
+            "docs": [
+              "The time from which the action can be executed."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "The reserve of the pending action, used for future use."
+            ],
+            "type": {
+              "array": [
+                "u128",
+                2
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6019,
-      "name": "invalidTradeStatus"
+      "name": "pendingActionCancelled",
+      "docs": [
+        "- @dev Event emitted when admin or guardian cancels a queued sensitive operation\n    - Related function: cancel_pending_action()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "actionId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "action",
+            "type": {
+              "defined": {
+                "name": "timelockAction"
+              }
+            }
+          },
+          {
+            "name": "operator",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6020,
-      "name": "closeNotAvailable"
+      "name": "pendingActionExecuted",
+      "docs": [
+        "- @dev Event emitted when a queued sensitive operation is executed\n    - Related function: grant_role(), revoke_role(), add_fee_receiver(), remove_fee_receiver(), set_timelock_delay()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "actionId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "action",
+            "type": {
+              "defined": {
+                "name": "timelockAction"
+              }
+            }
+          },
+          {
+            "name": "admin",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6021,
-      "name": "invalidTokenAccount"
-    }
-  ],
-  "types": [
-    {
-      "name": "claimArgs",
+      "name": "pendingActionQueued",
       "docs": [
-        "Parameters required for the claim function."
+        "- @dev Event emitted when admin queues a sensitive operation behind the timelock\n    - Related function: queue_pending_action()"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "tradeId",
-            "docs": [
-              "The tradeId, unique identifier for the trade."
-            ],
+            "name": "actionId",
             "type": {
               "array": [
                 "u8",
                 32
               ]
             }
+          },
+          {
+            "name": "action",
+            "type": {
+              "defined": {
+                "name": "timelockAction"
+              }
+            }
+          },
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "eta",
+            "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "claimed",
+      "name": "refunded",
       "docs": [
-        "- @dev Event emitted when a user successfully claims the deposit after timeout\n    - Related function: claim()"
+        "- @dev Event emitted when MPC and user agree to refund the deposit before the timeout\n    - Related function: cooperative_refund()"
       ],
       "type": {
         "kind": "struct",
@@ -1810,120 +7697,96 @@ export type OptimexSolSmartcontract = {
       }
     },
     {
-      "name": "closeFinishedTradeArgs",
+      "name": "role",
       "docs": [
-        "Parameters rquired for the deposit function."
+        "A role of the protocol, granted by the admin to any number of members."
       ],
       "type": {
-        "kind": "struct",
-        "fields": [
+        "kind": "enum",
+        "variants": [
           {
-            "name": "tradeId",
-            "docs": [
-              "The tradeId, unique identifier for the trade."
-            ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "whitelistManager"
+          },
+          {
+            "name": "durationManager"
+          },
+          {
+            "name": "feeManager"
+          },
+          {
+            "name": "guardian"
           }
         ]
       }
     },
     {
-      "name": "config",
+      "name": "roleChanged",
       "docs": [
-        "The config PDA account that contains the protocol configuration."
+        "- @dev Event emitted when admin grants or revokes a role, or migrates a legacy operator or guardian\n    - Related function: grant_role(), revoke_role(), migrate_operator(), migrate_guardian()"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "reserve",
-            "docs": [
-              "The reserve field space, used to upgrade in the future."
-            ],
+            "name": "role",
             "type": {
-              "array": [
-                "u128",
-                7
-              ]
+              "defined": {
+                "name": "role"
+              }
             }
           },
           {
-            "name": "admin",
-            "docs": [
-              "The admin account of the protocol. Set by the upgrade authority. Used to manage the operators."
-            ],
+            "name": "member",
             "type": "pubkey"
           },
           {
-            "name": "closeTradeDuration",
-            "docs": [
-              "The duration for closing a finished trade."
-            ],
-            "type": "u64"
-          },
-          {
-            "name": "closePaymentDuration",
-            "docs": [
-              "The duration for closing a payment receipt."
-            ],
-            "type": "u64"
+            "name": "admin",
+            "type": "pubkey"
           },
           {
-            "name": "operators",
-            "docs": [
-              "The operators of the protocol. Set by the admin. Used to manage close wait time and whitelist token."
-            ],
-            "type": {
-              "vec": "pubkey"
-            }
+            "name": "isGrant",
+            "type": "bool"
           }
         ]
       }
     },
     {
-      "name": "depositArgs",
+      "name": "roleMember",
       "docs": [
-        "Parameters rquired for the deposit function"
+        "The role member PDA account, a [Role] granted to a member.",
+        "",
+        "There is one PDA for each role and member, it is closed when the role is revoked."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "input",
+            "name": "role",
             "docs": [
-              "Input trade information."
+              "The granted role."
             ],
             "type": {
               "defined": {
-                "name": "tradeInput"
+                "name": "role"
               }
             }
           },
           {
-            "name": "data",
+            "name": "member",
             "docs": [
-              "Detailed trade data."
+              "The member of the role."
             ],
-            "type": {
-              "defined": {
-                "name": "tradeDetailInput"
-              }
-            }
+            "type": "pubkey"
           },
           {
-            "name": "tradeId",
+            "name": "reserve",
             "docs": [
-              "The tradeId, unique identifier for the trade."
+              "The reserve of the role member, used for future use."
             ],
             "type": {
               "array": [
-                "u8",
-                32
+                "u128",
+                2
               ]
             }
           }
@@ -1931,116 +7794,123 @@ export type OptimexSolSmartcontract = {
       }
     },
     {
-      "name": "deposited",
+      "name": "setCloseWaitDurationArgs",
       "docs": [
-        "- @dev Event emitted when a user successfully deposits tokens or SOL\n    - Related function: deposit()"
+        "Parameters required for setting the close wait duration."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "tradeId",
+            "name": "closeTradeDuration",
+            "docs": [
+              "The waiting duration for closing a finished trade [Config::close_trade_duration].",
+              "If it is none, the close_trade_duratin will not changed."
+            ],
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "option": "u64"
             }
           },
           {
-            "name": "fromPubkey",
-            "type": "pubkey"
-          },
-          {
-            "name": "token",
+            "name": "closePaymentDuration",
+            "docs": [
+              "The waiting duration for closing a payment receipt [Config::close_payment_duration].",
+              "If it is none, the close_payment_duration will not changed."
+            ],
             "type": {
-              "option": "pubkey"
+              "option": "u64"
             }
-          },
-          {
-            "name": "amount",
-            "type": "u64"
-          },
-          {
-            "name": "vault",
-            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "feeReceiver",
+      "name": "setPauseArgs",
       "docs": [
-        "The fee receiver PDA account that contains the fee receiver information."
+        "Parameters required for pausing or unpausing the protocol instructions."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "receiver",
+            "name": "deposit",
             "docs": [
-              "The pubkey of the fee receiver."
+              "Whether to pause the `deposit` instruction. If it is none, the flag will not changed."
             ],
-            "type": "pubkey"
+            "type": {
+              "option": "bool"
+            }
           },
           {
-            "name": "reserve",
+            "name": "payment",
             "docs": [
-              "The reserve of the fee receiver, used for future use."
+              "Whether to pause the `payment` instruction. If it is none, the flag will not changed."
             ],
             "type": {
-              "array": [
-                "u128",
-                4
-              ]
+              "option": "bool"
+            }
+          },
+          {
+            "name": "settlement",
+            "docs": [
+              "Whether to pause the `settlement` instruction. If it is none, the flag will not changed."
+            ],
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "distributeFees",
+            "docs": [
+              "Whether to pause the `distribute_fees` instruction. If it is none, the flag will not changed."
+            ],
+            "type": {
+              "option": "bool"
             }
           }
         ]
       }
     },
     {
-      "name": "initArgs",
+      "name": "setTotalFeeArgs",
       "docs": [
-        "Parameters required for the init function."
+        "Parameters rquired for setting the total fee"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "admin",
+            "name": "tradeId",
             "docs": [
-              "The admin of the protocol. If this is not none, the instruction will set the admin."
+              "Unique identifier for the trade"
             ],
             "type": {
-              "option": "pubkey"
+              "array": [
+                "u8",
+                32
+              ]
             }
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "Amount of the protocol fee"
+            ],
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "nonceCheckAccount",
-      "docs": [
-        "The nonce check PDA account that contains the nonce check information."
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": []
-      }
-    },
-    {
-      "name": "paymentArgs",
+      "name": "settled",
       "docs": [
-        "Parameters rquired for the payment instruction."
+        "- @dev Event emitted when MPC successfully settles the trade\n    - Related function: settlement()"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
             "name": "tradeId",
-            "docs": [
-              "Unique identifier for the trade."
-            ],
             "type": {
               "array": [
                 "u8",
@@ -2048,43 +7918,43 @@ export type OptimexSolSmartcontract = {
               ]
             }
           },
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
           {
             "name": "token",
-            "docs": [
-              "Token public key for SPL token payments, none if SOL payment."
-            ],
             "type": {
               "option": "pubkey"
             }
           },
           {
-            "name": "amount",
-            "docs": [
-              "Amount to be transferred."
-            ],
+            "name": "settlementAmount",
             "type": "u64"
           },
           {
             "name": "totalFee",
-            "docs": [
-              "Total fee to be deducted from the amount, and transferred to the protocol."
-            ],
             "type": "u64"
           },
           {
-            "name": "deadline",
-            "docs": [
-              "Deadline for the payment transaction."
-            ],
-            "type": "i64"
+            "name": "vault",
+            "type": "pubkey"
+          },
+          {
+            "name": "protocol",
+            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "paymentReceipt",
+      "name": "settlementArgs",
       "docs": [
-        "The payment receipt PDA account that contains the payment receipt information."
+        "Parameters rquired for the settlement function"
       ],
       "type": {
         "kind": "struct",
@@ -2092,7 +7962,7 @@ export type OptimexSolSmartcontract = {
           {
             "name": "tradeId",
             "docs": [
-              "The trade id of the payment receipt."
+              "The tradeId, unique identifier for the trade"
             ],
             "type": {
               "array": [
@@ -2100,70 +7970,58 @@ export type OptimexSolSmartcontract = {
                 32
               ]
             }
-          },
-          {
-            "name": "fromPubkey",
-            "docs": [
-              "The from pubkey of the payment receipt. Who paid the payment."
-            ],
-            "type": "pubkey"
-          },
-          {
-            "name": "toPubkey",
-            "docs": [
-              "The to pubkey of the payment receipt. Who received the payment."
-            ],
-            "type": "pubkey"
-          },
-          {
-            "name": "token",
-            "docs": [
-              "The token of the payment receipt. None if the payment is SOL."
-            ],
-            "type": {
-              "option": "pubkey"
-            }
-          },
-          {
-            "name": "paymentAmount",
-            "docs": [
-              "The payment amount of the payment receipt, included fee, with decimals."
-            ],
-            "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "settlementLeg",
+      "docs": [
+        "A leg of the split settlement, the amount paid to a pmm."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "totalFee",
+            "name": "pmm",
             "docs": [
-              "The total fee of the payment receipt, with decimals."
+              "The pmm that receives the amount."
             ],
-            "type": "u64"
+            "type": "pubkey"
           },
           {
-            "name": "paymentTime",
+            "name": "amount",
             "docs": [
-              "The time that the payment is made."
+              "The amount paid to the pmm, with decimals."
             ],
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "solverSignatureRequiredChanged",
+      "docs": [
+        "- @dev Event emitted when admin sets whether the deposits require the solver signature\n    - Related function: set_solver_signature_required()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
           },
           {
-            "name": "reserve",
-            "docs": [
-              "The reserve field space, used to upgrade in the future."
-            ],
-            "type": {
-              "array": [
-                "u128",
-                8
-              ]
-            }
+            "name": "required",
+            "type": "bool"
           }
         ]
       }
     },
     {
-      "name": "paymentTransferred",
+      "name": "splitSettled",
       "docs": [
-        "- @dev Event emitted when PMM successfully settle the payment\n    - Related function: payment();"
+        "- @dev Event emitted when MPC successfully settles the trade to multiple pmms\n    - Related function: split_settlement()"
       ],
       "type": {
         "kind": "struct",
@@ -2178,11 +8036,7 @@ export type OptimexSolSmartcontract = {
             }
           },
           {
-            "name": "fromPubkey",
-            "type": "pubkey"
-          },
-          {
-            "name": "toPubkey",
+            "name": "operator",
             "type": "pubkey"
           },
           {
@@ -2192,13 +8046,23 @@ export type OptimexSolSmartcontract = {
             }
           },
           {
-            "name": "paymentAmount",
-            "type": "u64"
+            "name": "legs",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "settlementLeg"
+                }
+              }
+            }
           },
           {
             "name": "totalFee",
             "type": "u64"
           },
+          {
+            "name": "vault",
+            "type": "pubkey"
+          },
           {
             "name": "protocol",
             "type": "pubkey"
@@ -2207,70 +8071,115 @@ export type OptimexSolSmartcontract = {
       }
     },
     {
-      "name": "setCloseWaitDurationArgs",
+      "name": "splitSettlementArgs",
       "docs": [
-        "Parameters required for setting the close wait duration."
+        "Parameters required for the split settlement function"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "closeTradeDuration",
+            "name": "tradeId",
             "docs": [
-              "The waiting duration for closing a finished trade [Config::close_trade_duration].",
-              "If it is none, the close_trade_duratin will not changed."
+              "The tradeId, unique identifier for the trade"
             ],
             "type": {
-              "option": "u64"
+              "array": [
+                "u8",
+                32
+              ]
             }
           },
           {
-            "name": "closePaymentDuration",
+            "name": "legs",
             "docs": [
-              "The waiting duration for closing a payment receipt [Config::close_payment_duration].",
-              "If it is none, the close_payment_duration will not changed."
+              "The legs of the settlement. The sum of the amounts must be the settlement amount, [TradeDetail::amount] minus [TradeDetail::total_fee]."
             ],
             "type": {
-              "option": "u64"
+              "vec": {
+                "defined": {
+                  "name": "settlementLeg"
+                }
+              }
             }
           }
         ]
       }
     },
     {
-      "name": "setTotalFeeArgs",
+      "name": "timelockAction",
       "docs": [
-        "Parameters rquired for setting the total fee"
+        "A sensitive admin operation, which only takes effect after the [crate::Config::timelock_delay]."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "grantRole",
+            "fields": [
+              {
+                "defined": {
+                  "name": "role"
+                }
+              },
+              "pubkey"
+            ]
+          },
+          {
+            "name": "revokeRole",
+            "fields": [
+              {
+                "defined": {
+                  "name": "role"
+                }
+              },
+              "pubkey"
+            ]
+          },
+          {
+            "name": "addFeeReceiver",
+            "fields": [
+              "pubkey"
+            ]
+          },
+          {
+            "name": "removeFeeReceiver",
+            "fields": [
+              "pubkey"
+            ]
+          },
+          {
+            "name": "setTimelockDelay",
+            "fields": [
+              "u64"
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "timelockDelayChanged",
+      "docs": [
+        "- @dev Event emitted when admin changes the timelock delay of the sensitive operations\n    - Related function: set_timelock_delay()"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "tradeId",
-            "docs": [
-              "Unique identifier for the trade"
-            ],
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "admin",
+            "type": "pubkey"
           },
           {
-            "name": "amount",
-            "docs": [
-              "Amount of the protocol fee"
-            ],
+            "name": "timelockDelay",
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "settled",
+      "name": "timeoutExtended",
       "docs": [
-        "- @dev Event emitted when MPC successfully settles the trade\n    - Related function: settlement()"
+        "- @dev Event emitted when the MPC and the user extend the timeout of a trade\n    - Related function: extend_timeout()"
       ],
       "type": {
         "kind": "struct",
@@ -2289,53 +8198,74 @@ export type OptimexSolSmartcontract = {
             "type": "pubkey"
           },
           {
-            "name": "toPubkey",
-            "type": "pubkey"
+            "name": "previousTimeout",
+            "type": "i64"
           },
           {
-            "name": "token",
+            "name": "newTimeout",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "totalFeeSet",
+      "docs": [
+        "- @dev Event emitted when MPC sets the total fee of a trade\n    - Related function: set_total_fee()"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tradeId",
             "type": {
-              "option": "pubkey"
+              "array": [
+                "u8",
+                32
+              ]
             }
           },
           {
-            "name": "settlementAmount",
-            "type": "u64"
+            "name": "operator",
+            "type": "pubkey"
           },
           {
             "name": "totalFee",
             "type": "u64"
-          },
-          {
-            "name": "vault",
-            "type": "pubkey"
-          },
-          {
-            "name": "protocol",
-            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "settlementArgs",
+      "name": "tradeClosed",
       "docs": [
-        "Parameters rquired for the settlement function"
+        "- @dev Event emitted when a finished trade is closed and its rent is reclaimed\n    - Related function: close_finished_trade()"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
             "name": "tradeId",
-            "docs": [
-              "The tradeId, unique identifier for the trade"
-            ],
             "type": {
               "array": [
                 "u8",
                 32
               ]
             }
+          },
+          {
+            "name": "operator",
+            "type": "pubkey"
+          },
+          {
+            "name": "toPubkey",
+            "type": "pubkey"
+          },
+          {
+            "name": "token",
+            "type": {
+              "option": "pubkey"
+            }
           }
         ]
       }
@@ -2379,7 +8309,8 @@ export type OptimexSolSmartcontract = {
           {
             "name": "amount",
             "docs": [
-              "The amount of the trade, with decimals."
+              "The amount of the trade, with decimals.",
+              "This is the amount the vault actually received, after the Token-2022 transfer fee if any."
             ],
             "type": "u64"
           },
@@ -2438,6 +8369,45 @@ export type OptimexSolSmartcontract = {
             ],
             "type": "pubkey"
           },
+          {
+            "name": "timeoutExtension",
+            "docs": [
+              "The total duration in seconds the [TradeDetail::timeout] was extended by since the deposit."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "payerPubkey",
+            "docs": [
+              "The account that paid the rent of the trade accounts, the depositor or a relayer that sponsored the deposit.",
+              "The default pubkey for the trades deposited before it was recorded, see [TradeDetail::rent_payer]."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tradeIdScheme",
+            "docs": [
+              "The scheme the [TradeDetail::trade_id] was derived with.",
+              "[TradeIdScheme::Sha256Abi] for the trades deposited before it was recorded."
+            ],
+            "type": {
+              "defined": {
+                "name": "tradeIdScheme"
+              }
+            }
+          },
+          {
+            "name": "padding",
+            "docs": [
+              "The padding of the [TradeDetail::trade_id_scheme] in the reserve space."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
           {
             "name": "reserve",
             "docs": [
@@ -2445,8 +8415,8 @@ export type OptimexSolSmartcontract = {
             ],
             "type": {
               "array": [
-                "u128",
-                8
+                "u64",
+                10
               ]
             }
           }
@@ -2473,6 +8443,28 @@ export type OptimexSolSmartcontract = {
         ]
       }
     },
+    {
+      "name": "tradeIdScheme",
+      "docs": [
+        "The scheme of the trade id, the hashing and encoding of the [TradeInput] it is derived from.",
+        "",
+        "Chosen by the depositor in the [DepositArgs::trade_id_scheme], and recorded in the [TradeDetail::trade_id_scheme]."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "sha256Abi"
+          },
+          {
+            "name": "keccak256Abi"
+          },
+          {
+            "name": "sha256Borsh"
+          }
+        ]
+      }
+    },
     {
       "name": "tradeInfo",
       "docs": [
@@ -2582,6 +8574,9 @@ export type OptimexSolSmartcontract = {
           },
           {
             "name": "claimed"
+          },
+          {
+            "name": "refunded"
           }
         ]
       }
@@ -2616,6 +8611,38 @@ export type OptimexSolSmartcontract = {
             ],
             "type": "u64"
           },
+          {
+            "name": "maxAmount",
+            "docs": [
+              "The maximum amount of a single deposit, with decimals. Zero means no limit."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxOutstanding",
+            "docs": [
+              "The maximum amount of the token held by the trade vaults, with decimals. Zero means no limit."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "outstanding",
+            "docs": [
+              "The amount of the token currently held by the trade vaults, with decimals.",
+              "Increased by deposits, decreased when the trades are settled or claimed."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxFeeBps",
+            "docs": [
+              "The maximum total fee for this token, in basis points of the amount. Set by the admin.",
+              "Overrides the [crate::Config::max_fee_bps] when it is not none."
+            ],
+            "type": {
+              "option": "u16"
+            }
+          },
           {
             "name": "reserve",
             "docs": [
@@ -2623,8 +8650,8 @@ export type OptimexSolSmartcontract = {
             ],
             "type": {
               "array": [
-                "u128",
-                4
+                "u8",
+                37
               ]
             }
           }
@@ -2632,28 +8659,36 @@ export type OptimexSolSmartcontract = {
       }
     },
     {
-      "name": "withdrawTotalFeeArgs",
+      "name": "whitelistUpdated",
       "docs": [
-        "Parameters required for the withdraw total fee instruction."
+        "- @dev Event emitted when a token is added to, updated in or removed from the whitelist\n    - Related function: add_or_update_whitelist(), remove_whitelist()"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
             "name": "token",
-            "docs": [
-              "Token public key for SPL token payments, none if SOL payment."
-            ],
-            "type": {
-              "option": "pubkey"
-            }
+            "type": "pubkey"
+          },
+          {
+            "name": "operator",
+            "type": "pubkey"
           },
           {
             "name": "amount",
-            "docs": [
-              "Amount to be transferred."
-            ],
             "type": "u64"
+          },
+          {
+            "name": "maxAmount",
+            "type": "u64"
+          },
+          {
+            "name": "maxOutstanding",
+            "type": "u64"
+          },
+          {
+            "name": "isAdd",
+            "type": "bool"
           }
         ]
       }
@@ -4,8 +4,9 @@ import { AccountMeta, Connection, PublicKey } from '@solana/web3.js'
 import { createAssociatedTokenAccountInstructionIfNeeded } from './helpers'
 
 import { getOptimexProgram } from '../artifacts'
-import { getProtocolPda, getTradeVaultPda, getUserTradeDetailPda } from '../pda/get_pda_address'
-import { getTradeDetailData } from '../pda/get_pda_data'
+import { WSOL_MINT } from '../constants'
+import { getProtocolPda, getTradeVaultPda, getUserTradeDetailPda, getWhitelistPda } from '../pda/get_pda_address'
+import { getTradeDetailData, getTradeRentPayer } from '../pda/get_pda_data'
 import { bigintToBytes32 } from '../utils/parse_utils'
 
 /**
@@ -61,12 +62,13 @@ export async function createClaimAndRefundAtaAndProtocolAtaIfNeededInstructions(
     .claim({
       tradeId: tradeIdBytes,
     })
-    .accounts({
+    .accountsPartial({
       signer: userPubkey,
       userTradeDetail,
       refundAccount: refundPubkey,
-      userAccount: userTradeDetailData.userPubkey,
+      userAccount: getTradeRentPayer(userTradeDetailData),
       vault: tradeVaultPda,
+      whitelistToken: getWhitelistPda(tokenPubkey || WSOL_MINT),
     })
     .remainingAccounts(remaminingAcocunts)
     .instruction()
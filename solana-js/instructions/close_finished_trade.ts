@@ -1,9 +1,9 @@
-import { getAssociatedTokenAddress } from '@solana/spl-token'
+import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from '@solana/spl-token'
 import { Connection, PublicKey } from '@solana/web3.js'
 
 import { getOptimexProgram } from '../artifacts'
 import { getTradeVaultPda, getUserTradeDetailPda } from '../pda/get_pda_address'
-import { getTradeDetailData } from '../pda/get_pda_data'
+import { getTradeDetailData, getTradeRentPayer } from '../pda/get_pda_data'
 import { bigintToBytes32 } from '../utils/parse_utils'
 
 /**
@@ -31,8 +31,11 @@ export async function createCloseFinishedTradeInstructions(params: CloseFinished
   const userTradeDetail = getUserTradeDetailPda(tradeId)
   const userTradeDetailData = await getTradeDetailData(tradeId, connection)
   const tokenPubkey = userTradeDetailData.token
+  const rentPayer = getTradeRentPayer(userTradeDetailData)
   const tradeVaultPda = getTradeVaultPda(tradeId)
   const tradeIdBytes = bigintToBytes32(BigInt(tradeId))
+  // The token program owning the mint, either the SPL Token or the Token-2022 program
+  const tokenProgram = tokenPubkey ? (await connection.getAccountInfo(tokenPubkey))!.owner : TOKEN_PROGRAM_ID
   const closeIns = await onchainProgram.methods
     .closeFinishedTrade({
       tradeId: tradeIdBytes,
@@ -40,12 +43,18 @@ export async function createCloseFinishedTradeInstructions(params: CloseFinished
     .accounts({
       signer: userPubkey,
       userTradeDetail,
-      userAccount: userTradeDetailData.userPubkey,
+      userAccount: rentPayer,
       vault: tradeVaultPda,
-      vaultTokenAccount: tokenPubkey ? await getAssociatedTokenAddress(tokenPubkey, tradeVaultPda, true) : null,
+      vaultTokenAccount: tokenPubkey
+        ? await getAssociatedTokenAddress(tokenPubkey, tradeVaultPda, true, tokenProgram)
+        : null,
+      // The remaining tokens of the vault go to the depositor, even when the deposit was sponsored
       userTokenAccount: tokenPubkey
-        ? await getAssociatedTokenAddress(tokenPubkey, userTradeDetailData.userPubkey, true)
+        ? await getAssociatedTokenAddress(tokenPubkey, userTradeDetailData.userPubkey, true, tokenProgram)
         : null,
+      mint: tokenPubkey,
+      tokenProgram,
+      mpcGroup: null,
     })
     .instruction()
 
@@ -10,7 +10,7 @@ import { createEphemeralNonceAccountInstruction } from './emepheral_nonce_accoun
 
 import { getOptimexProgram } from '../artifacts'
 import { WSOL_MINT } from '../constants'
-import { getTradeVaultPda, getUserTradeDetailPda, getWhitelistPda } from '../pda/get_pda_address'
+import { getMpcRegistryPda, getTradeVaultPda, getUserTradeDetailPda, getWhitelistPda } from '../pda/get_pda_address'
 import { IToken } from '../types/token_interface'
 import { TradeDetailInput } from '../types/trade_info'
 import { getTradeInput } from '../utils/param_utils'
@@ -149,13 +149,16 @@ export async function createDepositInstruction(params: DepositInstructionParam)
         tradeId: tradeIdBytes,
         data: tradeDetail,
         input: tradeInput,
+        // The tradeId is the sha256 of the ABI encoded trade input, see `getTradeId`
+        tradeIdScheme: { sha256Abi: {} },
       })
-      .accounts({
+      .accountsPartial({
         signer: userPubkey,
         userTradeDetail: userTradeDetail,
         ephemeralAccount: userEphemeralPubkey,
         whitelistToken,
         vault: tradeVaultPda,
+        mpcRegistry: getMpcRegistryPda(mpcPubkey),
       })
       .remainingAccounts(depositRemainingAccounts)
       .instruction(),
@@ -0,0 +1,112 @@
+import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { AccountMeta, Commitment, Connection, PublicKey, TransactionInstruction } from '@solana/web3.js'
+
+import { createAssociatedTokenAccountInstructionIfNeeded } from './helpers'
+
+import { getOptimexProgram } from '../artifacts'
+import { getFeeLedgerPda, getFeeReceiverPda, getProtocolPda } from '../pda/get_pda_address'
+
+/**
+ * Parameters for creating a distribute fees instruction
+ */
+export type DistributeFeesInstructionParam = {
+  /** A solana connection */
+  connection: Connection
+  /**
+   * The signer account who perform the distribution
+   * Can be anyone, must sign the transaction
+   */
+  signer: PublicKey
+  /**
+   * The token pubkey of the fee,
+   * If the token is null, the fee will be distributed in SOL.
+   */
+  token: PublicKey | null
+  /**
+   * The fee receivers, must include every fee receiver with a share.
+   * If the receivers are not provided, all the fee receivers with a share are fetched.
+   */
+  receivers?: PublicKey[]
+  /**
+   * The commitment level, default is confirmed
+   */
+  commitment?: Commitment
+}
+
+/**
+ * Get all the fee receivers with a share in the distributed fee
+ * @param connection - A solana connection
+ * @returns The pubkeys of the fee receivers with a share
+ */
+export async function getFeeReceiversWithShare(connection: Connection): Promise<PublicKey[]> {
+  const onchainProgram = await getOptimexProgram(connection)
+  const feeReceivers = await onchainProgram.account.feeReceiver.all()
+  return feeReceivers
+    .filter((feeReceiver) => feeReceiver.account.shareBps > 0)
+    .map((feeReceiver) => feeReceiver.account.receiver)
+}
+
+/**
+ * Create a group of instructions for distributing the available fee of a token to the fee receivers
+ * @param params - Parameters for creating a distribute fees instruction
+ * @returns An array of instructions for distributing the fee
+ */
+export async function createDistributeFeesInstruction(
+  params: DistributeFeesInstructionParam
+): Promise<TransactionInstruction[]> {
+  // Anysigner can perform this action.
+  // The shares of the receivers are set by the admin already.
+  const { connection, token, signer, commitment } = params
+  const receivers = params.receivers || (await getFeeReceiversWithShare(connection))
+  const protocolPda = getProtocolPda()
+  const tokenProgram = token
+    ? (await connection.getAccountInfo(token, commitment || 'confirmed'))!.owner
+    : TOKEN_PROGRAM_ID
+  const onchainProgram = await getOptimexProgram(connection)
+
+  // For each receiver, the fee receiver PDA then the accounts of the transfer
+  const remainingAccounts: AccountMeta[] = []
+  for (const receiver of receivers) {
+    remainingAccounts.push({ pubkey: getFeeReceiverPda(receiver), isSigner: false, isWritable: false })
+    if (token) {
+      const protocolAta = await getAssociatedTokenAddress(token, protocolPda, true, tokenProgram)
+      const receiverAta = await getAssociatedTokenAddress(token, receiver, true, tokenProgram)
+      remainingAccounts.push({ pubkey: tokenProgram, isSigner: false, isWritable: false })
+      remainingAccounts.push({ pubkey: token, isSigner: false, isWritable: false })
+      remainingAccounts.push({ pubkey: protocolAta, isSigner: false, isWritable: true })
+      remainingAccounts.push({ pubkey: receiverAta, isSigner: false, isWritable: true })
+    } else {
+      remainingAccounts.push({ pubkey: receiver, isSigner: false, isWritable: true })
+    }
+  }
+
+  const distributeFeesIns = await onchainProgram.methods
+    .distributeFees({ token })
+    .accountsPartial({
+      signer,
+      feeLedger: getFeeLedgerPda(token),
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction()
+  return [distributeFeesIns]
+}
+
+/**
+ * Create a group of instructions for creating the receiver Atas if needed and distributing the fee
+ * @param params - Parameters for creating a distribute fees instruction
+ * @returns An array of instructions for creating the receiver Atas if needed and distributing the fee
+ */
+export async function createReceiverAtasIfNeededAndDistributeFeesInstruction(
+  params: DistributeFeesInstructionParam
+): Promise<TransactionInstruction[]> {
+  const { connection, token, signer } = params
+  const receivers = params.receivers || (await getFeeReceiversWithShare(connection))
+  const createFeeReceiverAtaIns: TransactionInstruction[] = []
+  for (const receiver of receivers) {
+    createFeeReceiverAtaIns.push(
+      ...(await createAssociatedTokenAccountInstructionIfNeeded(connection, signer, token, receiver))
+    )
+  }
+  const distributeFeesIns = await createDistributeFeesInstruction({ ...params, receivers })
+  return [...createFeeReceiverAtaIns, ...distributeFeesIns]
+}
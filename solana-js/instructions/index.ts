@@ -10,4 +10,4 @@ export * from './manage_operator'
 export * from './manage_config'
 export * from './close_finished_trade'
 export * from './close_payment_receipt'
-export * from './withdraw_total_fee'
+export * from './distribute_fees'
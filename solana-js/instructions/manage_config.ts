@@ -3,7 +3,7 @@ import { Connection, PublicKey } from '@solana/web3.js'
 
 import { getOptimexProgram } from '../artifacts'
 import { InvalidParamError } from '../errors'
-import { getWhitelistPda } from '../pda'
+import { getRoleMemberPda, getWhitelistPda } from '../pda'
 
 /**
  * Parameter for adding or updating whitelist token
@@ -11,7 +11,7 @@ import { getWhitelistPda } from '../pda'
 export type AddOrUpdateWhitelistInstructionParam = {
   /**
    * The signer authority who can manage whitelist token
-   * Must be one of the operator
+   * Must be a member of the whitelist manager role
    * Must sign the transaction
    */
   operator: PublicKey
@@ -19,6 +19,10 @@ export type AddOrUpdateWhitelistInstructionParam = {
   token: PublicKey
   /** The minimum amount needed for deposit, with decimals */
   amount: bigint
+  /** The maximum amount of a single deposit, with decimals. Zero means no limit */
+  maxAmount: bigint
+  /** The maximum amount of the token held by the trade vaults, with decimals. Zero means no limit */
+  maxOutstanding: bigint
   /** A solana connection */
   connection: Connection
 }
@@ -29,14 +33,15 @@ export type AddOrUpdateWhitelistInstructionParam = {
  * @returns An array of length 1 containt the add or update whitelist token
  */
 export async function createAddOrUpdateWhitelistInstruction(param: AddOrUpdateWhitelistInstructionParam) {
-  const { connection, operator, token, amount } = param
+  const { connection, operator, token, amount, maxAmount, maxOutstanding } = param
   const onchainProgram = await getOptimexProgram(connection)
   return [
     await onchainProgram.methods
-      .addOrUpdateWhitelist(new BN(amount.toString()))
+      .addOrUpdateWhitelist(new BN(amount.toString()), new BN(maxAmount.toString()), new BN(maxOutstanding.toString()))
       .accounts({
         operator,
         token,
+        roleMember: getRoleMemberPda('whitelistManager', operator),
       })
       .instruction(),
   ]
@@ -48,7 +53,7 @@ export async function createAddOrUpdateWhitelistInstruction(param: AddOrUpdateWh
 export type RemoveWhitelistInstructionParam = {
   /**
    * The signer authority who can manage whitelist token
-   * Must be the operator
+   * Must be a member of the whitelist manager role
    * Must sign the transaction
    */
   operator: PublicKey
@@ -74,6 +79,7 @@ export async function createRemoveWhitelistInstruction(param: RemoveWhitelistIns
         operator,
         whitelistToken,
         token,
+        roleMember: getRoleMemberPda('whitelistManager', operator),
       })
       .instruction(),
   ]
@@ -84,8 +90,8 @@ export async function createRemoveWhitelistInstruction(param: RemoveWhitelistIns
  */
 export type SetCloseWaitDurationInstructionParam = {
   /**
-   * The signer authority who can manage whitelist token
-   * Must be the operator
+   * The signer authority who can set the close wait duration
+   * Must be a member of the duration manager role
    * Must sign the transaction
    */
   operator: PublicKey
@@ -116,6 +122,7 @@ export async function createSetCloseWaitDurationInstruction(param: SetCloseWaitD
       })
       .accounts({
         operator,
+        roleMember: getRoleMemberPda('durationManager', operator),
       })
       .instruction(),
   ]
@@ -135,6 +142,11 @@ export type AddFeeReceiverInstructionParam = {
   signer: PublicKey
   /** The fee receiver address */
   receiver: PublicKey
+  /**
+   * The pending action queued for this operation, required once the admin sets a timelock delay
+   * Its rent fee goes back to the signer
+   */
+  pendingAction?: PublicKey
 }
 
 /**
@@ -143,13 +155,14 @@ export type AddFeeReceiverInstructionParam = {
  * @returns An array of length 1 containing the add fee receiver instruction
  */
 export async function createAddFeeReceiverInstruction(param: AddFeeReceiverInstructionParam) {
-  const { connection, signer, receiver } = param
+  const { connection, signer, receiver, pendingAction } = param
   const onchainProgram = await getOptimexProgram(connection)
   return [
     await onchainProgram.methods
       .addFeeReceiver(receiver)
       .accounts({
         signer,
+        pendingAction: pendingAction || null,
       })
       .instruction(),
   ]
@@ -169,6 +182,11 @@ export type RemoveFeeReceiverInstructionParam = {
   signer: PublicKey
   /** The fee receiver address */
   receiver: PublicKey
+  /**
+   * The pending action queued for this operation, required once the admin sets a timelock delay
+   * Its rent fee goes back to the signer
+   */
+  pendingAction?: PublicKey
 }
 
 /**
@@ -177,11 +195,120 @@ export type RemoveFeeReceiverInstructionParam = {
  * @returns An array of length 1 containing the remove fee receiver instruction
  */
 export async function createRemoveFeeReceiverInstruction(param: RemoveFeeReceiverInstructionParam) {
-  const { connection, signer, receiver } = param
+  const { connection, signer, receiver, pendingAction } = param
   const onchainProgram = await getOptimexProgram(connection)
   return [
     await onchainProgram.methods
       .removeFeeReceiver(receiver)
+      .accounts({
+        signer,
+        pendingAction: pendingAction || null,
+      })
+      .instruction(),
+  ]
+}
+
+/**
+ * Parameter for setting the share of a fee receiver instruction
+ */
+export type SetFeeReceiverShareInstructionParam = {
+  /** A solana connection */
+  connection: Connection
+  /**
+   * The signer authority who can set the share of a fee receiver
+   * Must be the admin
+   * Must sign the transaction
+   */
+  signer: PublicKey
+  /** The fee receiver address */
+  receiver: PublicKey
+  /** The share of the fee receiver in the distributed fee, in basis points */
+  shareBps: number
+}
+
+/**
+ * Create set fee receiver share instruction
+ * @param param - Parameters for setting the share of a fee receiver
+ * @returns An array of length 1 containing the set fee receiver share instruction
+ */
+export async function createSetFeeReceiverShareInstruction(param: SetFeeReceiverShareInstructionParam) {
+  const { connection, signer, receiver, shareBps } = param
+  const onchainProgram = await getOptimexProgram(connection)
+  return [
+    await onchainProgram.methods
+      .setFeeReceiverShare(receiver, shareBps)
+      .accounts({
+        signer,
+      })
+      .instruction(),
+  ]
+}
+
+/**
+ * Parameter for registering or updating an MPC key
+ */
+export type AddOrUpdateMpcRegistryInstructionParam = {
+  /** A solana connection */
+  connection: Connection
+  /**
+   * The signer authority who can manage the MPC registry
+   * Must be the admin
+   * Must sign the transaction
+   */
+  signer: PublicKey
+  /** The MPC key, or the address of an MPC group */
+  mpcPubkey: PublicKey
+  /** The unix timestamp in seconds from which the deposits can reference the MPC key */
+  validFrom: number
+  /** The unix timestamp in seconds until which the deposits can reference the MPC key. Zero means no limit */
+  validUntil: number
+}
+
+/**
+ * Create add or update MPC registry instruction
+ * @param param - Parameters for registering or updating the MPC key
+ * @returns An array of length 1 containing the add or update MPC registry instruction
+ */
+export async function createAddOrUpdateMpcRegistryInstruction(param: AddOrUpdateMpcRegistryInstructionParam) {
+  const { connection, signer, mpcPubkey, validFrom, validUntil } = param
+  const onchainProgram = await getOptimexProgram(connection)
+  return [
+    await onchainProgram.methods
+      .addOrUpdateMpcRegistry(mpcPubkey, new BN(validFrom), new BN(validUntil))
+      .accounts({
+        signer,
+      })
+      .instruction(),
+  ]
+}
+
+/**
+ * Parameter for retiring an MPC key
+ */
+export type RetireMpcRegistryInstructionParam = {
+  /** A solana connection */
+  connection: Connection
+  /**
+   * The signer authority who can manage the MPC registry
+   * Must be the admin
+   * Must sign the transaction
+   */
+  signer: PublicKey
+  /** The MPC key to retire */
+  mpcPubkey: PublicKey
+}
+
+/**
+ * Create retire MPC registry instruction
+ * @param param - Parameters for retiring the MPC key
+ * @returns An array of length 1 containing the retire MPC registry instruction
+ */
+export async function createRetireMpcRegistryInstruction(param: RetireMpcRegistryInstructionParam) {
+  const { connection, signer, mpcPubkey } = param
+  const onchainProgram = await getOptimexProgram(connection)
+  return [
+    await onchainProgram.methods
+      .retireMpcRegistry(mpcPubkey)
       .accounts({
         signer,
       })
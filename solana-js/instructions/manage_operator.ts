@@ -1,72 +1,104 @@
 import { Connection, PublicKey } from '@solana/web3.js'
 
 import { getOptimexProgram } from '../artifacts'
+import { getRoleMemberPda } from '../pda/get_pda_address'
+import { Role, RoleName } from '../types/role'
 
 /**
- * Parameter for adding operator
+ * Parameter for granting or revoking a role
  */
-export type AddOperatorInstructionParam = {
+export type ManageRoleInstructionParam = {
   /**
-   * The signer authority who can manage operator
-   * Must be the upgrade authority of the program
+   * The signer authority who can manage the roles
+   * Must be the admin
+   * Must sign the transaction
    */
   signer: PublicKey
+  /** The role to grant or revoke */
+  role: RoleName
+  /** The member of the role */
+  member: PublicKey
   /**
-   * The operator we want to add
+   * The pending action queued for this operation, required once the admin sets a timelock delay
+   * Its rent fee goes back to the signer
    */
-  operator: PublicKey
+  pendingAction?: PublicKey
   /** A solana connection */
   connection: Connection
 }
 
 /**
- * Create add operator instruction
- * @param param - Paramters for adding an operator
- * @returns An array of length 1 containt the add operator instruction
+ * Create grant role instruction
+ * @param param - Parameters for granting a role
+ * @returns An array of length 1 containing the grant role instruction
  */
-export async function createAddOperatorInstruction(param: AddOperatorInstructionParam) {
-  const { connection, signer, operator } = param
+export async function createGrantRoleInstruction(param: ManageRoleInstructionParam) {
+  const { connection, signer, role, member, pendingAction } = param
+  const onchainProgram = await getOptimexProgram(connection)
+  return [
+    await onchainProgram.methods
+      .grantRole({ [role]: {} } as Role, member)
+      .accounts({
+        signer,
+        roleMember: getRoleMemberPda(role, member),
+        pendingAction: pendingAction || null,
+      })
+      .instruction(),
+  ]
+}
+
+/**
+ * Create revoke role instruction
+ * @param param - Parameters for revoking a role
+ * @returns An array of length 1 containing the revoke role instruction
+ */
+export async function createRevokeRoleInstruction(param: ManageRoleInstructionParam) {
+  const { connection, signer, role, member, pendingAction } = param
   const onchainProgram = await getOptimexProgram(connection)
   return [
     await onchainProgram.methods
-      .addOrRemoveOperator(operator, true)
+      .revokeRole({ [role]: {} } as Role, member)
       .accounts({
         signer,
+        roleMember: getRoleMemberPda(role, member),
+        pendingAction: pendingAction || null,
       })
       .instruction(),
   ]
 }
 
 /**
- * Parameter for removing operator
+ * Parameter for migrating a legacy operator
  */
-export type RemoveOperatorInstructionParam = {
+export type MigrateOperatorInstructionParam = {
   /**
-   * The signer authority who can manage operator
-   * Must be the upgrade authority of the program
+   * The signer authority who can manage the roles
+   * Must be the admin
+   * Must sign the transaction
    */
   signer: PublicKey
-  /**
-   * The operator we want to remove
-   */
+  /** The legacy operator of the config to migrate */
   operator: PublicKey
   /** A solana connection */
   connection: Connection
 }
 
 /**
- * Create remove operator instruction
- * @param param - Paramters for adding an operator
- * @returns An array of length 1 containt the add operator instruction
+ * Create migrate operator instruction
+ * The legacy operator is removed from the config, and granted the whitelist manager and duration manager roles
+ * @param param - Parameters for migrating a legacy operator
+ * @returns An array of length 1 containing the migrate operator instruction
  */
-export async function createRemoveOperatorInstruction(param: RemoveOperatorInstructionParam) {
+export async function createMigrateOperatorInstruction(param: MigrateOperatorInstructionParam) {
   const { connection, signer, operator } = param
   const onchainProgram = await getOptimexProgram(connection)
   return [
     await onchainProgram.methods
-      .addOrRemoveOperator(operator, false)
+      .migrateOperator(operator)
       .accounts({
         signer,
+        whitelistManager: getRoleMemberPda('whitelistManager', operator),
+        durationManager: getRoleMemberPda('durationManager', operator),
       })
       .instruction(),
   ]
@@ -6,7 +6,7 @@ import { createAssociatedTokenAccountInstructionIfNeeded } from './helpers'
 
 import { getOptimexProgram } from '../artifacts'
 import { WSOL_MINT } from '../constants'
-import { getPaymentReceiptPda, getProtocolPda, getWhitelistPda } from '../pda/get_pda_address'
+import { getFeeLedgerPda, getPaymentReceiptPda, getProtocolPda, getWhitelistPda } from '../pda/get_pda_address'
 import { bigintToBytes32 } from '../utils/parse_utils'
 
 /**
@@ -99,11 +99,12 @@ export async function createPaymentInstruction(param: CreatePaymentInstructionPa
       totalFee: new BN(totalFee.toString()),
       deadline: new BN(deadline),
     })
-    .accounts({
+    .accountsPartial({
       signer: fromUser,
       toUser: toUser,
       whitelistToken,
       paymentReceipt: paymentReceiptPda,
+      feeLedger: getFeeLedgerPda(token),
     })
     .remainingAccounts(remainingAccounts)
     .instruction()
@@ -2,7 +2,9 @@ import { BN } from '@coral-xyz/anchor'
 import { Connection, PublicKey } from '@solana/web3.js'
 
 import { getOptimexProgram } from '../artifacts'
-import { getUserTradeDetailPda } from '../pda/get_pda_address'
+import { WSOL_MINT } from '../constants'
+import { getUserTradeDetailPda, getWhitelistPda } from '../pda/get_pda_address'
+import { getTradeDetailData } from '../pda/get_pda_data'
 import { bigintToBytes32 } from '../utils/parse_utils'
 
 /**
@@ -32,14 +34,17 @@ export async function createSetTotalFeeInstructions(params: SetTotalFeeInstructi
   const tradeIdBytes = bigintToBytes32(BigInt(tradeId))
 
   const userTradeDetail = getUserTradeDetailPda(tradeId)
+  const userTradeDetailData = await getTradeDetailData(tradeId, connection)
   const setProtocolFeeInstruction = await onchainProgram.methods
     .setTotalFee({
       tradeId: tradeIdBytes,
       amount: new BN(amount.toString()),
     })
-    .accounts({
+    .accountsPartial({
       signer: mpcPubkey,
       userTradeDetail,
+      whitelistToken: getWhitelistPda(userTradeDetailData.token || WSOL_MINT),
+      mpcGroup: null,
     })
     .instruction()
 
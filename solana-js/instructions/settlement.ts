@@ -11,8 +11,15 @@ import {
 } from '@solana/web3.js'
 
 import { getOptimexProgram } from '../artifacts'
-import { getProtocolPda, getTradeVaultPda, getUserTradeDetailPda } from '../pda/get_pda_address'
-import { getTradeDetailData } from '../pda/get_pda_data'
+import { WSOL_MINT } from '../constants'
+import {
+  getFeeLedgerPda,
+  getProtocolPda,
+  getTradeVaultPda,
+  getUserTradeDetailPda,
+  getWhitelistPda,
+} from '../pda/get_pda_address'
+import { getTradeDetailData, getTradeRentPayer } from '../pda/get_pda_data'
 import { bigintToBytes32 } from '../utils/parse_utils'
 
 /**
@@ -75,14 +82,17 @@ export async function createSettlementInstructions(params: SettlementInstruction
     .settlement({
       tradeId: tradeIdBytes,
     })
-    .accounts({
+    .accountsPartial({
       signer: mpcPubkey,
       userEphemeralAccount: userEphemeralPubkey,
       userTradeDetail,
       pmm: pmmPubkey,
       refundAccount: userTradeDetailData.refundPubkey,
-      userAccount: userTradeDetailData.userPubkey,
+      userAccount: getTradeRentPayer(userTradeDetailData),
       vault: tradeVaultPda,
+      whitelistToken: getWhitelistPda(tokenPubkey || WSOL_MINT),
+      feeLedger: getFeeLedgerPda(tokenPubkey),
+      mpcGroup: null,
     })
     .remainingAccounts(remainingAccounts)
     .instruction()
@@ -2,7 +2,8 @@ import { BN } from '@coral-xyz/anchor'
 import { Connection, Finality, ParsedInstruction, PublicKey, SystemProgram } from '@solana/web3.js'
 
 import { getOffchainProgram } from '../artifacts'
-import { BPF_LOADER_PROGRAM } from '../constants'
+import { BPF_LOADER_PROGRAM, WSOL_MINT } from '../constants'
+import { RoleName, ROLES } from '../types/role'
 import { bigintToBytes32 } from '../utils/parse_utils'
 
 /**
@@ -102,6 +103,46 @@ export function getFeeReceiverPda(feeReceiver: PublicKey) {
   return feeReceiverPda
 }
 
+/**
+ * Get the fee ledger PDA, that tracks the protocol fee of a token
+ * @param token - The address of the token, null for SOL
+ * @returns The fee ledger PDA address
+ */
+export function getFeeLedgerPda(token: PublicKey | null) {
+  const [feeLedgerPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('fee_ledger'), (token || WSOL_MINT).toBuffer()],
+    program.programId
+  )
+  return feeLedgerPda
+}
+
+/**
+ * Get the role member PDA, that grants a role to a member
+ * @param role - The role
+ * @param member - The member of the role
+ * @returns The role member PDA address
+ */
+export function getRoleMemberPda(role: RoleName, member: PublicKey) {
+  const [roleMemberPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('role'), Buffer.from([ROLES.indexOf(role)]), member.toBuffer()],
+    program.programId
+  )
+  return roleMemberPda
+}
+
+/**
+ * Get the MPC registry PDA of a MPC key
+ * @param mpcPubkey - The MPC pubkey
+ * @returns The MPC registry PDA address
+ */
+export function getMpcRegistryPda(mpcPubkey: PublicKey) {
+  const [mpcRegistryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('mpc_registry'), mpcPubkey.toBuffer()],
+    program.programId
+  )
+  return mpcRegistryPda
+}
+
 /**
  * The parameters for getting the payment receipt PDA
  */
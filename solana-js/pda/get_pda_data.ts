@@ -28,6 +28,15 @@ export async function getTradeDetailData(
   }
 }
 
+/**
+ * Get the account that receives the rent of the trade accounts when they are closed
+ * @param tradeDetail - The trade detail data
+ * @returns The payer of the deposit, or the user for the trades deposited before the payer was recorded
+ */
+export function getTradeRentPayer(tradeDetail: { userPubkey: PublicKey; payerPubkey: PublicKey }) {
+  return tradeDetail.payerPubkey.equals(PublicKey.default) ? tradeDetail.userPubkey : tradeDetail.payerPubkey
+}
+
 /**
  *
  * Get the config account data of the program
@@ -0,0 +1,133 @@
+/**
+ * Generate the trade id golden vectors of the smart-contract tests,
+ * `programs/optimex-sol-smartcontract/tests/fixtures/trade_id_vectors.json`.
+ *
+ * The encoding is `abi.encode(uint256 sessionId, address solver, (uint256 amountIn, bytes[3] fromChain, bytes[3] toChain))`
+ * as computed by the EVM contracts, and the trade id is its sha256, computed by `getTradeId`.
+ *
+ * Run from `solana-js` with `yarn vectors:trade-id`.
+ */
+import { writeFileSync } from 'fs'
+import { join } from 'path'
+
+import { AbiCoder, BytesLike, getBytes, hexlify, toBeHex, toUtf8Bytes, zeroPadValue } from 'ethers'
+
+import { TradeInfoStruct } from '../types/trade_info'
+import { getTradeId } from '../utils/parse_utils'
+
+const OUTPUT = join(__dirname, '../../programs/optimex-sol-smartcontract/tests/fixtures/trade_id_vectors.json')
+
+const MAX_UINT256 = (1n << 256n) - 1n
+const SOLVER = '0xe291b8e5f3c9f3d4f7a7f2e1a5b1c9d8e7f6a5b4'
+const ZERO_ADDRESS = '0x0000000000000000000000000000000000000000'
+const MAX_ADDRESS = '0xffffffffffffffffffffffffffffffffffffffff'
+const SOL_USER = '3DYbLvuRV6tZN7iEBxAgHbpgv3AvWmAJGtWmrkp24Vew'
+const EVM_USER = '0x742d35Cc6634C0532925a3b844Bc454e4438f44e'
+
+type Vector = {
+  name: string
+  sessionId: bigint
+  solver: string
+  tradeInfo: TradeInfoStruct
+}
+
+const utf8 = (value: string): BytesLike => toUtf8Bytes(value)
+
+const vectors: Vector[] = [
+  {
+    name: 'sol_to_evm',
+    sessionId: 1n,
+    solver: SOLVER,
+    tradeInfo: {
+      amountIn: 100_000_000n,
+      fromChain: [utf8(SOL_USER), utf8('SOLANA'), utf8('native')],
+      toChain: [utf8(EVM_USER), utf8('ETHEREUM'), utf8('native')],
+    },
+  },
+  {
+    name: 'spl_to_bitcoin',
+    sessionId: 0x1234n,
+    solver: SOLVER,
+    tradeInfo: {
+      amountIn: 2_500_000n,
+      fromChain: [utf8(SOL_USER), utf8('SOLANA'), utf8('So11111111111111111111111111111111111111112')],
+      toChain: [utf8('bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh'), utf8('BITCOIN'), utf8('native')],
+    },
+  },
+  {
+    // The EVM address and token are raw 20 bytes rather than their hex strings
+    name: 'raw_evm_address',
+    sessionId: 42n,
+    solver: ZERO_ADDRESS,
+    tradeInfo: {
+      amountIn: 1n,
+      fromChain: [utf8(SOL_USER), utf8('SOLANA'), utf8('native')],
+      toChain: [EVM_USER, utf8('ETHEREUM'), ZERO_ADDRESS],
+    },
+  },
+  {
+    name: 'empty_elements',
+    sessionId: 0n,
+    solver: ZERO_ADDRESS,
+    tradeInfo: {
+      amountIn: 0n,
+      fromChain: ['0x', '0x', '0x'],
+      toChain: ['0x', '0x', '0x'],
+    },
+  },
+  {
+    // Elements of one byte below, at and above one and two 32 bytes words
+    name: 'word_boundaries',
+    sessionId: 7n,
+    solver: SOLVER,
+    tradeInfo: {
+      amountIn: 10n ** 18n,
+      fromChain: [utf8('a'.repeat(31)), utf8('b'.repeat(32)), utf8('c'.repeat(33))],
+      toChain: [utf8('d'.repeat(63)), utf8('e'.repeat(64)), utf8('f'.repeat(65))],
+    },
+  },
+  {
+    name: 'long_elements',
+    sessionId: 1n << 255n,
+    solver: MAX_ADDRESS,
+    tradeInfo: {
+      amountIn: 2n ** 64n - 1n,
+      fromChain: [utf8('x'.repeat(300)), utf8('SOLANA'), Uint8Array.from({ length: 256 }, (_, i) => i)],
+      toChain: [utf8('y'), utf8('z'.repeat(129)), utf8('native')],
+    },
+  },
+  {
+    name: 'max_values',
+    sessionId: MAX_UINT256,
+    solver: MAX_ADDRESS,
+    tradeInfo: {
+      amountIn: MAX_UINT256,
+      fromChain: [utf8(SOL_USER), utf8('SOLANA'), utf8('native')],
+      toChain: [utf8(EVM_USER), utf8('ETHEREUM'), utf8('native')],
+    },
+  },
+]
+
+const abiCoder = AbiCoder.defaultAbiCoder()
+const hex = (value: BytesLike): string => hexlify(value).slice(2)
+const word = (value: bigint): string => hex(zeroPadValue(toBeHex(value), 32))
+
+const output = vectors.map(({ name, sessionId, solver, tradeInfo }) => {
+  const { amountIn, fromChain, toChain } = tradeInfo
+  const encoded = abiCoder.encode(
+    ['uint256', 'address', 'tuple(uint256,bytes[3],bytes[3])'],
+    [sessionId, solver, [amountIn, fromChain, toChain]]
+  )
+  return {
+    name,
+    session_id: word(sessionId),
+    solver: hex(solver.toLowerCase()),
+    amount_in: word(BigInt(amountIn)),
+    from_chain: fromChain.map((element) => hex(getBytes(element))),
+    to_chain: toChain.map((element) => hex(getBytes(element))),
+    encoded: hex(encoded),
+    trade_id: hex(getTradeId(sessionId, solver, tradeInfo)),
+  }
+})
+
+writeFileSync(OUTPUT, JSON.stringify(output, null, 2) + '\n')
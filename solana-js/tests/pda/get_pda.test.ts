@@ -0,0 +1,28 @@
+import { Keypair, PublicKey } from '@solana/web3.js'
+
+import { expect } from 'chai'
+
+import { WSOL_MINT } from '../../constants'
+import { getFeeLedgerPda } from '../../pda/get_pda_address'
+import { getTradeRentPayer } from '../../pda/get_pda_data'
+
+describe('getFeeLedgerPda', () => {
+  it('should use the WSOL mint ledger for SOL', () => {
+    expect(getFeeLedgerPda(null).toBase58()).to.equal(getFeeLedgerPda(WSOL_MINT).toBase58())
+  })
+})
+
+describe('getTradeRentPayer', () => {
+  const userPubkey = Keypair.generate().publicKey
+  const payerPubkey = Keypair.generate().publicKey
+
+  it('should return the payer of a sponsored deposit', () => {
+    expect(getTradeRentPayer({ userPubkey, payerPubkey }).toBase58()).to.equal(payerPubkey.toBase58())
+  })
+
+  it('should return the user for the trades deposited before the payer was recorded', () => {
+    expect(getTradeRentPayer({ userPubkey, payerPubkey: PublicKey.default }).toBase58()).to.equal(
+      userPubkey.toBase58()
+    )
+  })
+})
@@ -1,3 +1,4 @@
 export * from './chain_info'
 export * from './token_interface'
 export * from './trade_info'
+export * from './role'
@@ -0,0 +1,10 @@
+import * as anchor from '@coral-xyz/anchor'
+
+import { OptimexSolSmartcontract } from '../artifacts/optimex_sol_smartcontract'
+
+export type Role = anchor.IdlTypes<OptimexSolSmartcontract>['role']
+
+/** The protocol roles, in the order of the on-chain `Role` enum */
+export const ROLES = ['whitelistManager', 'durationManager', 'feeManager', 'guardian'] as const
+
+export type RoleName = (typeof ROLES)[number]
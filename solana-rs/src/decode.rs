@@ -0,0 +1,96 @@
+//! Decoders for the accounts of the program.
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Result};
+use optimex_sol_smartcontract::{
+    Config, FeeLedger, FeeLedgerBalance, FeeReceiver, MpcGroup, MpcRegistry, PaymentExpectation, PaymentReceipt, PendingAction, RoleMember, TradeDetail, WhitelistToken,
+};
+
+/// Decode the data of an account of the program, the discriminator is checked.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Decode the data of a [TradeDetail] account.
+pub fn decode_trade_detail(data: &[u8]) -> Result<TradeDetail> {
+    decode_account(data)
+}
+
+/// Decode the data of a [PaymentReceipt] account.
+pub fn decode_payment_receipt(data: &[u8]) -> Result<PaymentReceipt> {
+    decode_account(data)
+}
+
+/// Decode the data of a [PaymentExpectation] account.
+pub fn decode_payment_expectation(data: &[u8]) -> Result<PaymentExpectation> {
+    decode_account(data)
+}
+
+/// Decode the data of the [Config] account.
+pub fn decode_config(data: &[u8]) -> Result<Config> {
+    decode_account(data)
+}
+
+/// Decode the data of a [WhitelistToken] account.
+pub fn decode_whitelist_token(data: &[u8]) -> Result<WhitelistToken> {
+    decode_account(data)
+}
+
+/// Decode the data of a [FeeReceiver] account.
+pub fn decode_fee_receiver(data: &[u8]) -> Result<FeeReceiver> {
+    decode_account(data)
+}
+
+/// Decode the data of an [MpcGroup] account.
+pub fn decode_mpc_group(data: &[u8]) -> Result<MpcGroup> {
+    decode_account(data)
+}
+
+/// Decode the data of an [MpcRegistry] account.
+pub fn decode_mpc_registry(data: &[u8]) -> Result<MpcRegistry> {
+    decode_account(data)
+}
+
+/// Decode the data of a [FeeLedger] account.
+pub fn decode_fee_ledger(data: &[u8]) -> Result<FeeLedger> {
+    decode_account(data)
+}
+
+/// Decode the data of a [PendingAction] account.
+pub fn decode_pending_action(data: &[u8]) -> Result<PendingAction> {
+    decode_account(data)
+}
+
+/// Decode the data of a [RoleMember] account.
+pub fn decode_role_member(data: &[u8]) -> Result<RoleMember> {
+    decode_account(data)
+}
+
+/// Decode the return data of the get fee ledger instruction.
+pub fn decode_fee_ledger_balance(return_data: &[u8]) -> Result<FeeLedgerBalance> {
+    Ok(FeeLedgerBalance::try_from_slice(return_data)?)
+}
+
+#[test]
+fn test_decode_trade_detail() {
+    use anchor_lang::{prelude::Pubkey, AccountSerialize};
+
+    let trade_detail = TradeDetail {
+        trade_id: [7; 32],
+        user_pubkey: Pubkey::new_unique(),
+        token: Some(Pubkey::new_unique()),
+        amount: 1_000,
+        timeout: 100,
+        ..Default::default()
+    };
+    let mut data = vec![];
+    trade_detail.try_serialize(&mut data).unwrap();
+
+    let decoded = decode_trade_detail(&data).unwrap();
+    assert_eq!(decoded.trade_id, trade_detail.trade_id);
+    assert_eq!(decoded.user_pubkey, trade_detail.user_pubkey);
+    assert_eq!(decoded.token, trade_detail.token);
+    assert_eq!(decoded.amount, trade_detail.amount);
+    assert_eq!(decoded.timeout, trade_detail.timeout);
+
+    // Another account type is rejected by the discriminator
+    assert!(decode_payment_receipt(&data).is_err());
+}
@@ -0,0 +1,1349 @@
+//! Builders for each instruction of the program.
+//!
+//! The instructions that act on an existing trade take the decoded [TradeDetail], so that the accounts
+//! are derived from the trade itself. SPL token transfers need the token program of the mint, given
+//! as a [TokenMint].
+//!
+//! The instructions authorized by the MPC of the trade take `mpc_signers`: None when the MPC is a single key,
+//! or the signing members when the MPC of the trade is an [MpcGroup](crate::MpcGroup).
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{ed25519_program, instruction::Instruction, secp256k1_program, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use optimex_sol_smartcontract::{accounts, instruction};
+
+use crate::{
+    pda::*,
+    remaining_accounts::{transfer_spl_token_accounts, TokenMint},
+    trade_id::calculate_trade_id_with_scheme,
+    BatchSettlementArgs, ClaimArgs, CloseFinishedTradeArgs, CooperativeRefundArgs, CreatePaymentExpectationArgs,
+    DepositArgs, DistributeFeesArgs, ExtendTimeoutArgs, InitArgs, PaymentArgs, Role, SetCloseWaitDurationArgs, SetPauseArgs, SetTotalFeeArgs,
+    SettlementArgs, SettlementLeg, SplitSettlementArgs, TimelockAction, TradeDetail, TradeDetailInput, TradeIdScheme, TradeInput,
+    ID, WSOL_MINT,
+};
+
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+    Instruction {
+        program_id: ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+/// The errors of the instruction builders, when the given accounts do not fit the trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// The token is not the mint of the token trade.
+    TokenMismatch,
+    /// No signing member is given for the MPC group.
+    NoMpcSigner,
+    /// No trade is given to the batch instruction.
+    NoTrade,
+    /// The trade id cannot be calculated from the trade input.
+    InvalidTradeInput,
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::TokenMismatch => write!(f, "the token must be the mint of the trade"),
+            BuildError::NoMpcSigner => write!(f, "an MPC group needs at least one signing member"),
+            BuildError::NoTrade => write!(f, "a batch needs at least one trade"),
+            BuildError::InvalidTradeInput => write!(f, "the trade id cannot be calculated from the trade input"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Check that the token matches the mint of the trade.
+fn trade_token(trade_detail: &TradeDetail, token: Option<&TokenMint>) -> Result<Option<TokenMint>, BuildError> {
+    match (trade_detail.token, token) {
+        (Some(mint), Some(token)) if token.mint == mint => Ok(Some(*token)),
+        (None, _) => Ok(None),
+        _ => Err(BuildError::TokenMismatch),
+    }
+}
+
+/// The signer, the MPC group and the co-signer accounts of an instruction authorized by the MPC of the trade.
+///
+/// The first member signs as the signer account, the other members are appended to the remaining accounts.
+fn mpc_accounts(
+    trade_detail: &TradeDetail,
+    mpc_signers: Option<&[Pubkey]>,
+) -> Result<(Pubkey, Option<Pubkey>, Vec<AccountMeta>), BuildError> {
+    match mpc_signers {
+        Some([signer, co_signers @ ..]) => Ok((
+            *signer,
+            Some(trade_detail.mpc_pubkey),
+            co_signers
+                .iter()
+                .map(|co_signer| AccountMeta::new_readonly(*co_signer, true))
+                .collect(),
+        )),
+        Some([]) => Err(BuildError::NoMpcSigner),
+        None => Ok((trade_detail.mpc_pubkey, None, vec![])),
+    }
+}
+
+/// Build the init instruction, signed by the upgrade authority of the program.
+pub fn init(signer: &Pubkey, admin: Option<Pubkey>) -> Instruction {
+    build(
+        accounts::Init {
+            signer: *signer,
+            vault: get_vault_pda(),
+            protocol: get_protocol_pda(),
+            config: get_config_pda(),
+            system_program: system_program::ID,
+            program: ID,
+            program_data: get_program_data(),
+        },
+        instruction::Init {
+            init_args: InitArgs { admin },
+        },
+        vec![],
+    )
+}
+
+/// The pending action account of a sensitive admin operation, when it was queued behind the timelock.
+fn pending_action(action: TimelockAction, timelocked: bool) -> Option<Pubkey> {
+    timelocked.then(|| get_pending_action_pda(&action))
+}
+
+/// Build the grant role instruction, signed by the admin.
+///
+/// `timelocked` is set when the operation was queued, see [queue_pending_action].
+pub fn grant_role(admin: &Pubkey, role: Role, member: Pubkey, timelocked: bool) -> Instruction {
+    build(
+        accounts::GrantRole {
+            signer: *admin,
+            config: get_config_pda(),
+            role_member: get_role_pda(role, &member),
+            system_program: system_program::ID,
+            pending_action: pending_action(TimelockAction::GrantRole(role, member), timelocked),
+        },
+        instruction::GrantRole { role, member },
+        vec![],
+    )
+}
+
+/// Build the revoke role instruction, signed by the admin.
+///
+/// `timelocked` is set when the operation was queued, see [queue_pending_action].
+pub fn revoke_role(admin: &Pubkey, role: Role, member: Pubkey, timelocked: bool) -> Instruction {
+    build(
+        accounts::RevokeRole {
+            signer: *admin,
+            config: get_config_pda(),
+            role_member: get_role_pda(role, &member),
+            pending_action: pending_action(TimelockAction::RevokeRole(role, member), timelocked),
+        },
+        instruction::RevokeRole { role, member },
+        vec![],
+    )
+}
+
+/// Build the migrate operator instruction, signed by the admin.
+pub fn migrate_operator(admin: &Pubkey, operator: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateOperator {
+            signer: *admin,
+            config: get_config_pda(),
+            whitelist_manager: get_role_pda(Role::WhitelistManager, &operator),
+            duration_manager: get_role_pda(Role::DurationManager, &operator),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateOperator { operator },
+        vec![],
+    )
+}
+
+/// Build the migrate guardian instruction, signed by the admin.
+/// The `guardian` is the legacy guardian of the config.
+pub fn migrate_guardian(admin: &Pubkey, guardian: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateGuardian {
+            signer: *admin,
+            config: get_config_pda(),
+            role_member: get_role_pda(Role::Guardian, guardian),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGuardian {},
+        vec![],
+    )
+}
+
+/// Build the add or update whitelist instruction, signed by a whitelist manager.
+pub fn add_or_update_whitelist(
+    operator: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    max_amount: u64,
+    max_outstanding: u64,
+) -> Instruction {
+    build(
+        accounts::AddOrUpdateWhitelist {
+            operator: *operator,
+            config: get_config_pda(),
+            whitelist_token: get_whitelist_pda(mint),
+            token: *mint,
+            system_program: system_program::ID,
+            role_member: get_role_pda(Role::WhitelistManager, operator),
+        },
+        instruction::AddOrUpdateWhitelist {
+            amount,
+            max_amount,
+            max_outstanding,
+        },
+        vec![],
+    )
+}
+
+/// Build the remove whitelist instruction, signed by a whitelist manager.
+pub fn remove_whitelist(operator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::RemoveWhitelist {
+            operator: *operator,
+            config: get_config_pda(),
+            whitelist_token: get_whitelist_pda(mint),
+            token: *mint,
+            system_program: system_program::ID,
+            role_member: get_role_pda(Role::WhitelistManager, operator),
+        },
+        instruction::RemoveWhitelist {},
+        vec![],
+    )
+}
+
+/// Build the set close wait duration instruction, signed by a duration manager.
+pub fn set_close_wait_duration(
+    operator: &Pubkey,
+    set_close_wait_duration_args: SetCloseWaitDurationArgs,
+) -> Instruction {
+    build(
+        accounts::SetCloseWaitDuration {
+            operator: *operator,
+            config: get_config_pda(),
+            role_member: get_role_pda(Role::DurationManager, operator),
+        },
+        instruction::SetCloseWaitDuration {
+            set_close_wait_duration_args,
+        },
+        vec![],
+    )
+}
+
+/// Build the set max timeout extension instruction, signed by a duration manager.
+pub fn set_max_timeout_extension(operator: &Pubkey, max_timeout_extension: u64) -> Instruction {
+    build(
+        accounts::SetMaxTimeoutExtension {
+            operator: *operator,
+            config: get_config_pda(),
+            role_member: get_role_pda(Role::DurationManager, operator),
+        },
+        instruction::SetMaxTimeoutExtension { max_timeout_extension },
+        vec![],
+    )
+}
+
+/// Build the add fee receiver instruction, signed by the admin.
+///
+/// `timelocked` is set when the operation was queued, see [queue_pending_action].
+pub fn add_fee_receiver(admin: &Pubkey, receiver_pubkey: Pubkey, timelocked: bool) -> Instruction {
+    build(
+        accounts::AddFeeReceiverAccounts {
+            signer: *admin,
+            config: get_config_pda(),
+            fee_receiver: get_fee_receiver_pda(&receiver_pubkey),
+            system_program: system_program::ID,
+            pending_action: pending_action(TimelockAction::AddFeeReceiver(receiver_pubkey), timelocked),
+        },
+        instruction::AddFeeReceiver { receiver_pubkey },
+        vec![],
+    )
+}
+
+/// Build the remove fee receiver instruction, signed by the admin.
+///
+/// `timelocked` is set when the operation was queued, see [queue_pending_action].
+pub fn remove_fee_receiver(admin: &Pubkey, receiver_pubkey: Pubkey, timelocked: bool) -> Instruction {
+    build(
+        accounts::RemoveFeeReceiverAccounts {
+            signer: *admin,
+            config: get_config_pda(),
+            fee_receiver_account: get_fee_receiver_pda(&receiver_pubkey),
+            system_program: system_program::ID,
+            pending_action: pending_action(TimelockAction::RemoveFeeReceiver(receiver_pubkey), timelocked),
+        },
+        instruction::RemoveFeeReceiver { receiver_pubkey },
+        vec![],
+    )
+}
+
+/// Build the queue pending action instruction, signed by the admin.
+pub fn queue_pending_action(admin: &Pubkey, action: TimelockAction) -> Instruction {
+    build(
+        accounts::QueuePendingAction {
+            signer: *admin,
+            config: get_config_pda(),
+            pending_action: get_pending_action_pda(&action),
+            system_program: system_program::ID,
+        },
+        instruction::QueuePendingAction { action },
+        vec![],
+    )
+}
+
+/// Build the cancel pending action instruction, signed by the admin or a guardian.
+///
+/// The rent fee is refunded to the `admin` who queued the action, see [crate::PendingAction::admin].
+pub fn cancel_pending_action(signer: &Pubkey, admin: &Pubkey, action: &TimelockAction) -> Instruction {
+    build(
+        accounts::CancelPendingAction {
+            signer: *signer,
+            config: get_config_pda(),
+            pending_action: get_pending_action_pda(action),
+            admin: *admin,
+            guardian: get_role_pda(Role::Guardian, signer),
+        },
+        instruction::CancelPendingAction {},
+        vec![],
+    )
+}
+
+/// Build the set timelock delay instruction, signed by the admin.
+///
+/// `timelocked` is set when the new delay was queued, see [queue_pending_action].
+pub fn set_timelock_delay(admin: &Pubkey, timelock_delay: u64, timelocked: bool) -> Instruction {
+    build(
+        accounts::SetTimelockDelay {
+            signer: *admin,
+            config: get_config_pda(),
+            pending_action: pending_action(TimelockAction::SetTimelockDelay(timelock_delay), timelocked),
+        },
+        instruction::SetTimelockDelay { timelock_delay },
+        vec![],
+    )
+}
+
+/// Build the set fee receiver share instruction, signed by the admin.
+pub fn set_fee_receiver_share(admin: &Pubkey, receiver_pubkey: Pubkey, share_bps: u16) -> Instruction {
+    build(
+        accounts::SetFeeReceiverShareAccounts {
+            signer: *admin,
+            config: get_config_pda(),
+            fee_receiver: get_fee_receiver_pda(&receiver_pubkey),
+        },
+        instruction::SetFeeReceiverShare {
+            receiver_pubkey,
+            share_bps,
+        },
+        vec![],
+    )
+}
+
+/// Build the set pause instruction, signed by the admin or a guardian.
+pub fn set_pause(signer: &Pubkey, set_pause_args: SetPauseArgs) -> Instruction {
+    build(
+        accounts::SetPause {
+            signer: *signer,
+            config: get_config_pda(),
+            guardian: get_role_pda(Role::Guardian, signer),
+        },
+        instruction::SetPause { set_pause_args },
+        vec![],
+    )
+}
+
+/// Build the propose admin instruction, signed by the current admin.
+pub fn propose_admin(admin: &Pubkey, new_admin: Pubkey) -> Instruction {
+    build(
+        accounts::ProposeAdmin {
+            signer: *admin,
+            config: get_config_pda(),
+        },
+        instruction::ProposeAdmin { new_admin },
+        vec![],
+    )
+}
+
+/// Build the accept admin instruction, signed by the pending admin.
+pub fn accept_admin(pending_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdmin {
+            signer: *pending_admin,
+            config: get_config_pda(),
+        },
+        instruction::AcceptAdmin {},
+        vec![],
+    )
+}
+
+/// Build the set max fee instruction, signed by a fee manager.
+/// Set the maximum fee of the `mint` when given, otherwise the global maximum fee.
+pub fn set_max_fee(fee_manager: &Pubkey, mint: Option<Pubkey>, max_fee_bps: Option<u16>) -> Instruction {
+    build(
+        accounts::SetMaxFee {
+            signer: *fee_manager,
+            config: get_config_pda(),
+            whitelist_token: mint.as_ref().map(get_whitelist_pda),
+            role_member: get_role_pda(Role::FeeManager, fee_manager),
+        },
+        instruction::SetMaxFee { max_fee_bps },
+        vec![],
+    )
+}
+
+/// Build the add MPC group instruction, signed by the admin.
+pub fn add_mpc_group(
+    admin: &Pubkey,
+    group_id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        accounts::AddMpcGroup {
+            signer: *admin,
+            config: get_config_pda(),
+            mpc_group: get_mpc_group_pda(group_id),
+            system_program: system_program::ID,
+        },
+        instruction::AddMpcGroup {
+            group_id,
+            members,
+            threshold,
+        },
+        vec![],
+    )
+}
+
+/// Build the remove MPC group instruction, signed by the admin.
+pub fn remove_mpc_group(admin: &Pubkey, group_id: u64) -> Instruction {
+    build(
+        accounts::RemoveMpcGroup {
+            signer: *admin,
+            config: get_config_pda(),
+            mpc_group: get_mpc_group_pda(group_id),
+            system_program: system_program::ID,
+        },
+        instruction::RemoveMpcGroup { group_id },
+        vec![],
+    )
+}
+
+/// Build the add or update MPC registry instruction, signed by the admin.
+pub fn add_or_update_mpc_registry(
+    admin: &Pubkey,
+    mpc_pubkey: Pubkey,
+    valid_from: i64,
+    valid_until: i64,
+) -> Instruction {
+    build(
+        accounts::AddOrUpdateMpcRegistry {
+            signer: *admin,
+            config: get_config_pda(),
+            mpc_registry: get_mpc_registry_pda(&mpc_pubkey),
+            system_program: system_program::ID,
+        },
+        instruction::AddOrUpdateMpcRegistry {
+            mpc_pubkey,
+            valid_from,
+            valid_until,
+        },
+        vec![],
+    )
+}
+
+/// Build the retire MPC registry instruction, signed by the admin.
+pub fn retire_mpc_registry(admin: &Pubkey, mpc_pubkey: Pubkey) -> Instruction {
+    build(
+        accounts::RetireMpcRegistry {
+            signer: *admin,
+            config: get_config_pda(),
+            mpc_registry: get_mpc_registry_pda(&mpc_pubkey),
+        },
+        instruction::RetireMpcRegistry { mpc_pubkey },
+        vec![],
+    )
+}
+
+/// Build the set solver signature required instruction, signed by the admin.
+pub fn set_solver_signature_required(admin: &Pubkey, required: bool) -> Instruction {
+    build(
+        accounts::SetSolverSignatureRequired {
+            signer: *admin,
+            config: get_config_pda(),
+        },
+        instruction::SetSolverSignatureRequired { required },
+        vec![],
+    )
+}
+
+/// Build the accrue legacy fee instruction, signed by the admin.
+///
+/// `token` is None for the SOL fee, the fee ledger is then the one of the WSOL mint.
+pub fn accrue_legacy_fee(admin: &Pubkey, token: Option<&TokenMint>) -> Instruction {
+    let mint = token.map(|token| token.mint);
+    build(
+        accounts::AccrueLegacyFee {
+            signer: *admin,
+            config: get_config_pda(),
+            protocol: get_protocol_pda(),
+            protocol_token_account: token.map(|token| token.ata(&get_protocol_pda())),
+            fee_ledger: get_fee_ledger_pda(&mint.unwrap_or(WSOL_MINT)),
+            system_program: system_program::ID,
+        },
+        instruction::AccrueLegacyFee { token: mint },
+        vec![],
+    )
+}
+
+/// Build the deposit instruction, signed by the user and the ephemeral account.
+///
+/// The trade id is calculated from the `input` with the `trade_id_scheme`. `token` is None when depositing SOL.
+pub fn deposit(
+    user: &Pubkey,
+    ephemeral_account: &Pubkey,
+    input: TradeInput,
+    trade_id_scheme: TradeIdScheme,
+    data: TradeDetailInput,
+    token: Option<&TokenMint>,
+) -> Result<Instruction, BuildError> {
+    let trade_id = calculate_trade_id_with_scheme(&input, trade_id_scheme).map_err(|_| BuildError::InvalidTradeInput)?;
+    let vault = get_trade_vault_pda(&trade_id);
+    let mint = token.map_or(WSOL_MINT, |token| token.mint);
+    let remaining_accounts = token.map_or(vec![], |token| {
+        transfer_spl_token_accounts(token, user, &vault, false)
+    });
+
+    Ok(build(
+        accounts::DepositAccounts {
+            signer: *user,
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            ephemeral_account: *ephemeral_account,
+            nonce_check_account: get_nonce_check_pda(ephemeral_account),
+            vault,
+            whitelist_token: get_whitelist_pda(&mint),
+            config: get_config_pda(),
+            system_program: system_program::ID,
+            mpc_registry: get_mpc_registry_pda(&data.mpc_pubkey),
+            instructions_sysvar: Some(sysvar::instructions::ID),
+        },
+        instruction::Deposit {
+            deposit_args: DepositArgs {
+                input,
+                data,
+                trade_id,
+                trade_id_scheme,
+            },
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Build the sponsored deposit instruction, signed by the payer, the user and the ephemeral account.
+///
+/// Same as [deposit], with the rent of the trade accounts paid by the `payer`.
+pub fn sponsored_deposit(
+    payer: &Pubkey,
+    user: &Pubkey,
+    ephemeral_account: &Pubkey,
+    input: TradeInput,
+    trade_id_scheme: TradeIdScheme,
+    data: TradeDetailInput,
+    token: Option<&TokenMint>,
+) -> Result<Instruction, BuildError> {
+    let trade_id = calculate_trade_id_with_scheme(&input, trade_id_scheme).map_err(|_| BuildError::InvalidTradeInput)?;
+    let vault = get_trade_vault_pda(&trade_id);
+    let mint = token.map_or(WSOL_MINT, |token| token.mint);
+    let remaining_accounts = token.map_or(vec![], |token| {
+        transfer_spl_token_accounts(token, user, &vault, false)
+    });
+
+    Ok(build(
+        accounts::SponsoredDepositAccounts {
+            payer: *payer,
+            signer: *user,
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            ephemeral_account: *ephemeral_account,
+            nonce_check_account: get_nonce_check_pda(ephemeral_account),
+            vault,
+            whitelist_token: get_whitelist_pda(&mint),
+            config: get_config_pda(),
+            system_program: system_program::ID,
+            mpc_registry: get_mpc_registry_pda(&data.mpc_pubkey),
+            instructions_sysvar: Some(sysvar::instructions::ID),
+        },
+        instruction::SponsoredDeposit {
+            deposit_args: DepositArgs {
+                input,
+                data,
+                trade_id,
+                trade_id_scheme,
+            },
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Build the Ed25519 program instruction that verifies the `signature` of the `message` by the `signer`,
+/// with the signature, the public key and the message in the instruction data itself.
+/// It must precede the [intent_deposit] instruction, the `message` being the [crate::DepositIntent::message].
+pub fn ed25519_verify(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    // One signature, then the offsets of the public key, the signature and the message
+    let public_key_offset: u16 = 16;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let mut data = vec![1u8, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Build the secp256k1 program instruction that verifies the `signature` and `recovery_id` of the keccak256 hash
+/// of the `message` by the `eth_address`, with the signature, the address and the message in the instruction data itself.
+/// The `instruction_index` is the position of this instruction in the transaction, which must precede the deposit.
+/// For the solver signature of a deposit, the `message` is the trade id.
+pub fn secp256k1_verify(
+    eth_address: &[u8; 20],
+    signature: &[u8; 64],
+    recovery_id: u8,
+    message: &[u8],
+    instruction_index: u8,
+) -> Instruction {
+    // One signature, then the offsets of the address, the signature with its recovery id and the message
+    let eth_address_offset: u16 = 12;
+    let signature_offset = eth_address_offset + 20;
+    let message_offset = signature_offset + 65;
+    let mut data = vec![1u8];
+    data.extend_from_slice(&signature_offset.to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(&eth_address_offset.to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(&message_offset.to_le_bytes());
+    data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(eth_address);
+    data.extend_from_slice(signature);
+    data.push(recovery_id);
+    data.extend_from_slice(message);
+    Instruction {
+        program_id: secp256k1_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// The deposit of a token trade from a deposit intent signed by the `user`, submitted and paid by the `payer`.
+/// The user must have approved the [get_intent_delegate_pda] on its token account for the amount,
+/// and the [ed25519_verify] instruction of the user signature must precede this instruction.
+pub fn intent_deposit(
+    payer: &Pubkey,
+    user: &Pubkey,
+    ephemeral_account: &Pubkey,
+    input: TradeInput,
+    trade_id_scheme: TradeIdScheme,
+    data: TradeDetailInput,
+    token: &TokenMint,
+) -> Result<Instruction, BuildError> {
+    let trade_id = calculate_trade_id_with_scheme(&input, trade_id_scheme).map_err(|_| BuildError::InvalidTradeInput)?;
+    let vault = get_trade_vault_pda(&trade_id);
+    let remaining_accounts = transfer_spl_token_accounts(token, user, &vault, false);
+
+    Ok(build(
+        accounts::IntentDepositAccounts {
+            payer: *payer,
+            user: *user,
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            ephemeral_account: *ephemeral_account,
+            nonce_check_account: get_nonce_check_pda(ephemeral_account),
+            vault,
+            whitelist_token: get_whitelist_pda(&token.mint),
+            config: get_config_pda(),
+            system_program: system_program::ID,
+            mpc_registry: get_mpc_registry_pda(&data.mpc_pubkey),
+            delegate: get_intent_delegate_pda(),
+            instructions_sysvar: sysvar::instructions::ID,
+        },
+        instruction::IntentDeposit {
+            deposit_args: DepositArgs {
+                input,
+                data,
+                trade_id,
+                trade_id_scheme,
+            },
+        },
+        remaining_accounts,
+    ))
+}
+
+fn payment_accounts(
+    signer: &Pubkey,
+    to_user: &Pubkey,
+    payment_args: &PaymentArgs,
+) -> accounts::PaymentAccounts {
+    accounts::PaymentAccounts {
+        signer: *signer,
+        to_user: *to_user,
+        protocol: get_protocol_pda(),
+        whitelist_token: get_whitelist_pda(&payment_args.token.unwrap_or(WSOL_MINT)),
+        payment_receipt: get_payment_receipt_pda(
+            &payment_args.trade_id,
+            signer,
+            to_user,
+            payment_args.amount,
+            payment_args.total_fee,
+            payment_args.token,
+        ),
+        config: get_config_pda(),
+        fee_ledger: get_fee_ledger_pda(&payment_args.token.unwrap_or(WSOL_MINT)),
+        system_program: system_program::ID,
+    }
+}
+
+fn payment_remaining_accounts(
+    signer: &Pubkey,
+    to_user: &Pubkey,
+    payment_args: &PaymentArgs,
+    token_program: &Pubkey,
+) -> Vec<AccountMeta> {
+    payment_args.token.map_or(vec![], |mint| {
+        let token = TokenMint {
+            mint,
+            token_program: *token_program,
+        };
+        transfer_spl_token_accounts(&token, signer, to_user, true)
+    })
+}
+
+/// Build the payment instruction, signed by the pmm.
+///
+/// `token_program` is the owner of the [PaymentArgs::token] mint, unused for SOL payments.
+pub fn payment(
+    signer: &Pubkey,
+    to_user: &Pubkey,
+    payment_args: PaymentArgs,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        payment_accounts(signer, to_user, &payment_args),
+        instruction::Payment {
+            payment_args: payment_args.clone(),
+        },
+        payment_remaining_accounts(signer, to_user, &payment_args, token_program),
+    )
+}
+
+/// Build the create payment expectation instruction, signed by the MPC.
+///
+/// With `mpc_group`, the expectation is published on behalf of the group by its signing members:
+/// the `signer` and the co-signers.
+pub fn create_payment_expectation(
+    signer: &Pubkey,
+    args: CreatePaymentExpectationArgs,
+    mpc_group: Option<(Pubkey, &[Pubkey])>,
+) -> Instruction {
+    let mpc_pubkey = mpc_group.map_or(*signer, |(mpc_group, _)| mpc_group);
+    let co_signers = mpc_group.map_or(&[][..], |(_, co_signers)| co_signers);
+    build(
+        accounts::CreatePaymentExpectationAccounts {
+            signer: *signer,
+            mpc_group: mpc_group.map(|(mpc_group, _)| mpc_group),
+            mpc_registry: get_mpc_registry_pda(&mpc_pubkey),
+            payment_expectation: get_payment_expectation_pda(&args.trade_id, &mpc_pubkey),
+            system_program: system_program::ID,
+        },
+        instruction::CreatePaymentExpectation { args },
+        co_signers
+            .iter()
+            .map(|co_signer| AccountMeta::new_readonly(*co_signer, true))
+            .collect(),
+    )
+}
+
+/// Build the strict payment instruction, signed by the pmm.
+///
+/// The payment is checked against the payment expectation published by the `mpc`.
+pub fn strict_payment(
+    signer: &Pubkey,
+    to_user: &Pubkey,
+    mpc: &Pubkey,
+    payment_args: PaymentArgs,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::StrictPaymentAccounts {
+            payment: payment_accounts(signer, to_user, &payment_args),
+            payment_expectation: get_payment_expectation_pda(&payment_args.trade_id, mpc),
+        },
+        instruction::StrictPayment {
+            payment_args: payment_args.clone(),
+        },
+        payment_remaining_accounts(signer, to_user, &payment_args, token_program),
+    )
+}
+
+/// Build the close payment expectation instruction, signed by the MPC.
+///
+/// With `mpc_group`, the expectation of the group is closed by its signing members: the `signer` and the co-signers.
+pub fn close_payment_expectation(
+    signer: &Pubkey,
+    trade_id: &[u8; 32],
+    mpc_group: Option<(Pubkey, &[Pubkey])>,
+) -> Instruction {
+    let mpc_pubkey = mpc_group.map_or(*signer, |(mpc_group, _)| mpc_group);
+    let co_signers = mpc_group.map_or(&[][..], |(_, co_signers)| co_signers);
+    build(
+        accounts::ClosePaymentExpectationAccounts {
+            signer: *signer,
+            payment_expectation: get_payment_expectation_pda(trade_id, &mpc_pubkey),
+            mpc_group: mpc_group.map(|(mpc_group, _)| mpc_group),
+        },
+        instruction::ClosePaymentExpectation {},
+        co_signers
+            .iter()
+            .map(|co_signer| AccountMeta::new_readonly(*co_signer, true))
+            .collect(),
+    )
+}
+
+/// Build the set total fee instruction, signed by the MPC of the trade.
+///
+/// # Errors
+/// * [BuildError::NoMpcSigner] when `mpc_signers` has no member.
+pub fn set_total_fee(
+    trade_detail: &TradeDetail,
+    amount: u64,
+    mpc_signers: Option<&[Pubkey]>,
+) -> Result<Instruction, BuildError> {
+    let (signer, mpc_group, co_signers) = mpc_accounts(trade_detail, mpc_signers)?;
+    Ok(build(
+        accounts::SetTotalFee {
+            signer,
+            user_trade_detail: get_user_trade_detail_pda(&trade_detail.trade_id),
+            config: get_config_pda(),
+            whitelist_token: get_whitelist_pda(&trade_detail.token.unwrap_or(WSOL_MINT)),
+            mpc_group,
+        },
+        instruction::SetTotalFee {
+            set_total_fee_args: SetTotalFeeArgs {
+                trade_id: trade_detail.trade_id,
+                amount,
+            },
+        },
+        co_signers,
+    ))
+}
+
+/// Build the settlement instruction, signed by the MPC and the ephemeral account of the trade.
+///
+/// # Errors
+/// * [BuildError::TokenMismatch] when `token` is not the mint of a token trade.
+/// * [BuildError::NoMpcSigner] when `mpc_signers` has no member.
+pub fn settlement(
+    trade_detail: &TradeDetail,
+    pmm: &Pubkey,
+    token: Option<&TokenMint>,
+    mpc_signers: Option<&[Pubkey]>,
+) -> Result<Instruction, BuildError> {
+    let trade_id = trade_detail.trade_id;
+    let vault = get_trade_vault_pda(&trade_id);
+    let (signer, mpc_group, co_signers) = mpc_accounts(trade_detail, mpc_signers)?;
+    let mut remaining_accounts = trade_token(trade_detail, token)?.map_or(vec![], |token| {
+        transfer_spl_token_accounts(&token, &vault, pmm, trade_detail.total_fee.is_some())
+    });
+    remaining_accounts.extend(co_signers);
+
+    Ok(build(
+        accounts::SettlementAccounts {
+            signer,
+            user_account: trade_detail.rent_payer(),
+            user_ephemeral_account: trade_detail.user_ephemeral_pubkey,
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            nonce_check_account: get_nonce_check_pda(&trade_detail.user_ephemeral_pubkey),
+            vault,
+            refund_account: trade_detail.refund_pubkey,
+            protocol: get_protocol_pda(),
+            pmm: *pmm,
+            config: get_config_pda(),
+            whitelist_token: get_whitelist_pda(&trade_detail.token.unwrap_or(WSOL_MINT)),
+            fee_ledger: get_fee_ledger_pda(&trade_detail.token.unwrap_or(WSOL_MINT)),
+            system_program: system_program::ID,
+            mpc_group,
+        },
+        instruction::Settlement {
+            settle_args: SettlementArgs { trade_id },
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Build the split settlement instruction, signed by the MPC and the ephemeral account of the trade.
+///
+/// # Errors
+/// * [BuildError::TokenMismatch] when `token` is not the mint of a token trade.
+/// * [BuildError::NoMpcSigner] when `mpc_signers` has no member.
+pub fn split_settlement(
+    trade_detail: &TradeDetail,
+    legs: Vec<SettlementLeg>,
+    token: Option<&TokenMint>,
+    mpc_signers: Option<&[Pubkey]>,
+) -> Result<Instruction, BuildError> {
+    let trade_id = trade_detail.trade_id;
+    let vault = get_trade_vault_pda(&trade_id);
+    let (signer, mpc_group, co_signers) = mpc_accounts(trade_detail, mpc_signers)?;
+    let mut remaining_accounts: Vec<AccountMeta> = match trade_token(trade_detail, token)? {
+        // The protocol ata follows the first leg when there is a total fee
+        Some(token) => legs
+            .iter()
+            .enumerate()
+            .flat_map(|(index, leg)| {
+                let with_protocol_fee = index == 0 && trade_detail.total_fee.is_some();
+                transfer_spl_token_accounts(&token, &vault, &leg.pmm, with_protocol_fee)
+            })
+            .collect(),
+        None => legs
+            .iter()
+            .map(|leg| AccountMeta::new(leg.pmm, false))
+            .collect(),
+    };
+    remaining_accounts.extend(co_signers);
+
+    Ok(build(
+        accounts::SplitSettlementAccounts {
+            signer,
+            user_account: trade_detail.rent_payer(),
+            user_ephemeral_account: trade_detail.user_ephemeral_pubkey,
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            nonce_check_account: get_nonce_check_pda(&trade_detail.user_ephemeral_pubkey),
+            vault,
+            refund_account: trade_detail.refund_pubkey,
+            protocol: get_protocol_pda(),
+            config: get_config_pda(),
+            whitelist_token: get_whitelist_pda(&trade_detail.token.unwrap_or(WSOL_MINT)),
+            fee_ledger: get_fee_ledger_pda(&trade_detail.token.unwrap_or(WSOL_MINT)),
+            system_program: system_program::ID,
+            mpc_group,
+        },
+        instruction::SplitSettlement {
+            split_settlement_args: SplitSettlementArgs { trade_id, legs },
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Build the batch settlement instruction, signed by the MPC and the ephemeral accounts of the trades.
+///
+/// All the trades share the same MPC, settled to the same `pmm`, and are with the same `token`, None for SOL.
+///
+/// # Errors
+/// * [BuildError::NoTrade] when there is no trade.
+/// * [BuildError::TokenMismatch] when `token` is not the mint of a token trade.
+/// * [BuildError::NoMpcSigner] when `mpc_signers` has no member.
+pub fn batch_settlement(
+    trade_details: &[TradeDetail],
+    pmm: &Pubkey,
+    token: Option<&TokenMint>,
+    mpc_signers: Option<&[Pubkey]>,
+) -> Result<Instruction, BuildError> {
+    let first_trade_detail = trade_details.first().ok_or(BuildError::NoTrade)?;
+    let (signer, mpc_group, co_signers) = mpc_accounts(first_trade_detail, mpc_signers)?;
+    let mut remaining_accounts: Vec<AccountMeta> = vec![];
+    for trade_detail in trade_details {
+        let vault = get_trade_vault_pda(&trade_detail.trade_id);
+        remaining_accounts.extend([
+            AccountMeta::new(get_user_trade_detail_pda(&trade_detail.trade_id), false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(trade_detail.rent_payer(), false),
+            AccountMeta::new_readonly(trade_detail.user_ephemeral_pubkey, true),
+            AccountMeta::new(get_nonce_check_pda(&trade_detail.user_ephemeral_pubkey), false),
+        ]);
+        if let Some(token) = trade_token(trade_detail, token)? {
+            remaining_accounts.extend(transfer_spl_token_accounts(&token, &vault, pmm, trade_detail.total_fee.is_some()));
+        }
+    }
+    remaining_accounts.extend(co_signers);
+
+    let mint = token.map(|token| token.mint);
+    Ok(build(
+        accounts::BatchSettlementAccounts {
+            signer,
+            protocol: get_protocol_pda(),
+            pmm: *pmm,
+            config: get_config_pda(),
+            whitelist_token: get_whitelist_pda(&mint.unwrap_or(WSOL_MINT)),
+            fee_ledger: get_fee_ledger_pda(&mint.unwrap_or(WSOL_MINT)),
+            system_program: system_program::ID,
+            mpc_group,
+        },
+        instruction::BatchSettlement {
+            batch_settlement_args: BatchSettlementArgs {
+                token: mint,
+                trade_ids: trade_details.iter().map(|trade_detail| trade_detail.trade_id).collect(),
+            },
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Build the claim instruction, signed by anyone after the timeout of the trade.
+///
+/// # Errors
+/// * [BuildError::TokenMismatch] when `token` is not the mint of a token trade.
+pub fn claim(signer: &Pubkey, trade_detail: &TradeDetail, token: Option<&TokenMint>) -> Result<Instruction, BuildError> {
+    let trade_id = trade_detail.trade_id;
+    let vault = get_trade_vault_pda(&trade_id);
+    let remaining_accounts = trade_token(trade_detail, token)?.map_or(vec![], |token| {
+        transfer_spl_token_accounts(&token, &vault, &trade_detail.refund_pubkey, false)
+    });
+
+    Ok(build(
+        accounts::Claim {
+            signer: *signer,
+            user_account: trade_detail.rent_payer(),
+            nonce_check_account: get_nonce_check_pda(&trade_detail.user_ephemeral_pubkey),
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            vault,
+            refund_account: trade_detail.refund_pubkey,
+            whitelist_token: get_whitelist_pda(&trade_detail.token.unwrap_or(WSOL_MINT)),
+            system_program: system_program::ID,
+        },
+        instruction::Claim {
+            claim_args: ClaimArgs { trade_id },
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Build the cooperative refund instruction, signed by the MPC and the ephemeral account of the trade.
+///
+/// # Errors
+/// * [BuildError::TokenMismatch] when `token` is not the mint of a token trade.
+/// * [BuildError::NoMpcSigner] when `mpc_signers` has no member.
+pub fn cooperative_refund(
+    trade_detail: &TradeDetail,
+    token: Option<&TokenMint>,
+    mpc_signers: Option<&[Pubkey]>,
+) -> Result<Instruction, BuildError> {
+    let trade_id = trade_detail.trade_id;
+    let vault = get_trade_vault_pda(&trade_id);
+    let (signer, mpc_group, co_signers) = mpc_accounts(trade_detail, mpc_signers)?;
+    let mut remaining_accounts = trade_token(trade_detail, token)?.map_or(vec![], |token| {
+        transfer_spl_token_accounts(&token, &vault, &trade_detail.refund_pubkey, false)
+    });
+    remaining_accounts.extend(co_signers);
+
+    Ok(build(
+        accounts::CooperativeRefundAccounts {
+            signer,
+            user_account: trade_detail.rent_payer(),
+            user_ephemeral_account: trade_detail.user_ephemeral_pubkey,
+            nonce_check_account: get_nonce_check_pda(&trade_detail.user_ephemeral_pubkey),
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            vault,
+            refund_account: trade_detail.refund_pubkey,
+            whitelist_token: get_whitelist_pda(&trade_detail.token.unwrap_or(WSOL_MINT)),
+            system_program: system_program::ID,
+            mpc_group,
+        },
+        instruction::CooperativeRefund {
+            cooperative_refund_args: CooperativeRefundArgs { trade_id },
+        },
+        remaining_accounts,
+    ))
+}
+
+/// Build the extend timeout instruction, signed by the MPC and the ephemeral account of the trade.
+///
+/// # Errors
+/// * [BuildError::NoMpcSigner] when `mpc_signers` has no member.
+pub fn extend_timeout(trade_detail: &TradeDetail, new_timeout: i64, mpc_signers: Option<&[Pubkey]>) -> Result<Instruction, BuildError> {
+    let trade_id = trade_detail.trade_id;
+    let (signer, mpc_group, co_signers) = mpc_accounts(trade_detail, mpc_signers)?;
+    Ok(build(
+        accounts::ExtendTimeoutAccounts {
+            signer,
+            user_ephemeral_account: trade_detail.user_ephemeral_pubkey,
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            config: get_config_pda(),
+            mpc_group,
+        },
+        instruction::ExtendTimeout {
+            extend_timeout_args: ExtendTimeoutArgs { trade_id, new_timeout },
+        },
+        co_signers,
+    ))
+}
+
+/// Build the close finished trade instruction.
+///
+/// For a token trade, the vault token account is closed and its remaining amount is sent to the token account of the depositor,
+/// its rent fee goes to the rent payer.
+/// `mpc_signers` is only needed when an MPC group closes a settled trade before anyone can, then its first member is the signer.
+///
+/// # Errors
+/// * [BuildError::TokenMismatch] when `token` is not the mint of a token trade.
+/// * [BuildError::NoMpcSigner] when `mpc_signers` has no member.
+pub fn close_finished_trade(
+    signer: &Pubkey,
+    trade_detail: &TradeDetail,
+    token: Option<&TokenMint>,
+    mpc_signers: Option<&[Pubkey]>,
+) -> Result<Instruction, BuildError> {
+    let trade_id = trade_detail.trade_id;
+    let vault = get_trade_vault_pda(&trade_id);
+    let token = trade_token(trade_detail, token)?;
+    let (signer, mpc_group, co_signers) = match mpc_signers {
+        Some(_) => mpc_accounts(trade_detail, mpc_signers)?,
+        None => (*signer, None, vec![]),
+    };
+
+    Ok(build(
+        accounts::CloseFinishedTradeAccounts {
+            signer,
+            user_account: trade_detail.rent_payer(),
+            user_trade_detail: get_user_trade_detail_pda(&trade_id),
+            vault,
+            config: get_config_pda(),
+            vault_token_account: token.map(|token| token.ata(&vault)),
+            user_token_account: token.map(|token| token.ata(&trade_detail.user_pubkey)),
+            mint: token.map(|token| token.mint),
+            token_program: token.map_or(anchor_spl::token::ID, |token| token.token_program),
+            mpc_group,
+        },
+        instruction::CloseFinishedTrade {
+            _close_finished_trade_args: CloseFinishedTradeArgs { trade_id },
+        },
+        co_signers,
+    ))
+}
+
+/// Build the close payment receipt instruction, signed by the payer of the payment.
+pub fn close_payment_receipt(
+    signer: &Pubkey,
+    trade_id: &[u8; 32],
+    to_pubkey: &Pubkey,
+    amount: u64,
+    total_fee: u64,
+    token: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::ClosePaymentReceiptAccounts {
+            signer: *signer,
+            payment_receipt: get_payment_receipt_pda(
+                trade_id, signer, to_pubkey, amount, total_fee, token,
+            ),
+            config: get_config_pda(),
+            system_program: system_program::ID,
+        },
+        instruction::ClosePaymentReceipt {},
+        vec![],
+    )
+}
+
+/// Build the distribute fees instruction, signed by anyone. `token` is None when distributing SOL.
+///
+/// `receivers` must be all the fee receivers with a share.
+pub fn distribute_fees(signer: &Pubkey, receivers: &[Pubkey], token: Option<&TokenMint>) -> Instruction {
+    let protocol = get_protocol_pda();
+    let remaining_accounts = receivers
+        .iter()
+        .flat_map(|receiver| {
+            let fee_receiver = AccountMeta::new_readonly(get_fee_receiver_pda(receiver), false);
+            let transfer_accounts = match token {
+                Some(token) => transfer_spl_token_accounts(token, &protocol, receiver, false),
+                None => vec![AccountMeta::new(*receiver, false)],
+            };
+            std::iter::once(fee_receiver).chain(transfer_accounts)
+        })
+        .collect();
+
+    build(
+        accounts::DistributeFeesAccounts {
+            signer: *signer,
+            protocol,
+            config: get_config_pda(),
+            fee_ledger: get_fee_ledger_pda(&token.map_or(WSOL_MINT, |token| token.mint)),
+        },
+        instruction::DistributeFees {
+            distribute_fees_args: DistributeFeesArgs {
+                token: token.map(|token| token.mint),
+            },
+        },
+        remaining_accounts,
+    )
+}
+
+/// Build the get fee ledger instruction of a mint, [WSOL_MINT] for SOL.
+///
+/// Meant to be simulated, the [FeeLedgerBalance](crate::FeeLedgerBalance) is the return data, see [crate::decode::decode_fee_ledger_balance].
+pub fn get_fee_ledger(mint: &Pubkey) -> Instruction {
+    build(
+        accounts::GetFeeLedgerAccounts {
+            fee_ledger: get_fee_ledger_pda(mint),
+        },
+        instruction::GetFeeLedger {},
+        vec![],
+    )
+}
+
+/// Build the batch close finished trades instruction, signed by the MPC of the trades or anyone.
+///
+/// Each trade is given with its token, None for a trade with SOL.
+/// The trades of an [MpcGroup](crate::MpcGroup) are closed by their MPC with the `mpc_group`, the group and its co-signing
+/// members other than the `signer`.
+///
+/// # Errors
+/// * [BuildError::TokenMismatch] when a token is not the mint of its token trade.
+pub fn batch_close_finished_trades(
+    signer: &Pubkey,
+    trades: &[(TradeDetail, Option<TokenMint>)],
+    mpc_group: Option<(Pubkey, &[Pubkey])>,
+) -> Result<Instruction, BuildError> {
+    let co_signers = mpc_group.map_or(&[][..], |(_, co_signers)| co_signers);
+    let mut remaining_accounts: Vec<AccountMeta> = co_signers
+        .iter()
+        .map(|co_signer| AccountMeta::new_readonly(*co_signer, true))
+        .collect();
+    for (trade_detail, token) in trades {
+        let vault = get_trade_vault_pda(&trade_detail.trade_id);
+        let rent_payer = trade_detail.rent_payer();
+        remaining_accounts.extend([
+            AccountMeta::new(get_user_trade_detail_pda(&trade_detail.trade_id), false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(rent_payer, false),
+        ]);
+        match trade_token(trade_detail, token.as_ref())? {
+            Some(token) => remaining_accounts.extend([
+                AccountMeta::new_readonly(token.token_program, false),
+                AccountMeta::new(token.mint, false),
+                AccountMeta::new(token.ata(&vault), false),
+                AccountMeta::new(token.ata(&trade_detail.user_pubkey), false),
+            ]),
+            // The program ID stands for the token accounts of a trade with SOL
+            None => remaining_accounts.extend(vec![AccountMeta::new_readonly(ID, false); 4]),
+        }
+    }
+
+    Ok(build(
+        accounts::BatchCloseFinishedTradesAccounts {
+            signer: *signer,
+            config: get_config_pda(),
+            mpc_group: mpc_group.map(|(mpc_group, _)| mpc_group),
+        },
+        instruction::BatchCloseFinishedTrades {},
+        remaining_accounts,
+    ))
+}
+
+/// Build the batch close payment receipts instruction, signed by the payer of the payments.
+///
+/// `payment_receipts` are the payment receipt PDAs, see [get_payment_receipt_pda].
+pub fn batch_close_payment_receipts(signer: &Pubkey, payment_receipts: &[Pubkey]) -> Instruction {
+    build(
+        accounts::BatchClosePaymentReceiptsAccounts {
+            signer: *signer,
+            config: get_config_pda(),
+        },
+        instruction::BatchClosePaymentReceipts {},
+        payment_receipts
+            .iter()
+            .map(|payment_receipt| AccountMeta::new(*payment_receipt, false))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_split_settlement_remaining_accounts() {
+    let mint = Pubkey::new_unique();
+    let token = TokenMint {
+        mint,
+        token_program: anchor_spl::token_2022::ID,
+    };
+    let trade_detail = TradeDetail {
+        trade_id: [1; 32],
+        token: Some(mint),
+        amount: 100,
+        total_fee: Some(10),
+        mpc_pubkey: Pubkey::new_unique(),
+        user_ephemeral_pubkey: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    let legs = vec![
+        SettlementLeg {
+            pmm: Pubkey::new_unique(),
+            amount: 60,
+        },
+        SettlementLeg {
+            pmm: Pubkey::new_unique(),
+            amount: 30,
+        },
+    ];
+    let ix = split_settlement(&trade_detail, legs.clone(), Some(&token), None).unwrap();
+
+    let remaining: Vec<Pubkey> = ix.accounts[13..].iter().map(|account| account.pubkey).collect();
+    let vault = get_trade_vault_pda(&trade_detail.trade_id);
+    assert_eq!(
+        remaining,
+        vec![
+            token.token_program,
+            mint,
+            token.ata(&vault),
+            token.ata(&legs[0].pmm),
+            token.ata(&get_protocol_pda()),
+            token.token_program,
+            mint,
+            token.ata(&vault),
+            token.ata(&legs[1].pmm),
+        ]
+    );
+    assert!(ix.accounts[0].is_signer && ix.accounts[2].is_signer);
+    assert_eq!(ix.accounts[0].pubkey, trade_detail.mpc_pubkey);
+}
+
+#[test]
+fn test_build_errors() {
+    let trade_detail = TradeDetail {
+        trade_id: [1; 32],
+        token: Some(Pubkey::new_unique()),
+        mpc_pubkey: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    let other_token = TokenMint {
+        mint: Pubkey::new_unique(),
+        token_program: anchor_spl::token::ID,
+    };
+    assert_eq!(
+        claim(&Pubkey::new_unique(), &trade_detail, Some(&other_token)),
+        Err(BuildError::TokenMismatch)
+    );
+    assert_eq!(claim(&Pubkey::new_unique(), &trade_detail, None), Err(BuildError::TokenMismatch));
+    assert_eq!(set_total_fee(&trade_detail, 10, Some(&[])), Err(BuildError::NoMpcSigner));
+    assert_eq!(
+        batch_settlement(&[], &Pubkey::new_unique(), None, None),
+        Err(BuildError::NoTrade)
+    );
+}
@@ -0,0 +1,22 @@
+//! Rust client for the Optimex Solana program.
+//!
+//! Built on the public types of the program, it provides:
+//! * [pda]: PDA derivation helpers for every seed of the program.
+//! * [instructions]: builders for each instruction, with the ordered remaining accounts.
+//! * [remaining_accounts]: the remaining accounts expected by the token transfers of the program.
+//! * [decode]: decoders for the accounts of the program.
+//! * [trade_id]: the trade id calculator.
+pub mod decode;
+pub mod instructions;
+pub mod pda;
+pub mod remaining_accounts;
+pub mod trade_id;
+
+pub use optimex_sol_smartcontract as program;
+pub use optimex_sol_smartcontract::{
+    constants::WSOL_MINT, BatchSettlementArgs, ClaimArgs, CloseFinishedTradeArgs, Config, CooperativeRefundArgs,
+    CreatePaymentExpectationArgs, DepositArgs, DepositIntent, DistributeFeesArgs, ExtendTimeoutArgs, FeeLedger, FeeLedgerBalance, FeeReceiver, InitArgs, MpcGroup, MpcRegistry, PaymentArgs,
+    PaymentExpectation, PaymentReceipt, PendingAction, Role, RoleMember, SetCloseWaitDurationArgs, SetPauseArgs, SetTotalFeeArgs,
+    SettlementArgs, SettlementLeg, SplitSettlementArgs, TimelockAction, TradeDetail, TradeDetailInput, TradeIdScheme, TradeInfo,
+    TradeInput, TradeStatus, WhitelistToken, ID,
+};
@@ -0,0 +1,116 @@
+//! PDA derivation helpers for every seed of the program.
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+use optimex_sol_smartcontract::{
+    Config, DepositIntent, FeeLedger, FeeReceiver, MpcGroup, MpcRegistry, NonceCheckAccount, PaymentExpectation, PaymentReceipt,
+    PendingAction, Role, RoleMember, TimelockAction, TradeVault, WhitelistToken, ID,
+};
+
+/// Get the vault PDA address.
+pub fn get_vault_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"vault"], &ID).0
+}
+
+/// Get the trade vault PDA address, that holds the deposited amount of the trade.
+pub fn get_trade_vault_pda(trade_id: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[TradeVault::SEED, trade_id], &ID).0
+}
+
+/// Get the user trade detail PDA address, that contains the [optimex_sol_smartcontract::TradeDetail].
+pub fn get_user_trade_detail_pda(trade_id: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[trade_id], &ID).0
+}
+
+/// Get the protocol PDA address, that holds the total fee.
+pub fn get_protocol_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"protocol"], &ID).0
+}
+
+/// Get the program data address of the program, owned by the upgradeable loader.
+pub fn get_program_data() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// Get the config PDA address.
+pub fn get_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[Config::SEED], &ID).0
+}
+
+/// Get the whitelist token PDA address of a mint, [crate::WSOL_MINT] for SOL.
+pub fn get_whitelist_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[WhitelistToken::SEED, mint.as_ref()], &ID).0
+}
+
+/// Get the nonce check PDA address of an ephemeral account.
+pub fn get_nonce_check_pda(ephemeral_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[NonceCheckAccount::SEED, ephemeral_account.as_ref()], &ID).0
+}
+
+/// Get the fee receiver PDA address of a receiver.
+pub fn get_fee_receiver_pda(receiver: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[FeeReceiver::SEED, receiver.as_ref()], &ID).0
+}
+
+/// Get the payment receipt PDA address.
+///
+/// All the payment information is part of the seeds, to support multiple payments for each trade.
+pub fn get_payment_receipt_pda(
+    trade_id: &[u8; 32],
+    from_pubkey: &Pubkey,
+    to_pubkey: &Pubkey,
+    amount: u64,
+    total_fee: u64,
+    token: Option<Pubkey>,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PaymentReceipt::SEED,
+            trade_id,
+            from_pubkey.as_ref(),
+            to_pubkey.as_ref(),
+            &amount.to_le_bytes(),
+            &total_fee.to_le_bytes(),
+            token.unwrap_or_default().as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// Get the payment expectation PDA address, published by an MPC for a trade.
+pub fn get_payment_expectation_pda(trade_id: &[u8; 32], mpc_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PaymentExpectation::SEED, trade_id, mpc_pubkey.as_ref()],
+        &ID,
+    )
+    .0
+}
+
+/// Get the MPC group PDA address. A trade references the group by this address as its MPC.
+pub fn get_mpc_group_pda(group_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[MpcGroup::SEED, &group_id.to_le_bytes()], &ID).0
+}
+
+/// Get the MPC registry PDA address of an MPC key, checked by the deposits.
+pub fn get_mpc_registry_pda(mpc_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MpcRegistry::SEED, mpc_pubkey.as_ref()], &ID).0
+}
+
+/// Get the fee ledger PDA address of a mint, [crate::WSOL_MINT] for SOL.
+pub fn get_fee_ledger_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[FeeLedger::SEED, mint.as_ref()], &ID).0
+}
+
+/// Get the pending action PDA address of a sensitive admin operation queued behind the timelock.
+pub fn get_pending_action_pda(action: &TimelockAction) -> Pubkey {
+    Pubkey::find_program_address(&[PendingAction::SEED, &action.id()], &ID).0
+}
+
+/// Get the role member PDA address of a member, that grants the [Role] to the member.
+pub fn get_role_pda(role: Role, member: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[RoleMember::SEED, &[role as u8], member.as_ref()], &ID).0
+}
+
+/// Get the intent delegate PDA address, that the user approves on its token account for the deposit intents.
+pub fn get_intent_delegate_pda() -> Pubkey {
+    Pubkey::find_program_address(&[DepositIntent::DELEGATE_SEED], &ID).0
+}
@@ -0,0 +1,70 @@
+//! The remaining accounts expected by the token transfers of the program.
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+use crate::pda::get_protocol_pda;
+
+/// A SPL token, with the token program that owns the mint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenMint {
+    /// The mint of the token.
+    pub mint: Pubkey,
+    /// The token program, either the SPL Token or the Token-2022 program.
+    pub token_program: Pubkey,
+}
+
+impl TokenMint {
+    /// Get the associated token address of an owner for this token.
+    pub fn ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, &self.mint, &self.token_program)
+    }
+}
+
+/// The remaining accounts of a token transfer from the `from_owner` to the `to_owner`, in the order
+/// read by `transfer_spl_token`: token program, mint, source ata, destination ata,
+/// and the protocol ata when `with_protocol_fee` is set.
+pub fn transfer_spl_token_accounts(
+    token: &TokenMint,
+    from_owner: &Pubkey,
+    to_owner: &Pubkey,
+    with_protocol_fee: bool,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(token.token_program, false),
+        AccountMeta::new_readonly(token.mint, false),
+        AccountMeta::new(token.ata(from_owner), false),
+        AccountMeta::new(token.ata(to_owner), false),
+    ];
+    if with_protocol_fee {
+        accounts.push(AccountMeta::new(token.ata(&get_protocol_pda()), false));
+    }
+    accounts
+}
+
+#[test]
+fn test_transfer_spl_token_accounts() {
+    let token = TokenMint {
+        mint: Pubkey::new_unique(),
+        token_program: anchor_spl::token::ID,
+    };
+    let from_owner = Pubkey::new_unique();
+    let to_owner = Pubkey::new_unique();
+
+    let accounts = transfer_spl_token_accounts(&token, &from_owner, &to_owner, false);
+    let keys: Vec<Pubkey> = accounts.iter().map(|account| account.pubkey).collect();
+    assert_eq!(
+        keys,
+        vec![
+            token.token_program,
+            token.mint,
+            token.ata(&from_owner),
+            token.ata(&to_owner)
+        ]
+    );
+    assert!(!accounts[0].is_writable && !accounts[1].is_writable);
+    assert!(accounts[2].is_writable && accounts[3].is_writable);
+
+    let accounts = transfer_spl_token_accounts(&token, &from_owner, &to_owner, true);
+    assert_eq!(accounts.len(), 5);
+    assert_eq!(accounts[4].pubkey, token.ata(&get_protocol_pda()));
+}
@@ -0,0 +1,14 @@
+//! The trade id calculator.
+use anchor_lang::Result;
+use optimex_sol_smartcontract::{TradeIdScheme, TradeInput};
+
+/// Calculate the trade id of a trade input with the default [TradeIdScheme::Sha256Abi] scheme,
+/// the same way the deposit instruction validates it.
+pub fn calculate_trade_id(input: &TradeInput) -> [u8; 32] {
+    input.calculate_trade_id()
+}
+
+/// Calculate the trade id of a trade input with the hashing and encoding of the `scheme`.
+pub fn calculate_trade_id_with_scheme(input: &TradeInput, scheme: TradeIdScheme) -> Result<[u8; 32]> {
+    input.calculate_trade_id_with_scheme(scheme)
+}
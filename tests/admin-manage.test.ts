@@ -1,17 +1,17 @@
 import * as anchor from '@coral-xyz/anchor';
+import { OptimexSolSmartcontract } from '../target/types/optimex_sol_smartcontract';
 import {
   Keypair,
   LAMPORTS_PER_SOL,
   sendAndConfirmTransaction,
   Transaction,
-  TransactionInstruction,
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { expect, assert } from 'chai';
 import { createInitializeProgramInstructions } from '../solana-js/instructions/intialize';
-import { createAddOperatorInstruction, createRemoveOperatorInstruction } from '../solana-js/instructions/manage_operator';
+import { createGrantRoleInstruction, createMigrateOperatorInstruction, createRevokeRoleInstruction } from '../solana-js/instructions/manage_operator';
 import { getConfigData, getFeeReceiverData, getWhitelistTokenData } from '../solana-js/pda/get_pda_data';
-import { getConfigPda, getFeeReceiverPda, getWhitelistPda } from '../solana-js/pda/get_pda_address';
+import { getConfigPda, getFeeReceiverPda, getRoleMemberPda, getWhitelistPda } from '../solana-js/pda/get_pda_address';
 import { createAddFeeReceiverInstruction, createAddOrUpdateWhitelistInstruction, createRemoveFeeReceiverInstruction, createRemoveWhitelistInstruction } from '../solana-js/instructions/manage_config';
 import { WSOL_MINT } from '../solana-js/constants';
 import { sleep } from './utils';
@@ -24,6 +24,8 @@ describe('Admin manage functional testing', () => {
   const connection = anchorProvider.connection;
   anchor.setProvider(anchorProvider);
 
+  const program = anchor.workspace
+    .OptimexSolSmartcontract as anchor.Program<OptimexSolSmartcontract>;
   const deployer = (anchorProvider.wallet as anchor.Wallet).payer;
   const admin = Keypair.generate();
   const oldAdmin = Keypair.generate();
@@ -102,12 +104,13 @@ describe('Admin manage functional testing', () => {
     });
   });
 
-  describe('Manage operator', () => {
+  describe('Manage roles', () => {
     const newOperator = Keypair.generate();
-    it('Add operator failed because of unauthorized', async () => {
-      const instruction = await createAddOperatorInstruction({
+    it('Grant role failed because of unauthorized', async () => {
+      const instruction = await createGrantRoleInstruction({
         signer: newOperator.publicKey,
-        operator: newOperator.publicKey,
+        role: 'whitelistManager',
+        member: newOperator.publicKey,
         connection,
       });
       const transaction = new Transaction().add(...instruction);
@@ -118,10 +121,12 @@ describe('Admin manage functional testing', () => {
         expect(error.toString().includes('Unauthorized'));
       }
     })
-    it('Add operator success', async () => {
-      const instruction = await createAddOperatorInstruction({
+
+    it('Grant role success', async () => {
+      const instruction = await createGrantRoleInstruction({
         signer: admin.publicKey,
-        operator: newOperator.publicKey,
+        role: 'whitelistManager',
+        member: newOperator.publicKey,
         connection,
       });
       const transaction = new Transaction().add(...instruction);
@@ -132,15 +137,16 @@ describe('Admin manage functional testing', () => {
         throw error;
       }
 
-      const configData = await getConfigData(connection);
-      assert.equal(configData.operators.length, 1, 'Operator length should be 1');
-      assert.equal(configData.operators[0].toBase58(), newOperator.publicKey.toBase58(), 'Operator mismatch');
+      const roleMember = await program.account.roleMember.fetch(getRoleMemberPda('whitelistManager', newOperator.publicKey));
+      assert.deepEqual(roleMember.role, { whitelistManager: {} }, 'Role mismatch');
+      assert.equal(roleMember.member.toBase58(), newOperator.publicKey.toBase58(), 'Member mismatch');
     })
 
-    it('Add operator failed because of operator already existed', async () => {
-      const instruction = await createAddOperatorInstruction({
+    it('Grant role failed because of role already granted', async () => {
+      const instruction = await createGrantRoleInstruction({
         signer: admin.publicKey,
-        operator: newOperator.publicKey,
+        role: 'whitelistManager',
+        member: newOperator.publicKey,
         connection,
       });
       const transaction = new Transaction().add(...instruction);
@@ -148,19 +154,15 @@ describe('Admin manage functional testing', () => {
         await sendAndConfirmTransaction(connection, transaction, [admin], { commitment: 'confirmed' });
         assert.fail('Should not reach here');
       } catch (error) {
-        expect(error.toString().includes('OperatorAlreadyExists'));
+        expect(error.toString().includes('already in use'));
       }
-
-      const configData = await getConfigData(connection);
-      assert.equal(configData.operators.length, 1, 'Operator length should be 1');
-      assert.equal(configData.operators[0].toBase58(), newOperator.publicKey.toBase58(), 'Operator mismatch');
     })
 
-    it('Remove operator failed because not existed operator', async () => {
-      const fakeOperator = Keypair.generate();
-      const instruction = await createRemoveOperatorInstruction({
+    it('Revoke role failed because of role not granted', async () => {
+      const instruction = await createRevokeRoleInstruction({
         signer: admin.publicKey,
-        operator: fakeOperator.publicKey,
+        role: 'durationManager',
+        member: newOperator.publicKey,
         connection,
       });
       const transaction = new Transaction().add(...instruction);
@@ -168,14 +170,15 @@ describe('Admin manage functional testing', () => {
         await sendAndConfirmTransaction(connection, transaction, [admin], { commitment: 'confirmed' });
         assert.fail('Should not reach here');
       } catch (error) {
-        expect(error.toString().includes('OperatorNotFound'));
+        expect(error.toString().includes('AccountNotInitialized'));
       }
     })
 
-    it('Remove operator success', async () => {
-      const instruction = await createRemoveOperatorInstruction({
+    it('Revoke role success', async () => {
+      const instruction = await createRevokeRoleInstruction({
         signer: admin.publicKey,
-        operator: newOperator.publicKey,
+        role: 'whitelistManager',
+        member: newOperator.publicKey,
         connection,
       });
       const transaction = new Transaction().add(...instruction);
@@ -186,50 +189,23 @@ describe('Admin manage functional testing', () => {
         throw error;
       }
 
-      const configData = await getConfigData(connection);
-      assert.equal(configData.operators.length, 0, 'Operator length should be 0');
+      const roleMember = await connection.getAccountInfo(getRoleMemberPda('whitelistManager', newOperator.publicKey));
+      assert.isNull(roleMember, 'Role member should be closed');
     })
 
-    it('Add up to 3 operator success', async () => {
-      const operators = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
-      const instructions: TransactionInstruction[] = [];
-      for (const operator of operators) {
-        const instruction = await createAddOperatorInstruction({
-          signer: admin.publicKey,
-          operator: operator.publicKey,
-          connection,
-        });
-        instructions.push(...instruction);
-      }
-      const transaction = new Transaction().add(...instructions);
+    it('Migrate operator failed because not existed operator', async () => {
+      const fakeOperator = Keypair.generate();
+      const instruction = await createMigrateOperatorInstruction({
+        signer: admin.publicKey,
+        operator: fakeOperator.publicKey,
+        connection,
+      });
+      const transaction = new Transaction().add(...instruction);
       try {
         await sendAndConfirmTransaction(connection, transaction, [admin], { commitment: 'confirmed' });
+        assert.fail('Should not reach here');
       } catch (error) {
-        console.log(error);
-        throw error;
-      }
-
-      const configData = await getConfigData(connection);
-      assert.equal(configData.operators.length, 3, 'Operator length should be 0');
-      assert.equal(configData.operators[0].toBase58(), operators[0].publicKey.toBase58(), 'Operator mismatch');
-      assert.equal(configData.operators[1].toBase58(), operators[1].publicKey.toBase58(), 'Operator mismatch');
-      assert.equal(configData.operators[2].toBase58(), operators[2].publicKey.toBase58(), 'Operator mismatch');
-
-      const removeInstructions: TransactionInstruction[] = [];
-      for (const operator of operators) {
-        const instruction = await createRemoveOperatorInstruction({
-          signer: admin.publicKey,
-          operator: operator.publicKey,
-          connection,
-        });
-        removeInstructions.push(...instruction);
-      }
-      const removeTransaction = new Transaction().add(...removeInstructions);
-      try {
-        await sendAndConfirmTransaction(connection, removeTransaction, [admin], { commitment: 'confirmed' });
-      } catch (error) {
-        console.log(error);
-        throw error;
+        expect(error.toString().includes('OperatorNotFound'));
       }
     })
   })
@@ -237,12 +213,13 @@ describe('Admin manage functional testing', () => {
   describe('Manage whitelist', async () => {
     const newOperator = Keypair.generate();
     const fakeOperator = Keypair.generate();
-    before('Add and fund operator success', async () => {
+    before('Grant role and fund operator success', async () => {
       await connection.requestAirdrop(newOperator.publicKey, 10 * LAMPORTS_PER_SOL);
       await connection.requestAirdrop(fakeOperator.publicKey, 10 * LAMPORTS_PER_SOL);
-      const instruction = await createAddOperatorInstruction({
+      const instruction = await createGrantRoleInstruction({
         signer: admin.publicKey,
-        operator: newOperator.publicKey,
+        role: 'whitelistManager',
+        member: newOperator.publicKey,
         connection,
       });
       const transaction = new Transaction().add(...instruction);
@@ -252,10 +229,6 @@ describe('Admin manage functional testing', () => {
         console.log(error);
         throw error;
       }
-
-      const configData = await getConfigData(connection);
-      assert.equal(configData.operators.length, 1, 'Operator length should be 1');
-      assert.equal(configData.operators[0].toBase58(), newOperator.publicKey.toBase58(), 'Operator mismatch');
     })
 
     it(`Add whitelist success`, async () => {
@@ -265,6 +238,8 @@ describe('Admin manage functional testing', () => {
         token: WSOL_MINT,
         connection,
         amount: BigInt(minAmount),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
       });
       const transaction = new Transaction().add(...instruction);
       try {
@@ -285,6 +260,8 @@ describe('Admin manage functional testing', () => {
         token: WSOL_MINT,
         connection,
         amount: BigInt(minAmount),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
       });
       const transaction = new Transaction().add(...instruction);
       try {
@@ -297,11 +274,15 @@ describe('Admin manage functional testing', () => {
 
     it('Update whitelist success', async () => {
       const minAmount = BigInt(0.02 * LAMPORTS_PER_SOL);
+      const maxAmount = BigInt(10 * LAMPORTS_PER_SOL);
+      const maxOutstanding = BigInt(100 * LAMPORTS_PER_SOL);
       const instruction = await createAddOrUpdateWhitelistInstruction({
         operator: newOperator.publicKey,
         token: WSOL_MINT,
         connection,
         amount: minAmount,
+        maxAmount,
+        maxOutstanding,
       });
       const transaction = new Transaction().add(...instruction);
       try {
@@ -314,6 +295,8 @@ describe('Admin manage functional testing', () => {
       const whitelistTokenData = await getWhitelistTokenData(WSOL_MINT, connection);
       assert.equal(whitelistTokenData.token.toBase58(), WSOL_MINT.toBase58(), 'Token mismatch');
       assert.equal(whitelistTokenData.amount.toString(), (Number(minAmount)).toString(), 'Amount mismatch');
+      assert.equal(whitelistTokenData.maxAmount.toString(), maxAmount.toString(), 'Max amount mismatch');
+      assert.equal(whitelistTokenData.maxOutstanding.toString(), maxOutstanding.toString(), 'Max outstanding mismatch');
     })
 
     it('Remove whitelist success', async () => {
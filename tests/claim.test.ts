@@ -12,7 +12,7 @@ import {
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { expect, assert } from 'chai';
-import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep } from './utils';
+import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { solverAddress } from './example-data';
 import {
@@ -25,11 +25,10 @@ import { DepositInstructionParam } from '../solana-js/instructions/deposit';
 import { delay } from '../scripts/utils/helper';
 import { createDepositAndVaultAtaIfNeededAndNonceAccountInstructions } from '../solana-js/instructions/deposit';
 import { createInitializeProgramInstructions } from '../solana-js/instructions/intialize';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
-import { createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
+import { createAddOrUpdateWhitelistInstruction, createAddOrUpdateMpcRegistryInstruction } from '../solana-js/instructions/manage_config';
 import { createClaimAndRefundAtaAndProtocolAtaIfNeededInstructions } from '../solana-js/instructions/claim';
 import { WSOL_MINT } from '../solana-js/constants';
-import { getNonceCheckPda, getTradeVaultPda, getUserTradeDetailPda } from '../solana-js/pda/get_pda_address';
+import { getNonceCheckPda, getTradeVaultPda, getUserTradeDetailPda, getWhitelistPda } from '../solana-js/pda/get_pda_address';
 import { getTradeDetailData } from '../solana-js/pda/get_pda_data';
 import { getTradeInput } from '../solana-js/utils/param_utils';
 dotenv.config();
@@ -64,12 +63,15 @@ describe('Claim() functional testing', () => {
       await connection.requestAirdrop(mpcKey.publicKey, 10 * LAMPORTS_PER_SOL);
     })
 
-    it('Add operator successfully', async () => {
-      const addOperatorIns = await createAddOperatorInstruction({
+    it('Grant operator roles and register MPC successfully', async () => {
+      const addOperatorIns = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
+      addOperatorIns.push(...(await createAddOrUpdateMpcRegistryInstruction({
+        connection,
         signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+        mpcPubkey: mpcKey.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      })));
       const transaction = new Transaction().add(...addOperatorIns);
       try {
         await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
@@ -95,6 +97,8 @@ describe('Claim() functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -279,6 +283,8 @@ describe('Claim() functional testing', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
 
@@ -347,12 +353,13 @@ describe('Claim() functional testing', () => {
       }
       try {
         await program.methods.claim({ tradeId: correctTradeIdBytes })
-        .accounts({
+        .accountsPartial({
           signer: user.publicKey,
           userTradeDetail: correctUserTradeDetail,
           refundAccount: refundKey.publicKey,
           userAccount: user.publicKey,
           vault: vaultPda,
+          whitelistToken: getWhitelistPda(tokenMint),
         })
         .remainingAccounts(newRemainingAccounts)
         .signers([user])
@@ -372,12 +379,13 @@ describe('Claim() functional testing', () => {
       }
       try {
         await program.methods.claim({ tradeId: correctTradeIdBytes })
-        .accounts({
+        .accountsPartial({
           signer: user.publicKey,
           userTradeDetail: correctUserTradeDetail,
           refundAccount: refundKey.publicKey,
           userAccount: user.publicKey,
           vault: vaultPda,
+          whitelistToken: getWhitelistPda(tokenMint),
         })
         .remainingAccounts(newRemainingAccounts)
         .signers([user])
@@ -397,12 +405,13 @@ describe('Claim() functional testing', () => {
       }
       try {
         await program.methods.claim({ tradeId: correctTradeIdBytes })
-        .accounts({
+        .accountsPartial({
           signer: user.publicKey,
           userTradeDetail: correctUserTradeDetail,
           refundAccount: refundKey.publicKey,
           userAccount: user.publicKey,
           vault: vaultPda,
+          whitelistToken: getWhitelistPda(tokenMint),
         })
         .remainingAccounts(newRemainingAccounts)
         .signers([user])
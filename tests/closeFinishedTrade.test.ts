@@ -10,7 +10,7 @@ import {
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { assert } from 'chai';
-import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep } from './utils';
+import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { solverAddress } from './example-data';
 import {
@@ -24,10 +24,9 @@ import { delay } from '../scripts/utils/helper';
 import { createDepositAndVaultAtaIfNeededAndNonceAccountInstructions  } from '../solana-js/instructions/deposit';
 import { createAssociatedTokenAccountInstructionIfNeeded } from '../solana-js/instructions/helpers';
 import { createInitializeProgramInstructions } from '../solana-js/instructions/intialize';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
-import { createAddOrUpdateWhitelistInstruction, createSetCloseWaitDurationInstruction } from '../solana-js/instructions/manage_config';
+import { createAddOrUpdateWhitelistInstruction, createSetCloseWaitDurationInstruction, createAddOrUpdateMpcRegistryInstruction } from '../solana-js/instructions/manage_config';
 import { WSOL_MINT } from '../solana-js/constants';
-import { getNonceCheckPda, getTradeVaultPda } from '../solana-js/pda/get_pda_address';
+import { getFeeLedgerPda, getNonceCheckPda, getTradeVaultPda, getWhitelistPda } from '../solana-js/pda/get_pda_address';
 import { getTradeDetailData } from '../solana-js/pda/get_pda_data';
 import { createCloseFinishedTradeInstructions } from '../solana-js/instructions/close_finished_trade';
 import { getTradeInput } from '../solana-js/utils/param_utils';
@@ -66,12 +65,15 @@ describe('Close finished trade functional testing', () => {
       await delay(3000);
     })
 
-    it('Add operator success', async () => {
-      const addOperatorIns = await createAddOperatorInstruction({
+    it('Grant operator roles and register MPC success', async () => {
+      const addOperatorIns = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
+      addOperatorIns.push(...(await createAddOrUpdateMpcRegistryInstruction({
+        connection,
         signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+        mpcPubkey: mpcKey.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      })));
       const addOperatorTransaction = new Transaction().add(...addOperatorIns);
       await sendAndConfirmTransaction(connection, addOperatorTransaction, [deployer], { commitment: 'confirmed' });
     })
@@ -103,6 +105,8 @@ describe('Close finished trade functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -143,6 +147,9 @@ describe('Close finished trade functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: getTradeVaultPda(correctTradeId),
+        whitelistToken: getWhitelistPda(WSOL_MINT),
+        feeLedger: getFeeLedgerPda(null),
+        mpcGroup: null,
       };
     })
 
@@ -156,7 +163,7 @@ describe('Close finished trade functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -219,6 +226,8 @@ describe('Close finished trade functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -259,6 +268,9 @@ describe('Close finished trade functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: getTradeVaultPda(correctTradeId),
+        whitelistToken: getWhitelistPda(WSOL_MINT),
+        feeLedger: getFeeLedgerPda(null),
+        mpcGroup: null,
       };
     })
 
@@ -272,7 +284,7 @@ describe('Close finished trade functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -333,6 +345,8 @@ describe('Close finished trade functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -373,6 +387,9 @@ describe('Close finished trade functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: getTradeVaultPda(correctTradeId),
+        whitelistToken: getWhitelistPda(WSOL_MINT),
+        feeLedger: getFeeLedgerPda(null),
+        mpcGroup: null,
       };
     })
 
@@ -397,7 +414,7 @@ describe('Close finished trade functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -446,6 +463,8 @@ describe('Close finished trade functional testing', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -489,6 +508,9 @@ describe('Close finished trade functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: vaultPda,
+        whitelistToken: getWhitelistPda(tokenMint),
+        feeLedger: getFeeLedgerPda(tokenMint),
+        mpcGroup: null,
       };
       remainingAccounts = [
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
@@ -510,7 +532,7 @@ describe('Close finished trade functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .remainingAccounts(remainingAccounts)
@@ -545,6 +567,9 @@ describe('Close finished trade functional testing', () => {
             vault: vaultPda,
             vaultTokenAccount: userAta,
             userTokenAccount: userAta,
+            mint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            mpcGroup: null,
           })
           .signers([mpcKey])
           .rpc({ commitment: 'confirmed' });
@@ -571,6 +596,9 @@ describe('Close finished trade functional testing', () => {
             vault: vaultPda,
             vaultTokenAccount: null,
             userTokenAccount: userAta,
+            mint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            mpcGroup: null,
           })
           .signers([mpcKey])
           .rpc({ commitment: 'confirmed' });
@@ -597,6 +625,9 @@ describe('Close finished trade functional testing', () => {
             vault: vaultPda,
             vaultTokenAccount: vaultAta,
             userTokenAccount: vaultAta,
+            mint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            mpcGroup: null,
           })
           .signers([mpcKey])
           .rpc({ commitment: 'confirmed' });
@@ -623,6 +654,9 @@ describe('Close finished trade functional testing', () => {
             vault: vaultPda,
             vaultTokenAccount: userAta,
             userTokenAccount: null,
+            mint: tokenMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            mpcGroup: null,
           })
           .signers([mpcKey])
           .rpc({ commitment: 'confirmed' });
@@ -10,7 +10,7 @@ import {
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { expect, assert } from 'chai';
-import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance } from './utils';
+import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { solverAddress } from './example-data';
 import {
@@ -20,10 +20,9 @@ import { bigintToBytes32, encodeAddress, getUserTradeDetailPda, TradeInput, Trad
 import { DepositInstructionParam } from '../solana-js/instructions/deposit';
 import { createInitializeProgramInstructions } from '../solana-js/instructions/intialize';
 import { createDepositAndVaultAtaIfNeededAndNonceAccountInstructions } from '../solana-js/instructions/deposit';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
 import { WSOL_MINT } from '../solana-js/constants';
-import { createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
-import { getNonceCheckPda, getTradeVaultPda, getWhitelistPda } from '../solana-js/pda/get_pda_address';
+import { createAddOrUpdateMpcRegistryInstruction, createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
+import { getMpcRegistryPda, getNonceCheckPda, getTradeVaultPda, getWhitelistPda } from '../solana-js/pda/get_pda_address';
 import { getTradeInput } from '../solana-js/utils/param_utils';
 import { getTradeDetailData } from '../solana-js/pda/get_pda_data';
 import { delay } from '../scripts/utils/helper';
@@ -63,12 +62,8 @@ describe('Deposit () functional testing', () => {
       await delay(3000);
     });
 
-    it('Add operator success', async () => {
-      const instructions = await createAddOperatorInstruction({
-        signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+    it('Grant operator roles success', async () => {
+      const instructions = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
       const transaction = new Transaction().add(...instructions);
       try {
         await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
@@ -82,6 +77,8 @@ describe('Deposit () functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const transaction = new Transaction().add(...instructions);
@@ -111,6 +108,14 @@ describe('Deposit () functional testing', () => {
     let depositParam: DepositInstructionParam;
     let whitelistToken: PublicKey;
     before(async () => {
+      const registerMpcIns = await createAddOrUpdateMpcRegistryInstruction({
+        connection,
+        signer: deployer.publicKey,
+        mpcPubkey: mpcKey.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      });
+      await sendAndConfirmTransaction(connection, new Transaction().add(...registerMpcIns), [deployer], { commitment: 'confirmed' });
       depositParam = {
         sessionId,
         userPubkey: user.publicKey,
@@ -157,13 +162,15 @@ describe('Deposit () functional testing', () => {
             data: correctTradeDetail,
             input: tradeInput,
             tradeId: correctTradeIdBytes,
+            tradeIdScheme: { sha256Abi: {} },
           })
-          .accounts({
+          .accountsPartial({
             signer: user.publicKey,
             userTradeDetail: correctUserTradeDetail,
             ephemeralAccount: userEphemeralKey.publicKey,
             whitelistToken,
             vault: getTradeVaultPda(correctTradeId),
+            mpcRegistry: getMpcRegistryPda(mpcKey.publicKey),
           })
           .signers([userEphemeralKey, user])
           .rpc();
@@ -186,13 +193,15 @@ describe('Deposit () functional testing', () => {
             data: tradeDetail,
             input: correctTradeInput,
             tradeId: correctTradeIdBytes,
+            tradeIdScheme: { sha256Abi: {} },
           })
-          .accounts({
+          .accountsPartial({
             signer: user.publicKey,
             userTradeDetail: correctUserTradeDetail,
             ephemeralAccount: userEphemeralKey.publicKey,
             whitelistToken,
             vault: getTradeVaultPda(correctTradeId),
+            mpcRegistry: getMpcRegistryPda(mpcKey.publicKey),
           })
           .signers([userEphemeralKey, user])
           .rpc();
@@ -216,13 +225,15 @@ describe('Deposit () functional testing', () => {
             data: correctTradeDetail,
             input: newTradeInput,
             tradeId: correctTradeIdBytes,
+            tradeIdScheme: { sha256Abi: {} },
           })
-          .accounts({
+          .accountsPartial({
             signer: user.publicKey,
             userTradeDetail: correctUserTradeDetail,
             ephemeralAccount: userEphemeralKey.publicKey,
             whitelistToken,
             vault: getTradeVaultPda(correctTradeId),
+            mpcRegistry: getMpcRegistryPda(mpcKey.publicKey),
           })
           .signers([userEphemeralKey, user])
           .rpc();
@@ -251,13 +262,15 @@ describe('Deposit () functional testing', () => {
             data: correctTradeDetail,
             input: tradeInput,
             tradeId: correctTradeIdBytes,
+            tradeIdScheme: { sha256Abi: {} },
           })
-          .accounts({
+          .accountsPartial({
             signer: user.publicKey,
             userTradeDetail: correctUserTradeDetail,
             ephemeralAccount: userEphemeralKey.publicKey,
             whitelistToken,
             vault: getTradeVaultPda(correctTradeId),
+            mpcRegistry: getMpcRegistryPda(mpcKey.publicKey),
           })
           .signers([userEphemeralKey, user])
           .rpc();
@@ -335,6 +348,14 @@ describe('Deposit () functional testing', () => {
     let depositParam: DepositInstructionParam;
     let whitelistToken: PublicKey;
     before(async () => {
+      const registerMpcIns = await createAddOrUpdateMpcRegistryInstruction({
+        connection,
+        signer: deployer.publicKey,
+        mpcPubkey: mpcKey.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      });
+      await sendAndConfirmTransaction(connection, new Transaction().add(...registerMpcIns), [deployer], { commitment: 'confirmed' });
       await createMint(
         connection,
         deployer,
@@ -368,6 +389,8 @@ describe('Deposit () functional testing', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -440,13 +463,15 @@ describe('Deposit () functional testing', () => {
             data: tradeDetail,
             input: tradeInput,
             tradeId: tradeIdBytes,
+            tradeIdScheme: { sha256Abi: {} },
           })
-          .accounts({
+          .accountsPartial({
             signer: user.publicKey,
             userTradeDetail,
             ephemeralAccount: userEphemeralKey.publicKey,
             whitelistToken,
             vault: getTradeVaultPda(tradeId),
+            mpcRegistry: getMpcRegistryPda(mpcKey.publicKey),
           })
           .signers([userEphemeralKey, user])
           .rpc();
@@ -9,8 +9,7 @@ import {
   Transaction,
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
-import { expect } from 'chai';
-import { createTokenPair, getTokenBalance } from './utils';
+import { createTokenPair, getTokenBalance, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { assert } from 'chai';
 import crypto from 'crypto';
@@ -20,27 +19,25 @@ import {
   createSyncNativeInstruction,
   getAssociatedTokenAddressSync,
 } from '@solana/spl-token';
-import { getFeeReceiverPda, getProtocolPda, getTradeVaultPda, getUserTradeDetailPda, getVaultPda } from '../solana-js/pda/get_pda_address';
+import { getFeeLedgerPda, getFeeReceiverPda, getProtocolPda, getTradeVaultPda, getUserTradeDetailPda, getVaultPda } from '../solana-js/pda/get_pda_address';
 import { createInitializeProgramInstructions } from '../solana-js/instructions/intialize';
 import { createDepositAndVaultAtaIfNeededAndNonceAccountInstructions } from '../solana-js/instructions/deposit';
 import { createUserPresignSettlementTransactionAndSerializeToString } from '../solana-js/instructions/settlement';
 import { createAssociatedTokenAccountInstructionIfNeeded } from '../solana-js/instructions/helpers';
 import { getTradeInput } from '../solana-js/utils/param_utils';
 import { createSetTotalFeeInstructions } from '../solana-js/instructions/set_total_fee';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
 import { WSOL_MINT } from '../solana-js/constants';
-import { createAddFeeReceiverInstruction, createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
+import { createAddFeeReceiverInstruction, createAddOrUpdateWhitelistInstruction, createAddOrUpdateMpcRegistryInstruction, createSetFeeReceiverShareInstruction } from '../solana-js/instructions/manage_config';
 import { SystemProgram } from '@solana/web3.js';
 import { getTradeDetailData } from '../solana-js/pda/get_pda_data';
 import { bigintToBytes32 } from '../solana-js/utils/parse_utils';
-import { createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction } from '../solana-js/instructions/withdraw_total_fee';
-import { InvalidParamError } from '../solana-js/errors/invalid_param_error';
+import { createReceiverAtasIfNeededAndDistributeFeesInstruction } from '../solana-js/instructions/distribute_fees';
 
 dotenv.config();
 
 let anchorProvider: anchor.AnchorProvider;
 
-describe('Withdraw total fee', () => {
+describe('Distribute fees', () => {
   // Configure the client to use the local cluster.
   anchorProvider = anchor.AnchorProvider.env();
   anchor.setProvider(anchorProvider);
@@ -57,6 +54,7 @@ describe('Withdraw total fee', () => {
   const mpc = Keypair.generate();
   const operator = Keypair.generate();
   const feeReceiver = Keypair.generate();
+  const secondFeeReceiver = Keypair.generate();
   let rentForSpace8 = 0;
   before(async () => {
     await anchorProvider.connection.requestAirdrop(user.publicKey, LAMPORTS_PER_SOL * 1000);
@@ -98,27 +96,39 @@ describe('Withdraw total fee', () => {
 
     });
 
-    it('Admin add operator successfully', async () => {
+    it('Admin grant operator roles and register MPC successfully', async () => {
       await connection.requestAirdrop(operator.publicKey, LAMPORTS_PER_SOL * 10);
       await connection.requestAirdrop(feeReceiver.publicKey, LAMPORTS_PER_SOL * 10);
-      const addOperatorIns = await createAddOperatorInstruction({
+      await connection.requestAirdrop(secondFeeReceiver.publicKey, LAMPORTS_PER_SOL * 10);
+      const addOperatorIns = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
+      addOperatorIns.push(...(await createAddOrUpdateMpcRegistryInstruction({
+        connection,
         signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+        mpcPubkey: mpc.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      })));
       const transaction = new Transaction().add(...addOperatorIns);
       transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
       await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
     });
 
-    it('Admin add fee receiver successfully', async () => {
-      await connection.requestAirdrop(feeReceiver.publicKey, LAMPORTS_PER_SOL * 10);
-      const addFeeReceiverIns = await createAddFeeReceiverInstruction({
-        signer: deployer.publicKey,
-        receiver: feeReceiver.publicKey,
-        connection: connection,
-      });
-      const transaction = new Transaction().add(...addFeeReceiverIns);
+    it('Admin add fee receivers with their shares successfully', async () => {
+      const instructions = [];
+      for (const [receiver, shareBps] of [[feeReceiver, 7_500], [secondFeeReceiver, 2_500]] as const) {
+        instructions.push(...(await createAddFeeReceiverInstruction({
+          signer: deployer.publicKey,
+          receiver: receiver.publicKey,
+          connection: connection,
+        })));
+        instructions.push(...(await createSetFeeReceiverShareInstruction({
+          signer: deployer.publicKey,
+          receiver: receiver.publicKey,
+          shareBps,
+          connection: connection,
+        })));
+      }
+      const transaction = new Transaction().add(...instructions);
       transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
       await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
     });
@@ -128,6 +138,8 @@ describe('Withdraw total fee', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const transaction = new Transaction().add(...addWhitelistIns);
@@ -145,7 +157,6 @@ describe('Withdraw total fee', () => {
     const [fromToken, toToken] = createTokenPair();
     const amount = 0.05 * LAMPORTS_PER_SOL;
     const feeAmount = 0.01 * LAMPORTS_PER_SOL;
-    const smallFee = 0.0001 * LAMPORTS_PER_SOL;
     const depositParams = {
       sessionId,
       userPubkey: user.publicKey,
@@ -251,81 +262,57 @@ describe('Withdraw total fee', () => {
       assert.equal(userTradeDetailData.settledPmm.toBase58(), pmmKey.publicKey.toBase58(), 'User trade detail settled pmm should be the pmm pubkey');
     })
 
-    it('Should withdraw fee error when wrong fee receiver', async () => {
-      const fakeFeeReceiver = Keypair.generate();
-      const feeReceiverPda = getFeeReceiverPda(feeReceiver.publicKey);
+    it('Should distribute fees error when a fee receiver with a share is missing', async () => {
       try {
         await program.methods
-          .withdrawTotalFee({
-            token: null,
-            amount: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
-          })
-          .accounts({
+          .distributeFees({ token: null })
+          .accountsPartial({
             signer: operator.publicKey,
-            toUser: fakeFeeReceiver.publicKey,
-            feeReceiver: feeReceiverPda,
-          } as any)
-          .signers([operator])
-          .rpc({ commitment: 'confirmed' })
-          assert.fail('Should not reach here');
-      } catch (error) {
-        assert.ok(error.toString().includes('InvalidFeeReceiver'), 'Should return InvalidRefundPubkey error');
-      }
-    })
-
-    it('Should withdraw fee error when fee receiver is not set', async () => {
-      const fakeFeeReceiver = Keypair.generate();
-      try {
-        await program.methods
-          .withdrawTotalFee({
-            token: null,
-            amount: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+            feeLedger: getFeeLedgerPda(null),
           })
-          .accounts({
-            signer: operator.publicKey,
-            toUser: fakeFeeReceiver.publicKey,
-            feeReceiver: fakeFeeReceiver,
-          } as any)
+          .remainingAccounts([
+            { pubkey: getFeeReceiverPda(feeReceiver.publicKey), isSigner: false, isWritable: false },
+            { pubkey: feeReceiver.publicKey, isSigner: false, isWritable: true },
+          ])
           .signers([operator])
           .rpc({ commitment: 'confirmed' })
           assert.fail('Should not reach here');
       } catch (error) {
-        assert.ok(error.errorLogs.some(log => log.includes('AccountNotInitialized') && log.includes('fee_receiver')), 'Should return InvalidRefundPubkey error');
+        assert.ok(error.toString().includes('InvalidFeeShare'), 'Should return InvalidFeeShare error');
       }
     })
 
-    it('Should withdraw fee error when fee receiver is not set', async () => {
+    it('Should distribute fees error when fee receiver is not set', async () => {
       const fakeFeeReceiver = Keypair.generate();
       try {
         await program.methods
-          .withdrawTotalFee({
-            token: null,
-            amount: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
-          })
-          .accounts({
+          .distributeFees({ token: null })
+          .accountsPartial({
             signer: operator.publicKey,
-            toUser: fakeFeeReceiver.publicKey,
-            feeReceiver: fakeFeeReceiver,
-          } as any)
+            feeLedger: getFeeLedgerPda(null),
+          })
+          .remainingAccounts([
+            { pubkey: getFeeReceiverPda(fakeFeeReceiver.publicKey), isSigner: false, isWritable: false },
+            { pubkey: fakeFeeReceiver.publicKey, isSigner: false, isWritable: true },
+          ])
           .signers([operator])
           .rpc({ commitment: 'confirmed' })
           assert.fail('Should not reach here');
       } catch (error) {
-        assert.ok(error.errorLogs.some(log => log.includes('AccountNotInitialized') && log.includes('fee_receiver')), 'Should return InvalidRefundPubkey error');
+        assert.ok(error.toString().includes('InvalidFeeReceiver'), 'Should return InvalidFeeReceiver error');
       }
     });
 
-    it('Should withdraw a small fee successfully', async () => {
-      const withdrawTotalFeeIns = await createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction({
+    it('Should distribute fees successfully', async () => {
+      const distributeFeesIns = await createReceiverAtasIfNeededAndDistributeFeesInstruction({
         connection,
         token: null,
-        receiverPubkey: feeReceiver.publicKey,
         signer: operator.publicKey,
-        amount: BigInt(smallFee),
       });
       const beforeProtocolBalance = await connection.getBalance(protocolPda, { commitment: 'confirmed' });
       const beforeFeeReceiverBalance = await connection.getBalance(feeReceiver.publicKey, { commitment: 'confirmed' });
-      const transaction = new Transaction().add(...withdrawTotalFeeIns);
+      const beforeSecondFeeReceiverBalance = await connection.getBalance(secondFeeReceiver.publicKey, { commitment: 'confirmed' });
+      const transaction = new Transaction().add(...distributeFeesIns);
       try {
         await sendAndConfirmTransaction(connection, transaction, [operator], { commitment: 'confirmed' });
       } catch (error) {
@@ -334,67 +321,23 @@ describe('Withdraw total fee', () => {
       }
       const afterProtocolBalance = await connection.getBalance(protocolPda, { commitment: 'confirmed' });
       const afterFeeReceiverBalance = await connection.getBalance(feeReceiver.publicKey, { commitment: 'confirmed' });
-      assert.equal(beforeProtocolBalance - afterProtocolBalance, smallFee, 'The protocol balance should decrease by the amount of fee');
-      assert.equal(afterFeeReceiverBalance - beforeFeeReceiverBalance, smallFee, 'The fee receiver balance should increase by the amount of fee');
-    })
-
-    it('Should withdraw fee error when withdraw too much SOL', async () => {
-      const protocolBalance = await connection.getBalance(protocolPda, { commitment: 'confirmed' }) / LAMPORTS_PER_SOL;
-      try {
-        await program.methods.withdrawTotalFee({
-          token: null,
-          amount: new anchor.BN(protocolBalance * 10**9)
-        })
-        .accounts({
-          signer: operator.publicKey,
-          toUser: feeReceiver.publicKey,
-        })
-        .signers([operator])
-        .rpc({ commitment: 'confirmed' })
-        assert.fail('Should not reach here');
-      } catch (error) {
-        assert.ok(error.toString().includes('InvalidAmount'), 'Amount to withdraw is not valid');
-      }
+      const afterSecondFeeReceiverBalance = await connection.getBalance(secondFeeReceiver.publicKey, { commitment: 'confirmed' });
+      assert.equal(beforeProtocolBalance - afterProtocolBalance, feeAmount, 'The protocol balance should decrease by the amount of fee');
+      assert.equal(afterFeeReceiverBalance - beforeFeeReceiverBalance, feeAmount * 3 / 4, 'The fee receiver balance should increase by its share of the fee');
+      assert.equal(afterSecondFeeReceiverBalance - beforeSecondFeeReceiverBalance, feeAmount / 4, 'The second fee receiver balance should increase by its share of the fee');
     })
 
-    it('Should withdraw fee error when create instruction to withdraw too much SOL', async () => {
-      const protocolBalance = await connection.getBalance(protocolPda, { commitment: 'confirmed' });
-      try { 
-        await createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction({
-          connection,
-          token: null,
-          receiverPubkey: feeReceiver.publicKey,
-          signer: operator.publicKey,
-          amount: BigInt(protocolBalance),
-        });
-      } catch (error) {
-        expect(error).to.be.instanceOf(InvalidParamError);
-        assert.ok(error.toString().includes('Withdraw amount is greater'));
-      }
-    })
-
-
-    it('Should withdraw total fee successfully', async () => {
-      const withdrawTotalFeeIns = await createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction({
+    it('Should distribute nothing when all the fee is distributed', async () => {
+      const distributeFeesIns = await createReceiverAtasIfNeededAndDistributeFeesInstruction({
         connection,
         token: null,
-        receiverPubkey: feeReceiver.publicKey,
         signer: operator.publicKey,
-        amount: null,
       });
       const beforeProtocolBalance = await connection.getBalance(protocolPda, { commitment: 'confirmed' });
-      const beforeFeeReceiverBalance = await connection.getBalance(feeReceiver.publicKey, { commitment: 'confirmed' });
-      const transaction = new Transaction().add(...withdrawTotalFeeIns);
-      try {
-        await sendAndConfirmTransaction(connection, transaction, [operator], { commitment: 'confirmed' });
-      } catch (error) {
-        console.log(error);
-        throw error;
-      }
+      const transaction = new Transaction().add(...distributeFeesIns);
+      await sendAndConfirmTransaction(connection, transaction, [operator], { commitment: 'confirmed' });
       const afterProtocolBalance = await connection.getBalance(protocolPda, { commitment: 'confirmed' });
-      const afterFeeReceiverBalance = await connection.getBalance(feeReceiver.publicKey, { commitment: 'confirmed' });
-      assert.equal(beforeProtocolBalance - afterProtocolBalance, (feeAmount - smallFee), 'The protocol balance should decrease by the amount of fee');
-      assert.equal(afterFeeReceiverBalance - beforeFeeReceiverBalance, (feeAmount - smallFee), 'The fee receiver balance should increase by the amount of fee');
+      assert.equal(afterProtocolBalance, beforeProtocolBalance, 'The protocol balance should not change');
     })
   });
 
@@ -406,7 +349,6 @@ describe('Withdraw total fee', () => {
     const [fromToken, toToken] = createTokenPair(tokenMint.toBase58());
     const amount = 0.1 * LAMPORTS_PER_SOL;
     const feeAmount = 0.01 * LAMPORTS_PER_SOL;
-    const smallFee = 0.0001 * LAMPORTS_PER_SOL;
     const sessionId = BigInt(keccak256(toUtf8Bytes(crypto.randomUUID())));
     const wrapSolTx = new Transaction().add(
       createAssociatedTokenAccountInstruction(
@@ -547,40 +489,14 @@ describe('Withdraw total fee', () => {
       assert.equal(afterProtocolVaultBalance - beforeProtocolVaultBalance, feeAmount, 'Protocol vault balance should increase by the fee amount amount of token setup');
     })
 
-    it('Should withdraw a small fee successfully', async () => {
-      const withdrawTotalFeeIns = await createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction({
-        connection,
-        token: WSOL_MINT,
-        receiverPubkey: feeReceiver.publicKey,
-        signer: operator.publicKey,
-        amount: BigInt(smallFee),
-      });
-      const beforeProtocolBalance = await getTokenBalance(connection, WSOL_MINT, protocolPda);
-      const beforeFeeReceiverBalance = await getTokenBalance(connection, WSOL_MINT, feeReceiver.publicKey);
-      const transaction = new Transaction().add(...withdrawTotalFeeIns);
-      try {
-        await sendAndConfirmTransaction(connection, transaction, [operator], { commitment: 'confirmed' });
-      } catch (error) {
-        console.log(error);
-        throw error;
-      }
-      const afterProtocolBalance = await getTokenBalance(connection, WSOL_MINT, protocolPda);
-      const afterFeeReceiverBalance = await getTokenBalance(connection, WSOL_MINT, feeReceiver.publicKey);
-      assert.equal(beforeProtocolBalance - afterProtocolBalance, smallFee, 'The protocol balance should decrease by the amount of fee');
-      assert.equal(afterFeeReceiverBalance - beforeFeeReceiverBalance, smallFee, 'The fee receiver balance should increase by the amount of fee');
-    })
-
-    it('Should withdraw total fee successfully', async () => {
-      const withdrawTotalFeeIns = await createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction({
+    it('Should distribute fees and create the receiver atas successfully', async () => {
+      const distributeFeesIns = await createReceiverAtasIfNeededAndDistributeFeesInstruction({
         connection: connection,
         token: WSOL_MINT,
-        receiverPubkey: feeReceiver.publicKey,
         signer: operator.publicKey,
-        amount: null,
       });
       const beforeProtocolBalance = await getTokenBalance(connection, WSOL_MINT, protocolPda);
-      const beforeFeeReceiverBalance = await getTokenBalance(connection, WSOL_MINT, feeReceiver.publicKey);
-      const transaction = new Transaction().add(...withdrawTotalFeeIns);
+      const transaction = new Transaction().add(...distributeFeesIns);
       try {
         await sendAndConfirmTransaction(connection, transaction, [operator], { commitment: 'confirmed' });
       } catch (error) {
@@ -589,8 +505,10 @@ describe('Withdraw total fee', () => {
       }
       const afterProtocolBalance = await getTokenBalance(connection, WSOL_MINT, protocolPda);
       const afterFeeReceiverBalance = await getTokenBalance(connection, WSOL_MINT, feeReceiver.publicKey);
-      assert.equal(beforeProtocolBalance - afterProtocolBalance, (feeAmount - smallFee), 'The protocol balance should decrease by the amount of fee');
-      assert.equal(afterFeeReceiverBalance - beforeFeeReceiverBalance, (feeAmount - smallFee), 'The fee receiver balance should increase by the amount of fee');
+      const afterSecondFeeReceiverBalance = await getTokenBalance(connection, WSOL_MINT, secondFeeReceiver.publicKey);
+      assert.equal(beforeProtocolBalance - afterProtocolBalance, feeAmount, 'The protocol balance should decrease by the amount of fee');
+      assert.equal(afterFeeReceiverBalance, feeAmount * 3 / 4, 'The fee receiver balance should be its share of the fee');
+      assert.equal(afterSecondFeeReceiverBalance, feeAmount / 4, 'The second fee receiver balance should be its share of the fee');
     })
   });
 });
@@ -10,7 +10,7 @@ import {
   Transaction,
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
-import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep } from './utils';
+import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { assert } from 'chai';
 import crypto from 'crypto';
@@ -29,14 +29,13 @@ import { createAssociatedTokenAccountInstructionIfNeeded } from '../solana-js/in
 import { getTradeInput } from '../solana-js/utils/param_utils';
 import { createClaimAndRefundAtaAndProtocolAtaIfNeededInstructions } from '../solana-js/instructions/claim';
 import { createSetTotalFeeInstructions } from '../solana-js/instructions/set_total_fee';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
 import { WSOL_MINT } from '../solana-js/constants';
-import { createAddFeeReceiverInstruction, createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
+import { createAddFeeReceiverInstruction, createAddOrUpdateWhitelistInstruction, createAddOrUpdateMpcRegistryInstruction, createSetFeeReceiverShareInstruction } from '../solana-js/instructions/manage_config';
 import { SystemProgram } from '@solana/web3.js';
 import { getTradeDetailData } from '../solana-js/pda/get_pda_data';
 import { createCloseFinishedTradeInstructions } from '../solana-js/instructions/close_finished_trade';
 import { bigintToBytes32 } from '../solana-js/utils/parse_utils';
-import { createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction } from '../solana-js/instructions/withdraw_total_fee';
+import { createReceiverAtasIfNeededAndDistributeFeesInstruction } from '../solana-js/instructions/distribute_fees';
 
 dotenv.config();
 
@@ -99,14 +98,17 @@ describe('optimex-sol-smartcontract', () => {
       )
     });
 
-    it('Admin add operator successfully', async () => {
+    it('Admin grant operator roles and register MPC successfully', async () => {
       await connection.requestAirdrop(operator.publicKey, LAMPORTS_PER_SOL * 10);
       await connection.requestAirdrop(feeReceiver.publicKey, LAMPORTS_PER_SOL * 10);
-      const addOperatorIns = await createAddOperatorInstruction({
+      const addOperatorIns = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
+      addOperatorIns.push(...(await createAddOrUpdateMpcRegistryInstruction({
+        connection,
         signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+        mpcPubkey: mpc.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      })));
       const transaction = new Transaction().add(...addOperatorIns);
       transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
       await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
@@ -119,7 +121,14 @@ describe('optimex-sol-smartcontract', () => {
         receiver: feeReceiver.publicKey,
         connection: connection,
       });
-      const transaction = new Transaction().add(...addFeeReceiverIns);
+      // The only fee receiver gets the whole distributed fee
+      const setFeeReceiverShareIns = await createSetFeeReceiverShareInstruction({
+        signer: deployer.publicKey,
+        receiver: feeReceiver.publicKey,
+        shareBps: 10_000,
+        connection: connection,
+      });
+      const transaction = new Transaction().add(...addFeeReceiverIns, ...setFeeReceiverShareIns);
       transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
       await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
     });
@@ -129,6 +138,8 @@ describe('optimex-sol-smartcontract', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const transaction = new Transaction().add(...addWhitelistIns);
@@ -332,7 +343,7 @@ describe('optimex-sol-smartcontract', () => {
     })
   });
 
- describe('Deposit(), setFee and settlement, withdraw fee with SOL successfully', () => {
+ describe('Deposit(), setFee and settlement, distribute fees with SOL successfully', () => {
     const userEphemeralKey = Keypair.generate();
     const pmmKey = Keypair.generate();
     const refundKey = Keypair.generate();
@@ -445,17 +456,16 @@ describe('optimex-sol-smartcontract', () => {
       assert.equal(userTradeDetailData.settledPmm.toBase58(), pmmKey.publicKey.toBase58(), 'User trade detail settled pmm should be the pmm pubkey');
     })
 
-    it('Should withdraw total fee successfully', async () => {
-      const withdrawTotalFeeIns = await createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction({
+    it('Should distribute fees successfully', async () => {
+      const distributeFeesIns = await createReceiverAtasIfNeededAndDistributeFeesInstruction({
         connection,
         token: null,
-        receiverPubkey: feeReceiver.publicKey,
+        receivers: [feeReceiver.publicKey],
         signer: operator.publicKey,
-        amount: null,
       });
       const beforeProtocolBalance = await connection.getBalance(protocolPda, { commitment: 'confirmed' });
       const beforeFeeReceiverBalance = await connection.getBalance(feeReceiver.publicKey, { commitment: 'confirmed' });
-      const transaction = new Transaction().add(...withdrawTotalFeeIns);
+      const transaction = new Transaction().add(...distributeFeesIns);
       try {
         await sendAndConfirmTransaction(connection, transaction, [operator], { commitment: 'confirmed' });
       } catch (error) {
@@ -515,6 +525,8 @@ describe('optimex-sol-smartcontract', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
 
@@ -643,6 +655,8 @@ describe('optimex-sol-smartcontract', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -750,6 +764,8 @@ describe('optimex-sol-smartcontract', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -897,7 +913,7 @@ describe('optimex-sol-smartcontract', () => {
       await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
     });
 
-    it('Should succeed with SPL token deposit, withdraw total fee', async () => {
+    it('Should succeed with SPL token deposit, distribute fees', async () => {
       const instructions = await createDepositAndVaultAtaIfNeededAndNonceAccountInstructions(depositParams)
 
       const beforeUserTokenBalance = await getTokenBalance(connection, tokenMint, user.publicKey);
@@ -986,17 +1002,16 @@ describe('optimex-sol-smartcontract', () => {
       assert.equal(afterProtocolVaultBalance - beforeProtocolVaultBalance, feeAmount, 'Protocol vault balance should increase by the fee amount amount of token setup');
     })
 
-    it('Should withdraw total fee successfully', async () => {
-      const withdrawTotalFeeIns = await createReceiverAtaIfNeededAndWithdrawTotalFeeInstruction({
+    it('Should distribute fees successfully', async () => {
+      const distributeFeesIns = await createReceiverAtasIfNeededAndDistributeFeesInstruction({
         connection: connection,
         token: WSOL_MINT,
-        receiverPubkey: feeReceiver.publicKey,
+        receivers: [feeReceiver.publicKey],
         signer: operator.publicKey,
-        amount: null,
       });
       const beforeProtocolBalance = await getTokenBalance(connection, WSOL_MINT, protocolPda);
       const beforeFeeReceiverBalance = await getTokenBalance(connection, WSOL_MINT, feeReceiver.publicKey);
-      const transaction = new Transaction().add(...withdrawTotalFeeIns);
+      const transaction = new Transaction().add(...distributeFeesIns);
       try {
         await sendAndConfirmTransaction(connection, transaction, [operator], { commitment: 'confirmed' });
       } catch (error) {
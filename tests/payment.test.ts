@@ -10,14 +10,13 @@ import {
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { expect, assert } from 'chai';
-import { airdropTokenToUser, getTokenBalance, sleep } from './utils';
+import { airdropTokenToUser, getTokenBalance, sleep, createGrantOperatorRolesInstructions } from './utils';
 import { sha256 } from 'ethers';
 import {
   createMint,
 } from '@solana/spl-token';
 import { createPaymentAndRefundAtaAndProtocolAtaIfNeededInstructions } from '../solana-js/instructions/payment';
 import { bigintToBytes32, delay, getBlockTime } from '../scripts/utils/helper';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
 import { createAddOrUpdateWhitelistInstruction, createSetCloseWaitDurationInstruction } from '../solana-js/instructions/manage_config';
 import { WSOL_MINT } from '../solana-js/constants';
 import { getPaymentReceiptPda } from '../solana-js/pda/get_pda_address';
@@ -70,12 +69,8 @@ describe('Payment() functional testing', () => {
       await delay(2000);
     });
 
-    it('Add operator success', async () => {
-      const instructions = await createAddOperatorInstruction({
-        signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+    it('Grant operator roles success', async () => {
+      const instructions = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
       const transaction = new Transaction().add(...instructions);
       try {
         await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
@@ -89,6 +84,8 @@ describe('Payment() functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const transaction = new Transaction().add(...instructions);
@@ -391,6 +388,8 @@ describe('Payment() functional testing', () => {
         operator: operator.publicKey,
         token: mint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection,
       });
       const transaction = new Transaction().add(...addWhitelistIns);
@@ -7,16 +7,15 @@ import {
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { assert } from 'chai';
-import { createTokenPair, getBlockTime, sleep } from './utils';
+import { createTokenPair, getBlockTime, sleep, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { solverAddress } from './example-data';
 import { createInitializeProgramInstructions } from '../solana-js/instructions/intialize';
 import { createDepositAndVaultAtaIfNeededInstructions, DepositInstructionParam } from '../solana-js/instructions/deposit';
 import { delay } from '../scripts/utils/helper';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
 import { getTradeDetailData } from '../solana-js/pda/get_pda_data';
 import { WSOL_MINT } from '../solana-js/constants';
-import { createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
+import { createAddOrUpdateWhitelistInstruction, createAddOrUpdateMpcRegistryInstruction } from '../solana-js/instructions/manage_config';
 import { getTradeInput } from '../solana-js/utils/param_utils';
 import { createSetTotalFeeInstructions } from '../solana-js/instructions/set_total_fee';
 
@@ -52,12 +51,15 @@ describe('Set Total Fee () functional testing', () => {
       await delay(3000);
     })
 
-    it('Add operator successfully', async () => {
-      const addOperatorIns = await createAddOperatorInstruction({
+    it('Grant operator roles and register MPC successfully', async () => {
+      const addOperatorIns = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
+      addOperatorIns.push(...(await createAddOrUpdateMpcRegistryInstruction({
+        connection,
         signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+        mpcPubkey: mpcKey.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      })));
       const transaction = new Transaction().add(...addOperatorIns);
       try {
         await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
@@ -71,6 +73,8 @@ describe('Set Total Fee () functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const transaction = new Transaction().add(...addWhitelistIns);
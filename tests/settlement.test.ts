@@ -12,7 +12,7 @@ import {
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { expect, assert } from 'chai';
-import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep } from './utils';
+import { airdropTokenToUser, createTokenPair, getBlockTime, getTokenBalance, sleep, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { solverAddress } from './example-data';
 import {
@@ -26,11 +26,10 @@ import { delay } from '../scripts/utils/helper';
 import { createDepositAndVaultAtaIfNeededAndNonceAccountInstructions, createDepositAndVaultAtaIfNeededInstructions } from '../solana-js/instructions/deposit';
 import { createAssociatedTokenAccountInstructionIfNeeded } from '../solana-js/instructions/helpers';
 import { createInitializeProgramInstructions } from '../solana-js/instructions/intialize';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
-import { createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
+import { createAddOrUpdateWhitelistInstruction, createAddOrUpdateMpcRegistryInstruction } from '../solana-js/instructions/manage_config';
 import { createSetTotalFeeInstructions } from '../solana-js/instructions/set_total_fee';
 import { WSOL_MINT } from '../solana-js/constants';
-import { getNonceCheckPda, getTradeVaultPda } from '../solana-js/pda/get_pda_address';
+import { getFeeLedgerPda, getNonceCheckPda, getTradeVaultPda, getWhitelistPda } from '../solana-js/pda/get_pda_address';
 import { getTradeDetailData } from '../solana-js/pda/get_pda_data';
 import { getTradeInput } from '../solana-js/utils/param_utils';
 dotenv.config();
@@ -69,12 +68,15 @@ describe('Settlement() functional testing', () => {
       await delay(3000);
     })
 
-    it('Add operator success', async () => {
-      const addOperatorIns = await createAddOperatorInstruction({
+    it('Grant operator roles and register MPC success', async () => {
+      const addOperatorIns = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
+      addOperatorIns.push(...(await createAddOrUpdateMpcRegistryInstruction({
+        connection,
         signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+        mpcPubkey: mpcKey.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      })));
       const addOperatorTransaction = new Transaction().add(...addOperatorIns);
       await sendAndConfirmTransaction(connection, addOperatorTransaction, [deployer], { commitment: 'confirmed' });
     })
@@ -96,6 +98,8 @@ describe('Settlement() functional testing', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -136,6 +140,9 @@ describe('Settlement() functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: getTradeVaultPda(correctTradeId),
+        whitelistToken: getWhitelistPda(WSOL_MINT),
+        feeLedger: getFeeLedgerPda(null),
+        mpcGroup: null,
       };
     })
 
@@ -150,7 +157,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -196,7 +203,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -255,6 +262,9 @@ describe('Settlement() functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: getTradeVaultPda(correctTradeId),
+        whitelistToken: getWhitelistPda(WSOL_MINT),
+        feeLedger: getFeeLedgerPda(null),
+        mpcGroup: null,
       };
     })
 
@@ -266,7 +276,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...newUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -285,7 +295,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...newUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -319,7 +329,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...newUserAccount,
         })
           .signers([newEphemeralKey, mpcKey])
@@ -339,7 +349,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...newUserAccount,
         })
           .signers([userEphemeralKey, newMpcKey])
@@ -359,7 +369,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...newUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -379,7 +389,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...newUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -394,7 +404,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .signers([userEphemeralKey, mpcKey])
@@ -429,6 +439,8 @@ describe('Settlement() functional testing', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -472,6 +484,9 @@ describe('Settlement() functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: vaultPda,
+        whitelistToken: getWhitelistPda(tokenMint),
+        feeLedger: getFeeLedgerPda(tokenMint),
+        mpcGroup: null,
       };
       remainingAccounts = [
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
@@ -493,7 +508,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .remainingAccounts(remainingAccounts)
@@ -535,6 +550,8 @@ describe('Settlement() functional testing', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const addWhitelistTransaction = new Transaction().add(...addWhitelistIns);
@@ -578,6 +595,9 @@ describe('Settlement() functional testing', () => {
         pmm: pmm.publicKey,
         signer: mpcKey.publicKey,
         vault: vaultPda,
+        whitelistToken: getWhitelistPda(tokenMint),
+        feeLedger: getFeeLedgerPda(tokenMint),
+        mpcGroup: null,
       };
       remainingAccounts = [
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
@@ -617,7 +637,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         }).remainingAccounts(newRemainingAccounts)
           .signers([userEphemeralKey, mpcKey])
@@ -638,7 +658,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         }).remainingAccounts(newRemainingAccounts)
           .signers([userEphemeralKey, mpcKey])
@@ -659,7 +679,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         }).remainingAccounts(newRemainingAccounts)
           .signers([userEphemeralKey, mpcKey])
@@ -680,7 +700,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         }).remainingAccounts(newRemainingAccounts)
           .signers([userEphemeralKey, mpcKey])
@@ -696,7 +716,7 @@ describe('Settlement() functional testing', () => {
       try {
         await program.methods.settlement({
           tradeId: correctTradeIdBytes,
-        }).accounts({
+        }).accountsPartial({
           ...correctUserAccount,
         })
           .remainingAccounts(remainingAccounts)
@@ -3,6 +3,7 @@ import { Connection, Keypair, PublicKey, sendAndConfirmTransaction, SystemProgra
 import { IToken } from '../solana-js/types/token_interface';
 import { createMintToInstruction, getAssociatedTokenAddress, getAssociatedTokenAddressSync } from '@solana/spl-token';
 import { createAssociatedTokenAccountInstructionIfNeeded } from '../solana-js';
+import { createGrantRoleInstruction } from '../solana-js/instructions/manage_operator';
 
 export const createAccount = async ({
   provider,
@@ -151,3 +152,13 @@ export async function getBlockTime(connection: Connection) {
   const blockTime = await connection.getBlockTime(slot);
   return blockTime;
 }
+
+/**
+ * Create the instructions granting the whitelist manager and duration manager roles to an operator
+ */
+export async function createGrantOperatorRolesInstructions(connection: Connection, signer: PublicKey, operator: PublicKey) {
+  return [
+    ...(await createGrantRoleInstruction({ signer, role: 'whitelistManager', member: operator, connection })),
+    ...(await createGrantRoleInstruction({ signer, role: 'durationManager', member: operator, connection })),
+  ];
+}
@@ -10,7 +10,7 @@ import {
 } from '@solana/web3.js';
 import dotenv from 'dotenv';
 import { expect } from 'chai';
-import { airdropTokenToUser, createTokenPair, getTokenBalance } from './utils';
+import { airdropTokenToUser, createTokenPair, getTokenBalance, createGrantOperatorRolesInstructions } from './utils';
 import { keccak256, toUtf8Bytes } from 'ethers';
 import { assert } from 'chai';
 import crypto from 'crypto';
@@ -24,9 +24,8 @@ import { createDepositAndVaultAtaIfNeededAndNonceAccountInstructions } from '../
 import { createNonceAdvanceAndSettlementInstruction, createUserPresignSettlementTransactionAndSerializeToString } from '../solana-js/instructions/settlement';
 import { createAssociatedTokenAccountInstructionIfNeeded, verifyPresignSettlement } from '../solana-js/instructions/helpers';
 import { getTradeInput } from '../solana-js/utils/param_utils';
-import { createAddOperatorInstruction } from '../solana-js/instructions/manage_operator';
 import { WSOL_MINT } from '../solana-js/constants';
-import { createAddOrUpdateWhitelistInstruction } from '../solana-js/instructions/manage_config';
+import { createAddOrUpdateWhitelistInstruction, createAddOrUpdateMpcRegistryInstruction } from '../solana-js/instructions/manage_config';
 import { SystemProgram } from '@solana/web3.js';
 import { InvalidPresignStringError } from '../solana-js/errors';
 
@@ -90,13 +89,16 @@ describe('Verify presign settlement', () => {
       )
     });
 
-    it('Deployer add operator successfully', async () => {
+    it('Deployer grant operator roles and register MPC successfully', async () => {
       await connection.requestAirdrop(operator.publicKey, LAMPORTS_PER_SOL * 10);
-      const addOperatorIns = await createAddOperatorInstruction({
+      const addOperatorIns = await createGrantOperatorRolesInstructions(connection, deployer.publicKey, operator.publicKey);
+      addOperatorIns.push(...(await createAddOrUpdateMpcRegistryInstruction({
+        connection,
         signer: deployer.publicKey,
-        operator: operator.publicKey,
-        connection: connection,
-      });
+        mpcPubkey: mpc.publicKey,
+        validFrom: 0,
+        validUntil: 0,
+      })));
       const transaction = new Transaction().add(...addOperatorIns);
       transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
       await sendAndConfirmTransaction(connection, transaction, [deployer], { commitment: 'confirmed' });
@@ -107,6 +109,8 @@ describe('Verify presign settlement', () => {
         operator: operator.publicKey,
         token: WSOL_MINT,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
       const transaction = new Transaction().add(...addWhitelistIns);
@@ -442,6 +446,8 @@ describe('Verify presign settlement', () => {
         operator: operator.publicKey,
         token: tokenMint,
         amount: BigInt(0.001 * LAMPORTS_PER_SOL),
+        maxAmount: BigInt(0),
+        maxOutstanding: BigInt(0),
         connection: connection,
       });
 